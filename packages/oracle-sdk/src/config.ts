@@ -1,5 +1,10 @@
 import * as anchor from '@project-serum/anchor';
-import { PublicKey, TransactionInstruction } from '@solana/web3.js';
+import {
+	PublicKey,
+	SYSVAR_RENT_PUBKEY,
+	TransactionInstruction,
+} from '@solana/web3.js';
+import * as spl from '@solana/spl-token';
 import { Oracle } from './idls/oracle.js';
 import { SureOracleSDK } from './sdk.js';
 import { TransactionEnvelope } from '@saberhq/solana-contrib/dist/cjs';
@@ -9,6 +14,7 @@ import { getOrCreateAssociatedTokenAccountIx } from './utils.js';
 export type InitializeOracleConfig = {
 	protocolAuthority: PublicKey;
 	tokenMint: PublicKey;
+	locker: PublicKey;
 };
 
 export type UpdateConfig = {
@@ -40,21 +46,31 @@ export class Config {
 	/**
 	 * initialize oracle config
 	 *
-	 * initialize
+	 * initialize the config and the protocol treasury
+	 * for the given token mint
 	 *
 	 * @param protocolAuthority - owner of the config
 	 * @param tokenMint - mint of vault
+	 * @param locker - locker of the escrows that can vote, see Tribeca
 	 */
 	async initializeOracleConfig({
 		protocolAuthority,
 		tokenMint,
+		locker,
 	}: InitializeOracleConfig): Promise<TransactionEnvelope> {
+		const [config] = this.sdk.pda.findOracleConfig({ tokenMint });
+		const [protocolTreasury] = this.sdk.pda.findProtocolTreasury({ config });
 		const ixs: TransactionInstruction[] = [];
 		ixs.push(
 			await this.program.methods
 				.initializeConfig(protocolAuthority)
 				.accounts({
+					config,
 					tokenMint,
+					locker,
+					protocolTreasury,
+					tokenProgram: spl.TOKEN_PROGRAM_ID,
+					rent: SYSVAR_RENT_PUBKEY,
 				})
 				.instruction()
 		);
@@ -158,43 +174,88 @@ export class Config {
 	/**
 	 * collect protocol fees
 	 *
+	 * sweep the accrued protocol fees of the proposals
+	 * into the protocol treasury
 	 *
 	 * @params tokenMint - the mint of the vault
-	 * @params proposalName - name of the proposal
-	 * @params feeDestination - the token account for where to send the fees
+	 * @params proposals - the proposals to collect the fees from
 	 */
 	async collectProtocolFees({
 		tokenMint,
-		proposalName,
-		feeDestination,
+		proposals,
 	}: {
 		tokenMint: PublicKey;
-		proposalName: string;
-		feeDestination: PublicKey;
+		proposals: PublicKey[];
 	}): Promise<TransactionEnvelope> {
 		const [config] = this.sdk.pda.findOracleConfig({ tokenMint });
-		const [proposal] = this.sdk.pda.findProposalAddress({ proposalName });
-		const [proposalVault] = this.sdk.pda.findProposalVault({ proposal });
-
-		const proposerAccount = await getOrCreateAssociatedTokenAccountIx({
-			connection: this.sdk.provider.connection,
-			payer: (this.sdk.provider.wallet as anchor.Wallet).payer,
-			mint: tokenMint,
-			owner: this.sdk.provider.walletKey,
+		const [protocolTreasury] = this.sdk.pda.findProtocolTreasury({ config });
+		const remainingAccounts = proposals.flatMap((proposal) => {
+			const [proposalVault] = this.sdk.pda.findProposalVault({ proposal });
+			return [
+				{ pubkey: proposal, isSigner: false, isWritable: true },
+				{ pubkey: proposalVault, isSigner: false, isWritable: true },
+			];
 		});
+
 		const ixs: TransactionInstruction[] = [];
-		if (proposerAccount.instruction) {
-			ixs.push(proposerAccount.instruction);
+		ixs.push(
+			await this.program.methods
+				.collectProtocolFees()
+				.accounts({
+					config,
+					protocolTreasury,
+					tokenProgram: spl.TOKEN_PROGRAM_ID,
+				})
+				.remainingAccounts(remainingAccounts)
+				.instruction()
+		);
+		return this.sdk.provider.newTX(ixs);
+	}
+
+	/**
+	 * withdraw protocol fees
+	 *
+	 * the protocol authority withdraws fees from
+	 * the protocol treasury
+	 *
+	 * @params tokenMint - the mint of the vault
+	 * @params amount - the amount of tokens to withdraw
+	 * @params feeDestination - the token account for where to send the fees
+	 */
+	async withdrawProtocolFees({
+		tokenMint,
+		amount,
+		feeDestination,
+	}: {
+		tokenMint: PublicKey;
+		amount: anchor.BN;
+		feeDestination?: PublicKey;
+	}): Promise<TransactionEnvelope> {
+		const [config] = this.sdk.pda.findOracleConfig({ tokenMint });
+		const [protocolTreasury] = this.sdk.pda.findProtocolTreasury({ config });
+
+		const ixs: TransactionInstruction[] = [];
+		if (!feeDestination) {
+			const destinationAccount = await getOrCreateAssociatedTokenAccountIx({
+				connection: this.sdk.provider.connection,
+				payer: (this.sdk.provider.wallet as anchor.Wallet).payer,
+				mint: tokenMint,
+				owner: this.sdk.provider.walletKey,
+			});
+			if (destinationAccount.instruction) {
+				ixs.push(destinationAccount.instruction);
+			}
+			feeDestination = destinationAccount.address;
 		}
 
 		ixs.push(
 			await this.program.methods
-				.collectProtocolFees()
+				.withdrawProtocolFees(amount)
 				.accounts({
 					config,
-					proposal,
-					proposalVault,
+					protocolTreasury,
 					feeDestination,
+					tokenProgram: spl.TOKEN_PROGRAM_ID,
 				})
 				.instruction()
 		);
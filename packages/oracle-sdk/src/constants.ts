@@ -11,8 +11,14 @@ export const SURE_ORACLE_VOTE_SEED =
 export const SURE_ORACLE_REVEAL_ARRAY_SEED = anchor.utils.bytes.utf8.encode(
 	'sure-oracle-reveal-array'
 );
+export const SURE_ORACLE_PROPOSAL_VAULT_SEED = anchor.utils.bytes.utf8.encode(
+	'sure-oracle-propsal-vault'
+);
 export const SURE_ORACLE_CONFIG_SEED =
 	anchor.utils.bytes.utf8.encode('sure-oracle-config');
+export const SURE_ORACLE_TREASURY_SEED = anchor.utils.bytes.utf8.encode(
+	'sure-oracle-treasury'
+);
 
 /// sure token
 
@@ -13,7 +13,10 @@ export type Oracle = {
 				'### args',
 				'* protocol_authority<Pubkey>: unique for vault mint. the authority can',
 				'- change config parameters',
-				'- collect protocol fees'
+				'- collect protocol fees',
+				'',
+				'### accounts',
+				'* locker: locker of the escrows that can vote on proposals'
 			];
 			accounts: [
 				{
@@ -46,6 +49,43 @@ export type Oracle = {
 					isMut: false;
 					isSigner: false;
 				},
+				{
+					name: 'locker';
+					isMut: false;
+					isSigner: false;
+					docs: ['locker of the escrows that can vote'];
+				},
+				{
+					name: 'protocolTreasury';
+					isMut: true;
+					isSigner: false;
+					docs: ['treasury for the protocol fees'];
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-treasury';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Config';
+								path: 'config';
+							}
+						];
+					};
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'rent';
+					isMut: false;
+					isSigner: false;
+				},
 				{
 					name: 'systemProgram';
 					isMut: false;
@@ -78,7 +118,7 @@ export type Oracle = {
 				},
 				{
 					name: 'config';
-					isMut: false;
+					isMut: true;
 					isSigner: false;
 				},
 				{
@@ -113,7 +153,7 @@ export type Oracle = {
 				},
 				{
 					name: 'config';
-					isMut: false;
+					isMut: true;
 					isSigner: false;
 				},
 				{
@@ -147,7 +187,7 @@ export type Oracle = {
 				},
 				{
 					name: 'config';
-					isMut: false;
+					isMut: true;
 					isSigner: false;
 				},
 				{
@@ -182,7 +222,7 @@ export type Oracle = {
 				},
 				{
 					name: 'config';
-					isMut: false;
+					isMut: true;
 					isSigner: false;
 				},
 				{
@@ -218,7 +258,7 @@ export type Oracle = {
 				},
 				{
 					name: 'config';
-					isMut: false;
+					isMut: true;
 					isSigner: false;
 				},
 				{
@@ -252,7 +292,7 @@ export type Oracle = {
 				},
 				{
 					name: 'config';
-					isMut: false;
+					isMut: true;
 					isSigner: false;
 				},
 				{
@@ -268,6 +308,115 @@ export type Oracle = {
 				}
 			];
 		},
+		{
+			name: 'updateReputationEnabled';
+			docs: [
+				'update config: reputation',
+				'',
+				'multiply the vote power by the reputation factor of the voter',
+				'',
+				'### args',
+				'* reputation_enabled<bool>: whether the reputation factor applies'
+			];
+			accounts: [
+				{
+					name: 'protocolAuthority';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'config';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'reputationEnabled';
+					type: 'bool';
+				}
+			];
+		},
+		{
+			name: 'updateConsensusEstimator';
+			docs: [
+				'update config: consensus estimator',
+				'',
+				'select how the consensus is calculated from the',
+				'revealed votes of new proposals',
+				'',
+				'### args',
+				'* consensus_estimator<u8>: 0 = mean, 1 = weighted median, 2 = trimmed mean',
+				'* consensus_trim_rate<u32>: 1/x of the vote power trimmed from each tail'
+			];
+			accounts: [
+				{
+					name: 'protocolAuthority';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'config';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'consensusEstimator';
+					type: 'u8';
+				},
+				{
+					name: 'consensusTrimRate';
+					type: 'u32';
+				}
+			];
+		},
+		{
+			name: 'updateClaimBond';
+			docs: [
+				'update claim bond',
+				'',
+				'the tokens a proposer bonds to bind a proposal',
+				'to a pool. The bond is slashed if the claim is rejected',
+				'',
+				'### args',
+				'* claim_bond<u64>: amount of tokens bonded per claim'
+			];
+			accounts: [
+				{
+					name: 'protocolAuthority';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'config';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'claimBond';
+					type: 'u64';
+				}
+			];
+		},
 		{
 			name: 'proposeVote';
 			docs: [
@@ -280,6 +429,7 @@ export type Oracle = {
 				'* `ctx`: Context',
 				'* `name`: Name of the observation',
 				'* `description`: Clear description about the event',
+				'* `evidence`: Reference to the off-chain incident report',
 				'* `stake`: The amount staked on event. In BN:  x*10^{decimals}'
 			];
 			accounts: [
@@ -290,7 +440,7 @@ export type Oracle = {
 				},
 				{
 					name: 'config';
-					isMut: false;
+					isMut: true;
 					isSigner: false;
 					docs: ['configuration for the proposal'];
 				},
@@ -298,20 +448,6 @@ export type Oracle = {
 					name: 'proposal';
 					isMut: true;
 					isSigner: false;
-					pda: {
-						seeds: [
-							{
-								kind: 'const';
-								type: 'string';
-								value: 'sure-oracle';
-							},
-							{
-								kind: 'arg';
-								type: 'bytes';
-								path: 'id';
-							}
-						];
-					};
 				},
 				{
 					name: 'revealVoteArray';
@@ -352,7 +488,7 @@ export type Oracle = {
 							{
 								kind: 'const';
 								type: 'string';
-								value: 'sure-oracle';
+								value: 'sure-oracle-propsal-vault';
 							},
 							{
 								kind: 'account';
@@ -385,10 +521,6 @@ export type Oracle = {
 				}
 			];
 			args: [
-				{
-					name: 'id';
-					type: 'bytes';
-				},
 				{
 					name: 'name';
 					type: 'string';
@@ -397,6 +529,12 @@ export type Oracle = {
 					name: 'description';
 					type: 'string';
 				},
+				{
+					name: 'evidence';
+					type: {
+						defined: 'ProposalEvidence';
+					};
+				},
 				{
 					name: 'stake';
 					type: 'u64';
@@ -404,34 +542,33 @@ export type Oracle = {
 			];
 		},
 		{
-			name: 'submitVote';
+			name: 'bindPool';
 			docs: [
-				'Submit vote',
+				'bind pool',
 				'',
-				'lets user vote blindly on a proposal using a vote hash',
+				'bind the proposal to an insurance pool as a claim. The pool',
+				'is locked against new coverage until the vote is over.',
+				'The proposer bonds config.claim_bond tokens which are',
+				'slashed if the claim is rejected',
 				'',
-				'### Parameters',
-				'* `ctx` - context',
-				'* `vote_hash` - hash of vote with secret salt'
+				'### args',
+				'* claim_amount<u64>: amount claimed for the coverage position.',
+				'Voters vote on the share of the claim amount to pay out'
 			];
 			accounts: [
 				{
-					name: 'voter';
+					name: 'proposer';
 					isMut: true;
 					isSigner: true;
 				},
 				{
-					name: 'voterAccount';
+					name: 'proposerAccount';
 					isMut: true;
 					isSigner: false;
+					docs: ['account paying the claim bond'];
 				},
 				{
-					name: 'locker';
-					isMut: false;
-					isSigner: false;
-				},
-				{
-					name: 'userEscrow';
+					name: 'config';
 					isMut: false;
 					isSigner: false;
 				},
@@ -441,40 +578,60 @@ export type Oracle = {
 					isSigner: false;
 				},
 				{
-					name: 'proposalVault';
-					isMut: true;
-					isSigner: false;
-				},
-				{
-					name: 'proposalVaultMint';
-					isMut: false;
-					isSigner: false;
-				},
-				{
-					name: 'voteAccount';
+					name: 'claimBondVault';
 					isMut: true;
 					isSigner: false;
+					docs: [
+						'vault holding the claim bond until',
+						'the vote on the claim is over'
+					];
 					pda: {
 						seeds: [
 							{
 								kind: 'const';
 								type: 'string';
-								value: 'sure-oracle-vote';
+								value: 'sure-oracle-claim-bond';
 							},
 							{
 								kind: 'account';
 								type: 'publicKey';
 								account: 'Proposal';
 								path: 'proposal';
-							},
-							{
-								kind: 'account';
-								type: 'publicKey';
-								path: 'voter';
 							}
 						];
 					};
 				},
+				{
+					name: 'tokenMint';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'pool';
+					isMut: true;
+					isSigner: false;
+					docs: ['insurance pool to claim against'];
+				},
+				{
+					name: 'poolManager';
+					isMut: false;
+					isSigner: false;
+					docs: [
+						'pool manager holding the oracle config',
+						'trusted with claims'
+					];
+				},
+				{
+					name: 'coveragePosition';
+					isMut: false;
+					isSigner: false;
+					docs: ['coverage position the claim is paid to'];
+				},
+				{
+					name: 'poolProgram';
+					isMut: false;
+					isSigner: false;
+				},
 				{
 					name: 'tokenProgram';
 					isMut: false;
@@ -493,114 +650,217 @@ export type Oracle = {
 			];
 			args: [
 				{
-					name: 'voteHash';
-					type: 'bytes';
+					name: 'claimAmount';
+					type: 'u64';
 				}
 			];
 		},
 		{
-			name: 'updateVote';
+			name: 'releasePool';
 			docs: [
-				'Updates vote',
-				'',
-				'updates the vote hash of the previous submitted vote',
+				'release pool',
 				'',
-				'### parameters',
-				'* `ctx` - context',
-				'* `vote_hash` - hash of vote with secret salt'
+				'unlock the pool when the vote on the claim failed or',
+				'the vote results reject the claim. The claim bond is',
+				'sent to the protocol treasury'
 			];
 			accounts: [
 				{
-					name: 'voter';
+					name: 'signer';
 					isMut: false;
 					isSigner: true;
 				},
+				{
+					name: 'config';
+					isMut: false;
+					isSigner: false;
+				},
 				{
 					name: 'proposal';
-					isMut: true;
+					isMut: false;
 					isSigner: false;
 				},
 				{
-					name: 'voteAccount';
+					name: 'claimBondVault';
 					isMut: true;
 					isSigner: false;
+					docs: ['vault holding the claim bond'];
 					pda: {
 						seeds: [
 							{
 								kind: 'const';
 								type: 'string';
-								value: 'sure-oracle-vote';
+								value: 'sure-oracle-claim-bond';
 							},
 							{
 								kind: 'account';
 								type: 'publicKey';
 								account: 'Proposal';
 								path: 'proposal';
+							}
+						];
+					};
+				},
+				{
+					name: 'protocolTreasury';
+					isMut: true;
+					isSigner: false;
+					docs: ['receives the slashed claim bond'];
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-treasury';
 							},
 							{
 								kind: 'account';
 								type: 'publicKey';
-								path: 'voter';
+								account: 'Config';
+								path: 'config';
 							}
 						];
 					};
 				},
 				{
-					name: 'systemProgram';
+					name: 'pool';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'poolManager';
 					isMut: false;
 					isSigner: false;
-				}
-			];
-			args: [
+					docs: [
+						'pool manager holding the oracle config',
+						'trusted with claims'
+					];
+				},
 				{
-					name: 'voteHash';
-					type: 'bytes';
+					name: 'poolProgram';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
 				}
 			];
+			args: [];
 		},
 		{
-			name: 'cancelVote';
+			name: 'payClaim';
 			docs: [
-				'cancel vote',
+				'pay claim',
 				'',
-				'a user can cancel the vote in the voting period',
-				'',
-				'### parameters',
-				'* `ctx` - CancelVote context'
+				'pay the accepted claim to the coverage position through',
+				'the pool and unlock the pool. The payout is the share of',
+				'the claim amount resolved by the vote and can only be',
+				'paid once. Returns the claim bond to the proposer'
 			];
 			accounts: [
 				{
-					name: 'voter';
-					isMut: true;
+					name: 'signer';
+					isMut: false;
 					isSigner: true;
 				},
 				{
-					name: 'voterAccount';
+					name: 'config';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
 					isMut: true;
 					isSigner: false;
 				},
 				{
-					name: 'proposalVault';
+					name: 'claimBondVault';
+					isMut: true;
+					isSigner: false;
+					docs: ['vault holding the claim bond'];
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-claim-bond';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Proposal';
+								path: 'proposal';
+							}
+						];
+					};
+				},
+				{
+					name: 'proposerAccount';
 					isMut: true;
 					isSigner: false;
+					docs: ['receives the claim bond back'];
 				},
 				{
-					name: 'proposalVaultMint';
+					name: 'pool';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'poolManager';
 					isMut: false;
 					isSigner: false;
+					docs: [
+						'pool manager holding the oracle config',
+						'trusted with claims'
+					];
 				},
 				{
-					name: 'proposal';
+					name: 'tokenPool';
 					isMut: true;
 					isSigner: false;
+					docs: ['token pool the coverage is bought from'];
 				},
 				{
-					name: 'voteAccount';
+					name: 'coveragePosition';
 					isMut: true;
 					isSigner: false;
+					docs: ['coverage position the claim is paid to'];
 				},
 				{
-					name: 'systemProgram';
+					name: 'claimantTokenAccount';
+					isMut: true;
+					isSigner: false;
+					docs: ['token account of the coverage owner'];
+				},
+				{
+					name: 'liquidityVault';
+					isMut: true;
+					isSigner: false;
+					docs: ['liquidity vault of the token pool'];
+				},
+				{
+					name: 'tickArray0';
+					isMut: true;
+					isSigner: false;
+					docs: [
+						'tick arrays holding the liquidity',
+						'used by the coverage position'
+					];
+				},
+				{
+					name: 'tickArray1';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'tickArray2';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'poolProgram';
 					isMut: false;
 					isSigner: false;
 				},
@@ -613,17 +873,14 @@ export type Oracle = {
 			args: [];
 		},
 		{
-			name: 'revealVote';
+			name: 'initializeVoterReputation';
 			docs: [
-				'reveal vote',
-				'',
-				'let the user reveal the vote when the voting period is over',
-				'the user can only receive rewards if revealed',
+				'initialize voter reputation',
 				'',
-				'### parameters',
-				'* `ctx` - RevealVote context',
-				'* `salt` - the salt used to hash the vote',
-				'* `vote`- the actual vote value'
+				'creates the persistent reputation of the voter. The',
+				'reputation is required to vote when the reputation factor',
+				'is enabled and tracks the accuracy, participation and',
+				'reveal rate of the voter'
 			];
 			accounts: [
 				{
@@ -632,49 +889,12 @@ export type Oracle = {
 					isSigner: true;
 				},
 				{
-					name: 'proposal';
-					isMut: true;
-					isSigner: false;
-					pda: {
-						seeds: [
-							{
-								kind: 'const';
-								type: 'string';
-								value: 'sure-oracle';
-							},
-							{
-								kind: 'account';
-								type: {
-									array: ['u8', 16];
-								};
-								account: 'Proposal';
-								path: 'proposal.id';
-							}
-						];
-					};
-				},
-				{
-					name: 'revealVoteArray';
-					isMut: true;
+					name: 'config';
+					isMut: false;
 					isSigner: false;
-					pda: {
-						seeds: [
-							{
-								kind: 'const';
-								type: 'string';
-								value: 'sure-oracle-reveal-array';
-							},
-							{
-								kind: 'account';
-								type: 'publicKey';
-								account: 'Proposal';
-								path: 'proposal';
-							}
-						];
-					};
 				},
 				{
-					name: 'voteAccount';
+					name: 'voterReputation';
 					isMut: true;
 					isSigner: false;
 					pda: {
@@ -682,13 +902,13 @@ export type Oracle = {
 							{
 								kind: 'const';
 								type: 'string';
-								value: 'sure-oracle-vote';
+								value: 'sure-oracle-reputation';
 							},
 							{
 								kind: 'account';
 								type: 'publicKey';
-								account: 'Proposal';
-								path: 'proposal';
+								account: 'Config';
+								path: 'config';
 							},
 							{
 								kind: 'account';
@@ -704,129 +924,124 @@ export type Oracle = {
 					isSigner: false;
 				}
 			];
-			args: [
-				{
-					name: 'salt';
-					type: 'string';
-				},
-				{
-					name: 'vote';
-					type: 'i64';
-				}
-			];
+			args: [];
 		},
 		{
-			name: 'finalizeVoteResults';
+			name: 'submitVote';
 			docs: [
-				'finalize vote results',
-				'',
-				'after the reveal period the proposal can be finalized',
-				'from this point on it is not possible to reveal the vote',
+				'Submit vote',
 				'',
-				'the proposer reward and scale parameter is calculated',
+				'lets user vote blindly on a proposal using a vote hash',
 				'',
-				'### parameters',
-				'*  `ctx` - the Finalize Vote context'
+				'### Parameters',
+				'* `ctx` - context',
+				'* `vote_hash` - hash of vote with secret salt'
 			];
 			accounts: [
 				{
-					name: 'finalizer';
+					name: 'voter';
 					isMut: true;
 					isSigner: true;
 				},
 				{
-					name: 'proposal';
+					name: 'voterAccount';
 					isMut: true;
 					isSigner: false;
 				},
 				{
-					name: 'revealedVotes';
+					name: 'config';
 					isMut: false;
 					isSigner: false;
 				},
 				{
-					name: 'systemProgram';
+					name: 'locker';
 					isMut: false;
 					isSigner: false;
-				}
-			];
-			args: [];
-		},
-		{
-			name: 'finalizeVote';
-			docs: [
-				'finalize vote',
-				'',
-				'after the vote results are finalized the voters can calculate',
-				'their vote share and close their vote account',
-				'',
-				'### parameters',
-				'* `ctx` - Finalize Vote context'
-			];
-			accounts: [
+				},
 				{
-					name: 'signer';
+					name: 'userEscrow';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
 					isMut: true;
-					isSigner: true;
+					isSigner: false;
 				},
 				{
-					name: 'voteAccount';
+					name: 'proposalVault';
 					isMut: true;
 					isSigner: false;
 				},
 				{
-					name: 'proposal';
+					name: 'proposalVaultMint';
 					isMut: false;
 					isSigner: false;
+				},
+				{
+					name: 'voteAccount';
+					isMut: true;
+					isSigner: false;
 					pda: {
 						seeds: [
 							{
 								kind: 'const';
 								type: 'string';
-								value: 'sure-oracle';
+								value: 'sure-oracle-vote';
 							},
 							{
 								kind: 'account';
-								type: {
-									array: ['u8', 16];
-								};
+								type: 'publicKey';
 								account: 'Proposal';
-								path: 'proposal.id';
+								path: 'proposal';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								path: 'voter';
 							}
 						];
 					};
 				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'rent';
+					isMut: false;
+					isSigner: false;
+				},
 				{
 					name: 'systemProgram';
 					isMut: false;
 					isSigner: false;
 				}
 			];
-			args: [];
+			args: [
+				{
+					name: 'voteHash';
+					type: 'bytes';
+				}
+			];
 		},
 		{
-			name: 'collectProposerReward';
+			name: 'updateVote';
 			docs: [
-				'collect proposer reward',
+				'Updates vote',
 				'',
-				'after the vote results are finalized the proposer is free to',
-				'collect the reward at any time'
+				'updates the vote hash of the previous submitted vote',
+				'',
+				'### parameters',
+				'* `ctx` - context',
+				'* `vote_hash` - hash of vote with secret salt'
 			];
 			accounts: [
 				{
-					name: 'proposer';
-					isMut: true;
-					isSigner: true;
-				},
-				{
-					name: 'config';
+					name: 'voter';
 					isMut: false;
-					isSigner: false;
-				},
-				{
-					name: 'proposerTokenAccount';
-					isMut: true;
-					isSigner: false;
+					isSigner: true;
 				},
 				{
 					name: 'proposal';
@@ -834,34 +1049,54 @@ export type Oracle = {
 					isSigner: false;
 				},
 				{
-					name: 'proposalVault';
-					isMut: true;
-					isSigner: false;
-				},
-				{
-					name: 'proposalVaultMint';
+					name: 'voteAccount';
 					isMut: true;
 					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-vote';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Proposal';
+								path: 'proposal';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								path: 'voter';
+							}
+						];
+					};
 				},
 				{
 					name: 'systemProgram';
 					isMut: false;
 					isSigner: false;
-				},
+				}
+			];
+			args: [
 				{
-					name: 'tokenProgram';
-					isMut: false;
-					isSigner: false;
+					name: 'voteHash';
+					type: 'bytes';
 				}
 			];
-			args: [];
 		},
 		{
-			name: 'collectVoteReward';
+			name: 'updateVoteStake';
 			docs: [
-				'collect vote reward',
+				'Update vote stake',
 				'',
-				'after the vote results are finalized the voter can collect rewards'
+				'top up the vote power after extending the escrow lock',
+				'or decrease it and get the stake refunded',
+				'',
+				'### parameters',
+				'* `ctx` - context',
+				'* `vote_power` - new vote power in tokens'
 			];
 			accounts: [
 				{
@@ -869,23 +1104,102 @@ export type Oracle = {
 					isMut: true;
 					isSigner: true;
 				},
+				{
+					name: 'voterAccount';
+					isMut: true;
+					isSigner: false;
+				},
 				{
 					name: 'config';
 					isMut: false;
 					isSigner: false;
 				},
 				{
-					name: 'voterAccount';
+					name: 'locker';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'userEscrow';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposalVault';
 					isMut: true;
 					isSigner: false;
 				},
+				{
+					name: 'proposalVaultMint';
+					isMut: false;
+					isSigner: false;
+				},
 				{
 					name: 'voteAccount';
 					isMut: true;
 					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-vote';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Proposal';
+								path: 'proposal';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								path: 'voter';
+							}
+						];
+					};
 				},
 				{
-					name: 'proposal';
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'votePower';
+					type: 'u64';
+				}
+			];
+		},
+		{
+			name: 'cancelVote';
+			docs: [
+				'cancel vote',
+				'',
+				'a user can cancel the vote in the voting period',
+				'',
+				'### parameters',
+				'* `ctx` - CancelVote context'
+			];
+			accounts: [
+				{
+					name: 'voter';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'voterAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposalVault';
 					isMut: true;
 					isSigner: false;
 				},
@@ -895,7 +1209,12 @@ export type Oracle = {
 					isSigner: false;
 				},
 				{
-					name: 'proposalVault';
+					name: 'proposal';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'voteAccount';
 					isMut: true;
 					isSigner: false;
 				},
@@ -913,906 +1232,3298 @@ export type Oracle = {
 			args: [];
 		},
 		{
-			name: 'collectProtocolFees';
+			name: 'revealVote';
 			docs: [
-				'collect protocol fees',
+				'reveal vote',
+				'',
+				'let the user reveal the vote when the voting period is over',
+				'the user can only receive rewards if revealed',
 				'',
-				'the config authority can at any time collect the protocol fees'
+				'### parameters',
+				'* `ctx` - RevealVote context',
+				'* `salt` - the salt used to hash the vote',
+				'* `vote`- the actual vote value'
 			];
 			accounts: [
 				{
-					name: 'protocolAuthority';
-					isMut: false;
+					name: 'voter';
+					isMut: true;
 					isSigner: true;
 				},
 				{
-					name: 'config';
-					isMut: false;
+					name: 'proposal';
+					isMut: true;
 					isSigner: false;
 					pda: {
 						seeds: [
 							{
 								kind: 'const';
 								type: 'string';
-								value: 'sure-oracle-config';
+								value: 'sure-oracle';
 							},
 							{
 								kind: 'account';
-								type: 'publicKey';
-								account: 'Config';
-								path: 'config.token_mint';
+								type: {
+									array: ['u8', 16];
+								};
+								account: 'Proposal';
+								path: 'proposal.id';
 							}
 						];
 					};
 				},
 				{
-					name: 'proposal';
+					name: 'revealVoteArray';
 					isMut: true;
 					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-reveal-array';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Proposal';
+								path: 'proposal';
+							}
+						];
+					};
 				},
 				{
-					name: 'proposalVault';
+					name: 'voteAccount';
 					isMut: true;
 					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-vote';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Proposal';
+								path: 'proposal';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								path: 'voter';
+							}
+						];
+					};
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'salt';
+					type: 'string';
 				},
 				{
-					name: 'feeDestination';
+					name: 'vote';
+					type: 'i64';
+				}
+			];
+		},
+		{
+			name: 'finalizeVoteResults';
+			docs: [
+				'finalize vote results',
+				'',
+				'after the reveal period the proposal can be finalized',
+				'from this point on it is not possible to reveal the vote',
+				'',
+				'the proposer reward and scale parameter is calculated',
+				'',
+				'### parameters',
+				'*  `ctx` - the Finalize Vote context'
+			];
+			accounts: [
+				{
+					name: 'finalizer';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'proposal';
 					isMut: true;
 					isSigner: false;
 				},
 				{
-					name: 'tokenProgram';
+					name: 'revealedVotes';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'proposalVault';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
 					isMut: false;
 					isSigner: false;
 				}
 			];
 			args: [];
-		}
-	];
-	accounts: [
+		},
 		{
-			name: 'config';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'bump';
-						type: 'u8';
-					},
-					{
-						name: 'votingLengthSeconds';
-						docs: ['voting period in seconds'];
-						type: 'i64';
-					},
-					{
-						name: 'revealLengthSeconds';
-						docs: ['the lenght of the reveal period', 'in seconds'];
-						type: 'i64';
-					},
-					{
-						name: 'defaultRequiredVotes';
-						docs: ['the default required votes to reach', 'quorum.'];
-						type: 'u64';
-					},
-					{
-						name: 'minimumProposalStake';
-						docs: [
-							'the minimum amount of tokens that must',
-							'be staked on a proposal'
-						];
-						type: 'u64';
-					},
-					{
-						name: 'voteStakeRate';
-						docs: [
-							'the 1/x of the voting power that needs',
-							'to be staked in order to vote'
-						];
-						type: 'u32';
-					},
-					{
-						name: 'protocolFeeRate';
-						docs: [
-							'the 1/x of the total voting escrow',
-							"that's going to the protocol"
+			name: 'finalizeVote';
+			docs: [
+				'finalize vote',
+				'',
+				'after the vote results are finalized the voters can calculate',
+				'their vote share and close their vote account',
+				'',
+				'### parameters',
+				'* `ctx` - Finalize Vote context'
+			];
+			accounts: [
+				{
+					name: 'signer';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'voteAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
+					isMut: true;
+					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle';
+							},
+							{
+								kind: 'account';
+								type: {
+									array: ['u8', 16];
+								};
+								account: 'Proposal';
+								path: 'proposal.id';
+							}
 						];
-						type: 'u32';
-					},
-					{
-						name: 'tokenMint';
-						docs: ['official mint of pool'];
-						type: 'publicKey';
-					},
-					{
-						name: 'protocolAuthority';
-						docs: ['who can collect the rewards'];
-						type: 'publicKey';
-					},
-					{
-						name: 'initialized';
-						type: 'bool';
-					}
-				];
-			};
+					};
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
 		},
 		{
-			name: 'proposal';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'config';
-						type: 'publicKey';
-					},
-					{
-						name: 'bump';
-						docs: ['bump for verification'];
-						type: 'u8';
-					},
-					{
-						name: 'bumpArray';
-						type: {
-							array: ['u8', 1];
-						};
-					},
-					{
-						name: 'locked';
-						docs: ['when the vote is finished and', 'users can reap rewards'];
-						type: 'bool';
-					},
-					{
-						name: 'optimistic';
-						docs: ['Optimistic'];
-						type: 'bool';
-					},
-					{
-						name: 'status';
-						type: 'u8';
-					},
-					{
-						name: 'name';
-						docs: ['name of vote'];
-						type: 'string';
-					},
-					{
-						name: 'id';
-						docs: ['id - hashed name'];
-						type: {
-							array: ['u8', 16];
-						};
-					},
-					{
-						name: 'description';
-						docs: ['description of vote'];
-						type: 'string';
-					},
-					{
-						name: 'proposedResult';
-						docs: ['Proposed result'];
-						type: 'i64';
-					},
-					{
-						name: 'proposer';
-						docs: ['user who proposed the vote'];
-						type: 'publicKey';
-					},
-					{
-						name: 'stakeRate';
-						docs: ['1/x of vote power that must be staked'];
-						type: 'u32';
-					},
-					{
-						name: 'staked';
-						docs: ['amount staked by propose Q32.32'];
-						type: 'u64';
-					},
-					{
-						name: 'vault';
-						docs: ['vault for storing stake and votes'];
-						type: 'publicKey';
-					},
-					{
-						name: 'requiredVotes';
-						docs: [
-							'% of ve tokens needed to conclude',
-							'represented as basis points 1% = 100bp'
-						];
-						type: 'u64';
-					},
-					{
-						name: 'protocolFeeRate';
-						docs: ['as 1/x of revealed vote staked'];
-						type: 'u32';
-					},
-					{
-						name: 'votes';
-						docs: [
-							'Current votes given in basis points',
-							'1 vote = 1 veToken@',
-							'Q64.0'
-						];
-						type: 'u64';
-					},
-					{
-						name: 'revealedVotes';
-						type: 'u64';
-					},
-					{
-						name: 'runningSumWeightedVote';
-						type: 'i64';
-					},
-					{
-						name: 'runningWeight';
-						type: 'u64';
-					},
-					{
-						name: 'voteStartAt';
-						docs: ['Start of vote'];
-						type: 'i64';
-					},
-					{
-						name: 'voteEndAt';
-						docs: ['Blind vote deadline'];
-						type: 'i64';
-					},
-					{
-						name: 'voteEndRevealAt';
-						docs: ['start reveal'];
-						type: 'i64';
-					},
-					{
-						name: 'earnedRewards';
-						docs: ['reward earned by propsing vote', 'Q64.64'];
-						type: 'u128';
-					},
-					{
-						name: 'protocolFees';
-						docs: ['protocol fees'];
-						type: 'u128';
-					},
-					{
-						name: 'scaleParameter';
-						docs: ['Scale parameter in exp(L)', 'Q16.16'];
-						type: 'u32';
-					},
-					{
-						name: 'scaleParameterCalculated';
-						type: 'bool';
-					},
-					{
-						name: 'voteFactorSum';
-						type: 'u64';
-					},
-					{
-						name: 'distributionSum';
-						type: 'u128';
-					},
+			name: 'collectProposerReward';
+			docs: [
+				'collect proposer reward',
+				'',
+				'after the vote results are finalized the proposer is free to',
+				'collect the reward at any time'
+			];
+			accounts: [
+				{
+					name: 'proposer';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'config';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'proposerTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposalVault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposalVaultMint';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'collectVoteReward';
+			docs: [
+				'collect vote reward',
+				'',
+				'after the vote results are finalized the voter can collect rewards'
+			];
+			accounts: [
+				{
+					name: 'voter';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'config';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'voterAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'voteAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposalVaultMint';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'proposalVault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'collectProtocolFees';
+			docs: [
+				'collect protocol fees',
+				'',
+				'sweep the accrued protocol fees of the proposals passed',
+				'as remaining accounts into the protocol treasury',
+				'',
+				'### remaining accounts',
+				'* pairs of (proposal, proposal_vault)'
+			];
+			accounts: [
+				{
+					name: 'signer';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'config';
+					isMut: false;
+					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-config';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Config';
+								path: 'config.token_mint';
+							}
+						];
+					};
+				},
+				{
+					name: 'protocolTreasury';
+					isMut: true;
+					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-treasury';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Config';
+								path: 'config';
+							}
+						];
+					};
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'withdrawProtocolFees';
+			docs: [
+				'withdraw protocol fees',
+				'',
+				'the config authority can at any time withdraw the',
+				'protocol fees from the protocol treasury',
+				'',
+				'### args',
+				'* amount<u64>: amount of tokens to withdraw'
+			];
+			accounts: [
+				{
+					name: 'protocolAuthority';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'config';
+					isMut: false;
+					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-config';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Config';
+								path: 'config.token_mint';
+							}
+						];
+					};
+				},
+				{
+					name: 'protocolTreasury';
+					isMut: true;
+					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-treasury';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Config';
+								path: 'config';
+							}
+						];
+					};
+				},
+				{
+					name: 'feeDestination';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'amount';
+					type: 'u64';
+				}
+			];
+		},
+		{
+			name: 'initializeIncentiveVault';
+			docs: [
+				'initialize incentive vault',
+				'',
+				'attach a vault in a separate mint to the proposal. The',
+				'incentives are distributed by the same weights as the',
+				'voter rewards. The proposer or the founder of the bound',
+				'pool can sponsor the incentives'
+			];
+			accounts: [
+				{
+					name: 'sponsor';
+					isMut: true;
+					isSigner: true;
+					docs: [
+						'sponsor of the incentives. Either the proposer',
+						'or the founder of the pool bound to the proposal'
+					];
+				},
+				{
+					name: 'proposal';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'incentiveMint';
+					isMut: false;
+					isSigner: false;
+					docs: ['mint of the incentive rewards'];
+				},
+				{
+					name: 'incentiveVault';
+					isMut: true;
+					isSigner: false;
+					pda: {
+						seeds: [
+							{
+								kind: 'const';
+								type: 'string';
+								value: 'sure-oracle-incentive-vault';
+							},
+							{
+								kind: 'account';
+								type: 'publicKey';
+								account: 'Proposal';
+								path: 'proposal';
+							}
+						];
+					};
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'rent';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'fundIncentiveVault';
+			docs: [
+				'fund incentive vault',
+				'',
+				'add incentives to the proposal until the vote',
+				'results are finalized',
+				'',
+				'### args',
+				'* amount<u64>: amount of incentive tokens'
+			];
+			accounts: [
+				{
+					name: 'funder';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'funderAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'incentiveVault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'amount';
+					type: 'u64';
+				}
+			];
+		},
+		{
+			name: 'collectVoteIncentive';
+			docs: [
+				'collect vote incentive',
+				'',
+				'after the vote results are finalized the voter can collect',
+				'the share of the incentives'
+			];
+			accounts: [
+				{
+					name: 'voter';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'voterAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'voteAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'incentiveVault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'reclaimIncentives';
+			docs: [
+				'reclaim incentives',
+				'',
+				'the sponsor can reclaim the incentives if the vote',
+				'failed or no voter is eligible for rewards'
+			];
+			accounts: [
+				{
+					name: 'sponsor';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'sponsorAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'proposal';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'incentiveVault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		}
+	];
+	accounts: [
+		{
+			name: 'config';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'bump';
+						type: 'u8';
+					},
+					{
+						name: 'bumpArray';
+						type: {
+							array: ['u8', 1];
+						};
+					},
+					{
+						name: 'votingLengthSeconds';
+						docs: ['voting period in seconds'];
+						type: 'i64';
+					},
+					{
+						name: 'revealLengthSeconds';
+						docs: ['the lenght of the reveal period', 'in seconds'];
+						type: 'i64';
+					},
+					{
+						name: 'defaultRequiredVotes';
+						docs: ['the default required votes to reach', 'quorum.'];
+						type: 'u64';
+					},
+					{
+						name: 'minimumProposalStake';
+						docs: [
+							'the minimum amount of tokens that must',
+							'be staked on a proposal'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'voteStakeRate';
+						docs: [
+							'the 1/x of the voting power that needs',
+							'to be staked in order to vote'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'protocolFeeRate';
+						docs: [
+							'the 1/x of the total voting escrow',
+							"that's going to the protocol"
+						];
+						type: 'u32';
+					},
+					{
+						name: 'tokenMint';
+						docs: ['official mint of pool'];
+						type: 'publicKey';
+					},
+					{
+						name: 'protocolAuthority';
+						docs: ['who can collect the rewards'];
+						type: 'publicKey';
+					},
+					{
+						name: 'locker';
+						docs: ['locker of the escrows used to vote'];
+						type: 'publicKey';
+					},
+					{
+						name: 'proposals';
+						docs: ['number of proposals created'];
+						type: 'u64';
+					},
+					{
+						name: 'reputationEnabled';
+						docs: [
+							'multiply the vote power by the',
+							'reputation factor of the voter'
+						];
+						type: 'bool';
+					},
+					{
+						name: 'consensusEstimator';
+						docs: [
+							'estimator used to reach consensus',
+							'see ConsensusEstimator'
+						];
+						type: 'u8';
+					},
+					{
+						name: 'consensusTrimRate';
+						docs: [
+							'the 1/x of the vote power trimmed from',
+							'each tail by the trimmed mean'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'claimBond';
+						docs: [
+							'tokens bonded by the proposer to bind a',
+							'pool. Slashed if the claim is rejected'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'initialized';
+						type: 'bool';
+					}
+				];
+			};
+		},
+		{
+			name: 'proposal';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'config';
+						type: 'publicKey';
+					},
+					{
+						name: 'bump';
+						docs: ['bump for verification'];
+						type: 'u8';
+					},
+					{
+						name: 'bumpArray';
+						type: {
+							array: ['u8', 1];
+						};
+					},
+					{
+						name: 'locked';
+						docs: ['when the vote is finished and', 'users can reap rewards'];
+						type: 'bool';
+					},
+					{
+						name: 'optimistic';
+						docs: ['Optimistic'];
+						type: 'bool';
+					},
+					{
+						name: 'status';
+						type: 'u8';
+					},
+					{
+						name: 'name';
+						docs: ['name of vote'];
+						type: 'string';
+					},
+					{
+						name: 'id';
+						docs: ['id - hash of the proposal content'];
+						type: {
+							array: ['u8', 16];
+						};
+					},
+					{
+						name: 'description';
+						docs: ['description of vote'];
+						type: 'string';
+					},
+					{
+						name: 'evidence';
+						docs: ['reference to the off-chain evidence'];
+						type: {
+							defined: 'ProposalEvidence';
+						};
+					},
+					{
+						name: 'pool';
+						docs: [
+							'insurance pool the proposal claims against.',
+							'Pubkey::default() if not bound to a pool'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'claimBond';
+						docs: [
+							'tokens bonded by the proposer when binding',
+							'the pool. Slashed if the claim is rejected'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'coveragePosition';
+						docs: ['coverage position the claim is paid to'];
+						type: 'publicKey';
+					},
+					{
+						name: 'claimAmount';
+						docs: [
+							'amount claimed for the coverage position.',
+							'The payout is the share of the claim',
+							'voted on by the voters'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'claimPaid';
+						docs: ['whether the accepted claim is paid out'];
+						type: 'bool';
+					},
+					{
+						name: 'proposedResult';
+						docs: ['Proposed result'];
+						type: 'i64';
+					},
+					{
+						name: 'proposer';
+						docs: ['user who proposed the vote'];
+						type: 'publicKey';
+					},
+					{
+						name: 'stakeRate';
+						docs: ['1/x of vote power that must be staked'];
+						type: 'u32';
+					},
+					{
+						name: 'staked';
+						docs: ['amount staked by propose Q32.32'];
+						type: 'u64';
+					},
+					{
+						name: 'vault';
+						docs: ['vault for storing stake and votes'];
+						type: 'publicKey';
+					},
+					{
+						name: 'requiredVotes';
+						docs: [
+							'% of ve tokens needed to conclude',
+							'represented as basis points 1% = 100bp'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'protocolFeeRate';
+						docs: ['as 1/x of revealed vote staked'];
+						type: 'u32';
+					},
+					{
+						name: 'votes';
+						docs: [
+							'Current votes given in basis points',
+							'1 vote = 1 veToken@',
+							'Q64.0'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'revealedVotes';
+						type: 'u64';
+					},
+					{
+						name: 'runningSumWeightedVote';
+						type: 'i64';
+					},
+					{
+						name: 'runningWeight';
+						type: 'u64';
+					},
+					{
+						name: 'voteStartAt';
+						docs: ['Start of vote'];
+						type: 'i64';
+					},
+					{
+						name: 'voteEndAt';
+						docs: ['Blind vote deadline'];
+						type: 'i64';
+					},
+					{
+						name: 'voteEndRevealAt';
+						docs: ['start reveal'];
+						type: 'i64';
+					},
+					{
+						name: 'earnedRewards';
+						docs: ['reward earned by propsing vote', 'Q64.64'];
+						type: 'u128';
+					},
+					{
+						name: 'protocolFees';
+						docs: ['protocol fees accrued', 'Q64.64'];
+						type: 'u128';
+					},
+					{
+						name: 'collectedProtocolFees';
+						docs: ['total protocol fees sent to the', 'protocol treasury'];
+						type: 'u64';
+					},
+					{
+						name: 'scaleParameter';
+						docs: ['Scale parameter in exp(L)', 'Q16.16'];
+						type: 'u32';
+					},
+					{
+						name: 'scaleParameterCalculated';
+						type: 'bool';
+					},
+					{
+						name: 'voteFactorSum';
+						docs: ['sum of the vote factors X_i', 'Q32.32'];
+						type: 'u64';
+					},
+					{
+						name: 'distributionSum';
+						docs: [
+							'sum of the reward weights F_i = V_i * X_i',
+							'used to normalize the voter rewards',
+							'Q64.32'
+						];
+						type: 'u128';
+					},
+					{
+						name: 'finalizedVotes';
+						docs: [
+							'vote power of the revealed votes that',
+							'have calculated their vote factor',
+							'Q64.0'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'rewardPool';
+						docs: [
+							'amount of tokens to be distributed',
+							'among the revealed votes'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'incentiveMint';
+						docs: [
+							'optional mint of the incentive rewards',
+							'Pubkey::default() if no incentives are attached'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'incentiveVault';
+						docs: ['vault holding the incentive rewards'];
+						type: 'publicKey';
+					},
+					{
+						name: 'incentiveSponsor';
+						docs: [
+							'account that attached the incentive vault. Receives',
+							'the incentives back if they cannot be distributed'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'incentivePool';
+						docs: [
+							'amount of incentive tokens to be distributed',
+							'among the revealed votes'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'consensus';
+						docs: ['consensus reached after the reveal', 'I32.32'];
+						type: 'i64';
+					},
+					{
+						name: 'consensusEstimator';
+						docs: [
+							'estimator used to calculate the consensus',
+							'see ConsensusEstimator'
+						];
+						type: 'u8';
+					},
+					{
+						name: 'consensusTrimRate';
+						docs: [
+							'1/x of the vote power trimmed from each',
+							'tail when using the trimmed mean'
+						];
+						type: 'u32';
+					}
+				];
+			};
+		},
+		{
+			name: 'voterReputation';
+			docs: [
+				'Voter reputation',
+				'',
+				'persistent record of the voting history of a voter',
+				'for a given config. Updated when the vote is submitted',
+				'and when the vote is finalized'
+			];
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'bump';
+						type: 'u8';
+					},
+					{
+						name: 'bumpArray';
+						type: {
+							array: ['u8', 1];
+						};
+					},
+					{
+						name: 'config';
+						type: 'publicKey';
+					},
+					{
+						name: 'owner';
+						type: 'publicKey';
+					},
+					{
+						name: 'proposalsAtStart';
+						docs: [
+							'number of proposals in the config when',
+							'the reputation was created'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'votes';
+						docs: ['number of submitted votes'];
+						type: 'u64';
+					},
+					{
+						name: 'finalizedVotes';
+						docs: ['number of revealed and finalized votes'];
+						type: 'u64';
+					},
+					{
+						name: 'accuracySum';
+						docs: ['sum of accuracy scores exp(-l*(x-X)^2)', 'Q32.32'];
+						type: 'u64';
+					},
+					{
+						name: 'consensusDistanceSum';
+						docs: ['sum of absolute distances to consensus', 'Q64.32'];
+						type: 'u128';
+					}
+				];
+			};
+		},
+		{
+			name: 'revealedVoteArray';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'bump';
+						type: 'u8';
+					},
+					{
+						name: 'proposal';
+						type: 'publicKey';
+					},
+					{
+						name: 'weightedVotes';
+						docs: ['Q32.32'];
+						type: {
+							array: ['i64', 768];
+						};
+					},
+					{
+						name: 'votePowers';
+						docs: ['vote power of each revealed vote'];
+						type: {
+							array: ['u32', 768];
+						};
+					},
+					{
+						name: 'lastIndex';
+						type: 'i16';
+					}
+				];
+			};
+		},
+		{
+			name: 'voteAccount';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'bump';
+						type: 'u8';
+					},
 					{
-						name: 'consensus';
+						name: 'bumpArray';
+						type: {
+							array: ['u8', 1];
+						};
+					},
+					{
+						name: 'proposal';
+						type: 'publicKey';
+					},
+					{
+						name: 'owner';
+						type: 'publicKey';
+					},
+					{
+						name: 'stakeMint';
+						type: 'publicKey';
+					},
+					{
+						name: 'voteHash';
+						type: {
+							array: ['u8', 32];
+						};
+					},
+					{
+						name: 'vote';
+						docs: ['real vote:', 'I32.32'];
 						type: 'i64';
+					},
+					{
+						name: 'staked';
+						type: 'u64';
+					},
+					{
+						name: 'voteFactor';
+						docs: ['F = V * l * exp(-l*(x-X))'];
+						type: 'u64';
+					},
+					{
+						name: 'earnedRewards';
+						docs: ['rewards earned from voting', 'C * F / S_v'];
+						type: 'u64';
+					},
+					{
+						name: 'votePower';
+						type: 'u32';
+					},
+					{
+						name: 'escrowAmount';
+						docs: [
+							'tokens locked in the escrow when the vote',
+							'was submitted. Caps later vote power updates'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'revealedVote';
+						type: 'bool';
+					},
+					{
+						name: 'voteFactorCalculated';
+						type: 'bool';
+					},
+					{
+						name: 'incentiveCollected';
+						type: 'bool';
+					},
+					{
+						name: 'locked';
+						type: 'bool';
+					}
+				];
+			};
+		}
+	];
+	types: [
+		{
+			name: 'ProposalEvidence';
+			docs: [
+				'Proposal evidence',
+				'',
+				'structured reference to the off-chain incident report',
+				'so that voters and keepers can verify what they vote on'
+			];
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'uri';
+						docs: ['where to find the incident report'];
+						type: 'string';
+					},
+					{
+						name: 'contentHash';
+						docs: ['sha3-256 hash of the incident report'];
+						type: {
+							array: ['u8', 32];
+						};
+					},
+					{
+						name: 'affectedProgram';
+						docs: ['program affected by the incident'];
+						type: 'publicKey';
+					},
+					{
+						name: 'startSlot';
+						docs: ['first slot of the incident'];
+						type: 'u64';
+					},
+					{
+						name: 'endSlot';
+						docs: ['last slot of the incident'];
+						type: 'u64';
+					}
+				];
+			};
+		},
+		{
+			name: 'Strategy';
+			docs: ['How a voter decides on the vote'];
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'Honest';
+					},
+					{
+						name: 'Lazy';
+					},
+					{
+						name: 'Adversarial';
+					}
+				];
+			};
+		},
+		{
+			name: 'ConsensusEstimator';
+			docs: ['Estimator used to reach consensus', 'from the revealed votes'];
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'Mean';
+					},
+					{
+						name: 'WeightedMedian';
+					},
+					{
+						name: 'TrimmedMean';
+					}
+				];
+			};
+		},
+		{
+			name: 'ProposalStatus';
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'Failed';
+					},
+					{
+						name: 'Proposed';
+					},
+					{
+						name: 'Voting';
+					},
+					{
+						name: 'ReachedQuorum';
+					},
+					{
+						name: 'RevealVote';
+					},
+					{
+						name: 'VoteRevealFinished';
+					},
+					{
+						name: 'RewardCalculation';
+					},
+					{
+						name: 'RewardPayout';
 					}
 				];
 			};
+		}
+	];
+	events: [
+		{
+			name: 'BoundPoolEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'pool';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'smartContract';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'coveragePosition';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'claimAmount';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'claimBond';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'CancelledVote';
+			fields: [
+				{
+					name: 'vote';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'refund';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'CollectProposerRewardEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'reward';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'CollectedProtocolFees';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'fees';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'totalCollectedFees';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'destination';
+					type: 'publicKey';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'SweptProtocolFees';
+			fields: [
+				{
+					name: 'config';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'fees';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'proposals';
+					type: 'u32';
+					index: false;
+				},
+				{
+					name: 'protocolTreasury';
+					type: 'publicKey';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'CollectVoteRewardEvent';
+			fields: [
+				{
+					name: 'vote';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'reward';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'InitializedConfigEvent';
+			fields: [
+				{
+					name: 'config';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'protocolTreasury';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'locker';
+					type: 'publicKey';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedVotingPeriod';
+			fields: [
+				{
+					name: 'oldVotingPeriod';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'votingPeriod';
+					type: 'i64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedRevealPeriod';
+			fields: [
+				{
+					name: 'oldRevealPeriod';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'revealPeriod';
+					type: 'i64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedRequiredVotes';
+			fields: [
+				{
+					name: 'oldRequiredVotes';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'requiredVotes';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedProposalMinumumStake';
+			fields: [
+				{
+					name: 'oldMinimumStake';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'minimumStake';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedVoteStakeRate';
+			fields: [
+				{
+					name: 'oldVoteStakeRate';
+					type: 'u32';
+					index: false;
+				},
+				{
+					name: 'voteStakeRate';
+					type: 'u32';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedProtocolFeerate';
+			fields: [
+				{
+					name: 'oldProtocolFeeRate';
+					type: 'u32';
+					index: false;
+				},
+				{
+					name: 'protocolFeeRate';
+					type: 'u32';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedReputationEnabled';
+			fields: [
+				{
+					name: 'reputationEnabled';
+					type: 'bool';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedConsensusEstimator';
+			fields: [
+				{
+					name: 'oldConsensusEstimator';
+					type: 'u8';
+					index: false;
+				},
+				{
+					name: 'consensusEstimator';
+					type: 'u8';
+					index: false;
+				},
+				{
+					name: 'consensusTrimRate';
+					type: 'u32';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedClaimBond';
+			fields: [
+				{
+					name: 'oldClaimBond';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'claimBond';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'FinalizedVoteResultsEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'revealedVotes';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'consensus';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'rewardPool';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'status';
+					type: 'u8';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'FinalizedVoteEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'CollectVoteIncentiveEvent';
+			fields: [
+				{
+					name: 'vote';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'reward';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'FundedIncentiveVaultEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'funder';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'amount';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'incentivePool';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'InitializedIncentiveVaultEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'incentiveMint';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'incentiveVault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'sponsor';
+					type: 'publicKey';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'ReclaimedIncentivesEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'sponsor';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'amount';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'InitializedVoterReputationEvent';
+			fields: [
+				{
+					name: 'config';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'voter';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'voterReputation';
+					type: 'publicKey';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'PaidClaimEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'pool';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'coveragePosition';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'claimAmount';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'claimPayout';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'claimBond';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'ProposeVoteEvent';
+			fields: [
+				{
+					name: 'name';
+					type: 'string';
+					index: false;
+				},
+				{
+					name: 'description';
+					type: 'string';
+					index: false;
+				},
+				{
+					name: 'id';
+					type: 'bytes';
+					index: false;
+				},
+				{
+					name: 'evidence';
+					type: {
+						defined: 'ProposalEvidence';
+					};
+					index: false;
+				},
+				{
+					name: 'proposer';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'stake';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'ReleasedPoolEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'pool';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'claimBond';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'bondDestination';
+					type: 'publicKey';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'RevealedVoteEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'revealedVote';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'votePower';
+					type: 'u32';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'SubmittedVoteEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'voteHash';
+					type: 'bytes';
+					index: false;
+				},
+				{
+					name: 'votePower';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedVoteStakeEvent';
+			fields: [
+				{
+					name: 'vote';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'votePower';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'stakeChange';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'increaseStake';
+					type: 'bool';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'UpdatedVoteEvent';
+			fields: [
+				{
+					name: 'proposal';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'time';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'voteHash';
+					type: 'bytes';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'WithdrewProtocolFees';
+			fields: [
+				{
+					name: 'config';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'amount';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'destination';
+					type: 'publicKey';
+					index: false;
+				}
+			];
+		}
+	];
+	errors: [
+		{
+			code: 6000;
+			name: 'StakeTooLittle';
+			msg: 'Not enough staked on vote';
+		},
+		{
+			code: 6001;
+			name: 'InvalidLockPeriod';
+			msg: 'Invalid lock period';
+		},
+		{
+			code: 6002;
+			name: 'InvalidVoteEndTime';
+			msg: 'Invalid vote end time';
+		},
+		{
+			code: 6003;
+			name: 'VotingPeriodEnded';
+			msg: 'Voting period for proposal has ended';
+		},
+		{
+			code: 6004;
+			name: 'RevealPeriodNotActive';
+			msg: 'Currently not in vote reveal period';
+		},
+		{
+			code: 6005;
+			name: 'RevealPeriodIsNotFinished';
+			msg: 'Reveal period is not over';
+		},
+		{
+			code: 6006;
+			name: 'InvalidSalt';
+			msg: 'Invalid salt resulted in invalid vote_hash';
+		},
+		{
+			code: 6007;
+			name: 'FullRevealList';
+			msg: 'Revealed vote list full';
+		},
+		{
+			code: 6008;
+			name: 'VoteNotRevealed';
+			msg: "Vote hasn't been revealed";
+		},
+		{
+			code: 6009;
+			name: 'OverflowU64';
+			msg: 'U64 overflow';
+		},
+		{
+			code: 6010;
+			name: 'OverflowU32';
+			msg: 'U32 overflow';
+		},
+		{
+			code: 6011;
+			name: 'OverflowU128';
+			msg: 'U128 overflow';
+		},
+		{
+			code: 6012;
+			name: 'NotPossibleToCalculateVoteReward';
+			msg: 'Could not calculate the vote reward at this time';
+		},
+		{
+			code: 6013;
+			name: 'NotPossibleToCollectProposerReward';
+			msg: 'Cannot payout the proposer reward at this time';
+		},
+		{
+			code: 6014;
+			name: 'NotPossibleToCollectVoterReward';
+			msg: 'Cannot payout the voter reward at this time';
+		},
+		{
+			code: 6015;
+			name: 'FailedToFinalizeVote';
+			msg: 'Cannot finalize user vote at this time';
+		},
+		{
+			code: 6016;
+			name: 'FailedToFinalizeVoteResult';
+			msg: 'Cannot finalize vote result at this time';
+		},
+		{
+			code: 6017;
+			name: 'FailedToCancelVote';
+			msg: 'Too late to cancel vote';
+		},
+		{
+			code: 6018;
+			name: 'InvalidOwnerOfVoteAccount';
+			msg: 'The owner of the vote account is not the signer';
+		},
+		{
+			code: 6019;
+			name: 'ProposalVaultMintKeyDoesNotMatchProposalStateVaultMint';
+			msg: 'Proposal.vault_mint does not match the input proposal_vault_mint key';
+		},
+		{
+			code: 6020;
+			name: 'ProposalVaultMintKeyDoesNotMatchVaultMint';
+			msg: 'Proposal.vault_mint does not match the vault mint key';
+		},
+		{
+			code: 6021;
+			name: 'NotEnoughProposalStake';
+			msg: 'Not enough stake to propose a vote ';
+		},
+		{
+			code: 6022;
+			name: 'InvalidRequiredVotesParam';
+			msg: 'Quorum requirements are too low';
+		},
+		{
+			code: 6023;
+			name: 'InvalidMinimumStakedParam';
+			msg: 'Invalid minimum staked on proposal';
+		},
+		{
+			code: 6024;
+			name: 'InvalidVoteStakeRateParam';
+			msg: 'Invalid vote stake rate param. Probably less than 1';
+		},
+		{
+			code: 6025;
+			name: 'InvalidProtocolFeeRateParam';
+			msg: 'Invalid protocol fee rate param. Probably less than 1';
+		},
+		{
+			code: 6026;
+			name: 'UnauthorizedSigner';
+			msg: 'Unauthorized signer';
+		},
+		{
+			code: 6027;
+			name: 'InvalidRemainingAccounts';
+			msg: 'Remaining accounts must be pairs of proposal and proposal vault';
+		},
+		{
+			code: 6028;
+			name: 'InvalidProposalConfig';
+			msg: 'Proposal does not belong to the config';
+		},
+		{
+			code: 6029;
+			name: 'InvalidProposalVault';
+			msg: 'Proposal vault does not match the proposal';
+		},
+		{
+			code: 6030;
+			name: 'IncentiveVaultAlreadyInitialized';
+			msg: 'Incentive vault is already attached to the proposal';
+		},
+		{
+			code: 6031;
+			name: 'IncentiveVaultNotInitialized';
+			msg: 'Proposal has no incentive vault';
+		},
+		{
+			code: 6032;
+			name: 'NotPossibleToFundIncentives';
+			msg: 'Cannot fund the incentives at this time';
+		},
+		{
+			code: 6033;
+			name: 'NotPossibleToReclaimIncentives';
+			msg: 'Cannot reclaim the incentives at this time';
+		},
+		{
+			code: 6034;
+			name: 'IncentiveAlreadyCollected';
+			msg: 'Incentive reward is already collected';
+		},
+		{
+			code: 6035;
+			name: 'InvalidIncentiveSponsor';
+			msg: 'Signer is not the sponsor of the incentives';
+		},
+		{
+			code: 6036;
+			name: 'InvalidLocker';
+			msg: 'Locker does not match the config locker';
+		},
+		{
+			code: 6037;
+			name: 'EscrowLockedAfterVoteStart';
+			msg: 'Escrow was locked after the vote started';
+		},
+		{
+			code: 6038;
+			name: 'InvalidVotePower';
+			msg: 'Could not calculate the vote power of the escrow';
+		},
+		{
+			code: 6039;
+			name: 'VotePowerExceedsEscrow';
+			msg: 'Vote power exceeds the vote power of the escrow';
+		},
+		{
+			code: 6040;
+			name: 'MissingVoterReputation';
+			msg: 'Voter reputation is required when the reputation is enabled';
+		},
+		{
+			code: 6041;
+			name: 'InvalidVoterReputation';
+			msg: 'Voter reputation does not belong to the voter';
+		},
+		{
+			code: 6042;
+			name: 'ProposalContentTooLong';
+			msg: 'Proposal name, description or evidence is too long';
+		},
+		{
+			code: 6043;
+			name: 'InvalidEvidenceSlotRange';
+			msg: 'Evidence slot range ends before it starts';
+		},
+		{
+			code: 6044;
+			name: 'ProposalAlreadyBoundToPool';
+			msg: 'Proposal is already bound to a pool';
+		},
+		{
+			code: 6045;
+			name: 'ProposalNotBoundToPool';
+			msg: 'Proposal is not bound to a pool';
+		},
+		{
+			code: 6046;
+			name: 'InvalidPoolForProposal';
+			msg: 'Pool does not insure the program affected by the proposal';
+		},
+		{
+			code: 6047;
+			name: 'NotPossibleToReleasePool';
+			msg: 'Cannot release the pool before the vote is over';
+		},
+		{
+			code: 6048;
+			name: 'InvalidClaimAmount';
+			msg: 'Claim amount is zero or larger than the coverage';
+		},
+		{
+			code: 6049;
+			name: 'ClaimNotAccepted';
+			msg: 'Claim is not accepted';
+		},
+		{
+			code: 6050;
+			name: 'ClaimAlreadyPaid';
+			msg: 'Claim is already paid';
+		},
+		{
+			code: 6051;
+			name: 'ClaimIsAccepted';
+			msg: 'Accepted claims release the pool when they are paid';
+		},
+		{
+			code: 6052;
+			name: 'InvalidTokenPool';
+			msg: 'Token pool does not hold the coverage of the claim';
+		},
+		{
+			code: 6053;
+			name: 'InvalidClaimant';
+			msg: 'Claimant is not the owner of the coverage position';
+		},
+		{
+			code: 6054;
+			name: 'PoolIsPaused';
+			msg: 'Pool is paused';
+		},
+		{
+			code: 6055;
+			name: 'PoolIsLocked';
+			msg: 'Pool is already locked for a claim';
+		},
+		{
+			code: 6056;
+			name: 'InvalidConsensusEstimator';
+			msg: 'Unknown consensus estimator';
+		},
+		{
+			code: 6057;
+			name: 'InvalidConsensusTrimRate';
+			msg: 'Trim rate must be larger than 2';
+		}
+	];
+};
+
+export const IDL: Oracle = {
+	version: '0.1.0',
+	name: 'oracle',
+	instructions: [
+		{
+			name: 'initializeConfig',
+			docs: [
+				'initialize config',
+				'',
+				'config will be used in proposals to set voting parameters',
+				'and limit protocol fee collectors to the protocol_authority',
+				'',
+				'### args',
+				'* protocol_authority<Pubkey>: unique for vault mint. the authority can',
+				'- change config parameters',
+				'- collect protocol fees',
+				'',
+				'### accounts',
+				'* locker: locker of the escrows that can vote on proposals',
+			],
+			accounts: [
+				{
+					name: 'signer',
+					isMut: true,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-config',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Mint',
+								path: 'token_mint',
+							},
+						],
+					},
+				},
+				{
+					name: 'tokenMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'locker',
+					isMut: false,
+					isSigner: false,
+					docs: ['locker of the escrows that can vote'],
+				},
+				{
+					name: 'protocolTreasury',
+					isMut: true,
+					isSigner: false,
+					docs: ['treasury for the protocol fees'],
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-treasury',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Config',
+								path: 'config',
+							},
+						],
+					},
+				},
+				{
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'rent',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'protocolAuthority',
+					type: 'publicKey',
+				},
+			],
+		},
+		{
+			name: 'updateVotingPeriod',
+			docs: [
+				'update config: voting period',
+				'',
+				'change the voting period and reveal period',
+				'',
+				'### args',
+				'* voting_period<i64>: period for which the voter can submit a vote hash. In seconds',
+				'* reveal_period<i64>: period for which the voter can reveal the vote. In seconds',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'votingPeriod',
+					type: 'i64',
+				},
+			],
+		},
+		{
+			name: 'updateRevealPeriod',
+			docs: [
+				'update config: reveal period',
+				'',
+				'change the reveal period and reveal period',
+				'',
+				'### args',
+				'* voting_period<i64>: period for which the voter can submit a vote hash. In seconds',
+				'* reveal_period<i64>: period for which the voter can reveal the vote. In seconds',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'votingPeriod',
+					type: 'i64',
+				},
+			],
+		},
+		{
+			name: 'updateRequiredVotes',
+			docs: [
+				'update required votes',
+				'',
+				'required votes to reach quorum',
+				'',
+				'### args',
+				'* require_votes<u64>: number of votes needed to conclude vote',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'requiredVotes',
+					type: 'u64',
+				},
+			],
+		},
+		{
+			name: 'updateProposalMinimumStake',
+			docs: [
+				'update proposal minimum stake',
+				'',
+				'the minimum amount that needs to be staked in order to create a',
+				'proposal',
+				'',
+				'### args',
+				'* minimum_stake<u64>: stake needed to propose vote',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'minimumStake',
+					type: 'u64',
+				},
+			],
+		},
+		{
+			name: 'updateVoteStakeRate',
+			docs: [
+				'update vote stake rate',
+				'',
+				'the stake rate sets requirements to how much a',
+				'voter needs to stake in order to vote. typically 1% of voting',
+				'power',
+				'',
+				'### args',
+				'* vote_stake_rate<u32>: 1/x of voting power',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'voteStakeRate',
+					type: 'u32',
+				},
+			],
+		},
+		{
+			name: 'updateProtocolFeeRate',
+			docs: [
+				'update protocol fee rate',
+				'',
+				'the amount the protocol can take in fees',
+				'',
+				'### args',
+				'* protocol_fee_rate<u32>: 1/x of the voting pool',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'protocolFeeRate',
+					type: 'u32',
+				},
+			],
 		},
 		{
-			name: 'revealedVoteArray';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'bump';
-						type: 'u8';
-					},
-					{
-						name: 'proposal';
-						type: 'publicKey';
-					},
-					{
-						name: 'weightedVotes';
-						docs: ['Q32.32'];
-						type: {
-							array: ['i64', 1024];
-						};
-					},
-					{
-						name: 'lastIndex';
-						type: 'i16';
-					}
-				];
-			};
+			name: 'updateReputationEnabled',
+			docs: [
+				'update config: reputation',
+				'',
+				'multiply the vote power by the reputation factor of the voter',
+				'',
+				'### args',
+				'* reputation_enabled<bool>: whether the reputation factor applies',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'reputationEnabled',
+					type: 'bool',
+				},
+			],
 		},
 		{
-			name: 'voteAccount';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'bump';
-						type: 'u8';
-					},
-					{
-						name: 'bumpArray';
-						type: {
-							array: ['u8', 1];
-						};
-					},
-					{
-						name: 'proposal';
-						type: 'publicKey';
-					},
-					{
-						name: 'owner';
-						type: 'publicKey';
-					},
-					{
-						name: 'stakeMint';
-						type: 'publicKey';
-					},
-					{
-						name: 'voteHash';
-						type: {
-							array: ['u8', 32];
-						};
-					},
-					{
-						name: 'vote';
-						docs: ['real vote:', 'I32.32'];
-						type: 'i64';
-					},
-					{
-						name: 'staked';
-						type: 'u64';
-					},
-					{
-						name: 'voteFactor';
-						docs: ['F = V * l * exp(-l*(x-X))'];
-						type: 'u64';
-					},
-					{
-						name: 'earnedRewards';
-						docs: ['rewards earned from voting', 'C * F / S_v'];
-						type: 'u64';
-					},
-					{
-						name: 'votePower';
-						type: 'u32';
-					},
-					{
-						name: 'revealedVote';
-						type: 'bool';
-					},
-					{
-						name: 'locked';
-						type: 'bool';
-					}
-				];
-			};
-		}
-	];
-	types: [
+			name: 'updateConsensusEstimator',
+			docs: [
+				'update config: consensus estimator',
+				'',
+				'select how the consensus is calculated from the',
+				'revealed votes of new proposals',
+				'',
+				'### args',
+				'* consensus_estimator<u8>: 0 = mean, 1 = weighted median, 2 = trimmed mean',
+				'* consensus_trim_rate<u32>: 1/x of the vote power trimmed from each tail',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'consensusEstimator',
+					type: 'u8',
+				},
+				{
+					name: 'consensusTrimRate',
+					type: 'u32',
+				},
+			],
+		},
 		{
-			name: 'ProposalStatus';
-			type: {
-				kind: 'enum';
-				variants: [
-					{
-						name: 'Failed';
-					},
-					{
-						name: 'Proposed';
-					},
-					{
-						name: 'Voting';
-					},
-					{
-						name: 'ReachedQuorum';
-					},
-					{
-						name: 'RevealVote';
-					},
-					{
-						name: 'VoteRevealFinished';
+			name: 'updateClaimBond',
+			docs: [
+				'update claim bond',
+				'',
+				'the tokens a proposer bonds to bind a proposal',
+				'to a pool. The bond is slashed if the claim is rejected',
+				'',
+				'### args',
+				'* claim_bond<u64>: amount of tokens bonded per claim',
+			],
+			accounts: [
+				{
+					name: 'protocolAuthority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'claimBond',
+					type: 'u64',
+				},
+			],
+		},
+		{
+			name: 'proposeVote',
+			docs: [
+				'Propose vote',
+				'',
+				'proposes a vote or observation that the holder of veSure can',
+				'vote on.',
+				'',
+				'### paramters',
+				'* `ctx`: Context',
+				'* `name`: Name of the observation',
+				'* `description`: Clear description about the event',
+				'* `evidence`: Reference to the off-chain incident report',
+				'* `stake`: The amount staked on event. In BN:  x*10^{decimals}',
+			],
+			accounts: [
+				{
+					name: 'proposer',
+					isMut: true,
+					isSigner: true,
+				},
+				{
+					name: 'config',
+					isMut: true,
+					isSigner: false,
+					docs: ['configuration for the proposal'],
+				},
+				{
+					name: 'proposal',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'revealVoteArray',
+					isMut: true,
+					isSigner: false,
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-reveal-array',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Proposal',
+								path: 'proposal',
+							},
+						],
 					},
-					{
-						name: 'RewardCalculation';
+				},
+				{
+					name: 'proposerAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'proposalVaultMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'proposalVault',
+					isMut: true,
+					isSigner: false,
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-propsal-vault',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Proposal',
+								path: 'proposal',
+							},
+						],
 					},
-					{
-						name: 'RewardPayout';
-					}
-				];
-			};
-		}
-	];
-	events: [
-		{
-			name: 'CancelledVote';
-			fields: [
+				},
 				{
-					name: 'vote';
-					type: 'publicKey';
-					index: false;
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'associatedTokenProgram',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'time';
-					type: 'i64';
-					index: false;
+					name: 'rent',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'refund';
-					type: 'u64';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'CollectProposerRewardEvent';
-			fields: [
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
 				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'name',
+					type: 'string',
 				},
 				{
-					name: 'time';
-					type: 'i64';
-					index: false;
+					name: 'description',
+					type: 'string',
 				},
 				{
-					name: 'reward';
-					type: 'u64';
-					index: false;
-				}
-			];
+					name: 'evidence',
+					type: {
+						defined: 'ProposalEvidence',
+					},
+				},
+				{
+					name: 'stake',
+					type: 'u64',
+				},
+			],
 		},
 		{
-			name: 'CollectedProtocolFees';
-			fields: [
+			name: 'bindPool',
+			docs: [
+				'bind pool',
+				'',
+				'bind the proposal to an insurance pool as a claim. The pool',
+				'is locked against new coverage until the vote is over.',
+				'The proposer bonds config.claim_bond tokens which are',
+				'slashed if the claim is rejected',
+				'',
+				'### args',
+				'* claim_amount<u64>: amount claimed for the coverage position.',
+				'Voters vote on the share of the claim amount to pay out',
+			],
+			accounts: [
 				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'proposer',
+					isMut: true,
+					isSigner: true,
 				},
 				{
-					name: 'fees';
-					type: 'u64';
-					index: false;
+					name: 'proposerAccount',
+					isMut: true,
+					isSigner: false,
+					docs: ['account paying the claim bond'],
 				},
 				{
-					name: 'destination';
-					type: 'publicKey';
-					index: false;
-				}
-			];
+					name: 'config',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'proposal',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'claimBondVault',
+					isMut: true,
+					isSigner: false,
+					docs: [
+						'vault holding the claim bond until',
+						'the vote on the claim is over',
+					],
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-claim-bond',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Proposal',
+								path: 'proposal',
+							},
+						],
+					},
+				},
+				{
+					name: 'tokenMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'pool',
+					isMut: true,
+					isSigner: false,
+					docs: ['insurance pool to claim against'],
+				},
+				{
+					name: 'poolManager',
+					isMut: false,
+					isSigner: false,
+					docs: [
+						'pool manager holding the oracle config',
+						'trusted with claims',
+					],
+				},
+				{
+					name: 'coveragePosition',
+					isMut: false,
+					isSigner: false,
+					docs: ['coverage position the claim is paid to'],
+				},
+				{
+					name: 'poolProgram',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'rent',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'claimAmount',
+					type: 'u64',
+				},
+			],
 		},
 		{
-			name: 'CollectVoteRewardEvent';
-			fields: [
+			name: 'releasePool',
+			docs: [
+				'release pool',
+				'',
+				'unlock the pool when the vote on the claim failed or',
+				'the vote results reject the claim. The claim bond is',
+				'sent to the protocol treasury',
+			],
+			accounts: [
 				{
-					name: 'vote';
-					type: 'publicKey';
-					index: false;
+					name: 'signer',
+					isMut: false,
+					isSigner: true,
 				},
 				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'config',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'proposal',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'claimBondVault',
+					isMut: true,
+					isSigner: false,
+					docs: ['vault holding the claim bond'],
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-claim-bond',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Proposal',
+								path: 'proposal',
+							},
+						],
+					},
+				},
+				{
+					name: 'protocolTreasury',
+					isMut: true,
+					isSigner: false,
+					docs: ['receives the slashed claim bond'],
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-treasury',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Config',
+								path: 'config',
+							},
+						],
+					},
 				},
 				{
-					name: 'time';
-					type: 'i64';
-					index: false;
+					name: 'pool',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'reward';
-					type: 'u64';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'InitializedConfigEvent';
-			fields: [];
-		},
-		{
-			name: 'UpdatedVotingPeriod';
-			fields: [
-				{
-					name: 'oldVotingPeriod';
-					type: 'i64';
-					index: false;
+					name: 'poolManager',
+					isMut: false,
+					isSigner: false,
+					docs: [
+						'pool manager holding the oracle config',
+						'trusted with claims',
+					],
 				},
 				{
-					name: 'votingPeriod';
-					type: 'i64';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'UpdatedRevealPeriod';
-			fields: [
-				{
-					name: 'oldRevealPeriod';
-					type: 'i64';
-					index: false;
+					name: 'poolProgram',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'revealPeriod';
-					type: 'i64';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'UpdatedRequiredVotes';
-			fields: [
-				{
-					name: 'oldRequiredVotes';
-					type: 'u64';
-					index: false;
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
 				},
-				{
-					name: 'requiredVotes';
-					type: 'u64';
-					index: false;
-				}
-			];
+			],
+			args: [],
 		},
 		{
-			name: 'UpdatedProposalMinumumStake';
-			fields: [
+			name: 'payClaim',
+			docs: [
+				'pay claim',
+				'',
+				'pay the accepted claim to the coverage position through',
+				'the pool and unlock the pool. The payout is the share of',
+				'the claim amount resolved by the vote and can only be',
+				'paid once. Returns the claim bond to the proposer',
+			],
+			accounts: [
 				{
-					name: 'oldMinimumStake';
-					type: 'u64';
-					index: false;
+					name: 'signer',
+					isMut: false,
+					isSigner: true,
 				},
 				{
-					name: 'minimumStake';
-					type: 'u64';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'UpdatedVoteStakeRate';
-			fields: [
-				{
-					name: 'oldVoteStakeRate';
-					type: 'u32';
-					index: false;
+					name: 'config',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'voteStakeRate';
-					type: 'u32';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'UpdatedProtocolFeerate';
-			fields: [
-				{
-					name: 'oldProtocolFeeRate';
-					type: 'u32';
-					index: false;
+					name: 'proposal',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'protocolFeeRate';
-					type: 'u32';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'FinalizedVoteResultsEvent';
-			fields: [
+					name: 'claimBondVault',
+					isMut: true,
+					isSigner: false,
+					docs: ['vault holding the claim bond'],
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-claim-bond',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Proposal',
+								path: 'proposal',
+							},
+						],
+					},
+				},
 				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'proposerAccount',
+					isMut: true,
+					isSigner: false,
+					docs: ['receives the claim bond back'],
 				},
 				{
-					name: 'time';
-					type: 'i64';
-					index: false;
+					name: 'pool',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'revealedVotes';
-					type: 'u64';
-					index: false;
+					name: 'poolManager',
+					isMut: false,
+					isSigner: false,
+					docs: [
+						'pool manager holding the oracle config',
+						'trusted with claims',
+					],
 				},
 				{
-					name: 'consensus';
-					type: 'i64';
-					index: false;
+					name: 'tokenPool',
+					isMut: true,
+					isSigner: false,
+					docs: ['token pool the coverage is bought from'],
 				},
 				{
-					name: 'status';
-					type: 'u8';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'FinalizedVoteEvent';
-			fields: [
+					name: 'coveragePosition',
+					isMut: true,
+					isSigner: false,
+					docs: ['coverage position the claim is paid to'],
+				},
 				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'claimantTokenAccount',
+					isMut: true,
+					isSigner: false,
+					docs: ['token account of the coverage owner'],
 				},
 				{
-					name: 'time';
-					type: 'i64';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'ProposeVoteEvent';
-			fields: [
+					name: 'liquidityVault',
+					isMut: true,
+					isSigner: false,
+					docs: ['liquidity vault of the token pool'],
+				},
 				{
-					name: 'name';
-					type: 'string';
-					index: false;
+					name: 'tickArray0',
+					isMut: true,
+					isSigner: false,
+					docs: [
+						'tick arrays holding the liquidity',
+						'used by the coverage position',
+					],
 				},
 				{
-					name: 'description';
-					type: 'string';
-					index: false;
+					name: 'tickArray1',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'id';
-					type: 'bytes';
-					index: false;
+					name: 'tickArray2',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'proposer';
-					type: 'publicKey';
-					index: false;
+					name: 'poolProgram',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'stake';
-					type: 'u64';
-					index: false;
-				}
-			];
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [],
 		},
 		{
-			name: 'RevealedVoteEvent';
-			fields: [
+			name: 'initializeVoterReputation',
+			docs: [
+				'initialize voter reputation',
+				'',
+				'creates the persistent reputation of the voter. The',
+				'reputation is required to vote when the reputation factor',
+				'is enabled and tracks the accuracy, participation and',
+				'reveal rate of the voter',
+			],
+			accounts: [
 				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'voter',
+					isMut: true,
+					isSigner: true,
 				},
 				{
-					name: 'time';
-					type: 'i64';
-					index: false;
+					name: 'config',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'revealedVote';
-					type: 'i64';
-					index: false;
+					name: 'voterReputation',
+					isMut: true,
+					isSigner: false,
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-reputation',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Config',
+								path: 'config',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								path: 'voter',
+							},
+						],
+					},
 				},
 				{
-					name: 'votePower';
-					type: 'u32';
-					index: false;
-				}
-			];
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [],
 		},
 		{
-			name: 'SubmittedVoteEvent';
-			fields: [
+			name: 'submitVote',
+			docs: [
+				'Submit vote',
+				'',
+				'lets user vote blindly on a proposal using a vote hash',
+				'',
+				'### Parameters',
+				'* `ctx` - context',
+				'* `vote_hash` - hash of vote with secret salt',
+			],
+			accounts: [
 				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'voter',
+					isMut: true,
+					isSigner: true,
 				},
 				{
-					name: 'time';
-					type: 'i64';
-					index: false;
+					name: 'voterAccount',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'voteHash';
-					type: 'bytes';
-					index: false;
+					name: 'config',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'votePower';
-					type: 'u64';
-					index: false;
-				}
-			];
-		},
-		{
-			name: 'UpdatedVoteEvent';
-			fields: [
-				{
-					name: 'proposal';
-					type: 'publicKey';
-					index: false;
+					name: 'locker',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'time';
-					type: 'i64';
-					index: false;
+					name: 'userEscrow',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'voteHash';
-					type: 'bytes';
-					index: false;
-				}
-			];
-		}
-	];
-	errors: [
-		{
-			code: 6000;
-			name: 'StakeTooLittle';
-			msg: 'Not enough staked on vote';
-		},
-		{
-			code: 6001;
-			name: 'InvalidLockPeriod';
-			msg: 'Invalid lock period';
-		},
-		{
-			code: 6002;
-			name: 'InvalidVoteEndTime';
-			msg: 'Invalid vote end time';
-		},
-		{
-			code: 6003;
-			name: 'VotingPeriodEnded';
-			msg: 'Voting period for proposal has ended';
-		},
-		{
-			code: 6004;
-			name: 'RevealPeriodNotActive';
-			msg: 'Currently not in vote reveal period';
-		},
-		{
-			code: 6005;
-			name: 'RevealPeriodIsNotFinished';
-			msg: 'Reveal period is not over';
-		},
-		{
-			code: 6006;
-			name: 'InvalidSalt';
-			msg: 'Invalid salt resulted in invalid vote_hash';
-		},
-		{
-			code: 6007;
-			name: 'FullRevealList';
-			msg: 'Revealed vote list full';
-		},
-		{
-			code: 6008;
-			name: 'VoteNotRevealed';
-			msg: "Vote hasn't been revealed";
-		},
-		{
-			code: 6009;
-			name: 'OverflowU64';
-			msg: 'U64 overflow';
-		},
-		{
-			code: 6010;
-			name: 'OverflowU32';
-			msg: 'U32 overflow';
-		},
-		{
-			code: 6011;
-			name: 'NotPossibleToCalculateVoteReward';
-			msg: 'Could not calculate the vote reward at this time';
-		},
-		{
-			code: 6012;
-			name: 'NotPossibleToCollectProposerReward';
-			msg: 'Cannot payout the proposer reward at this time';
-		},
-		{
-			code: 6013;
-			name: 'NotPossibleToCollectVoterReward';
-			msg: 'Cannot payout the voter reward at this time';
-		},
-		{
-			code: 6014;
-			name: 'FailedToFinalizeVote';
-			msg: 'Cannot finalize user vote at this time';
-		},
-		{
-			code: 6015;
-			name: 'FailedToFinalizeVoteResult';
-			msg: 'Cannot finalize vote result at this time';
-		},
-		{
-			code: 6016;
-			name: 'FailedToCancelVote';
-			msg: 'Too late to cancel vote';
-		},
-		{
-			code: 6017;
-			name: 'InvalidOwnerOfVoteAccount';
-			msg: 'The owner of the vote account is not the signer';
-		},
-		{
-			code: 6018;
-			name: 'ProposalVaultMintKeyDoesNotMatchProposalStateVaultMint';
-			msg: 'Proposal.vault_mint does not match the input proposal_vault_mint key';
-		},
-		{
-			code: 6019;
-			name: 'ProposalVaultMintKeyDoesNotMatchVaultMint';
-			msg: 'Proposal.vault_mint does not match the vault mint key';
-		},
-		{
-			code: 6020;
-			name: 'NotEnoughProposalStake';
-			msg: 'Not enough stake to propose a vote ';
-		},
-		{
-			code: 6021;
-			name: 'InvalidRequiredVotesParam';
-			msg: 'Quorum requirements are too low';
-		},
-		{
-			code: 6022;
-			name: 'InvalidMinimumStakedParam';
-			msg: 'Invalid minimum staked on proposal';
-		},
-		{
-			code: 6023;
-			name: 'InvalidVoteStakeRateParam';
-			msg: 'Invalid vote stake rate param. Probably less than 1';
-		},
-		{
-			code: 6024;
-			name: 'InvalidProtocolFeeRateParam';
-			msg: 'Invalid protocol fee rate param. Probably less than 1';
-		},
-		{
-			code: 6025;
-			name: 'UnauthorizedSigner';
-			msg: 'Unauthorized signer';
-		}
-	];
-};
-
-export const IDL: Oracle = {
-	version: '0.1.0',
-	name: 'oracle',
-	instructions: [
-		{
-			name: 'initializeConfig',
-			docs: [
-				'initialize config',
-				'',
-				'config will be used in proposals to set voting parameters',
-				'and limit protocol fee collectors to the protocol_authority',
-				'',
-				'### args',
-				'* protocol_authority<Pubkey>: unique for vault mint. the authority can',
-				'- change config parameters',
-				'- collect protocol fees',
-			],
-			accounts: [
+					name: 'proposal',
+					isMut: true,
+					isSigner: false,
+				},
 				{
-					name: 'signer',
+					name: 'proposalVault',
 					isMut: true,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
-					name: 'config',
+					name: 'proposalVaultMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'voteAccount',
 					isMut: true,
 					isSigner: false,
 					pda: {
@@ -1820,19 +4531,29 @@ export const IDL: Oracle = {
 							{
 								kind: 'const',
 								type: 'string',
-								value: 'sure-oracle-config',
+								value: 'sure-oracle-vote',
 							},
 							{
 								kind: 'account',
 								type: 'publicKey',
-								account: 'Mint',
-								path: 'token_mint',
+								account: 'Proposal',
+								path: 'proposal',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								path: 'voter',
 							},
 						],
 					},
 				},
 				{
-					name: 'tokenMint',
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'rent',
 					isMut: false,
 					isSigner: false,
 				},
@@ -1844,32 +4565,57 @@ export const IDL: Oracle = {
 			],
 			args: [
 				{
-					name: 'protocolAuthority',
-					type: 'publicKey',
+					name: 'voteHash',
+					type: 'bytes',
 				},
 			],
 		},
 		{
-			name: 'updateVotingPeriod',
+			name: 'updateVote',
 			docs: [
-				'update config: voting period',
+				'Updates vote',
 				'',
-				'change the voting period and reveal period',
+				'updates the vote hash of the previous submitted vote',
 				'',
-				'### args',
-				'* voting_period<i64>: period for which the voter can submit a vote hash. In seconds',
-				'* reveal_period<i64>: period for which the voter can reveal the vote. In seconds',
+				'### parameters',
+				'* `ctx` - context',
+				'* `vote_hash` - hash of vote with secret salt',
 			],
 			accounts: [
 				{
-					name: 'protocolAuthority',
+					name: 'voter',
 					isMut: false,
 					isSigner: true,
 				},
 				{
-					name: 'config',
-					isMut: false,
+					name: 'proposal',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'voteAccount',
+					isMut: true,
 					isSigner: false,
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-vote',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Proposal',
+								path: 'proposal',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								path: 'voter',
+							},
+						],
+					},
 				},
 				{
 					name: 'systemProgram',
@@ -1879,170 +4625,141 @@ export const IDL: Oracle = {
 			],
 			args: [
 				{
-					name: 'votingPeriod',
-					type: 'i64',
+					name: 'voteHash',
+					type: 'bytes',
 				},
 			],
 		},
 		{
-			name: 'updateRevealPeriod',
+			name: 'updateVoteStake',
 			docs: [
-				'update config: reveal period',
+				'Update vote stake',
 				'',
-				'change the reveal period and reveal period',
+				'top up the vote power after extending the escrow lock',
+				'or decrease it and get the stake refunded',
 				'',
-				'### args',
-				'* voting_period<i64>: period for which the voter can submit a vote hash. In seconds',
-				'* reveal_period<i64>: period for which the voter can reveal the vote. In seconds',
+				'### parameters',
+				'* `ctx` - context',
+				'* `vote_power` - new vote power in tokens',
 			],
 			accounts: [
 				{
-					name: 'protocolAuthority',
-					isMut: false,
+					name: 'voter',
+					isMut: true,
 					isSigner: true,
 				},
 				{
-					name: 'config',
-					isMut: false,
+					name: 'voterAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'systemProgram',
+					name: 'config',
 					isMut: false,
 					isSigner: false,
 				},
-			],
-			args: [
-				{
-					name: 'votingPeriod',
-					type: 'i64',
-				},
-			],
-		},
-		{
-			name: 'updateRequiredVotes',
-			docs: [
-				'update required votes',
-				'',
-				'required votes to reach quorum',
-				'',
-				'### args',
-				'* require_votes<u64>: number of votes needed to conclude vote',
-			],
-			accounts: [
 				{
-					name: 'protocolAuthority',
+					name: 'locker',
 					isMut: false,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
-					name: 'config',
+					name: 'userEscrow',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'systemProgram',
-					isMut: false,
+					name: 'proposal',
+					isMut: true,
 					isSigner: false,
 				},
-			],
-			args: [
 				{
-					name: 'requiredVotes',
-					type: 'u64',
+					name: 'proposalVault',
+					isMut: true,
+					isSigner: false,
 				},
-			],
-		},
-		{
-			name: 'updateProposalMinimumStake',
-			docs: [
-				'update proposal minimum stake',
-				'',
-				'the minimum amount that needs to be staked in order to create a',
-				'proposal',
-				'',
-				'### args',
-				'* minimum_stake<u64>: stake needed to propose vote',
-			],
-			accounts: [
 				{
-					name: 'protocolAuthority',
+					name: 'proposalVaultMint',
 					isMut: false,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
-					name: 'config',
-					isMut: false,
+					name: 'voteAccount',
+					isMut: true,
 					isSigner: false,
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-vote',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Proposal',
+								path: 'proposal',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								path: 'voter',
+							},
+						],
+					},
 				},
 				{
-					name: 'systemProgram',
+					name: 'tokenProgram',
 					isMut: false,
 					isSigner: false,
 				},
 			],
 			args: [
 				{
-					name: 'minimumStake',
+					name: 'votePower',
 					type: 'u64',
 				},
 			],
 		},
 		{
-			name: 'updateVoteStakeRate',
+			name: 'cancelVote',
 			docs: [
-				'update vote stake rate',
+				'cancel vote',
 				'',
-				'the stake rate sets requirements to how much a',
-				'voter needs to stake in order to vote. typically 1% of voting',
-				'power',
+				'a user can cancel the vote in the voting period',
 				'',
-				'### args',
-				'* vote_stake_rate<u32>: 1/x of voting power',
+				'### parameters',
+				'* `ctx` - CancelVote context',
 			],
 			accounts: [
 				{
-					name: 'protocolAuthority',
-					isMut: false,
+					name: 'voter',
+					isMut: true,
 					isSigner: true,
 				},
 				{
-					name: 'config',
-					isMut: false,
+					name: 'voterAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'systemProgram',
-					isMut: false,
+					name: 'proposalVault',
+					isMut: true,
 					isSigner: false,
 				},
-			],
-			args: [
 				{
-					name: 'voteStakeRate',
-					type: 'u32',
+					name: 'proposalVaultMint',
+					isMut: false,
+					isSigner: false,
 				},
-			],
-		},
-		{
-			name: 'updateProtocolFeeRate',
-			docs: [
-				'update protocol fee rate',
-				'',
-				'the amount the protocol can take in fees',
-				'',
-				'### args',
-				'* protocol_fee_rate<u32>: 1/x of the voting pool',
-			],
-			accounts: [
 				{
-					name: 'protocolAuthority',
-					isMut: false,
-					isSigner: true,
+					name: 'proposal',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'config',
-					isMut: false,
+					name: 'voteAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
@@ -2050,40 +4767,33 @@ export const IDL: Oracle = {
 					isMut: false,
 					isSigner: false,
 				},
-			],
-			args: [
 				{
-					name: 'protocolFeeRate',
-					type: 'u32',
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
 				},
 			],
+			args: [],
 		},
 		{
-			name: 'proposeVote',
+			name: 'revealVote',
 			docs: [
-				'Propose vote',
+				'reveal vote',
 				'',
-				'proposes a vote or observation that the holder of veSure can',
-				'vote on.',
+				'let the user reveal the vote when the voting period is over',
+				'the user can only receive rewards if revealed',
 				'',
-				'### paramters',
-				'* `ctx`: Context',
-				'* `name`: Name of the observation',
-				'* `description`: Clear description about the event',
-				'* `stake`: The amount staked on event. In BN:  x*10^{decimals}',
+				'### parameters',
+				'* `ctx` - RevealVote context',
+				'* `salt` - the salt used to hash the vote',
+				'* `vote`- the actual vote value',
 			],
 			accounts: [
 				{
-					name: 'proposer',
+					name: 'voter',
 					isMut: true,
 					isSigner: true,
 				},
-				{
-					name: 'config',
-					isMut: false,
-					isSigner: false,
-					docs: ['configuration for the proposal'],
-				},
 				{
 					name: 'proposal',
 					isMut: true,
@@ -2096,9 +4806,12 @@ export const IDL: Oracle = {
 								value: 'sure-oracle',
 							},
 							{
-								kind: 'arg',
-								type: 'bytes',
-								path: 'id',
+								kind: 'account',
+								type: {
+									array: ['u8', 16],
+								},
+								account: 'Proposal',
+								path: 'proposal.id',
 							},
 						],
 					},
@@ -2124,17 +4837,7 @@ export const IDL: Oracle = {
 					},
 				},
 				{
-					name: 'proposerAccount',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'proposalVaultMint',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'proposalVault',
+					name: 'voteAccount',
 					isMut: true,
 					isSigner: false,
 					pda: {
@@ -2142,7 +4845,7 @@ export const IDL: Oracle = {
 							{
 								kind: 'const',
 								type: 'string',
-								value: 'sure-oracle',
+								value: 'sure-oracle-vote',
 							},
 							{
 								kind: 'account',
@@ -2150,24 +4853,14 @@ export const IDL: Oracle = {
 								account: 'Proposal',
 								path: 'proposal',
 							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								path: 'voter',
+							},
 						],
 					},
 				},
-				{
-					name: 'tokenProgram',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'associatedTokenProgram',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'rent',
-					isMut: false,
-					isSigner: false,
-				},
 				{
 					name: 'systemProgram',
 					isMut: false,
@@ -2176,72 +4869,81 @@ export const IDL: Oracle = {
 			],
 			args: [
 				{
-					name: 'id',
-					type: 'bytes',
-				},
-				{
-					name: 'name',
-					type: 'string',
-				},
-				{
-					name: 'description',
+					name: 'salt',
 					type: 'string',
 				},
 				{
-					name: 'stake',
-					type: 'u64',
+					name: 'vote',
+					type: 'i64',
 				},
 			],
 		},
 		{
-			name: 'submitVote',
+			name: 'finalizeVoteResults',
 			docs: [
-				'Submit vote',
+				'finalize vote results',
 				'',
-				'lets user vote blindly on a proposal using a vote hash',
+				'after the reveal period the proposal can be finalized',
+				'from this point on it is not possible to reveal the vote',
 				'',
-				'### Parameters',
-				'* `ctx` - context',
-				'* `vote_hash` - hash of vote with secret salt',
+				'the proposer reward and scale parameter is calculated',
+				'',
+				'### parameters',
+				'*  `ctx` - the Finalize Vote context',
 			],
 			accounts: [
 				{
-					name: 'voter',
+					name: 'finalizer',
 					isMut: true,
 					isSigner: true,
 				},
 				{
-					name: 'voterAccount',
+					name: 'proposal',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'locker',
+					name: 'revealedVotes',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'userEscrow',
+					name: 'proposalVault',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'proposal',
-					isMut: true,
+					name: 'systemProgram',
+					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'finalizeVote',
+			docs: [
+				'finalize vote',
+				'',
+				'after the vote results are finalized the voters can calculate',
+				'their vote share and close their vote account',
+				'',
+				'### parameters',
+				'* `ctx` - Finalize Vote context',
+			],
+			accounts: [
 				{
-					name: 'proposalVault',
+					name: 'signer',
 					isMut: true,
-					isSigner: false,
+					isSigner: true,
 				},
 				{
-					name: 'proposalVaultMint',
-					isMut: false,
+					name: 'voteAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'voteAccount',
+					name: 'proposal',
 					isMut: true,
 					isSigner: false,
 					pda: {
@@ -2249,114 +4951,85 @@ export const IDL: Oracle = {
 							{
 								kind: 'const',
 								type: 'string',
-								value: 'sure-oracle-vote',
+								value: 'sure-oracle',
 							},
 							{
 								kind: 'account',
-								type: 'publicKey',
+								type: {
+									array: ['u8', 16],
+								},
 								account: 'Proposal',
-								path: 'proposal',
-							},
-							{
-								kind: 'account',
-								type: 'publicKey',
-								path: 'voter',
+								path: 'proposal.id',
 							},
 						],
 					},
 				},
-				{
-					name: 'tokenProgram',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'rent',
-					isMut: false,
-					isSigner: false,
-				},
 				{
 					name: 'systemProgram',
 					isMut: false,
 					isSigner: false,
 				},
 			],
-			args: [
-				{
-					name: 'voteHash',
-					type: 'bytes',
-				},
-			],
+			args: [],
 		},
 		{
-			name: 'updateVote',
+			name: 'collectProposerReward',
 			docs: [
-				'Updates vote',
-				'',
-				'updates the vote hash of the previous submitted vote',
+				'collect proposer reward',
 				'',
-				'### parameters',
-				'* `ctx` - context',
-				'* `vote_hash` - hash of vote with secret salt',
+				'after the vote results are finalized the proposer is free to',
+				'collect the reward at any time',
 			],
 			accounts: [
 				{
-					name: 'voter',
-					isMut: false,
+					name: 'proposer',
+					isMut: true,
 					isSigner: true,
 				},
+				{
+					name: 'config',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'proposerTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
 				{
 					name: 'proposal',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'voteAccount',
+					name: 'proposalVault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'proposalVaultMint',
 					isMut: true,
 					isSigner: false,
-					pda: {
-						seeds: [
-							{
-								kind: 'const',
-								type: 'string',
-								value: 'sure-oracle-vote',
-							},
-							{
-								kind: 'account',
-								type: 'publicKey',
-								account: 'Proposal',
-								path: 'proposal',
-							},
-							{
-								kind: 'account',
-								type: 'publicKey',
-								path: 'voter',
-							},
-						],
-					},
 				},
 				{
 					name: 'systemProgram',
 					isMut: false,
 					isSigner: false,
 				},
-			],
-			args: [
 				{
-					name: 'voteHash',
-					type: 'bytes',
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
 				},
 			],
+			args: [],
 		},
 		{
-			name: 'cancelVote',
+			name: 'collectVoteReward',
 			docs: [
-				'cancel vote',
-				'',
-				'a user can cancel the vote in the voting period',
+				'collect vote reward',
 				'',
-				'### parameters',
-				'* `ctx` - CancelVote context',
+				'after the vote results are finalized the voter can collect rewards',
 			],
 			accounts: [
 				{
@@ -2364,13 +5037,23 @@ export const IDL: Oracle = {
 					isMut: true,
 					isSigner: true,
 				},
+				{
+					name: 'config',
+					isMut: false,
+					isSigner: false,
+				},
 				{
 					name: 'voterAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'proposalVault',
+					name: 'voteAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'proposal',
 					isMut: true,
 					isSigner: false,
 				},
@@ -2380,19 +5063,79 @@ export const IDL: Oracle = {
 					isSigner: false,
 				},
 				{
-					name: 'proposal',
+					name: 'proposalVault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'voteAccount',
-					isMut: true,
+					name: 'systemProgram',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'systemProgram',
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [],
+		},
+		{
+			name: 'collectProtocolFees',
+			docs: [
+				'collect protocol fees',
+				'',
+				'sweep the accrued protocol fees of the proposals passed',
+				'as remaining accounts into the protocol treasury',
+				'',
+				'### remaining accounts',
+				'* pairs of (proposal, proposal_vault)',
+			],
+			accounts: [
+				{
+					name: 'signer',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'config',
 					isMut: false,
 					isSigner: false,
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-config',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Config',
+								path: 'config.token_mint',
+							},
+						],
+					},
+				},
+				{
+					name: 'protocolTreasury',
+					isMut: true,
+					isSigner: false,
+					pda: {
+						seeds: [
+							{
+								kind: 'const',
+								type: 'string',
+								value: 'sure-oracle-treasury',
+							},
+							{
+								kind: 'account',
+								type: 'publicKey',
+								account: 'Config',
+								path: 'config',
+							},
+						],
+					},
 				},
 				{
 					name: 'tokenProgram',
@@ -2403,48 +5146,44 @@ export const IDL: Oracle = {
 			args: [],
 		},
 		{
-			name: 'revealVote',
+			name: 'withdrawProtocolFees',
 			docs: [
-				'reveal vote',
+				'withdraw protocol fees',
 				'',
-				'let the user reveal the vote when the voting period is over',
-				'the user can only receive rewards if revealed',
+				'the config authority can at any time withdraw the',
+				'protocol fees from the protocol treasury',
 				'',
-				'### parameters',
-				'* `ctx` - RevealVote context',
-				'* `salt` - the salt used to hash the vote',
-				'* `vote`- the actual vote value',
+				'### args',
+				'* amount<u64>: amount of tokens to withdraw',
 			],
 			accounts: [
 				{
-					name: 'voter',
-					isMut: true,
+					name: 'protocolAuthority',
+					isMut: false,
 					isSigner: true,
 				},
 				{
-					name: 'proposal',
-					isMut: true,
+					name: 'config',
+					isMut: false,
 					isSigner: false,
 					pda: {
 						seeds: [
 							{
 								kind: 'const',
 								type: 'string',
-								value: 'sure-oracle',
+								value: 'sure-oracle-config',
 							},
 							{
 								kind: 'account',
-								type: {
-									array: ['u8', 16],
-								},
-								account: 'Proposal',
-								path: 'proposal.id',
+								type: 'publicKey',
+								account: 'Config',
+								path: 'config.token_mint',
 							},
 						],
 					},
 				},
 				{
-					name: 'revealVoteArray',
+					name: 'protocolTreasury',
 					isMut: true,
 					isSigner: false,
 					pda: {
@@ -2452,77 +5191,54 @@ export const IDL: Oracle = {
 							{
 								kind: 'const',
 								type: 'string',
-								value: 'sure-oracle-reveal-array',
+								value: 'sure-oracle-treasury',
 							},
 							{
 								kind: 'account',
 								type: 'publicKey',
-								account: 'Proposal',
-								path: 'proposal',
+								account: 'Config',
+								path: 'config',
 							},
 						],
 					},
 				},
 				{
-					name: 'voteAccount',
+					name: 'feeDestination',
 					isMut: true,
 					isSigner: false,
-					pda: {
-						seeds: [
-							{
-								kind: 'const',
-								type: 'string',
-								value: 'sure-oracle-vote',
-							},
-							{
-								kind: 'account',
-								type: 'publicKey',
-								account: 'Proposal',
-								path: 'proposal',
-							},
-							{
-								kind: 'account',
-								type: 'publicKey',
-								path: 'voter',
-							},
-						],
-					},
 				},
 				{
-					name: 'systemProgram',
+					name: 'tokenProgram',
 					isMut: false,
 					isSigner: false,
 				},
 			],
 			args: [
 				{
-					name: 'salt',
-					type: 'string',
-				},
-				{
-					name: 'vote',
-					type: 'i64',
+					name: 'amount',
+					type: 'u64',
 				},
 			],
 		},
 		{
-			name: 'finalizeVoteResults',
+			name: 'initializeIncentiveVault',
 			docs: [
-				'finalize vote results',
-				'',
-				'after the reveal period the proposal can be finalized',
-				'from this point on it is not possible to reveal the vote',
-				'',
-				'the proposer reward and scale parameter is calculated',
+				'initialize incentive vault',
 				'',
-				'### parameters',
-				'*  `ctx` - the Finalize Vote context',
+				'attach a vault in a separate mint to the proposal. The',
+				'incentives are distributed by the same weights as the',
+				'voter rewards. The proposer or the founder of the bound',
+				'pool can sponsor the incentives',
 			],
 			accounts: [
 				{
-					name: 'finalizer',
+					name: 'sponsor',
 					isMut: true,
 					isSigner: true,
+					docs: [
+						'sponsor of the incentives. Either the proposer',
+						'or the founder of the pool bound to the proposal',
+					],
 				},
 				{
 					name: 'proposal',
@@ -2530,62 +5246,41 @@ export const IDL: Oracle = {
 					isSigner: false,
 				},
 				{
-					name: 'revealedVotes',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'systemProgram',
+					name: 'incentiveMint',
 					isMut: false,
 					isSigner: false,
-				},
-			],
-			args: [],
-		},
-		{
-			name: 'finalizeVote',
-			docs: [
-				'finalize vote',
-				'',
-				'after the vote results are finalized the voters can calculate',
-				'their vote share and close their vote account',
-				'',
-				'### parameters',
-				'* `ctx` - Finalize Vote context',
-			],
-			accounts: [
-				{
-					name: 'signer',
-					isMut: true,
-					isSigner: true,
+					docs: ['mint of the incentive rewards'],
 				},
 				{
-					name: 'voteAccount',
+					name: 'incentiveVault',
 					isMut: true,
 					isSigner: false,
-				},
-				{
-					name: 'proposal',
-					isMut: false,
-					isSigner: false,
 					pda: {
 						seeds: [
 							{
 								kind: 'const',
 								type: 'string',
-								value: 'sure-oracle',
+								value: 'sure-oracle-incentive-vault',
 							},
 							{
 								kind: 'account',
-								type: {
-									array: ['u8', 16],
-								},
+								type: 'publicKey',
 								account: 'Proposal',
-								path: 'proposal.id',
+								path: 'proposal',
 							},
 						],
 					},
 				},
+				{
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'rent',
+					isMut: false,
+					isSigner: false,
+				},
 				{
 					name: 'systemProgram',
 					isMut: false,
@@ -2595,26 +5290,24 @@ export const IDL: Oracle = {
 			args: [],
 		},
 		{
-			name: 'collectProposerReward',
+			name: 'fundIncentiveVault',
 			docs: [
-				'collect proposer reward',
+				'fund incentive vault',
 				'',
-				'after the vote results are finalized the proposer is free to',
-				'collect the reward at any time',
+				'add incentives to the proposal until the vote',
+				'results are finalized',
+				'',
+				'### args',
+				'* amount<u64>: amount of incentive tokens',
 			],
 			accounts: [
 				{
-					name: 'proposer',
+					name: 'funder',
 					isMut: true,
 					isSigner: true,
 				},
 				{
-					name: 'config',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'proposerTokenAccount',
+					name: 'funderAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -2624,34 +5317,30 @@ export const IDL: Oracle = {
 					isSigner: false,
 				},
 				{
-					name: 'proposalVault',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'proposalVaultMint',
+					name: 'incentiveVault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'systemProgram',
+					name: 'tokenProgram',
 					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [
 				{
-					name: 'tokenProgram',
-					isMut: false,
-					isSigner: false,
+					name: 'amount',
+					type: 'u64',
 				},
 			],
-			args: [],
 		},
 		{
-			name: 'collectVoteReward',
+			name: 'collectVoteIncentive',
 			docs: [
-				'collect vote reward',
+				'collect vote incentive',
 				'',
-				'after the vote results are finalized the voter can collect rewards',
+				'after the vote results are finalized the voter can collect',
+				'the share of the incentives',
 			],
 			accounts: [
 				{
@@ -2659,11 +5348,6 @@ export const IDL: Oracle = {
 					isMut: true,
 					isSigner: true,
 				},
-				{
-					name: 'config',
-					isMut: false,
-					isSigner: false,
-				},
 				{
 					name: 'voterAccount',
 					isMut: true,
@@ -2676,24 +5360,14 @@ export const IDL: Oracle = {
 				},
 				{
 					name: 'proposal',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'proposalVaultMint',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'proposalVault',
+					name: 'incentiveVault',
 					isMut: true,
 					isSigner: false,
 				},
-				{
-					name: 'systemProgram',
-					isMut: false,
-					isSigner: false,
-				},
 				{
 					name: 'tokenProgram',
 					isMut: false,
@@ -2703,419 +5377,1085 @@ export const IDL: Oracle = {
 			args: [],
 		},
 		{
-			name: 'collectProtocolFees',
+			name: 'reclaimIncentives',
 			docs: [
-				'collect protocol fees',
+				'reclaim incentives',
 				'',
-				'the config authority can at any time collect the protocol fees',
+				'the sponsor can reclaim the incentives if the vote',
+				'failed or no voter is eligible for rewards',
 			],
 			accounts: [
 				{
-					name: 'protocolAuthority',
+					name: 'sponsor',
 					isMut: false,
 					isSigner: true,
 				},
 				{
-					name: 'config',
+					name: 'sponsorAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'proposal',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'incentiveVault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'tokenProgram',
 					isMut: false,
 					isSigner: false,
-					pda: {
-						seeds: [
-							{
-								kind: 'const',
-								type: 'string',
-								value: 'sure-oracle-config',
-							},
-							{
-								kind: 'account',
-								type: 'publicKey',
-								account: 'Config',
-								path: 'config.token_mint',
-							},
+				},
+			],
+			args: [],
+		},
+	],
+	accounts: [
+		{
+			name: 'config',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'bump',
+						type: 'u8',
+					},
+					{
+						name: 'bumpArray',
+						type: {
+							array: ['u8', 1],
+						},
+					},
+					{
+						name: 'votingLengthSeconds',
+						docs: ['voting period in seconds'],
+						type: 'i64',
+					},
+					{
+						name: 'revealLengthSeconds',
+						docs: ['the lenght of the reveal period', 'in seconds'],
+						type: 'i64',
+					},
+					{
+						name: 'defaultRequiredVotes',
+						docs: ['the default required votes to reach', 'quorum.'],
+						type: 'u64',
+					},
+					{
+						name: 'minimumProposalStake',
+						docs: [
+							'the minimum amount of tokens that must',
+							'be staked on a proposal',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'voteStakeRate',
+						docs: [
+							'the 1/x of the voting power that needs',
+							'to be staked in order to vote',
+						],
+						type: 'u32',
+					},
+					{
+						name: 'protocolFeeRate',
+						docs: [
+							'the 1/x of the total voting escrow',
+							"that's going to the protocol",
 						],
+						type: 'u32',
 					},
-				},
-				{
-					name: 'proposal',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'proposalVault',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'feeDestination',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'tokenProgram',
-					isMut: false,
-					isSigner: false,
-				},
-			],
-			args: [],
+					{
+						name: 'tokenMint',
+						docs: ['official mint of pool'],
+						type: 'publicKey',
+					},
+					{
+						name: 'protocolAuthority',
+						docs: ['who can collect the rewards'],
+						type: 'publicKey',
+					},
+					{
+						name: 'locker',
+						docs: ['locker of the escrows used to vote'],
+						type: 'publicKey',
+					},
+					{
+						name: 'proposals',
+						docs: ['number of proposals created'],
+						type: 'u64',
+					},
+					{
+						name: 'reputationEnabled',
+						docs: [
+							'multiply the vote power by the',
+							'reputation factor of the voter',
+						],
+						type: 'bool',
+					},
+					{
+						name: 'consensusEstimator',
+						docs: [
+							'estimator used to reach consensus',
+							'see ConsensusEstimator',
+						],
+						type: 'u8',
+					},
+					{
+						name: 'consensusTrimRate',
+						docs: [
+							'the 1/x of the vote power trimmed from',
+							'each tail by the trimmed mean',
+						],
+						type: 'u32',
+					},
+					{
+						name: 'claimBond',
+						docs: [
+							'tokens bonded by the proposer to bind a',
+							'pool. Slashed if the claim is rejected',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'initialized',
+						type: 'bool',
+					},
+				],
+			},
 		},
-	],
-	accounts: [
 		{
-			name: 'config',
+			name: 'proposal',
 			type: {
 				kind: 'struct',
 				fields: [
+					{
+						name: 'config',
+						type: 'publicKey',
+					},
 					{
 						name: 'bump',
+						docs: ['bump for verification'],
+						type: 'u8',
+					},
+					{
+						name: 'bumpArray',
+						type: {
+							array: ['u8', 1],
+						},
+					},
+					{
+						name: 'locked',
+						docs: ['when the vote is finished and', 'users can reap rewards'],
+						type: 'bool',
+					},
+					{
+						name: 'optimistic',
+						docs: ['Optimistic'],
+						type: 'bool',
+					},
+					{
+						name: 'status',
 						type: 'u8',
 					},
 					{
-						name: 'votingLengthSeconds',
-						docs: ['voting period in seconds'],
-						type: 'i64',
+						name: 'name',
+						docs: ['name of vote'],
+						type: 'string',
+					},
+					{
+						name: 'id',
+						docs: ['id - hash of the proposal content'],
+						type: {
+							array: ['u8', 16],
+						},
+					},
+					{
+						name: 'description',
+						docs: ['description of vote'],
+						type: 'string',
+					},
+					{
+						name: 'evidence',
+						docs: ['reference to the off-chain evidence'],
+						type: {
+							defined: 'ProposalEvidence',
+						},
+					},
+					{
+						name: 'pool',
+						docs: [
+							'insurance pool the proposal claims against.',
+							'Pubkey::default() if not bound to a pool',
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'claimBond',
+						docs: [
+							'tokens bonded by the proposer when binding',
+							'the pool. Slashed if the claim is rejected',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'coveragePosition',
+						docs: ['coverage position the claim is paid to'],
+						type: 'publicKey',
+					},
+					{
+						name: 'claimAmount',
+						docs: [
+							'amount claimed for the coverage position.',
+							'The payout is the share of the claim',
+							'voted on by the voters',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'claimPaid',
+						docs: ['whether the accepted claim is paid out'],
+						type: 'bool',
+					},
+					{
+						name: 'proposedResult',
+						docs: ['Proposed result'],
+						type: 'i64',
+					},
+					{
+						name: 'proposer',
+						docs: ['user who proposed the vote'],
+						type: 'publicKey',
+					},
+					{
+						name: 'stakeRate',
+						docs: ['1/x of vote power that must be staked'],
+						type: 'u32',
+					},
+					{
+						name: 'staked',
+						docs: ['amount staked by propose Q32.32'],
+						type: 'u64',
+					},
+					{
+						name: 'vault',
+						docs: ['vault for storing stake and votes'],
+						type: 'publicKey',
+					},
+					{
+						name: 'requiredVotes',
+						docs: [
+							'% of ve tokens needed to conclude',
+							'represented as basis points 1% = 100bp',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'protocolFeeRate',
+						docs: ['as 1/x of revealed vote staked'],
+						type: 'u32',
+					},
+					{
+						name: 'votes',
+						docs: [
+							'Current votes given in basis points',
+							'1 vote = 1 veToken@',
+							'Q64.0',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'revealedVotes',
+						type: 'u64',
+					},
+					{
+						name: 'runningSumWeightedVote',
+						type: 'i64',
+					},
+					{
+						name: 'runningWeight',
+						type: 'u64',
+					},
+					{
+						name: 'voteStartAt',
+						docs: ['Start of vote'],
+						type: 'i64',
+					},
+					{
+						name: 'voteEndAt',
+						docs: ['Blind vote deadline'],
+						type: 'i64',
+					},
+					{
+						name: 'voteEndRevealAt',
+						docs: ['start reveal'],
+						type: 'i64',
+					},
+					{
+						name: 'earnedRewards',
+						docs: ['reward earned by propsing vote', 'Q64.64'],
+						type: 'u128',
+					},
+					{
+						name: 'protocolFees',
+						docs: ['protocol fees accrued', 'Q64.64'],
+						type: 'u128',
+					},
+					{
+						name: 'collectedProtocolFees',
+						docs: ['total protocol fees sent to the', 'protocol treasury'],
+						type: 'u64',
+					},
+					{
+						name: 'scaleParameter',
+						docs: ['Scale parameter in exp(L)', 'Q16.16'],
+						type: 'u32',
+					},
+					{
+						name: 'scaleParameterCalculated',
+						type: 'bool',
+					},
+					{
+						name: 'voteFactorSum',
+						docs: ['sum of the vote factors X_i', 'Q32.32'],
+						type: 'u64',
+					},
+					{
+						name: 'distributionSum',
+						docs: [
+							'sum of the reward weights F_i = V_i * X_i',
+							'used to normalize the voter rewards',
+							'Q64.32',
+						],
+						type: 'u128',
+					},
+					{
+						name: 'finalizedVotes',
+						docs: [
+							'vote power of the revealed votes that',
+							'have calculated their vote factor',
+							'Q64.0',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'rewardPool',
+						docs: [
+							'amount of tokens to be distributed',
+							'among the revealed votes',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'incentiveMint',
+						docs: [
+							'optional mint of the incentive rewards',
+							'Pubkey::default() if no incentives are attached',
+						],
+						type: 'publicKey',
 					},
 					{
-						name: 'revealLengthSeconds',
-						docs: ['the lenght of the reveal period', 'in seconds'],
-						type: 'i64',
+						name: 'incentiveVault',
+						docs: ['vault holding the incentive rewards'],
+						type: 'publicKey',
 					},
 					{
-						name: 'defaultRequiredVotes',
-						docs: ['the default required votes to reach', 'quorum.'],
-						type: 'u64',
+						name: 'incentiveSponsor',
+						docs: [
+							'account that attached the incentive vault. Receives',
+							'the incentives back if they cannot be distributed',
+						],
+						type: 'publicKey',
 					},
 					{
-						name: 'minimumProposalStake',
+						name: 'incentivePool',
 						docs: [
-							'the minimum amount of tokens that must',
-							'be staked on a proposal',
+							'amount of incentive tokens to be distributed',
+							'among the revealed votes',
 						],
 						type: 'u64',
 					},
 					{
-						name: 'voteStakeRate',
+						name: 'consensus',
+						docs: ['consensus reached after the reveal', 'I32.32'],
+						type: 'i64',
+					},
+					{
+						name: 'consensusEstimator',
 						docs: [
-							'the 1/x of the voting power that needs',
-							'to be staked in order to vote',
+							'estimator used to calculate the consensus',
+							'see ConsensusEstimator',
 						],
-						type: 'u32',
+						type: 'u8',
 					},
 					{
-						name: 'protocolFeeRate',
+						name: 'consensusTrimRate',
 						docs: [
-							'the 1/x of the total voting escrow',
-							"that's going to the protocol",
+							'1/x of the vote power trimmed from each',
+							'tail when using the trimmed mean',
 						],
 						type: 'u32',
 					},
+				],
+			},
+		},
+		{
+			name: 'voterReputation',
+			docs: [
+				'Voter reputation',
+				'',
+				'persistent record of the voting history of a voter',
+				'for a given config. Updated when the vote is submitted',
+				'and when the vote is finalized',
+			],
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'bump',
+						type: 'u8',
+					},
 					{
-						name: 'tokenMint',
-						docs: ['official mint of pool'],
+						name: 'bumpArray',
+						type: {
+							array: ['u8', 1],
+						},
+					},
+					{
+						name: 'config',
 						type: 'publicKey',
 					},
 					{
-						name: 'protocolAuthority',
-						docs: ['who can collect the rewards'],
+						name: 'owner',
 						type: 'publicKey',
 					},
 					{
-						name: 'initialized',
-						type: 'bool',
+						name: 'proposalsAtStart',
+						docs: [
+							'number of proposals in the config when',
+							'the reputation was created',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'votes',
+						docs: ['number of submitted votes'],
+						type: 'u64',
+					},
+					{
+						name: 'finalizedVotes',
+						docs: ['number of revealed and finalized votes'],
+						type: 'u64',
+					},
+					{
+						name: 'accuracySum',
+						docs: ['sum of accuracy scores exp(-l*(x-X)^2)', 'Q32.32'],
+						type: 'u64',
+					},
+					{
+						name: 'consensusDistanceSum',
+						docs: ['sum of absolute distances to consensus', 'Q64.32'],
+						type: 'u128',
 					},
 				],
 			},
 		},
 		{
-			name: 'proposal',
+			name: 'revealedVoteArray',
 			type: {
 				kind: 'struct',
 				fields: [
-					{
-						name: 'config',
-						type: 'publicKey',
-					},
 					{
 						name: 'bump',
-						docs: ['bump for verification'],
 						type: 'u8',
 					},
 					{
-						name: 'bumpArray',
+						name: 'proposal',
+						type: 'publicKey',
+					},
+					{
+						name: 'weightedVotes',
+						docs: ['Q32.32'],
 						type: {
-							array: ['u8', 1],
+							array: ['i64', 768],
 						},
 					},
 					{
-						name: 'locked',
-						docs: ['when the vote is finished and', 'users can reap rewards'],
-						type: 'bool',
+						name: 'votePowers',
+						docs: ['vote power of each revealed vote'],
+						type: {
+							array: ['u32', 768],
+						},
 					},
 					{
-						name: 'optimistic',
-						docs: ['Optimistic'],
-						type: 'bool',
+						name: 'lastIndex',
+						type: 'i16',
 					},
+				],
+			},
+		},
+		{
+			name: 'voteAccount',
+			type: {
+				kind: 'struct',
+				fields: [
 					{
-						name: 'status',
+						name: 'bump',
 						type: 'u8',
 					},
 					{
-						name: 'name',
-						docs: ['name of vote'],
-						type: 'string',
-					},
-					{
-						name: 'id',
-						docs: ['id - hashed name'],
+						name: 'bumpArray',
 						type: {
-							array: ['u8', 16],
+							array: ['u8', 1],
 						},
 					},
 					{
-						name: 'description',
-						docs: ['description of vote'],
-						type: 'string',
+						name: 'proposal',
+						type: 'publicKey',
 					},
 					{
-						name: 'proposedResult',
-						docs: ['Proposed result'],
-						type: 'i64',
+						name: 'owner',
+						type: 'publicKey',
 					},
 					{
-						name: 'proposer',
-						docs: ['user who proposed the vote'],
+						name: 'stakeMint',
 						type: 'publicKey',
 					},
 					{
-						name: 'stakeRate',
-						docs: ['1/x of vote power that must be staked'],
-						type: 'u32',
+						name: 'voteHash',
+						type: {
+							array: ['u8', 32],
+						},
+					},
+					{
+						name: 'vote',
+						docs: ['real vote:', 'I32.32'],
+						type: 'i64',
 					},
 					{
 						name: 'staked',
-						docs: ['amount staked by propose Q32.32'],
 						type: 'u64',
 					},
 					{
-						name: 'vault',
-						docs: ['vault for storing stake and votes'],
-						type: 'publicKey',
+						name: 'voteFactor',
+						docs: ['F = V * l * exp(-l*(x-X))'],
+						type: 'u64',
 					},
 					{
-						name: 'requiredVotes',
-						docs: [
-							'% of ve tokens needed to conclude',
-							'represented as basis points 1% = 100bp',
-						],
+						name: 'earnedRewards',
+						docs: ['rewards earned from voting', 'C * F / S_v'],
 						type: 'u64',
 					},
 					{
-						name: 'protocolFeeRate',
-						docs: ['as 1/x of revealed vote staked'],
+						name: 'votePower',
 						type: 'u32',
 					},
 					{
-						name: 'votes',
+						name: 'escrowAmount',
 						docs: [
-							'Current votes given in basis points',
-							'1 vote = 1 veToken@',
-							'Q64.0',
+							'tokens locked in the escrow when the vote',
+							'was submitted. Caps later vote power updates',
 						],
 						type: 'u64',
 					},
 					{
-						name: 'revealedVotes',
-						type: 'u64',
+						name: 'revealedVote',
+						type: 'bool',
 					},
 					{
-						name: 'runningSumWeightedVote',
-						type: 'i64',
+						name: 'voteFactorCalculated',
+						type: 'bool',
 					},
 					{
-						name: 'runningWeight',
+						name: 'incentiveCollected',
+						type: 'bool',
+					},
+					{
+						name: 'locked',
+						type: 'bool',
+					},
+				],
+			},
+		},
+	],
+	types: [
+		{
+			name: 'ProposalEvidence',
+			docs: [
+				'Proposal evidence',
+				'',
+				'structured reference to the off-chain incident report',
+				'so that voters and keepers can verify what they vote on',
+			],
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'uri',
+						docs: ['where to find the incident report'],
+						type: 'string',
+					},
+					{
+						name: 'contentHash',
+						docs: ['sha3-256 hash of the incident report'],
+						type: {
+							array: ['u8', 32],
+						},
+					},
+					{
+						name: 'affectedProgram',
+						docs: ['program affected by the incident'],
+						type: 'publicKey',
+					},
+					{
+						name: 'startSlot',
+						docs: ['first slot of the incident'],
 						type: 'u64',
 					},
 					{
-						name: 'voteStartAt',
-						docs: ['Start of vote'],
-						type: 'i64',
+						name: 'endSlot',
+						docs: ['last slot of the incident'],
+						type: 'u64',
 					},
+				],
+			},
+		},
+		{
+			name: 'Strategy',
+			docs: ['How a voter decides on the vote'],
+			type: {
+				kind: 'enum',
+				variants: [
 					{
-						name: 'voteEndAt',
-						docs: ['Blind vote deadline'],
-						type: 'i64',
+						name: 'Honest',
 					},
 					{
-						name: 'voteEndRevealAt',
-						docs: ['start reveal'],
-						type: 'i64',
+						name: 'Lazy',
 					},
 					{
-						name: 'earnedRewards',
-						docs: ['reward earned by propsing vote', 'Q64.64'],
-						type: 'u128',
+						name: 'Adversarial',
+					},
+				],
+			},
+		},
+		{
+			name: 'ConsensusEstimator',
+			docs: ['Estimator used to reach consensus', 'from the revealed votes'],
+			type: {
+				kind: 'enum',
+				variants: [
+					{
+						name: 'Mean',
 					},
 					{
-						name: 'protocolFees',
-						docs: ['protocol fees'],
-						type: 'u128',
+						name: 'WeightedMedian',
 					},
 					{
-						name: 'scaleParameter',
-						docs: ['Scale parameter in exp(L)', 'Q16.16'],
-						type: 'u32',
+						name: 'TrimmedMean',
+					},
+				],
+			},
+		},
+		{
+			name: 'ProposalStatus',
+			type: {
+				kind: 'enum',
+				variants: [
+					{
+						name: 'Failed',
+					},
+					{
+						name: 'Proposed',
+					},
+					{
+						name: 'Voting',
+					},
+					{
+						name: 'ReachedQuorum',
 					},
 					{
-						name: 'scaleParameterCalculated',
-						type: 'bool',
+						name: 'RevealVote',
 					},
 					{
-						name: 'voteFactorSum',
-						type: 'u64',
+						name: 'VoteRevealFinished',
 					},
 					{
-						name: 'distributionSum',
-						type: 'u128',
+						name: 'RewardCalculation',
 					},
 					{
-						name: 'consensus',
-						type: 'i64',
+						name: 'RewardPayout',
 					},
 				],
 			},
 		},
+	],
+	events: [
+		{
+			name: 'BoundPoolEvent',
+			fields: [
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'pool',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'smartContract',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'coveragePosition',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'claimAmount',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'claimBond',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'CancelledVote',
+			fields: [
+				{
+					name: 'vote',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'time',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'refund',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'CollectProposerRewardEvent',
+			fields: [
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'time',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'reward',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'CollectedProtocolFees',
+			fields: [
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'fees',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'totalCollectedFees',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'destination',
+					type: 'publicKey',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'SweptProtocolFees',
+			fields: [
+				{
+					name: 'config',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'fees',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'proposals',
+					type: 'u32',
+					index: false,
+				},
+				{
+					name: 'protocolTreasury',
+					type: 'publicKey',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'CollectVoteRewardEvent',
+			fields: [
+				{
+					name: 'vote',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'time',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'reward',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'InitializedConfigEvent',
+			fields: [
+				{
+					name: 'config',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'protocolTreasury',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'locker',
+					type: 'publicKey',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'UpdatedVotingPeriod',
+			fields: [
+				{
+					name: 'oldVotingPeriod',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'votingPeriod',
+					type: 'i64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'UpdatedRevealPeriod',
+			fields: [
+				{
+					name: 'oldRevealPeriod',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'revealPeriod',
+					type: 'i64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'UpdatedRequiredVotes',
+			fields: [
+				{
+					name: 'oldRequiredVotes',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'requiredVotes',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'UpdatedProposalMinumumStake',
+			fields: [
+				{
+					name: 'oldMinimumStake',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'minimumStake',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'UpdatedVoteStakeRate',
+			fields: [
+				{
+					name: 'oldVoteStakeRate',
+					type: 'u32',
+					index: false,
+				},
+				{
+					name: 'voteStakeRate',
+					type: 'u32',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'UpdatedProtocolFeerate',
+			fields: [
+				{
+					name: 'oldProtocolFeeRate',
+					type: 'u32',
+					index: false,
+				},
+				{
+					name: 'protocolFeeRate',
+					type: 'u32',
+					index: false,
+				},
+			],
+		},
 		{
-			name: 'revealedVoteArray',
-			type: {
-				kind: 'struct',
-				fields: [
-					{
-						name: 'bump',
-						type: 'u8',
-					},
-					{
-						name: 'proposal',
-						type: 'publicKey',
-					},
-					{
-						name: 'weightedVotes',
-						docs: ['Q32.32'],
-						type: {
-							array: ['i64', 1024],
-						},
-					},
-					{
-						name: 'lastIndex',
-						type: 'i16',
-					},
-				],
-			},
+			name: 'UpdatedReputationEnabled',
+			fields: [
+				{
+					name: 'reputationEnabled',
+					type: 'bool',
+					index: false,
+				},
+			],
 		},
 		{
-			name: 'voteAccount',
-			type: {
-				kind: 'struct',
-				fields: [
-					{
-						name: 'bump',
-						type: 'u8',
-					},
-					{
-						name: 'bumpArray',
-						type: {
-							array: ['u8', 1],
-						},
-					},
-					{
-						name: 'proposal',
-						type: 'publicKey',
-					},
-					{
-						name: 'owner',
-						type: 'publicKey',
-					},
-					{
-						name: 'stakeMint',
-						type: 'publicKey',
-					},
-					{
-						name: 'voteHash',
-						type: {
-							array: ['u8', 32],
-						},
-					},
-					{
-						name: 'vote',
-						docs: ['real vote:', 'I32.32'],
-						type: 'i64',
-					},
-					{
-						name: 'staked',
-						type: 'u64',
-					},
-					{
-						name: 'voteFactor',
-						docs: ['F = V * l * exp(-l*(x-X))'],
-						type: 'u64',
-					},
-					{
-						name: 'earnedRewards',
-						docs: ['rewards earned from voting', 'C * F / S_v'],
-						type: 'u64',
-					},
-					{
-						name: 'votePower',
-						type: 'u32',
-					},
-					{
-						name: 'revealedVote',
-						type: 'bool',
-					},
-					{
-						name: 'locked',
-						type: 'bool',
-					},
-				],
-			},
+			name: 'UpdatedConsensusEstimator',
+			fields: [
+				{
+					name: 'oldConsensusEstimator',
+					type: 'u8',
+					index: false,
+				},
+				{
+					name: 'consensusEstimator',
+					type: 'u8',
+					index: false,
+				},
+				{
+					name: 'consensusTrimRate',
+					type: 'u32',
+					index: false,
+				},
+			],
 		},
-	],
-	types: [
 		{
-			name: 'ProposalStatus',
-			type: {
-				kind: 'enum',
-				variants: [
-					{
-						name: 'Failed',
-					},
-					{
-						name: 'Proposed',
-					},
-					{
-						name: 'Voting',
-					},
-					{
-						name: 'ReachedQuorum',
-					},
-					{
-						name: 'RevealVote',
-					},
-					{
-						name: 'VoteRevealFinished',
-					},
-					{
-						name: 'RewardCalculation',
-					},
-					{
-						name: 'RewardPayout',
-					},
-				],
-			},
+			name: 'UpdatedClaimBond',
+			fields: [
+				{
+					name: 'oldClaimBond',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'claimBond',
+					type: 'u64',
+					index: false,
+				},
+			],
 		},
-	],
-	events: [
 		{
-			name: 'CancelledVote',
+			name: 'FinalizedVoteResultsEvent',
+			fields: [
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'time',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'revealedVotes',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'consensus',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'rewardPool',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'status',
+					type: 'u8',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'FinalizedVoteEvent',
+			fields: [
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'time',
+					type: 'i64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'CollectVoteIncentiveEvent',
 			fields: [
 				{
 					name: 'vote',
@@ -3133,14 +6473,64 @@ export const IDL: Oracle = {
 					index: false,
 				},
 				{
-					name: 'refund',
-					type: 'u64',
+					name: 'reward',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'FundedIncentiveVaultEvent',
+			fields: [
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'funder',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'amount',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'incentivePool',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'InitializedIncentiveVaultEvent',
+			fields: [
+				{
+					name: 'proposal',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'incentiveMint',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'incentiveVault',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'sponsor',
+					type: 'publicKey',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'CollectProposerRewardEvent',
+			name: 'ReclaimedIncentivesEvent',
 			fields: [
 				{
 					name: 'proposal',
@@ -3148,158 +6538,136 @@ export const IDL: Oracle = {
 					index: false,
 				},
 				{
-					name: 'time',
-					type: 'i64',
+					name: 'sponsor',
+					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'reward',
+					name: 'amount',
 					type: 'u64',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'CollectedProtocolFees',
+			name: 'InitializedVoterReputationEvent',
 			fields: [
 				{
-					name: 'proposal',
+					name: 'config',
 					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'fees',
-					type: 'u64',
+					name: 'voter',
+					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'destination',
+					name: 'voterReputation',
 					type: 'publicKey',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'CollectVoteRewardEvent',
+			name: 'PaidClaimEvent',
 			fields: [
 				{
-					name: 'vote',
+					name: 'proposal',
 					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'proposal',
+					name: 'pool',
 					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'time',
-					type: 'i64',
+					name: 'coveragePosition',
+					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'reward',
+					name: 'claimAmount',
 					type: 'u64',
 					index: false,
 				},
-			],
-		},
-		{
-			name: 'InitializedConfigEvent',
-			fields: [],
-		},
-		{
-			name: 'UpdatedVotingPeriod',
-			fields: [
 				{
-					name: 'oldVotingPeriod',
-					type: 'i64',
+					name: 'claimPayout',
+					type: 'u64',
 					index: false,
 				},
 				{
-					name: 'votingPeriod',
-					type: 'i64',
+					name: 'claimBond',
+					type: 'u64',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'UpdatedRevealPeriod',
+			name: 'ProposeVoteEvent',
 			fields: [
 				{
-					name: 'oldRevealPeriod',
-					type: 'i64',
+					name: 'name',
+					type: 'string',
 					index: false,
 				},
 				{
-					name: 'revealPeriod',
-					type: 'i64',
+					name: 'description',
+					type: 'string',
 					index: false,
 				},
-			],
-		},
-		{
-			name: 'UpdatedRequiredVotes',
-			fields: [
 				{
-					name: 'oldRequiredVotes',
-					type: 'u64',
+					name: 'id',
+					type: 'bytes',
 					index: false,
 				},
 				{
-					name: 'requiredVotes',
-					type: 'u64',
+					name: 'evidence',
+					type: {
+						defined: 'ProposalEvidence',
+					},
 					index: false,
 				},
-			],
-		},
-		{
-			name: 'UpdatedProposalMinumumStake',
-			fields: [
 				{
-					name: 'oldMinimumStake',
-					type: 'u64',
+					name: 'proposer',
+					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'minimumStake',
+					name: 'stake',
 					type: 'u64',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'UpdatedVoteStakeRate',
+			name: 'ReleasedPoolEvent',
 			fields: [
 				{
-					name: 'oldVoteStakeRate',
-					type: 'u32',
+					name: 'proposal',
+					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'voteStakeRate',
-					type: 'u32',
+					name: 'pool',
+					type: 'publicKey',
 					index: false,
 				},
-			],
-		},
-		{
-			name: 'UpdatedProtocolFeerate',
-			fields: [
 				{
-					name: 'oldProtocolFeeRate',
-					type: 'u32',
+					name: 'claimBond',
+					type: 'u64',
 					index: false,
 				},
 				{
-					name: 'protocolFeeRate',
-					type: 'u32',
+					name: 'bondDestination',
+					type: 'publicKey',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'FinalizedVoteResultsEvent',
+			name: 'RevealedVoteEvent',
 			fields: [
 				{
 					name: 'proposal',
@@ -3312,24 +6680,19 @@ export const IDL: Oracle = {
 					index: false,
 				},
 				{
-					name: 'revealedVotes',
-					type: 'u64',
-					index: false,
-				},
-				{
-					name: 'consensus',
+					name: 'revealedVote',
 					type: 'i64',
 					index: false,
 				},
 				{
-					name: 'status',
-					type: 'u8',
+					name: 'votePower',
+					type: 'u32',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'FinalizedVoteEvent',
+			name: 'SubmittedVoteEvent',
 			fields: [
 				{
 					name: 'proposal',
@@ -3341,41 +6704,26 @@ export const IDL: Oracle = {
 					type: 'i64',
 					index: false,
 				},
-			],
-		},
-		{
-			name: 'ProposeVoteEvent',
-			fields: [
-				{
-					name: 'name',
-					type: 'string',
-					index: false,
-				},
-				{
-					name: 'description',
-					type: 'string',
-					index: false,
-				},
 				{
-					name: 'id',
+					name: 'voteHash',
 					type: 'bytes',
 					index: false,
 				},
 				{
-					name: 'proposer',
-					type: 'publicKey',
-					index: false,
-				},
-				{
-					name: 'stake',
+					name: 'votePower',
 					type: 'u64',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'RevealedVoteEvent',
+			name: 'UpdatedVoteStakeEvent',
 			fields: [
+				{
+					name: 'vote',
+					type: 'publicKey',
+					index: false,
+				},
 				{
 					name: 'proposal',
 					type: 'publicKey',
@@ -3387,19 +6735,24 @@ export const IDL: Oracle = {
 					index: false,
 				},
 				{
-					name: 'revealedVote',
-					type: 'i64',
+					name: 'votePower',
+					type: 'u64',
 					index: false,
 				},
 				{
-					name: 'votePower',
-					type: 'u32',
+					name: 'stakeChange',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'increaseStake',
+					type: 'bool',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'SubmittedVoteEvent',
+			name: 'UpdatedVoteEvent',
 			fields: [
 				{
 					name: 'proposal',
@@ -3416,29 +6769,24 @@ export const IDL: Oracle = {
 					type: 'bytes',
 					index: false,
 				},
-				{
-					name: 'votePower',
-					type: 'u64',
-					index: false,
-				},
 			],
 		},
 		{
-			name: 'UpdatedVoteEvent',
+			name: 'WithdrewProtocolFees',
 			fields: [
 				{
-					name: 'proposal',
+					name: 'config',
 					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'time',
-					type: 'i64',
+					name: 'amount',
+					type: 'u64',
 					index: false,
 				},
 				{
-					name: 'voteHash',
-					type: 'bytes',
+					name: 'destination',
+					type: 'publicKey',
 					index: false,
 				},
 			],
@@ -3502,78 +6850,238 @@ export const IDL: Oracle = {
 		},
 		{
 			code: 6011,
+			name: 'OverflowU128',
+			msg: 'U128 overflow',
+		},
+		{
+			code: 6012,
 			name: 'NotPossibleToCalculateVoteReward',
 			msg: 'Could not calculate the vote reward at this time',
 		},
 		{
-			code: 6012,
+			code: 6013,
 			name: 'NotPossibleToCollectProposerReward',
 			msg: 'Cannot payout the proposer reward at this time',
 		},
 		{
-			code: 6013,
+			code: 6014,
 			name: 'NotPossibleToCollectVoterReward',
 			msg: 'Cannot payout the voter reward at this time',
 		},
 		{
-			code: 6014,
+			code: 6015,
 			name: 'FailedToFinalizeVote',
 			msg: 'Cannot finalize user vote at this time',
 		},
 		{
-			code: 6015,
+			code: 6016,
 			name: 'FailedToFinalizeVoteResult',
 			msg: 'Cannot finalize vote result at this time',
 		},
 		{
-			code: 6016,
+			code: 6017,
 			name: 'FailedToCancelVote',
 			msg: 'Too late to cancel vote',
 		},
 		{
-			code: 6017,
+			code: 6018,
 			name: 'InvalidOwnerOfVoteAccount',
 			msg: 'The owner of the vote account is not the signer',
 		},
 		{
-			code: 6018,
+			code: 6019,
 			name: 'ProposalVaultMintKeyDoesNotMatchProposalStateVaultMint',
 			msg: 'Proposal.vault_mint does not match the input proposal_vault_mint key',
 		},
 		{
-			code: 6019,
+			code: 6020,
 			name: 'ProposalVaultMintKeyDoesNotMatchVaultMint',
 			msg: 'Proposal.vault_mint does not match the vault mint key',
 		},
 		{
-			code: 6020,
+			code: 6021,
 			name: 'NotEnoughProposalStake',
 			msg: 'Not enough stake to propose a vote ',
 		},
 		{
-			code: 6021,
+			code: 6022,
 			name: 'InvalidRequiredVotesParam',
 			msg: 'Quorum requirements are too low',
 		},
 		{
-			code: 6022,
+			code: 6023,
 			name: 'InvalidMinimumStakedParam',
 			msg: 'Invalid minimum staked on proposal',
 		},
 		{
-			code: 6023,
+			code: 6024,
 			name: 'InvalidVoteStakeRateParam',
 			msg: 'Invalid vote stake rate param. Probably less than 1',
 		},
 		{
-			code: 6024,
+			code: 6025,
 			name: 'InvalidProtocolFeeRateParam',
 			msg: 'Invalid protocol fee rate param. Probably less than 1',
 		},
 		{
-			code: 6025,
+			code: 6026,
 			name: 'UnauthorizedSigner',
 			msg: 'Unauthorized signer',
 		},
+		{
+			code: 6027,
+			name: 'InvalidRemainingAccounts',
+			msg: 'Remaining accounts must be pairs of proposal and proposal vault',
+		},
+		{
+			code: 6028,
+			name: 'InvalidProposalConfig',
+			msg: 'Proposal does not belong to the config',
+		},
+		{
+			code: 6029,
+			name: 'InvalidProposalVault',
+			msg: 'Proposal vault does not match the proposal',
+		},
+		{
+			code: 6030,
+			name: 'IncentiveVaultAlreadyInitialized',
+			msg: 'Incentive vault is already attached to the proposal',
+		},
+		{
+			code: 6031,
+			name: 'IncentiveVaultNotInitialized',
+			msg: 'Proposal has no incentive vault',
+		},
+		{
+			code: 6032,
+			name: 'NotPossibleToFundIncentives',
+			msg: 'Cannot fund the incentives at this time',
+		},
+		{
+			code: 6033,
+			name: 'NotPossibleToReclaimIncentives',
+			msg: 'Cannot reclaim the incentives at this time',
+		},
+		{
+			code: 6034,
+			name: 'IncentiveAlreadyCollected',
+			msg: 'Incentive reward is already collected',
+		},
+		{
+			code: 6035,
+			name: 'InvalidIncentiveSponsor',
+			msg: 'Signer is not the sponsor of the incentives',
+		},
+		{
+			code: 6036,
+			name: 'InvalidLocker',
+			msg: 'Locker does not match the config locker',
+		},
+		{
+			code: 6037,
+			name: 'EscrowLockedAfterVoteStart',
+			msg: 'Escrow was locked after the vote started',
+		},
+		{
+			code: 6038,
+			name: 'InvalidVotePower',
+			msg: 'Could not calculate the vote power of the escrow',
+		},
+		{
+			code: 6039,
+			name: 'VotePowerExceedsEscrow',
+			msg: 'Vote power exceeds the vote power of the escrow',
+		},
+		{
+			code: 6040,
+			name: 'MissingVoterReputation',
+			msg: 'Voter reputation is required when the reputation is enabled',
+		},
+		{
+			code: 6041,
+			name: 'InvalidVoterReputation',
+			msg: 'Voter reputation does not belong to the voter',
+		},
+		{
+			code: 6042,
+			name: 'ProposalContentTooLong',
+			msg: 'Proposal name, description or evidence is too long',
+		},
+		{
+			code: 6043,
+			name: 'InvalidEvidenceSlotRange',
+			msg: 'Evidence slot range ends before it starts',
+		},
+		{
+			code: 6044,
+			name: 'ProposalAlreadyBoundToPool',
+			msg: 'Proposal is already bound to a pool',
+		},
+		{
+			code: 6045,
+			name: 'ProposalNotBoundToPool',
+			msg: 'Proposal is not bound to a pool',
+		},
+		{
+			code: 6046,
+			name: 'InvalidPoolForProposal',
+			msg: 'Pool does not insure the program affected by the proposal',
+		},
+		{
+			code: 6047,
+			name: 'NotPossibleToReleasePool',
+			msg: 'Cannot release the pool before the vote is over',
+		},
+		{
+			code: 6048,
+			name: 'InvalidClaimAmount',
+			msg: 'Claim amount is zero or larger than the coverage',
+		},
+		{
+			code: 6049,
+			name: 'ClaimNotAccepted',
+			msg: 'Claim is not accepted',
+		},
+		{
+			code: 6050,
+			name: 'ClaimAlreadyPaid',
+			msg: 'Claim is already paid',
+		},
+		{
+			code: 6051,
+			name: 'ClaimIsAccepted',
+			msg: 'Accepted claims release the pool when they are paid',
+		},
+		{
+			code: 6052,
+			name: 'InvalidTokenPool',
+			msg: 'Token pool does not hold the coverage of the claim',
+		},
+		{
+			code: 6053,
+			name: 'InvalidClaimant',
+			msg: 'Claimant is not the owner of the coverage position',
+		},
+		{
+			code: 6054,
+			name: 'PoolIsPaused',
+			msg: 'Pool is paused',
+		},
+		{
+			code: 6055,
+			name: 'PoolIsLocked',
+			msg: 'Pool is already locked for a claim',
+		},
+		{
+			code: 6056,
+			name: 'InvalidConsensusEstimator',
+			msg: 'Unknown consensus estimator',
+		},
+		{
+			code: 6057,
+			name: 'InvalidConsensusTrimRate',
+			msg: 'Trim rate must be larger than 2',
+		},
 	],
 };
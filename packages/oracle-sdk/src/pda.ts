@@ -3,21 +3,29 @@ import { PublicKey } from '@solana/web3.js';
 import {
 	SURE_ADDRESSES,
 	SURE_ORACLE_CONFIG_SEED,
+	SURE_ORACLE_PROPOSAL_VAULT_SEED,
 	SURE_ORACLE_REVEAL_ARRAY_SEED,
 	SURE_ORACLE_SEED,
+	SURE_ORACLE_TREASURY_SEED,
 	SURE_ORACLE_VOTE_SEED,
 } from './constants.js';
-import { createProposalHash } from './utils.js';
+import { createProposalHash, ProposalEvidence } from './utils.js';
 
 export class PDA {
 	constructor() {}
 
 	findProposalAddress({
-		proposalName,
+		proposer,
+		name,
+		description,
+		evidence,
 	}: {
-		proposalName: string;
+		proposer: PublicKey;
+		name: string;
+		description: string;
+		evidence: ProposalEvidence;
 	}): [PublicKey, number] {
-		const id = createProposalHash({ name: proposalName });
+		const id = createProposalHash({ proposer, name, description, evidence });
 		return anchor.utils.publicKey.findProgramAddressSync(
 			[SURE_ORACLE_SEED, id],
 			SURE_ADDRESSES.Oracle
@@ -54,7 +62,7 @@ export class PDA {
 		proposal: PublicKey;
 	}): [PublicKey, number] {
 		return anchor.utils.publicKey.findProgramAddressSync(
-			[SURE_ORACLE_SEED, proposal.toBuffer()],
+			[SURE_ORACLE_PROPOSAL_VAULT_SEED, proposal.toBuffer()],
 			SURE_ADDRESSES.Oracle
 		);
 	}
@@ -69,4 +77,15 @@ export class PDA {
 			SURE_ADDRESSES.Oracle
 		);
 	}
+
+	findProtocolTreasury({
+		config,
+	}: {
+		config: PublicKey;
+	}): [PublicKey, number] {
+		return anchor.utils.publicKey.findProgramAddressSync(
+			[SURE_ORACLE_TREASURY_SEED, config.toBuffer()],
+			SURE_ADDRESSES.Oracle
+		);
+	}
 }
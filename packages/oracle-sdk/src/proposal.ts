@@ -5,8 +5,8 @@ import { SURE_MINT } from './constants.js';
 import { SureOracleSDK } from './sdk.js';
 import { TransactionEnvelope } from '@saberhq/solana-contrib/dist/cjs';
 import {
-	createProposalHash,
 	getOrCreateAssociatedTokenAccountIx,
+	ProposalEvidence,
 	validateKeys,
 } from './utils.js';
 import { ProposalType } from './program.js';
@@ -18,6 +18,7 @@ import * as spl from '@solana/spl-token';
 type ProposeVote = {
 	name: string;
 	description: string;
+	evidence: ProposalEvidence;
 	stake: anchor.BN;
 	mint?: PublicKey;
 };
@@ -41,11 +42,12 @@ export class Proposal {
 		return await this.program.account.proposal.all();
 	}
 
-	async fetchProposal({ name }: { name: string }): Promise<ProposalType> {
-		const [proposalPDA] = this.sdk.pda.findProposalAddress({
-			proposalName: name,
-		});
-		return await this.program.account.proposal.fetch(proposalPDA);
+	async fetchProposal({
+		proposal,
+	}: {
+		proposal: PublicKey;
+	}): Promise<ProposalType> {
+		return await this.program.account.proposal.fetch(proposal);
 	}
 
 	/**
@@ -55,6 +57,7 @@ export class Proposal {
 	 *
 	 * @param name - name of vote
 	 * @param description - description of vote
+	 * @param evidence - reference to the off-chain incident report
 	 * @param stake - the amount of stake the user bets on the vote
 	 * @param mint <optional> - SURE MINT if nothing specified
 	 * @returns
@@ -62,6 +65,7 @@ export class Proposal {
 	async proposeVote({
 		name,
 		description,
+		evidence,
 		stake,
 		mint,
 	}: ProposeVote): Promise<TransactionEnvelope> {
@@ -86,19 +90,27 @@ export class Proposal {
 			ixs.push(proposerAccount.instruction);
 		}
 
-		const id = createProposalHash({ name });
 		const [config] = this.sdk.pda.findOracleConfig({ tokenMint });
 		const [proposal] = SureOracleSDK.pda().findProposalAddress({
-			proposalName: name,
+			proposer: this.sdk.provider.walletKey,
+			name,
+			description,
+			evidence,
+		});
+		const [revealVoteArray] = this.sdk.pda.findRevealVoteArrayAddress({
+			proposal,
 		});
+		const [proposalVault] = this.sdk.pda.findProposalVault({ proposal });
 		ixs.push(
 			await this.program.methods
-				.proposeVote(id, name, description, stake)
+				.proposeVote(name, description, evidence, stake)
 				.accounts({
 					config,
 					proposal,
+					revealVoteArray,
 					proposerAccount: proposerAccount.address,
 					proposalVaultMint: tokenMint,
+					proposalVault,
 				})
 				.instruction()
 		);
@@ -7,7 +7,7 @@ import {
 } from '@solana/web3.js';
 import { ProposalType } from './program.js';
 import * as anchor from '@project-serum/anchor';
-import { SHAKE } from 'sha3';
+import { SHA3 } from 'sha3';
 import * as spl from '@solana/spl-token';
 
 export const validateKeys = (keys: { v: PublicKey; n: string }[]) => {
@@ -112,10 +112,61 @@ const isRevealVoteFinished = (
 	return currentTime >= proposal.voteEndRevealAt;
 };
 
-export const createProposalHash = ({ name }: { name: string }): Buffer => {
-	const hash = new SHAKE(128);
-	hash.update(name);
-	return hash.digest();
+export type ProposalEvidence = {
+	uri: string;
+	contentHash: number[];
+	affectedProgram: PublicKey;
+	startSlot: anchor.BN;
+	endSlot: anchor.BN;
+};
+
+const serializeString = (value: string): Buffer => {
+	const bytes = Buffer.from(value, 'utf8');
+	const length = Buffer.alloc(4);
+	length.writeUInt32LE(bytes.length);
+	return Buffer.concat([length, bytes]);
+};
+
+/**
+ * serialize proposal evidence
+ *
+ * borsh encoding of the evidence as stored on-chain
+ */
+export const serializeProposalEvidence = (
+	evidence: ProposalEvidence
+): Buffer => {
+	return Buffer.concat([
+		serializeString(evidence.uri),
+		Buffer.from(evidence.contentHash),
+		evidence.affectedProgram.toBuffer(),
+		evidence.startSlot.toArrayLike(Buffer, 'le', 8),
+		evidence.endSlot.toArrayLike(Buffer, 'le', 8),
+	]);
+};
+
+/**
+ * create proposal hash
+ *
+ * the proposal id is the first 16 bytes of the sha3-256
+ * hash of the proposer and the content of the proposal
+ */
+export const createProposalHash = ({
+	proposer,
+	name,
+	description,
+	evidence,
+}: {
+	proposer: PublicKey;
+	name: string;
+	description: string;
+	evidence: ProposalEvidence;
+}): Buffer => {
+	const hash = new SHA3(256);
+	hash.update(proposer.toBuffer());
+	hash.update(serializeString(name));
+	hash.update(serializeString(description));
+	hash.update(serializeProposalEvidence(evidence));
+	return hash.digest().subarray(0, 16);
 };
 
 type ATAInput = {
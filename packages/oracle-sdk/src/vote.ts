@@ -110,6 +110,7 @@ export class Vote {
 			owner: this.sdk.provider.walletKey,
 		});
 		const [proposalVault] = await this.sdk.pda.findProposalVault({ proposal });
+		const [config] = this.sdk.pda.findOracleConfig({ tokenMint });
 
 		if (createATA.instruction) {
 			ixs.push(createATA.instruction);
@@ -120,6 +121,7 @@ export class Vote {
 				.submitVote(voteHash)
 				.accounts({
 					voterAccount: createATA.address,
+					config,
 					locker,
 					userEscrow,
 					proposal,
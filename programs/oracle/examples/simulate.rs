@@ -0,0 +1,50 @@
+//! Sweep the oracle config parameters and write the
+//! expected return of each voter strategy as CSV
+//!
+//! cargo run -p oracle --example simulate --features simulation -- results.csv
+
+use std::{fs::File, io::BufWriter, io::Write};
+
+use oracle::{
+    simulation::{simulate, write_csv, SimulationParams, CSV_HEADER},
+    states::ConsensusEstimator,
+};
+
+const VOTE_STAKE_RATES: [u32; 3] = [10, 50, 100];
+const PROTOCOL_FEE_RATES: [u32; 3] = [20, 50, 100];
+const REQUIRED_VOTES: [u64; 3] = [10_000, 40_000, 60_000];
+const CONSENSUS_ESTIMATORS: [ConsensusEstimator; 3] = [
+    ConsensusEstimator::Mean,
+    ConsensusEstimator::WeightedMedian,
+    ConsensusEstimator::TrimmedMean,
+];
+const SEED: u64 = 0x5eed_5eed;
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "oracle_simulation.csv".to_string());
+    let mut writer = BufWriter::new(File::create(&path)?);
+    writeln!(writer, "{}", CSV_HEADER)?;
+
+    for vote_stake_rate in VOTE_STAKE_RATES {
+        for protocol_fee_rate in PROTOCOL_FEE_RATES {
+            for required_votes in REQUIRED_VOTES {
+                for consensus_estimator in CONSENSUS_ESTIMATORS {
+                    let params = SimulationParams {
+                        vote_stake_rate,
+                        protocol_fee_rate,
+                        required_votes,
+                        consensus_estimator,
+                        ..Default::default()
+                    };
+                    let results = simulate(&params, SEED).expect("simulation failed");
+                    write_csv(&mut writer, &params, &results)?;
+                }
+            }
+        }
+    }
+    writer.flush()?;
+    eprintln!("wrote {}", path);
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use sure_pool::{
+    program::SurePool,
+    states::{CoveragePosition, Pool, PoolManager},
+    utils::SURE_PROTOCOL_OWNER,
+};
+
+use crate::{
+    states::{Config, Proposal},
+    utils::{tokenTx, SureError, SURE_ORACLE_CLAIM_BOND_SEED},
+};
+
+#[derive(Accounts)]
+#[instruction(claim_amount: u64)]
+pub struct BindPool<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// account paying the claim bond
+    #[account(
+        mut,
+        constraint = proposer_account.mint == config.token_mint,
+    )]
+    pub proposer_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = proposal.config @ SureError::InvalidProposalConfig)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        has_one = proposer
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// vault holding the claim bond until
+    /// the vote on the claim is over
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [
+            SURE_ORACLE_CLAIM_BOND_SEED.as_bytes().as_ref(),
+            proposal.key().as_ref(),
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = proposal,
+    )]
+    pub claim_bond_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = config.token_mint)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    /// insurance pool to claim against
+    #[account(
+        mut,
+        constraint = !pool.paused @ SureError::PoolIsPaused,
+        constraint = !pool.locked @ SureError::PoolIsLocked,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// pool manager holding the oracle config
+    /// trusted with claims
+    #[account(
+        seeds = [SURE_PROTOCOL_OWNER.as_bytes()],
+        bump = pool_manager.bump,
+        seeds::program = pool_program.key(),
+        constraint = pool_manager.oracle_config == config.key() @ SureError::InvalidProposalConfig,
+    )]
+    pub pool_manager: Box<Account<'info, PoolManager>>,
+
+    /// coverage position the claim is paid to
+    #[account(
+        constraint = coverage_position.load()?.pool == pool.key() @ SureError::InvalidPoolForProposal,
+        constraint = coverage_position.load()?.active @ SureError::InvalidClaimAmount,
+        constraint = claim_amount <= coverage_position.load()?.insured_amount @ SureError::InvalidClaimAmount,
+    )]
+    pub coverage_position: AccountLoader<'info, CoveragePosition>,
+
+    pub pool_program: Program<'info, SurePool>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Bind pool
+///
+/// bind the proposal to an insurance pool. The pool is
+/// locked against new coverage until the vote is over and
+/// rejects concurrent claims.
+///
+/// Only proposals of the oracle config trusted by the
+/// pool manager can claim against pools.
+///
+/// The proposer bonds config.claim_bond tokens. The bond
+/// is slashed if the claim is rejected so that locking
+/// pools with frivolous claims is costly
+///
+/// ### args
+/// * claim_amount: amount claimed for the coverage position
+pub fn handler(ctx: Context<BindPool>, claim_amount: u64) -> Result<()> {
+    let proposal = ctx.accounts.proposal.as_mut();
+    let claim_bond = ctx.accounts.config.claim_bond;
+    let time = clock::Clock::get()?.unix_timestamp;
+
+    proposal.bind_pool(
+        &ctx.accounts.pool.key(),
+        &ctx.accounts.pool.smart_contract,
+        &ctx.accounts.coverage_position.key(),
+        claim_amount,
+        claim_bond,
+        time,
+    )?;
+
+    // post the claim bond
+    tokenTx::deposit_into_vault(
+        &ctx.accounts.proposer,
+        &ctx.accounts.claim_bond_vault,
+        &ctx.accounts.proposer_account,
+        &ctx.accounts.token_program,
+        claim_bond,
+    )?;
+
+    // lock pool and record the claim
+    sure_pool::cpi::lock_pool_for_claim(CpiContext::new_with_signer(
+        ctx.accounts.pool_program.to_account_info(),
+        sure_pool::cpi::accounts::LockPoolForClaim {
+            claim: proposal.to_account_info(),
+            pool_manager: ctx.accounts.pool_manager.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+        },
+        &[&proposal.seeds()],
+    ))?;
+
+    emit!(BoundPoolEvent {
+        proposal: proposal.key(),
+        pool: ctx.accounts.pool.key(),
+        smart_contract: ctx.accounts.pool.smart_contract,
+        coverage_position: ctx.accounts.coverage_position.key(),
+        claim_amount,
+        claim_bond,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct BoundPoolEvent {
+    pub proposal: Pubkey,
+    pub pool: Pubkey,
+    pub smart_contract: Pubkey,
+    pub coverage_position: Pubkey,
+    pub claim_amount: u64,
+    pub claim_bond: u64,
+}
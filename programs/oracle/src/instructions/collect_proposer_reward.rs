@@ -45,14 +45,13 @@ pub struct CollectProposerReward<'info> {
 
 pub fn handler(ctx: Context<CollectProposerReward>) -> Result<()> {
     let proposal = ctx.accounts.proposal.as_mut();
-    let decimals = ctx.accounts.proposal_vault_mint.decimals;
     let time = clock::Clock::get()?.unix_timestamp;
 
     // check if the proposer can claim payout
     proposal.can_collect_proposer_rewards(time)?;
 
     // get reward
-    let reward = proposal.payout_earned_rewards_at_time(decimals, time)?;
+    let reward = proposal.payout_earned_rewards_at_time(time)?;
 
     // payout
     tokenTx::withdraw_from_vault(
@@ -2,70 +2,111 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 
 use crate::states::{Config, Proposal};
-use crate::utils::{tokenTx, SureError, SURE_ORACLE_CONFIG_SEED};
+use crate::utils::{tokenTx, SureError, SURE_ORACLE_CONFIG_SEED, SURE_ORACLE_TREASURY_SEED};
 
 #[derive(Accounts)]
 pub struct CollectProtocolFees<'info> {
-    #[account( address = config.protocol_authority @ SureError::UnauthorizedSigner )]
-    pub protocol_authority: Signer<'info>,
+    pub signer: Signer<'info>,
 
     #[account(
         seeds = [
             SURE_ORACLE_CONFIG_SEED.as_bytes().as_ref(),
             config.token_mint.key().as_ref(),
         ],
-        bump,
+        bump = config.bump,
     )]
     pub config: Box<Account<'info, Config>>,
 
     #[account(
         mut,
-        has_one = config
-    )]
-    pub proposal: Box<Account<'info, Proposal>>,
-
-    #[account(
-        mut,
-        constraint = proposal_vault.key() == proposal.vault
-    )]
-    pub proposal_vault: Box<Account<'info, TokenAccount>>,
-
-    #[account(
-        mut,
-        constraint = fee_destination.mint == config.token_mint,
-        constraint = fee_destination.mint == proposal_vault.mint
+        seeds = [
+            SURE_ORACLE_TREASURY_SEED.as_bytes().as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+        constraint = protocol_treasury.mint == config.token_mint
     )]
-    pub fee_destination: Account<'info, TokenAccount>,
+    pub protocol_treasury: Box<Account<'info, TokenAccount>>,
 
     #[account(address = token::ID)]
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
-    let proposal = ctx.accounts.proposal.as_mut();
+/// collect protocol fees
+///
+/// sweeps the accrued protocol fees of the proposals into
+/// the protocol treasury. Anyone can sweep the fees since
+/// they can only end up in the treasury
+///
+/// ### remaining accounts
+/// pairs of
+/// * proposal: mut, belongs to the config
+/// * proposal_vault: mut, the vault of the proposal
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CollectProtocolFees<'info>>) -> Result<()> {
+    let config = ctx.accounts.config.as_ref();
+    let remaining_accounts = ctx.remaining_accounts;
+    if remaining_accounts.is_empty() || remaining_accounts.len() % 2 != 0 {
+        return Err(SureError::InvalidRemainingAccounts.into());
+    }
+
+    let mut total_fees: u64 = 0;
+    for accounts in remaining_accounts.chunks(2) {
+        let mut proposal = Account::<Proposal>::try_from(&accounts[0])?;
+        let proposal_vault = Account::<TokenAccount>::try_from(&accounts[1])?;
+        if proposal.config != config.key() {
+            return Err(SureError::InvalidProposalConfig.into());
+        }
+        if proposal.vault != proposal_vault.key() {
+            return Err(SureError::InvalidProposalVault.into());
+        }
+
+        let protocol_fees = proposal.payout_accrued_protocol_fees()?;
+
+        // withdraw
+        tokenTx::withdraw_from_vault(
+            &proposal,
+            &proposal_vault,
+            &ctx.accounts.protocol_treasury,
+            &ctx.accounts.token_program,
+            protocol_fees,
+        )?;
 
-    let protocol_fees = proposal.payout_accrued_protocol_fees()?;
+        // persist proposal
+        proposal.exit(ctx.program_id)?;
 
-    // withdraw
-    tokenTx::withdraw_from_vault(
-        proposal,
-        &ctx.accounts.proposal_vault,
-        &ctx.accounts.fee_destination,
-        &ctx.accounts.token_program,
-        protocol_fees,
-    )?;
+        total_fees = total_fees
+            .checked_add(protocol_fees)
+            .ok_or(SureError::OverflowU64)?;
 
-    emit!(CollectedProtocolFees {
-        proposal: proposal.key(),
-        fees: protocol_fees,
-        destination: ctx.accounts.fee_destination.key(),
+        emit!(CollectedProtocolFees {
+            proposal: proposal.key(),
+            fees: protocol_fees,
+            total_collected_fees: proposal.collected_protocol_fees,
+            destination: ctx.accounts.protocol_treasury.key(),
+        });
+    }
+
+    emit!(SweptProtocolFees {
+        config: config.key(),
+        fees: total_fees,
+        proposals: (remaining_accounts.len() / 2) as u32,
+        protocol_treasury: ctx.accounts.protocol_treasury.key(),
     });
     Ok(())
 }
 
 #[event]
 pub struct CollectedProtocolFees {
-    proposal: Pubkey,
-    fees: u64,
-    destination: Pubkey,
+    pub proposal: Pubkey,
+    pub fees: u64,
+    pub total_collected_fees: u64,
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct SweptProtocolFees {
+    pub config: Pubkey,
+    pub fees: u64,
+    pub proposals: u32,
+    pub protocol_treasury: Pubkey,
 }
@@ -23,7 +23,11 @@ pub struct CollectVoteReward<'info> {
     )]
     pub voter_account: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = vote_account.load()?.proposal == proposal.key(),
+        constraint = vote_account.load()?.owner == voter.key() @ SureError::InvalidOwnerOfVoteAccount
+    )]
     pub vote_account: AccountLoader<'info, VoteAccount>,
 
     #[account(
@@ -53,13 +57,12 @@ pub fn handler(ctx: Context<CollectVoteReward>) -> Result<()> {
     let mut vote_account = ctx.accounts.vote_account.load_mut()?;
     let proposal = ctx.accounts.proposal.as_ref();
     let time = clock::Clock::get()?.unix_timestamp;
-    let mint_decimals = ctx.accounts.proposal_vault_mint.decimals;
 
     // check if it is possible to collect rewards
     proposal.can_collect_voter_reward(time)?;
 
     // get the user vote reward
-    let reward = vote_account.calculate_token_reward_at_time(proposal, mint_decimals, time)?;
+    let reward = vote_account.calculate_token_reward_at_time(proposal, time)?;
 
     tokenTx::withdraw_from_vault(
         proposal,
@@ -1,7 +1,8 @@
 use crate::states::*;
-use crate::utils::SURE_ORACLE_CONFIG_SEED;
+use crate::utils::{SureError, SURE_ORACLE_CONFIG_SEED, SURE_ORACLE_TREASURY_SEED};
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use locked_voter::Locker;
 
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
@@ -23,12 +24,36 @@ pub struct InitializeConfig<'info> {
     #[account()]
     pub token_mint: Box<Account<'info, Mint>>,
 
+    /// locker of the escrows that can vote
+    #[account(
+        constraint = locker.token_mint == token_mint.key() @ SureError::InvalidLocker
+    )]
+    pub locker: Box<Account<'info, Locker>>,
+
+    /// treasury for the protocol fees
+    #[account(
+        init,
+        payer = signer,
+        seeds = [
+            SURE_ORACLE_TREASURY_SEED.as_bytes().as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = config,
+    )]
+    pub protocol_treasury: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
     pub system_program: Program<'info, System>,
 }
 
 /// initialize config
 ///
-/// unique for a given token mint
+/// unique for a given token mint. Creates the
+/// protocol treasury that receives the protocol fees
+/// and fixes the locker whose escrows can vote
 ///
 /// ### args
 /// * protocol_authority<Pubkey>: permissions
@@ -36,11 +61,25 @@ pub struct InitializeConfig<'info> {
 ///     - collect protocol fees
 pub fn handler(ctx: Context<InitializeConfig>, protocol_authority: Pubkey) -> Result<()> {
     let config = ctx.accounts.config.as_mut();
-    config.initialize(ctx.accounts.token_mint.as_ref(), protocol_authority);
+    let config_bump = *ctx.bumps.get("config").unwrap();
+    config.initialize(
+        config_bump,
+        ctx.accounts.token_mint.as_ref(),
+        protocol_authority,
+        ctx.accounts.locker.key(),
+    );
 
-    emit!(InitializedConfigEvent {});
+    emit!(InitializedConfigEvent {
+        config: config.key(),
+        protocol_treasury: ctx.accounts.protocol_treasury.key(),
+        locker: ctx.accounts.locker.key(),
+    });
     Ok(())
 }
 
 #[event]
-pub struct InitializedConfigEvent {}
+pub struct InitializedConfigEvent {
+    pub config: Pubkey,
+    pub protocol_treasury: Pubkey,
+    pub locker: Pubkey,
+}
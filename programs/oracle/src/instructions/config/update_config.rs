@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::{
     states::Config,
-    utils::{SureError, MIN_VOTING_LENGTH_SECONDS},
+    utils::{SureError, MAX_VOTING_LENGTH_SECONDS, MIN_VOTING_LENGTH_SECONDS},
 };
 
 #[derive(Accounts)]
@@ -10,13 +10,14 @@ pub struct UpdateConfig<'info> {
     #[account(address = config.protocol_authority)]
     pub protocol_authority: Signer<'info>,
 
+    #[account(mut)]
     pub config: Box<Account<'info, Config>>,
 
     pub system_program: Program<'info, System>,
 }
 
 pub fn update_voting_period(ctx: Context<UpdateConfig>, voting_period: i64) -> Result<()> {
-    if voting_period < MIN_VOTING_LENGTH_SECONDS {
+    if voting_period < MIN_VOTING_LENGTH_SECONDS || voting_period > MAX_VOTING_LENGTH_SECONDS {
         return Err(SureError::InvalidVoteEndTime.into());
     }
     let old_voting_period = ctx.accounts.config.voting_length_seconds;
@@ -37,7 +38,7 @@ pub struct UpdatedVotingPeriod {
 }
 
 pub fn update_reveal_period(ctx: Context<UpdateConfig>, reveal_period: i64) -> Result<()> {
-    if reveal_period < MIN_VOTING_LENGTH_SECONDS {
+    if reveal_period < MIN_VOTING_LENGTH_SECONDS || reveal_period > MAX_VOTING_LENGTH_SECONDS {
         return Err(SureError::InvalidVoteEndTime.into());
     }
     let old_reveal_period = ctx.accounts.config.reveal_length_seconds;
@@ -136,3 +137,59 @@ pub struct UpdatedProtocolFeerate {
     old_protocol_fee_rate: u32,
     protocol_fee_rate: u32,
 }
+
+pub fn update_reputation_enabled(
+    ctx: Context<UpdateConfig>,
+    reputation_enabled: bool,
+) -> Result<()> {
+    ctx.accounts
+        .config
+        .update_reputation_enabled(reputation_enabled)?;
+    emit!(UpdatedReputationEnabled { reputation_enabled });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedReputationEnabled {
+    reputation_enabled: bool,
+}
+
+pub fn update_consensus_estimator(
+    ctx: Context<UpdateConfig>,
+    consensus_estimator: u8,
+    consensus_trim_rate: u32,
+) -> Result<()> {
+    let old_consensus_estimator = ctx.accounts.config.consensus_estimator;
+    ctx.accounts
+        .config
+        .update_consensus_estimator(consensus_estimator, consensus_trim_rate)?;
+    emit!(UpdatedConsensusEstimator {
+        old_consensus_estimator,
+        consensus_estimator,
+        consensus_trim_rate
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedConsensusEstimator {
+    old_consensus_estimator: u8,
+    consensus_estimator: u8,
+    consensus_trim_rate: u32,
+}
+
+pub fn update_claim_bond(ctx: Context<UpdateConfig>, claim_bond: u64) -> Result<()> {
+    let old_claim_bond = ctx.accounts.config.claim_bond;
+    ctx.accounts.config.update_claim_bond(claim_bond)?;
+    emit!(UpdatedClaimBond {
+        old_claim_bond,
+        claim_bond
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedClaimBond {
+    old_claim_bond: u64,
+    claim_bond: u64,
+}
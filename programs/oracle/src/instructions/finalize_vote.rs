@@ -1,6 +1,6 @@
 use anchor_lang::{prelude::*, solana_program::clock};
 
-use crate::states::{Proposal, VoteAccount};
+use crate::states::{Proposal, VoteAccount, VoterReputation};
 use crate::utils::SURE_ORACLE_SEED;
 #[derive(Accounts)]
 pub struct FinalizeVote<'info> {
@@ -15,6 +15,7 @@ pub struct FinalizeVote<'info> {
     pub vote_account: AccountLoader<'info, VoteAccount>,
 
     #[account(
+        mut,
         seeds = [
             SURE_ORACLE_SEED.as_bytes().as_ref(),
             proposal.id.as_ref(), // checkpoint - don't use name as seed 
@@ -29,10 +30,15 @@ pub struct FinalizeVote<'info> {
 /// prepare vote reward
 ///
 /// when the reveal period is over and the scale parameter is calculated
-/// the user can calculate the vote factor F = l*exp(-l*(x-X))
-pub fn handler(ctx: Context<FinalizeVote>) -> Result<()> {
+/// the user can calculate the vote factor X = l*exp(-l*(x-X))
+/// and add its reward weight to the proposal distribution
+///
+/// ### remaining accounts
+/// * voter_reputation: mut, optional. Records the accuracy
+///     of the vote
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, FinalizeVote<'info>>) -> Result<()> {
     let mut vote_account = ctx.accounts.vote_account.load_mut()?;
-    let proposal = ctx.accounts.proposal.as_ref();
+    let proposal = ctx.accounts.proposal.as_mut();
     let time = clock::Clock::get()?.unix_timestamp;
 
     // check if vote can be finalized
@@ -40,6 +46,24 @@ pub fn handler(ctx: Context<FinalizeVote>) -> Result<()> {
 
     vote_account.calculate_vote_factor(proposal)?;
 
+    // add reward weight to sum F
+    proposal.update_distribution_sum(&vote_account)?;
+
+    // update accuracy of the voter
+    let voter_reputation = VoterReputation::load_optional(
+        ctx.remaining_accounts,
+        &proposal.config,
+        &ctx.accounts.signer.key(),
+        false,
+    )?;
+    if let Some(mut voter_reputation) = voter_reputation {
+        voter_reputation.record_finalized_vote(proposal, &vote_account)?;
+        voter_reputation.exit(ctx.program_id)?;
+    }
+
+    // cb: update status of proposal
+    proposal.update_status(time);
+
     emit!(FinalizedVoteEvent {
         proposal: proposal.key(),
         time,
@@ -1,5 +1,6 @@
 use crate::states::{Proposal, RevealedVoteArray};
 use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::TokenAccount;
 
 use super::reveal_vote;
 
@@ -16,6 +17,11 @@ pub struct FinalizeVoteResults<'info> {
     )]
     pub revealed_votes: AccountLoader<'info, RevealedVoteArray>,
 
+    #[account(
+        constraint = proposal_vault.key() == proposal.vault
+    )]
+    pub proposal_vault: Box<Account<'info, TokenAccount>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -35,13 +41,21 @@ pub fn handler(ctx: Context<FinalizeVoteResults>) -> Result<()> {
     // check if it's possible to finalie vote result
     proposal.can_finalize_vote_results(time)?;
 
-    proposal.try_finalize_vote_after_reveal(&revealed_votes, time)?;
+    proposal.try_finalize_vote_after_reveal(
+        &revealed_votes,
+        ctx.accounts.proposal_vault.amount,
+        time,
+    )?;
+
+    // cb: update status of proposal
+    proposal.update_status(time);
 
     emit!(FinalizedVoteResultsEvent {
         proposal: proposal.key(),
         time,
         revealed_votes: proposal.revealed_votes,
         consensus: proposal.consensus,
+        reward_pool: proposal.reward_pool,
         status: proposal.status
     });
     Ok(())
@@ -53,5 +67,6 @@ pub struct FinalizedVoteResultsEvent {
     pub time: i64,
     pub revealed_votes: u64,
     pub consensus: i64,
+    pub reward_pool: u64,
     pub status: u8,
 }
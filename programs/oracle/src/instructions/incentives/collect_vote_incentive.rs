@@ -0,0 +1,70 @@
+use crate::states::*;
+use crate::utils::{tokenTx, SureError};
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CollectVoteIncentive<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = voter_account.mint == incentive_vault.mint,
+        constraint = voter_account.owner == voter.key()
+    )]
+    pub voter_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vote_account.load()?.proposal == proposal.key(),
+        constraint = vote_account.load()?.owner == voter.key() @ SureError::InvalidOwnerOfVoteAccount
+    )]
+    pub vote_account: AccountLoader<'info, VoteAccount>,
+
+    #[account(
+        has_one = incentive_vault @ SureError::IncentiveVaultNotInitialized
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub incentive_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// collect vote incentive
+///
+/// after the vote is finalized the voter can collect
+/// the share of the incentive pool
+pub fn handler(ctx: Context<CollectVoteIncentive>) -> Result<()> {
+    let mut vote_account = ctx.accounts.vote_account.load_mut()?;
+    let proposal = ctx.accounts.proposal.as_ref();
+    let time = clock::Clock::get()?.unix_timestamp;
+
+    let reward = vote_account.calculate_incentive_reward_at_time(proposal, time)?;
+
+    tokenTx::withdraw_from_vault(
+        proposal,
+        &ctx.accounts.incentive_vault,
+        &ctx.accounts.voter_account,
+        &ctx.accounts.token_program,
+        reward,
+    )?;
+
+    emit!(CollectVoteIncentiveEvent {
+        vote: ctx.accounts.vote_account.key(),
+        proposal: proposal.key(),
+        time,
+        reward,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct CollectVoteIncentiveEvent {
+    pub vote: Pubkey,
+    pub proposal: Pubkey,
+    pub time: i64,
+    pub reward: u64,
+}
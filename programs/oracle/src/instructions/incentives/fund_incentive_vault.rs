@@ -0,0 +1,66 @@
+use crate::states::*;
+use crate::utils::{tokenTx, SureError};
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct FundIncentiveVault<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = funder_account.mint == incentive_vault.mint,
+        constraint = funder_account.owner == funder.key()
+    )]
+    pub funder_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = incentive_vault @ SureError::IncentiveVaultNotInitialized
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub incentive_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// fund incentive vault
+///
+/// anyone can add incentives to the proposal until
+/// the vote results are finalized
+///
+/// ### args
+/// * amount<u64>: amount of incentive tokens
+pub fn handler(ctx: Context<FundIncentiveVault>, amount: u64) -> Result<()> {
+    let proposal = ctx.accounts.proposal.as_mut();
+    let time = clock::Clock::get()?.unix_timestamp;
+
+    proposal.fund_incentives(amount, time)?;
+
+    tokenTx::deposit_into_vault(
+        &ctx.accounts.funder,
+        &ctx.accounts.incentive_vault,
+        &ctx.accounts.funder_account,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(FundedIncentiveVaultEvent {
+        proposal: proposal.key(),
+        funder: ctx.accounts.funder.key(),
+        amount,
+        incentive_pool: proposal.incentive_pool,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct FundedIncentiveVaultEvent {
+    pub proposal: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub incentive_pool: u64,
+}
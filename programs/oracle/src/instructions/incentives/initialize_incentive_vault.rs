@@ -0,0 +1,102 @@
+use crate::states::*;
+use crate::utils::{SureError, SURE_ORACLE_INCENTIVE_VAULT_SEED};
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use sure_pool::states::Pool;
+
+#[derive(Accounts)]
+pub struct InitializeIncentiveVault<'info> {
+    /// sponsor of the incentives. Either the proposer
+    /// or the founder of the pool bound to the proposal
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// mint of the incentive rewards
+    pub incentive_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = sponsor,
+        seeds = [
+            SURE_ORACLE_INCENTIVE_VAULT_SEED.as_bytes().as_ref(),
+            proposal.key().as_ref(),
+        ],
+        bump,
+        token::mint = incentive_mint,
+        token::authority = proposal,
+    )]
+    pub incentive_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+/// initialize incentive vault
+///
+/// attach a vault in a separate mint to the proposal.
+/// The incentives are distributed among the voters
+/// using the same weights as the voter rewards
+///
+/// ### remaining accounts
+/// * pool: optional. Pool bound to the proposal, required
+///     if the pool founder sponsors the incentives
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitializeIncentiveVault<'info>>,
+) -> Result<()> {
+    let proposal = ctx.accounts.proposal.as_mut();
+    let time = clock::Clock::get()?.unix_timestamp;
+    let pool_founder = load_pool_founder(ctx.remaining_accounts, proposal)?;
+
+    proposal.initialize_incentives(
+        &ctx.accounts.incentive_mint.key(),
+        &ctx.accounts.incentive_vault.key(),
+        &ctx.accounts.sponsor.key(),
+        pool_founder.as_ref(),
+        time,
+    )?;
+
+    emit!(InitializedIncentiveVaultEvent {
+        proposal: proposal.key(),
+        incentive_mint: ctx.accounts.incentive_mint.key(),
+        incentive_vault: ctx.accounts.incentive_vault.key(),
+        sponsor: ctx.accounts.sponsor.key(),
+    });
+    Ok(())
+}
+
+/// Load the founder of the pool bound to the proposal
+///
+/// the pool is passed as the first remaining account
+///
+/// ### Arguments
+/// * remaining_accounts: remaining accounts of the instruction
+/// * proposal: proposal the incentives are attached to
+fn load_pool_founder<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    proposal: &Proposal,
+) -> Result<Option<Pubkey>> {
+    let pool_info = match remaining_accounts.first() {
+        Some(pool_info) => pool_info,
+        None => return Ok(None),
+    };
+    if !proposal.is_bound_to_pool() {
+        return Err(SureError::ProposalNotBoundToPool.into());
+    }
+    if pool_info.key() != proposal.pool {
+        return Err(SureError::InvalidPoolForProposal.into());
+    }
+    let pool = Account::<Pool>::try_from(pool_info)?;
+    Ok(Some(pool.founder))
+}
+
+#[event]
+pub struct InitializedIncentiveVaultEvent {
+    pub proposal: Pubkey,
+    pub incentive_mint: Pubkey,
+    pub incentive_vault: Pubkey,
+    pub sponsor: Pubkey,
+}
@@ -0,0 +1,9 @@
+pub mod collect_vote_incentive;
+pub mod fund_incentive_vault;
+pub mod initialize_incentive_vault;
+pub mod reclaim_incentives;
+
+pub use collect_vote_incentive::*;
+pub use fund_incentive_vault::*;
+pub use initialize_incentive_vault::*;
+pub use reclaim_incentives::*;
@@ -0,0 +1,61 @@
+use crate::states::*;
+use crate::utils::{tokenTx, SureError};
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::{Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ReclaimIncentives<'info> {
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = sponsor_account.mint == incentive_vault.mint,
+        constraint = sponsor_account.owner == sponsor.key()
+    )]
+    pub sponsor_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = incentive_vault @ SureError::IncentiveVaultNotInitialized,
+        constraint = proposal.incentive_sponsor == sponsor.key() @ SureError::InvalidIncentiveSponsor
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub incentive_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// reclaim incentives
+///
+/// if the vote failed or no voter is eligible for
+/// rewards the sponsor can reclaim the incentives
+pub fn handler(ctx: Context<ReclaimIncentives>) -> Result<()> {
+    let proposal = ctx.accounts.proposal.as_mut();
+    let time = clock::Clock::get()?.unix_timestamp;
+
+    let amount = proposal.reclaim_incentives_at_time(time)?;
+
+    tokenTx::withdraw_from_vault(
+        proposal,
+        &ctx.accounts.incentive_vault,
+        &ctx.accounts.sponsor_account,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(ReclaimedIncentivesEvent {
+        proposal: proposal.key(),
+        sponsor: ctx.accounts.sponsor.key(),
+        amount,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct ReclaimedIncentivesEvent {
+    pub proposal: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+}
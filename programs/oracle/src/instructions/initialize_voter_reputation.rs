@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{Config, VoterReputation};
+use crate::utils::SURE_ORACLE_REPUTATION_SEED;
+
+#[derive(Accounts)]
+pub struct InitializeVoterReputation<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init,
+        payer = voter,
+        seeds = [
+            SURE_ORACLE_REPUTATION_SEED.as_bytes().as_ref(),
+            config.key().as_ref(),
+            voter.key().as_ref(),
+        ],
+        bump,
+        space = 8 + VoterReputation::SPACE,
+    )]
+    pub voter_reputation: Box<Account<'info, VoterReputation>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// initialize voter reputation
+///
+/// creates the persistent reputation of the voter
+/// for the config. Required to vote
+pub fn handler(ctx: Context<InitializeVoterReputation>) -> Result<()> {
+    let config = ctx.accounts.config.as_ref();
+    let voter_reputation = ctx.accounts.voter_reputation.as_mut();
+    let bump = *ctx.bumps.get("voter_reputation").unwrap();
+    voter_reputation.initialize(
+        bump,
+        &config.key(),
+        &ctx.accounts.voter.key(),
+        config.proposals,
+    );
+
+    emit!(InitializedVoterReputationEvent {
+        config: config.key(),
+        voter: ctx.accounts.voter.key(),
+        voter_reputation: voter_reputation.key(),
+    });
+    Ok(())
+}
+
+#[event]
+pub struct InitializedVoterReputationEvent {
+    pub config: Pubkey,
+    pub voter: Pubkey,
+    pub voter_reputation: Pubkey,
+}
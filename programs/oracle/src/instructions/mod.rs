@@ -1,3 +1,4 @@
+pub mod bind_pool;
 pub mod cancel_vote;
 pub mod collect_proposer_reward;
 pub mod collect_protocol_fees;
@@ -5,11 +6,18 @@ pub mod collect_vote_reward;
 pub mod config;
 pub mod finalize_vote;
 pub mod finalize_vote_results;
+pub mod incentives;
+pub mod initialize_voter_reputation;
+pub mod pay_claim;
 pub mod propose_vote;
+pub mod release_pool;
 pub mod reveal_vote;
 pub mod submit_vote;
 pub mod update_vote;
+pub mod update_vote_stake;
+pub mod withdraw_protocol_fees;
 
+pub use bind_pool::*;
 pub use cancel_vote::*;
 pub use collect_proposer_reward::*;
 pub use collect_protocol_fees::*;
@@ -17,7 +25,13 @@ pub use collect_vote_reward::*;
 pub use config::*;
 pub use finalize_vote::*;
 pub use finalize_vote_results::*;
+pub use incentives::*;
+pub use initialize_voter_reputation::*;
+pub use pay_claim::*;
 pub use propose_vote::*;
+pub use release_pool::*;
 pub use reveal_vote::*;
 pub use submit_vote::*;
 pub use update_vote::*;
+pub use update_vote_stake::*;
+pub use withdraw_protocol_fees::*;
@@ -0,0 +1,177 @@
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::{Token, TokenAccount};
+use sure_pool::{
+    program::SurePool,
+    states::{CoveragePosition, Pool, PoolManager, TickArray, TokenPool},
+    utils::SURE_PROTOCOL_OWNER,
+};
+
+use crate::{
+    states::{Config, Proposal},
+    utils::{tokenTx, SureError, SURE_ORACLE_CLAIM_BOND_SEED},
+};
+
+#[derive(Accounts)]
+pub struct PayClaim<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(address = proposal.config @ SureError::InvalidProposalConfig)]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// vault holding the claim bond
+    #[account(
+        mut,
+        seeds = [
+            SURE_ORACLE_CLAIM_BOND_SEED.as_bytes().as_ref(),
+            proposal.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub claim_bond_vault: Box<Account<'info, TokenAccount>>,
+
+    /// receives the claim bond back
+    #[account(
+        mut,
+        constraint = proposer_account.owner == proposal.proposer,
+        constraint = proposer_account.mint == config.token_mint,
+    )]
+    pub proposer_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = proposal.pool @ SureError::ProposalNotBoundToPool
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// pool manager holding the oracle config
+    /// trusted with claims
+    #[account(
+        seeds = [SURE_PROTOCOL_OWNER.as_bytes()],
+        bump = pool_manager.bump,
+        seeds::program = pool_program.key(),
+        constraint = pool_manager.oracle_config == config.key() @ SureError::InvalidProposalConfig,
+    )]
+    pub pool_manager: Box<Account<'info, PoolManager>>,
+
+    /// token pool the coverage is bought from
+    #[account(
+        mut,
+        has_one = pool @ SureError::InvalidTokenPool,
+        constraint = token_pool.vault == liquidity_vault.key() @ SureError::InvalidTokenPool,
+        constraint = token_pool.token_mint == coverage_position.load()?.token_mint @ SureError::InvalidTokenPool,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
+    /// coverage position the claim is paid to
+    #[account(
+        mut,
+        address = proposal.coverage_position @ SureError::ProposalNotBoundToPool
+    )]
+    pub coverage_position: AccountLoader<'info, CoveragePosition>,
+
+    /// token account of the coverage owner
+    #[account(
+        mut,
+        constraint = claimant_token_account.owner == coverage_position.load()?.owner @ SureError::InvalidClaimant,
+        constraint = claimant_token_account.mint == token_pool.token_mint @ SureError::InvalidClaimant,
+    )]
+    pub claimant_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// liquidity vault of the token pool
+    #[account(mut)]
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
+
+    /// tick arrays holding the liquidity
+    /// used by the coverage position
+    #[account(mut)]
+    pub tick_array_0: AccountLoader<'info, TickArray>,
+
+    #[account(mut)]
+    pub tick_array_1: AccountLoader<'info, TickArray>,
+
+    #[account(mut)]
+    pub tick_array_2: AccountLoader<'info, TickArray>,
+
+    pub pool_program: Program<'info, SurePool>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pay claim
+///
+/// pay the accepted claim to the coverage position and
+/// unlock the pool. The payout is the share of the claim
+/// amount resolved by the vote, capped at the coverage
+/// left in the position. The claim can only be paid once.
+/// The claim bond is returned to the proposer
+pub fn handler(ctx: Context<PayClaim>) -> Result<()> {
+    let time = clock::Clock::get()?.unix_timestamp;
+    let claim_payout = ctx.accounts.proposal.pay_claim(time)?;
+    let insured_amount = ctx.accounts.coverage_position.load()?.insured_amount;
+    let claim_payout = claim_payout.min(insured_amount);
+
+    let proposal = ctx.accounts.proposal.as_ref();
+    let claim_bond = ctx.accounts.claim_bond_vault.amount;
+    tokenTx::withdraw_from_vault(
+        &ctx.accounts.proposal,
+        &ctx.accounts.claim_bond_vault,
+        &ctx.accounts.proposer_account,
+        &ctx.accounts.token_program,
+        claim_bond,
+    )?;
+
+    if claim_payout > 0 {
+        sure_pool::cpi::pay_claim(
+            CpiContext::new_with_signer(
+                ctx.accounts.pool_program.to_account_info(),
+                sure_pool::cpi::accounts::PayClaim {
+                    claim: proposal.to_account_info(),
+                    pool_manager: ctx.accounts.pool_manager.to_account_info(),
+                    pool: ctx.accounts.pool.to_account_info(),
+                    token_pool: ctx.accounts.token_pool.to_account_info(),
+                    coverage_position: ctx.accounts.coverage_position.to_account_info(),
+                    claimant_token_account: ctx.accounts.claimant_token_account.to_account_info(),
+                    liquidity_vault: ctx.accounts.liquidity_vault.to_account_info(),
+                    tick_array_0: ctx.accounts.tick_array_0.to_account_info(),
+                    tick_array_1: ctx.accounts.tick_array_1.to_account_info(),
+                    tick_array_2: ctx.accounts.tick_array_2.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                &[&proposal.seeds()],
+            ),
+            claim_payout,
+        )?;
+    }
+
+    sure_pool::cpi::release_pool_claim(CpiContext::new_with_signer(
+        ctx.accounts.pool_program.to_account_info(),
+        sure_pool::cpi::accounts::ReleasePoolClaim {
+            claim: proposal.to_account_info(),
+            pool_manager: ctx.accounts.pool_manager.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+        },
+        &[&proposal.seeds()],
+    ))?;
+
+    emit!(PaidClaimEvent {
+        proposal: proposal.key(),
+        pool: ctx.accounts.pool.key(),
+        coverage_position: ctx.accounts.coverage_position.key(),
+        claim_amount: proposal.claim_amount,
+        claim_payout,
+        claim_bond,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct PaidClaimEvent {
+    pub proposal: Pubkey,
+    pub pool: Pubkey,
+    pub coverage_position: Pubkey,
+    pub claim_amount: u64,
+    pub claim_payout: u64,
+    pub claim_bond: u64,
+}
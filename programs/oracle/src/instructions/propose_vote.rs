@@ -5,7 +5,7 @@ use anchor_spl::{
 };
 use std::{io::Read, mem::size_of};
 
-use crate::states::{proposal::Proposal, Config, RevealedVoteArray};
+use crate::states::{proposal::Proposal, Config, ProposalEvidence, RevealedVoteArray};
 use crate::utils::tokenTx;
 use crate::utils::{SureError, SURE_ORACLE_PROPOSAL_VAULT_SEED, SURE_ORACLE_REVEAL_ARRAY_SEED, SURE_ORACLE_SEED};
 pub const MINIMUM_STAKE: u64 = 3_000_000;
@@ -24,12 +24,13 @@ pub fn validate_stake(stake: u64) -> Result<()> {
 }
 
 #[derive(Accounts)]
-#[instruction(id: Vec<u8>)]
+#[instruction(name: String, description: String, evidence: ProposalEvidence)]
 pub struct ProposeVote<'info> {
     #[account(mut)]
     pub proposer: Signer<'info>,
 
     /// configuration for the proposal
+    #[account(mut)]
     pub config: Box<Account<'info, Config>>,
 
     #[account(
@@ -37,7 +38,7 @@ pub struct ProposeVote<'info> {
         payer = proposer,
         seeds = [
             SURE_ORACLE_SEED.as_bytes().as_ref(),
-            id.as_ref(), 
+            Proposal::calculate_id(&proposer.key(), &name, &description, &evidence).as_ref(),
         ],
         bump,
         space = 8 + Proposal::SPACE
@@ -49,7 +50,7 @@ pub struct ProposeVote<'info> {
         payer = proposer,
         seeds = [
             SURE_ORACLE_REVEAL_ARRAY_SEED.as_bytes().as_ref(),
-            id.as_ref(), 
+            proposal.key().as_ref(),
         ],
         bump,
         space = 8 + size_of::<RevealedVoteArray>()
@@ -72,7 +73,7 @@ pub struct ProposeVote<'info> {
         payer = proposer,
         seeds = [
             SURE_ORACLE_PROPOSAL_VAULT_SEED.as_bytes().as_ref(),
-            id.as_ref(), 
+            proposal.key().as_ref(),
         ],
         bump,
         token::mint = proposal_vault_mint,
@@ -90,18 +91,19 @@ pub struct ProposeVote<'info> {
 /// Propose vote
 ///
 /// proposes a vote or observation that the holder of veSure can
-/// vote on
+/// vote on. The proposal id is the hash of the proposer and the content
 ///
 /// # Arguments
 /// * ctx: Context
 /// * name: Name of the observation
 /// * description: Clear description about the event
+/// * evidence: reference to the off-chain incident report
 /// * stake: The amount staked on event. In BN:  x*10^{decimals}
 pub fn handler(
     ctx: Context<ProposeVote>,
-    id: Vec<u8>,
     name: String,
     description: String,
+    evidence: ProposalEvidence,
     stake: u64, //Q64.0
 ) -> Result<()> {
     let proposal = ctx.accounts.proposal.as_mut();
@@ -111,22 +113,26 @@ pub fn handler(
     let mut reveal_vote_array = ctx.accounts.reveal_vote_array.load_init()?;
     let time = clock::Clock::get()?.unix_timestamp;
 
-    // TODO: might fail if not used hasher 
-    let id_hash: [u8; 16] = id.clone().try_into().unwrap();
+    let id = Proposal::calculate_id(&ctx.accounts.proposer.key(), &name, &description, &evidence);
 
     // Initialize state
     proposal.initialize(
         config,
         proposal_bump,
         name.clone(),
-        &id_hash,
+        &id,
         &description,
+        &evidence,
         &ctx.accounts.proposer.key(),
         stake,
         &ctx.accounts.proposal_vault.key(),
         None,
+        time,
     )?;
 
+    // count proposal for the voter participation
+    ctx.accounts.config.record_proposal()?;
+
     // initialize reveal_vote_array
     reveal_vote_array.initialize(proposal.key(), reveal_vote_array_bump);
 
@@ -145,7 +151,8 @@ pub fn handler(
     emit!(ProposeVoteEvent {
         name: name,
         description: description,
-        id: id.clone().to_vec(),
+        id: id.to_vec(),
+        evidence: evidence,
         proposer: ctx.accounts.proposer.key(),
         stake: stake,
     }); 
@@ -158,6 +165,7 @@ pub struct ProposeVoteEvent {
     pub name: String,
     pub description: String,
     pub id: Vec<u8>,
+    pub evidence: ProposalEvidence,
     pub proposer: Pubkey,
     pub stake: u64,
 }
@@ -0,0 +1,111 @@
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::{Token, TokenAccount};
+use sure_pool::{
+    program::SurePool,
+    states::{Pool, PoolManager},
+    utils::SURE_PROTOCOL_OWNER,
+};
+
+use crate::{
+    states::{Config, Proposal},
+    utils::{tokenTx, SureError, SURE_ORACLE_CLAIM_BOND_SEED, SURE_ORACLE_TREASURY_SEED},
+};
+
+#[derive(Accounts)]
+pub struct ReleasePool<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(address = proposal.config @ SureError::InvalidProposalConfig)]
+    pub config: Box<Account<'info, Config>>,
+
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    /// vault holding the claim bond
+    #[account(
+        mut,
+        seeds = [
+            SURE_ORACLE_CLAIM_BOND_SEED.as_bytes().as_ref(),
+            proposal.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub claim_bond_vault: Box<Account<'info, TokenAccount>>,
+
+    /// receives the slashed claim bond
+    #[account(
+        mut,
+        seeds = [
+            SURE_ORACLE_TREASURY_SEED.as_bytes().as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub protocol_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = proposal.pool @ SureError::ProposalNotBoundToPool
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// pool manager holding the oracle config
+    /// trusted with claims
+    #[account(
+        seeds = [SURE_PROTOCOL_OWNER.as_bytes()],
+        bump = pool_manager.bump,
+        seeds::program = pool_program.key(),
+        constraint = pool_manager.oracle_config == config.key() @ SureError::InvalidProposalConfig,
+    )]
+    pub pool_manager: Box<Account<'info, PoolManager>>,
+
+    pub pool_program: Program<'info, SurePool>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Release pool
+///
+/// unlock the pool when the vote on the claim failed or
+/// the claim is rejected. The claim bond is slashed to the
+/// protocol treasury. Accepted claims are released by
+/// pay_claim
+pub fn handler(ctx: Context<ReleasePool>) -> Result<()> {
+    let proposal = ctx.accounts.proposal.as_ref();
+    let time = clock::Clock::get()?.unix_timestamp;
+
+    proposal.can_release_pool(time)?;
+
+    let claim_bond = ctx.accounts.claim_bond_vault.amount;
+    tokenTx::withdraw_from_vault(
+        &ctx.accounts.proposal,
+        &ctx.accounts.claim_bond_vault,
+        &ctx.accounts.protocol_treasury,
+        &ctx.accounts.token_program,
+        claim_bond,
+    )?;
+
+    sure_pool::cpi::release_pool_claim(CpiContext::new_with_signer(
+        ctx.accounts.pool_program.to_account_info(),
+        sure_pool::cpi::accounts::ReleasePoolClaim {
+            claim: proposal.to_account_info(),
+            pool_manager: ctx.accounts.pool_manager.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+        },
+        &[&proposal.seeds()],
+    ))?;
+
+    emit!(ReleasedPoolEvent {
+        proposal: proposal.key(),
+        pool: ctx.accounts.pool.key(),
+        claim_bond,
+        bond_destination: ctx.accounts.protocol_treasury.key(),
+    });
+    Ok(())
+}
+
+#[event]
+pub struct ReleasedPoolEvent {
+    pub proposal: Pubkey,
+    pub pool: Pubkey,
+    pub claim_bond: u64,
+    pub bond_destination: Pubkey,
+}
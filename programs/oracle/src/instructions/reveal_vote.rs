@@ -53,16 +53,8 @@ pub fn handler(ctx: Context<RevealVote>, salt: String, vote: i64) -> Result<()>
     let proposal = ctx.accounts.proposal.as_mut();
     let time = clock::Clock::get()?.unix_timestamp;
 
-    // check if can reveal vote
-    proposal.can_reveal_vote(time)?;
-
-    // reveal vote in vote account
-    vote_account.reveal_vote(proposal, &salt, vote, time)?;
-
-    proposal.update_protocol_fee(vote_account.staked);
-
-    // reveal vote in reveal vote list
-    reveal_vote_array.reveal_vote(&vote_account)?;
+    // reveal vote, update consensus and the reveal vote list
+    proposal.reveal_vote(&mut vote_account, &mut reveal_vote_array, &salt, vote, time)?;
 
     emit!(RevealedVoteEvent {
         proposal: proposal.key(),
@@ -8,7 +8,7 @@ use anchor_spl::token::{TokenAccount, Mint, self, Token};
 use locked_voter::{Locker, Escrow};
 
 use crate::utils::{SURE, SureError, deposit_into_vault,SURE_ORACLE_VOTE_SEED};
-use crate::states::{Proposal,VoteAccount, ProposalStatus};
+use crate::states::{Config, Proposal,VoteAccount, ProposalStatus, VoterReputation};
 
 #[derive(Accounts)]
 pub struct SubmitVote<'info> {
@@ -23,12 +23,15 @@ pub struct SubmitVote<'info> {
     )]
     pub voter_account: Box<Account<'info,TokenAccount>>,
 
+    pub config: Box<Account<'info,Config>>,
+
     #[account(
-        //constraint = locker.token_mint.key() == SURE
+        address = config.locker @ SureError::InvalidLocker
     )]
     pub locker: Account<'info,Locker>,
 
     #[account(
+        constraint = user_escrow.locker == locker.key() @ SureError::InvalidLocker,
         constraint = user_escrow.owner == voter.key(),
         constraint = user_escrow.amount > 0 
     )]
@@ -36,7 +39,7 @@ pub struct SubmitVote<'info> {
 
     #[account(
         mut,
-        constraint = proposal.has_ended()? == false
+        has_one = config,
     )]
     pub proposal: Account<'info,Proposal>,
 
@@ -69,16 +72,31 @@ pub struct SubmitVote<'info> {
     pub system_program: Program<'info, System>,
 }
 
-
-pub fn handler(ctx:Context<SubmitVote>,vote_hash: Vec<u8>) -> Result<()>{
+/// Submit vote
+///
+/// ### remaining accounts
+/// * voter_reputation: mut, optional. Required if the
+///     config applies the reputation factor
+pub fn handler<'info>(ctx:Context<'_, '_, '_, 'info, SubmitVote<'info>>,vote_hash: Vec<u8>) -> Result<()>{
     let time = Clock::get()?.unix_timestamp;
     let proposal =  &mut ctx.accounts.proposal;
+
+    // check if the vote is still open
+    proposal.can_submit_vote(time)?;
+
     let locker =&ctx.accounts.locker;
-    let voting_power = ctx.accounts.user_escrow.voting_power(&locker.params)?;
+    // vote power as of the start of the vote
+    let mut voting_power = VoteAccount::calculate_vote_power_snapshot(&ctx.accounts.user_escrow, &locker.params, proposal.vote_start_at)?;
+    let config = &ctx.accounts.config;
+    let voter_reputation = VoterReputation::load_optional(ctx.remaining_accounts, &config.key(), &ctx.accounts.voter.key(), config.reputation_enabled)?;
+    if let Some(mut voter_reputation) = voter_reputation {
+        if config.reputation_enabled {
+            voting_power = voter_reputation.apply_reputation_factor(voting_power, config.proposals)?;
+        }
+        voter_reputation.record_vote()?;
+        voter_reputation.exit(ctx.program_id)?;
+    }
     let decimals = ctx.accounts.proposal_vault_mint.decimals;
-    
-    // check f 
-    proposal.can_submit_vote(time)?;
 
     //initialize vote account
     let mut vote_account = ctx.accounts.vote_account.load_init()?;
@@ -87,6 +105,8 @@ pub fn handler(ctx:Context<SubmitVote>,vote_hash: Vec<u8>) -> Result<()>{
     msg!("vote hash bytes length: {}",vote_hash_bytes.len());
     // Initialize vote account
     let vote_update = vote_account.initialize(proposal.stake_rate,vote_account_bump, &ctx.accounts.voter.key(), &proposal.key(),&vote_hash_bytes, ctx.accounts.proposal_vault_mint.key(),voting_power,decimals)?;
+    // cap later vote power updates to the tokens locked now
+    vote_account.escrow_amount = ctx.accounts.user_escrow.amount;
 
     // Update proposal with vote 
     proposal.cast_vote_at_time(vote_account, time)?;
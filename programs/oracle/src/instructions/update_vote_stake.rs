@@ -0,0 +1,161 @@
+use crate::states::{Config, Proposal, VoteAccount, VoterReputation};
+use crate::utils::{tokenTx, SureError, SURE_ORACLE_VOTE_SEED};
+use anchor_lang::{prelude::*, solana_program::clock};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use locked_voter::{Escrow, Locker};
+
+#[derive(Accounts)]
+pub struct UpdateVoteStake<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = voter_account.mint == proposal_vault.mint,
+        constraint = voter_account.owner == voter.key()
+    )]
+    pub voter_account: Box<Account<'info, TokenAccount>>,
+
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        address = config.locker @ SureError::InvalidLocker
+    )]
+    pub locker: Box<Account<'info, Locker>>,
+
+    #[account(
+        constraint = user_escrow.locker == locker.key() @ SureError::InvalidLocker,
+        constraint = user_escrow.owner == voter.key()
+    )]
+    pub user_escrow: Box<Account<'info, Escrow>>,
+
+    #[account(
+        mut,
+        has_one = config,
+        constraint = proposal.vault == proposal_vault.key()
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(mut)]
+    pub proposal_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        constraint = proposal_vault_mint.key() == proposal_vault.mint
+    )]
+    pub proposal_vault_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            SURE_ORACLE_VOTE_SEED.as_ref(),
+            proposal.key().as_ref(),
+            voter.key().as_ref(),
+        ],
+        bump = vote_account.load()?.bump,
+        constraint = vote_account.load()?.owner == voter.key() @ SureError::InvalidOwnerOfVoteAccount
+    )]
+    pub vote_account: AccountLoader<'info, VoteAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Update vote stake
+///
+/// top up the vote power after extending the escrow lock
+/// or decrease it and get the stake refunded. Tokens added
+/// to the escrow after the vote was submitted are not counted.
+///
+/// ## Arguments
+/// * ctx: UpdateVoteStake
+/// * vote_power: new vote power in tokens
+///
+/// ### remaining accounts
+/// * voter_reputation: optional. Required if the config
+///     applies the reputation factor
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdateVoteStake<'info>>,
+    vote_power: u64,
+) -> Result<()> {
+    let proposal = ctx.accounts.proposal.as_mut();
+    let time = clock::Clock::get()?.unix_timestamp;
+    let decimals = ctx.accounts.proposal_vault_mint.decimals;
+    let config = ctx.accounts.config.as_ref();
+    let voter_reputation = VoterReputation::load_optional(
+        ctx.remaining_accounts,
+        &config.key(),
+        &ctx.accounts.voter.key(),
+        config.reputation_enabled,
+    )?;
+
+    // check if user can update vote
+    proposal.can_submit_vote(time)?;
+
+    // remove the previous vote power from the proposal
+    proposal.cancel_vote_at_time(ctx.accounts.vote_account.load_mut()?, time)?;
+
+    let vote_update = {
+        let mut vote_account = ctx.accounts.vote_account.load_mut()?;
+        let mut vote_power_cap = vote_account.calculate_vote_power_cap(
+            &ctx.accounts.user_escrow,
+            &ctx.accounts.locker.params,
+            proposal.vote_start_at,
+        )?;
+        if let Some(voter_reputation) = voter_reputation.as_ref() {
+            if config.reputation_enabled {
+                vote_power_cap =
+                    voter_reputation.apply_reputation_factor(vote_power_cap, config.proposals)?;
+            }
+        }
+        vote_account.update_vote_power_at_time(
+            proposal,
+            vote_power,
+            vote_power_cap,
+            decimals,
+            time,
+        )?
+    };
+
+    // add the new vote power to the proposal
+    proposal.cast_vote_at_time(ctx.accounts.vote_account.load_mut()?, time)?;
+
+    // cb: update status of proposal
+    proposal.update_status(time);
+
+    if vote_update.increase_stake {
+        tokenTx::deposit_into_vault(
+            &ctx.accounts.voter,
+            &ctx.accounts.proposal_vault,
+            &ctx.accounts.voter_account,
+            &ctx.accounts.token_program,
+            vote_update.stake_change,
+        )?;
+    } else {
+        tokenTx::withdraw_from_vault(
+            proposal,
+            &ctx.accounts.proposal_vault,
+            &ctx.accounts.voter_account,
+            &ctx.accounts.token_program,
+            vote_update.stake_change,
+        )?;
+    }
+
+    emit!(UpdatedVoteStakeEvent {
+        vote: ctx.accounts.vote_account.key(),
+        proposal: proposal.key(),
+        time,
+        vote_power,
+        stake_change: vote_update.stake_change,
+        increase_stake: vote_update.increase_stake,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedVoteStakeEvent {
+    pub vote: Pubkey,
+    pub proposal: Pubkey,
+    pub time: i64,
+    pub vote_power: u64,
+    pub stake_change: u64,
+    pub increase_stake: bool,
+}
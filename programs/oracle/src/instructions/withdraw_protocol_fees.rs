@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+use crate::states::Config;
+use crate::utils::{tokenTx, SureError, SURE_ORACLE_CONFIG_SEED, SURE_ORACLE_TREASURY_SEED};
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account( address = config.protocol_authority @ SureError::UnauthorizedSigner )]
+    pub protocol_authority: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SURE_ORACLE_CONFIG_SEED.as_bytes().as_ref(),
+            config.token_mint.key().as_ref(),
+        ],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [
+            SURE_ORACLE_TREASURY_SEED.as_bytes().as_ref(),
+            config.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub protocol_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = fee_destination.mint == config.token_mint,
+    )]
+    pub fee_destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+/// withdraw protocol fees
+///
+/// the protocol authority can withdraw from the
+/// protocol treasury at any time
+///
+/// ### args
+/// * amount: tokens to withdraw
+pub fn handler(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+    tokenTx::withdraw_from_treasury(
+        &ctx.accounts.config,
+        &ctx.accounts.protocol_treasury,
+        &ctx.accounts.fee_destination,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(WithdrewProtocolFees {
+        config: ctx.accounts.config.key(),
+        amount,
+        destination: ctx.accounts.fee_destination.key(),
+    });
+    Ok(())
+}
+
+#[event]
+pub struct WithdrewProtocolFees {
+    pub config: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+}
@@ -1,11 +1,14 @@
 use anchor_lang::prelude::*;
 pub mod factory;
 pub mod instructions;
+#[cfg(any(test, feature = "simulation"))]
+pub mod simulation;
 pub mod states;
 pub mod utils;
 
 use crate::instructions::ProposeVote;
 use instructions::*;
+use states::ProposalEvidence;
 
 declare_id!("2prR7H6LfRqwiP2iTyZG1suG4B3zU6JEpUBXWeQB66qH");
 #[program]
@@ -21,6 +24,9 @@ pub mod oracle {
     /// * protocol_authority<Pubkey>: unique for vault mint. the authority can
     ///     - change config parameters
     ///     - collect protocol fees
+    ///
+    /// ### accounts
+    /// * locker: locker of the escrows that can vote on proposals
     pub fn initialize_config(
         ctx: Context<InitializeConfig>,
         protocol_authority: Pubkey,
@@ -99,6 +105,46 @@ pub mod oracle {
         instructions::update_protocol_fee_rate(ctx, protocol_fee_rate)
     }
 
+    /// update config: reputation
+    ///
+    /// multiply the vote power by the reputation factor of the voter
+    ///
+    /// ### args
+    /// * reputation_enabled<bool>: whether the reputation factor applies
+    pub fn update_reputation_enabled(
+        ctx: Context<UpdateConfig>,
+        reputation_enabled: bool,
+    ) -> Result<()> {
+        instructions::update_reputation_enabled(ctx, reputation_enabled)
+    }
+
+    /// update config: consensus estimator
+    ///
+    /// select how the consensus is calculated from the
+    /// revealed votes of new proposals
+    ///
+    /// ### args
+    /// * consensus_estimator<u8>: 0 = mean, 1 = weighted median, 2 = trimmed mean
+    /// * consensus_trim_rate<u32>: 1/x of the vote power trimmed from each tail
+    pub fn update_consensus_estimator(
+        ctx: Context<UpdateConfig>,
+        consensus_estimator: u8,
+        consensus_trim_rate: u32,
+    ) -> Result<()> {
+        instructions::update_consensus_estimator(ctx, consensus_estimator, consensus_trim_rate)
+    }
+
+    /// update claim bond
+    ///
+    /// the tokens a proposer bonds to bind a proposal
+    /// to a pool. The bond is slashed if the claim is rejected
+    ///
+    /// ### args
+    /// * claim_bond<u64>: amount of tokens bonded per claim
+    pub fn update_claim_bond(ctx: Context<UpdateConfig>, claim_bond: u64) -> Result<()> {
+        instructions::update_claim_bond(ctx, claim_bond)
+    }
+
     /// Propose vote
     ///
     /// proposes a vote or observation that the holder of veSure can
@@ -108,15 +154,59 @@ pub mod oracle {
     /// * `ctx`: Context
     /// * `name`: Name of the observation
     /// * `description`: Clear description about the event
+    /// * `evidence`: Reference to the off-chain incident report
     /// * `stake`: The amount staked on event. In BN:  x*10^{decimals}
     pub fn propose_vote(
         ctx: Context<ProposeVote>,
-        id: Vec<u8>,
         name: String,
         description: String,
+        evidence: ProposalEvidence,
         stake: u64,
     ) -> Result<()> {
-        instructions::propose_vote::handler(ctx, id, name, description, stake)
+        instructions::propose_vote::handler(ctx, name, description, evidence, stake)
+    }
+
+    /// bind pool
+    ///
+    /// bind the proposal to an insurance pool as a claim. The pool
+    /// is locked against new coverage until the vote is over.
+    /// The proposer bonds config.claim_bond tokens which are
+    /// slashed if the claim is rejected
+    ///
+    /// ### args
+    /// * claim_amount<u64>: amount claimed for the coverage position.
+    ///     Voters vote on the share of the claim amount to pay out
+    pub fn bind_pool(ctx: Context<BindPool>, claim_amount: u64) -> Result<()> {
+        instructions::bind_pool::handler(ctx, claim_amount)
+    }
+
+    /// release pool
+    ///
+    /// unlock the pool when the vote on the claim failed or
+    /// the vote results reject the claim. The claim bond is
+    /// sent to the protocol treasury
+    pub fn release_pool(ctx: Context<ReleasePool>) -> Result<()> {
+        instructions::release_pool::handler(ctx)
+    }
+
+    /// pay claim
+    ///
+    /// pay the accepted claim to the coverage position through
+    /// the pool and unlock the pool. The payout is the share of
+    /// the claim amount resolved by the vote and can only be
+    /// paid once. Returns the claim bond to the proposer
+    pub fn pay_claim(ctx: Context<PayClaim>) -> Result<()> {
+        instructions::pay_claim::handler(ctx)
+    }
+
+    /// initialize voter reputation
+    ///
+    /// creates the persistent reputation of the voter. The
+    /// reputation is required to vote when the reputation factor
+    /// is enabled and tracks the accuracy, participation and
+    /// reveal rate of the voter
+    pub fn initialize_voter_reputation(ctx: Context<InitializeVoterReputation>) -> Result<()> {
+        instructions::initialize_voter_reputation::handler(ctx)
     }
 
     /// Submit vote
@@ -126,7 +216,10 @@ pub mod oracle {
     /// ### Parameters
     /// * `ctx` - context
     /// * `vote_hash` - hash of vote with secret salt
-    pub fn submit_vote(ctx: Context<SubmitVote>, vote_hash: Vec<u8>) -> Result<()> {
+    pub fn submit_vote<'info>(
+        ctx: Context<'_, '_, '_, 'info, SubmitVote<'info>>,
+        vote_hash: Vec<u8>,
+    ) -> Result<()> {
         instructions::submit_vote::handler(ctx, vote_hash)
     }
 
@@ -141,6 +234,21 @@ pub mod oracle {
         instructions::update_vote::handler(ctx, vote_hash)
     }
 
+    /// Update vote stake
+    ///
+    /// top up the vote power after extending the escrow lock
+    /// or decrease it and get the stake refunded
+    ///
+    /// ### parameters
+    /// * `ctx` - context
+    /// * `vote_power` - new vote power in tokens
+    pub fn update_vote_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateVoteStake<'info>>,
+        vote_power: u64,
+    ) -> Result<()> {
+        instructions::update_vote_stake::handler(ctx, vote_power)
+    }
+
     /// cancel vote
     ///
     /// a user can cancel the vote in the voting period
@@ -184,7 +292,9 @@ pub mod oracle {
     ///
     /// ### parameters
     /// * `ctx` - Finalize Vote context
-    pub fn finalize_vote(ctx: Context<FinalizeVote>) -> Result<()> {
+    pub fn finalize_vote<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeVote<'info>>,
+    ) -> Result<()> {
         instructions::finalize_vote::handler(ctx)
     }
 
@@ -205,8 +315,64 @@ pub mod oracle {
 
     /// collect protocol fees
     ///
-    /// the config authority can at any time collect the protocol fees
-    pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+    /// sweep the accrued protocol fees of the proposals passed
+    /// as remaining accounts into the protocol treasury
+    ///
+    /// ### remaining accounts
+    /// * pairs of (proposal, proposal_vault)
+    pub fn collect_protocol_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectProtocolFees<'info>>,
+    ) -> Result<()> {
         instructions::collect_protocol_fees::handler(ctx)
     }
+
+    /// withdraw protocol fees
+    ///
+    /// the config authority can at any time withdraw the
+    /// protocol fees from the protocol treasury
+    ///
+    /// ### args
+    /// * amount<u64>: amount of tokens to withdraw
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_protocol_fees::handler(ctx, amount)
+    }
+
+    /// initialize incentive vault
+    ///
+    /// attach a vault in a separate mint to the proposal. The
+    /// incentives are distributed by the same weights as the
+    /// voter rewards. The proposer or the founder of the bound
+    /// pool can sponsor the incentives
+    pub fn initialize_incentive_vault<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeIncentiveVault<'info>>,
+    ) -> Result<()> {
+        instructions::initialize_incentive_vault::handler(ctx)
+    }
+
+    /// fund incentive vault
+    ///
+    /// add incentives to the proposal until the vote
+    /// results are finalized
+    ///
+    /// ### args
+    /// * amount<u64>: amount of incentive tokens
+    pub fn fund_incentive_vault(ctx: Context<FundIncentiveVault>, amount: u64) -> Result<()> {
+        instructions::fund_incentive_vault::handler(ctx, amount)
+    }
+
+    /// collect vote incentive
+    ///
+    /// after the vote results are finalized the voter can collect
+    /// the share of the incentives
+    pub fn collect_vote_incentive(ctx: Context<CollectVoteIncentive>) -> Result<()> {
+        instructions::collect_vote_incentive::handler(ctx)
+    }
+
+    /// reclaim incentives
+    ///
+    /// the sponsor can reclaim the incentives if the vote
+    /// failed or no voter is eligible for rewards
+    pub fn reclaim_incentives(ctx: Context<ReclaimIncentives>) -> Result<()> {
+        instructions::reclaim_incentives::handler(ctx)
+    }
 }
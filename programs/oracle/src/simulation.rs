@@ -0,0 +1,434 @@
+//! Oracle economics simulator
+//!
+//! Drives the `Proposal` and `VoteAccount` state machines
+//! off-chain with a population of voters following different
+//! strategies. Used to compare the expected returns of each
+//! strategy before governance changes the config parameters.
+//!
+//! Run a parameter sweep with
+//! `cargo run -p oracle --example simulate --features simulation -- <output.csv>`
+
+use std::{cell::RefCell, io::Write};
+
+use anchor_lang::prelude::*;
+use sha3::{Digest, Sha3_256};
+
+use crate::states::{ConsensusEstimator, Proposal, ProposalStatus, RevealedVoteArray, VoteAccount};
+
+/// decimals of the simulated token
+pub const SIMULATION_DECIMALS: u8 = 6;
+/// length of the voting and the reveal period
+pub const SIMULATION_PERIOD_SECONDS: i64 = 86_400;
+
+const SALT: &str = "simulation";
+
+pub const CSV_HEADER: &str = "vote_stake_rate,protocol_fee_rate,required_votes,consensus_estimator,strategy,participants,votes,revealed,staked,payout,expected_return";
+
+/// xorshift64 so that the simulation is reproducible
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// random number in [low, high)
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low)
+    }
+}
+
+/// How a voter decides on the vote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// researches the outcome and votes close to it
+    Honest,
+    /// guesses the outcome and sometimes forgets to reveal
+    Lazy,
+    /// votes together with the other adversaries on a
+    /// result biased away from the outcome
+    Adversarial,
+}
+
+impl Strategy {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Strategy::Honest => "honest",
+            Strategy::Lazy => "lazy",
+            Strategy::Adversarial => "adversarial",
+        }
+    }
+}
+
+/// Voters following the same strategy
+#[derive(Debug, Clone)]
+pub struct VoterGroup {
+    pub strategy: Strategy,
+    pub voters: u32,
+    /// vote power of each voter in tokens
+    pub vote_power: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationParams {
+    /// 1/x of the vote power staked by a voter
+    pub vote_stake_rate: u32,
+    /// 1/x of the revealed stake paid to the protocol
+    pub protocol_fee_rate: u32,
+    /// vote power needed to reach quorum
+    pub required_votes: u64,
+    pub consensus_estimator: ConsensusEstimator,
+    /// tokens staked by the proposer
+    pub proposer_stake: u64,
+    /// number of proposals to simulate
+    pub rounds: u32,
+    /// the outcome of a proposal is drawn from [0, max_result)
+    pub max_result: u64,
+    /// max distance of an honest vote from the outcome
+    /// I32.32
+    pub honest_noise: i64,
+    /// probability in % that a lazy voter reveals the vote
+    pub lazy_reveal_rate: u64,
+    /// distance of the adversarial vote from the outcome
+    /// I32.32
+    pub adversarial_bias: i64,
+    pub groups: Vec<VoterGroup>,
+}
+
+impl Default for SimulationParams {
+    fn default() -> Self {
+        let decimals = 10_u64.pow(SIMULATION_DECIMALS as u32);
+        Self {
+            vote_stake_rate: 100,
+            protocol_fee_rate: 50,
+            required_votes: 10_000,
+            consensus_estimator: ConsensusEstimator::Mean,
+            proposer_stake: 10 * decimals,
+            rounds: 20,
+            max_result: 100,
+            honest_noise: 1 << 32,
+            lazy_reveal_rate: 70,
+            adversarial_bias: 20 << 32,
+            groups: vec![
+                VoterGroup {
+                    strategy: Strategy::Honest,
+                    voters: 20,
+                    vote_power: 1_000 * decimals,
+                },
+                VoterGroup {
+                    strategy: Strategy::Lazy,
+                    voters: 10,
+                    vote_power: 1_000 * decimals,
+                },
+                VoterGroup {
+                    strategy: Strategy::Adversarial,
+                    voters: 5,
+                    vote_power: 2_000 * decimals,
+                },
+            ],
+        }
+    }
+}
+
+/// Outcome for all participants following a strategy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StrategyResult {
+    pub strategy: String,
+    /// number of participants per round
+    pub participants: u32,
+    pub votes: u64,
+    pub revealed: u64,
+    /// tokens staked over all rounds
+    pub staked: u64,
+    /// tokens received over all rounds
+    pub payout: u64,
+}
+
+impl StrategyResult {
+    fn new(strategy: &str, participants: u32) -> Self {
+        Self {
+            strategy: strategy.to_string(),
+            participants,
+            ..Default::default()
+        }
+    }
+
+    /// return per staked token
+    pub fn expected_return(&self) -> f64 {
+        if self.staked == 0 {
+            return 0.0;
+        }
+        (self.payout as f64 - self.staked as f64) / self.staked as f64
+    }
+}
+
+struct SimulatedVote {
+    group: usize,
+    vote_account: VoteAccount,
+    vote: i64,
+    reveal: bool,
+}
+
+pub fn hash_vote(vote: i64, salt: &str) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(format!("{}{}", vote, salt).as_bytes());
+    hasher.finalize().try_into().unwrap()
+}
+
+fn initialize_proposal(params: &SimulationParams) -> Proposal {
+    let mut proposal = Proposal::default();
+    proposal.stake_rate = params.vote_stake_rate;
+    proposal.protocol_fee_rate = params.protocol_fee_rate;
+    proposal.required_votes = params.required_votes;
+    proposal.consensus_estimator = params.consensus_estimator.get_id();
+    proposal.consensus_trim_rate = 10;
+    proposal.staked = params.proposer_stake;
+    proposal.vote_start_at = 0;
+    proposal.vote_end_at = SIMULATION_PERIOD_SECONDS;
+    proposal.vote_end_reveal_at = 2 * SIMULATION_PERIOD_SECONDS;
+    proposal
+}
+
+/// Draw a vote for the strategy
+///
+/// ### Returns
+/// * (vote: I32.32, reveal)
+fn draw_vote(
+    params: &SimulationParams,
+    strategy: Strategy,
+    outcome: i64,
+    rng: &mut Rng,
+) -> (i64, bool) {
+    match strategy {
+        Strategy::Honest => {
+            let noise =
+                rng.range(0, 2 * params.honest_noise as u64 + 1) as i64 - params.honest_noise;
+            (outcome + noise, true)
+        }
+        Strategy::Lazy => {
+            let guess = (rng.range(0, params.max_result) as i64) << 32;
+            (guess, rng.range(0, 100) < params.lazy_reveal_rate)
+        }
+        Strategy::Adversarial => (outcome + params.adversarial_bias, true),
+    }
+}
+
+/// Simulate a single proposal through its lifecycle
+fn simulate_round(
+    params: &SimulationParams,
+    rng: &mut Rng,
+    results: &mut [StrategyResult],
+) -> Result<()> {
+    let proposer_index = results.len() - 1;
+    let mut proposal = initialize_proposal(params);
+    let mut vault_amount = proposal.staked;
+    // I32.32
+    let outcome = (rng.range(0, params.max_result) as i64) << 32;
+
+    // submit votes
+    let vote_time = proposal.vote_start_at;
+    let mut votes = Vec::new();
+    for (group_index, group) in params.groups.iter().enumerate() {
+        for _ in 0..group.voters {
+            let (vote, reveal) = draw_vote(params, group.strategy, outcome, rng);
+            let mut vote_account = VoteAccount::default();
+            vote_account.initialize(
+                proposal.stake_rate,
+                0,
+                &Pubkey::default(),
+                &Pubkey::default(),
+                &hash_vote(vote, SALT),
+                Pubkey::default(),
+                group.vote_power,
+                SIMULATION_DECIMALS,
+            )?;
+            vault_amount += vote_account.staked;
+            proposal.cast_vote_at_time(RefCell::new(vote_account).borrow_mut(), vote_time)?;
+            votes.push(SimulatedVote {
+                group: group_index,
+                vote_account,
+                vote,
+                reveal,
+            });
+        }
+    }
+
+    // reveal votes
+    let reveal_time = proposal.vote_end_at;
+    let mut revealed_votes = RevealedVoteArray::default();
+    if proposal.get_status(reveal_time) == Some(ProposalStatus::RevealVote) {
+        for vote in votes.iter_mut().filter(|vote| vote.reveal) {
+            proposal.reveal_vote(
+                &mut vote.vote_account,
+                &mut revealed_votes,
+                SALT,
+                vote.vote,
+                reveal_time,
+            )?;
+        }
+
+        // finalize the vote and calculate the vote factors
+        let finalize_time = proposal.vote_end_reveal_at;
+        proposal.try_finalize_vote_after_reveal(&revealed_votes, vault_amount, finalize_time)?;
+        for vote in votes.iter_mut().filter(|vote| vote.reveal) {
+            // skip votes whose vote factor cannot be calculated
+            if vote.vote_account.calculate_vote_factor(&proposal).is_ok() {
+                proposal.update_distribution_sum(&vote.vote_account)?;
+            }
+        }
+    }
+
+    // payout
+    let payout_time = proposal.vote_end_reveal_at + SIMULATION_PERIOD_SECONDS;
+    for vote in votes.iter_mut() {
+        let result = &mut results[vote.group];
+        result.votes += 1;
+        result.revealed += vote.vote_account.revealed_vote as u64;
+        result.staked += vote.vote_account.staked;
+        result.payout += vote
+            .vote_account
+            .calculate_token_reward_at_time(&proposal, payout_time)
+            .unwrap_or(0);
+    }
+    let proposer = &mut results[proposer_index];
+    proposer.votes += 1;
+    proposer.staked += proposal.staked;
+    proposer.payout += proposal
+        .payout_earned_rewards_at_time(payout_time)
+        .unwrap_or(0);
+    Ok(())
+}
+
+/// Simulate the proposals
+///
+/// ### Arguments
+/// * params: the simulation parameters
+/// * seed: seed of the random number generator, must be non-zero
+///
+/// ### Returns
+/// * one result per voter group followed by the result of the proposer
+pub fn simulate(params: &SimulationParams, seed: u64) -> Result<Vec<StrategyResult>> {
+    let mut rng = Rng(seed);
+    let mut results: Vec<StrategyResult> = params
+        .groups
+        .iter()
+        .map(|group| StrategyResult::new(group.strategy.name(), group.voters))
+        .collect();
+    results.push(StrategyResult::new("proposer", 1));
+
+    for _ in 0..params.rounds {
+        simulate_round(params, &mut rng, &mut results)?;
+    }
+    Ok(results)
+}
+
+/// Write the results as CSV rows without the header
+pub fn write_csv<W: Write>(
+    writer: &mut W,
+    params: &SimulationParams,
+    results: &[StrategyResult],
+) -> std::io::Result<()> {
+    for result in results {
+        writeln!(
+            writer,
+            "{},{},{},{:?},{},{},{},{},{},{},{:.6}",
+            params.vote_stake_rate,
+            params.protocol_fee_rate,
+            params.required_votes,
+            params.consensus_estimator,
+            result.strategy,
+            result.participants,
+            result.votes,
+            result.revealed,
+            result.staked,
+            result.payout,
+            result.expected_return()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod test_simulation {
+    use super::*;
+
+    #[test]
+    pub fn test_honest_voters_outperform() {
+        pub struct Test {
+            name: String,
+            consensus_estimator: ConsensusEstimator,
+        }
+        let tests = [
+            Test {
+                name: "1. mean".to_string(),
+                consensus_estimator: ConsensusEstimator::Mean,
+            },
+            Test {
+                name: "2. weighted median".to_string(),
+                consensus_estimator: ConsensusEstimator::WeightedMedian,
+            },
+        ];
+
+        for test in tests {
+            let params = SimulationParams {
+                consensus_estimator: test.consensus_estimator,
+                ..Default::default()
+            };
+            let results = simulate(&params, 0x5eed).unwrap();
+            let honest = &results[0];
+            let lazy = &results[1];
+            let adversarial = &results[2];
+            assert!(
+                honest.expected_return() > lazy.expected_return(),
+                "{}: honest {} should beat lazy {}",
+                test.name,
+                honest.expected_return(),
+                lazy.expected_return()
+            );
+            assert!(
+                honest.expected_return() > adversarial.expected_return(),
+                "{}: honest {} should beat adversarial {}",
+                test.name,
+                honest.expected_return(),
+                adversarial.expected_return()
+            );
+            assert!(lazy.revealed < lazy.votes, "{}: lazy reveals", test.name);
+        }
+    }
+
+    #[test]
+    pub fn test_failed_quorum_refunds_voters() {
+        let params = SimulationParams {
+            required_votes: u64::MAX,
+            rounds: 2,
+            ..Default::default()
+        };
+        let results = simulate(&params, 0x5eed).unwrap();
+        for result in results.iter().take(params.groups.len()) {
+            assert_eq!(result.payout, result.staked, "{}", result.strategy);
+            assert_eq!(result.revealed, 0, "{}", result.strategy);
+        }
+    }
+
+    #[test]
+    pub fn test_write_csv() {
+        let params = SimulationParams {
+            rounds: 1,
+            ..Default::default()
+        };
+        let results = simulate(&params, 1).unwrap();
+        let mut csv = Vec::new();
+        write_csv(&mut csv, &params, &results).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        let columns = CSV_HEADER.split(',').count();
+        assert_eq!(csv.lines().count(), results.len());
+        for line in csv.lines() {
+            assert_eq!(line.split(',').count(), columns, "{}", line);
+        }
+        assert!(csv.starts_with("100,50,10000,Mean,honest,20,"));
+    }
+}
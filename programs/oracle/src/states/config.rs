@@ -3,11 +3,14 @@ use std::ops::{Div, Mul};
 use anchor_lang::{prelude::*, solana_program::clock::SECONDS_PER_DAY};
 use anchor_spl::token::Mint;
 
-use crate::utils::VOTING_FRACTION_REQUIRED;
+use crate::utils::{SureError, SURE_ORACLE_CONFIG_SEED, VOTING_FRACTION_REQUIRED};
+
+use super::ConsensusEstimator;
 
 #[account]
 pub struct Config {
-    pub bump: u8, //                        1 byte
+    pub bump: u8,            //                        1 byte
+    pub bump_array: [u8; 1], //             1 byte
 
     /// voting period in seconds
     pub voting_length_seconds: i64, //      8 bytes
@@ -38,17 +41,56 @@ pub struct Config {
     /// who can collect the rewards
     pub protocol_authority: Pubkey, //      32 bytes
 
+    /// locker of the escrows used to vote
+    pub locker: Pubkey, //                  32 bytes
+
+    /// number of proposals created
+    pub proposals: u64, //                  8 bytes
+
+    /// multiply the vote power by the
+    /// reputation factor of the voter
+    pub reputation_enabled: bool, //        1 byte
+
+    /// estimator used to reach consensus
+    /// see ConsensusEstimator
+    pub consensus_estimator: u8, //         1 byte
+
+    /// the 1/x of the vote power trimmed from
+    /// each tail by the trimmed mean
+    pub consensus_trim_rate: u32, //        4 bytes
+
+    /// tokens bonded by the proposer to bind a
+    /// pool. Slashed if the claim is rejected
+    pub claim_bond: u64, //                 8 bytes
+
     pub initialized: bool, //               1 byte
 }
 
 impl Config {
-    pub const SPACE: usize = 1 + 4 * 8 + 2 * 4 + 2 * 32 + 1;
+    pub const SPACE: usize = 1 + 1 + 4 * 8 + 3 * 4 + 3 * 32 + 8 + 1 + 1 + 8 + 1;
+
+    pub fn seeds(&self) -> [&[u8]; 3] {
+        [
+            SURE_ORACLE_CONFIG_SEED.as_bytes().as_ref() as &[u8],
+            self.token_mint.as_ref(),
+            self.bump_array.as_ref(),
+        ]
+    }
 
-    pub fn initialize(&mut self, token_mint: &Account<Mint>, protocol_authority: Pubkey) {
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        token_mint: &Account<Mint>,
+        protocol_authority: Pubkey,
+        locker: Pubkey,
+    ) {
         let mint = token_mint.key();
         let token_supply = token_mint.supply;
         let decimals = token_mint.decimals;
 
+        self.bump = bump;
+        self.bump_array = [bump; 1];
+
         // default voting and reveal time is one day
         self.voting_length_seconds = SECONDS_PER_DAY as i64;
         self.reveal_length_seconds = SECONDS_PER_DAY as i64;
@@ -64,6 +106,17 @@ impl Config {
 
         self.token_mint = mint;
         self.protocol_authority = protocol_authority;
+        self.locker = locker;
+        self.proposals = 0;
+        self.reputation_enabled = false;
+
+        // default to the weighted mean, trim 10% if
+        // the trimmed mean is selected
+        self.consensus_estimator = ConsensusEstimator::Mean.get_id();
+        self.consensus_trim_rate = 10;
+
+        // default to the minimum proposal stake
+        self.claim_bond = self.minimum_proposal_stake;
         self.initialized = true;
     }
 
@@ -134,4 +187,35 @@ impl Config {
         self.protocol_fee_rate = protocol_fee_rate;
         Ok(())
     }
+
+    pub fn update_reputation_enabled(&mut self, reputation_enabled: bool) -> Result<()> {
+        self.reputation_enabled = reputation_enabled;
+        Ok(())
+    }
+
+    pub fn update_consensus_estimator(
+        &mut self,
+        consensus_estimator: u8,
+        consensus_trim_rate: u32,
+    ) -> Result<()> {
+        ConsensusEstimator::from_id(consensus_estimator)?
+            .validate_trim_rate(consensus_trim_rate)?;
+        self.consensus_estimator = consensus_estimator;
+        self.consensus_trim_rate = consensus_trim_rate;
+        Ok(())
+    }
+
+    pub fn update_claim_bond(&mut self, claim_bond: u64) -> Result<()> {
+        self.claim_bond = claim_bond;
+        Ok(())
+    }
+
+    /// count a new proposal
+    pub fn record_proposal(&mut self) -> Result<()> {
+        self.proposals = self
+            .proposals
+            .checked_add(1)
+            .ok_or(SureError::OverflowU64)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,270 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::SureError;
+
+/// Estimator used to reach consensus
+/// from the revealed votes
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[repr(u8)]
+pub enum ConsensusEstimator {
+    /// weighted mean of the votes
+    Mean = 0,
+    /// vote where half of the vote power
+    /// lies below and half above
+    WeightedMedian = 1,
+    /// weighted mean after 1/x of the vote
+    /// power is removed from each tail
+    TrimmedMean = 2,
+}
+
+impl ConsensusEstimator {
+    pub fn get_id(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_id(id: u8) -> Result<Self> {
+        match id {
+            0 => Ok(Self::Mean),
+            1 => Ok(Self::WeightedMedian),
+            2 => Ok(Self::TrimmedMean),
+            _ => Err(SureError::InvalidConsensusEstimator.into()),
+        }
+    }
+
+    /// validate the trim rate used by the estimator
+    ///
+    /// trimming 1/2 or more from each tail
+    /// would remove every vote
+    pub fn validate_trim_rate(&self, trim_rate: u32) -> Result<()> {
+        if *self == Self::TrimmedMean && trim_rate <= 2 {
+            return Err(SureError::InvalidConsensusTrimRate.into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for ConsensusEstimator {
+    #[inline]
+    fn default() -> Self {
+        Self::Mean
+    }
+}
+
+/// Calculate the weighted mean
+///
+/// ### Arguments
+/// * votes: (vote: I32.32, weight: Q64.0)
+///
+/// ### Returns
+/// * consensus: I32.32
+pub fn calculate_weighted_mean(votes: &[(i64, u64)]) -> i64 {
+    let mut sum_weighted_votes: i128 = 0;
+    let mut sum_weights: i128 = 0;
+    for (vote, weight) in votes {
+        sum_weighted_votes += (*vote as i128) * (*weight as i128);
+        sum_weights += *weight as i128;
+    }
+    if sum_weights == 0 {
+        return 0;
+    }
+    (sum_weighted_votes / sum_weights) as i64
+}
+
+/// Calculate the weighted median
+///
+/// the smallest vote such that at least half
+/// of the vote power voted at or below it
+///
+/// ### Arguments
+/// * votes: (vote: I32.32, weight: Q64.0)
+///
+/// ### Returns
+/// * consensus: I32.32
+pub fn calculate_weighted_median(votes: &mut [(i64, u64)]) -> i64 {
+    votes.sort_unstable_by_key(|(vote, _)| *vote);
+    let total_weight: u128 = votes.iter().map(|(_, weight)| *weight as u128).sum();
+
+    let mut cumulative_weight: u128 = 0;
+    for (vote, weight) in votes.iter() {
+        cumulative_weight += *weight as u128;
+        if cumulative_weight * 2 >= total_weight && cumulative_weight > 0 {
+            return *vote;
+        }
+    }
+    0
+}
+
+/// Calculate the weighted trimmed mean
+///
+/// removes W/trim_rate of the vote power from each
+/// tail before calculating the weighted mean. Votes
+/// on the boundary are partially trimmed.
+///
+/// ### Arguments
+/// * votes: (vote: I32.32, weight: Q64.0)
+/// * trim_rate: 1/x of the vote power removed from each tail
+///
+/// ### Returns
+/// * consensus: I32.32
+pub fn calculate_trimmed_mean(votes: &mut [(i64, u64)], trim_rate: u32) -> i64 {
+    votes.sort_unstable_by_key(|(vote, _)| *vote);
+    let total_weight: u128 = votes.iter().map(|(_, weight)| *weight as u128).sum();
+    if trim_rate == 0 {
+        return calculate_weighted_mean(votes);
+    }
+    let trim = total_weight / trim_rate as u128;
+    let lower = trim;
+    let upper = total_weight - trim;
+
+    let mut sum_weighted_votes: i128 = 0;
+    let mut sum_weights: i128 = 0;
+    let mut cumulative_weight: u128 = 0;
+    for (vote, weight) in votes.iter() {
+        let start = cumulative_weight;
+        let end = cumulative_weight + *weight as u128;
+        cumulative_weight = end;
+
+        // weight of the vote inside [lower, upper]
+        let kept_start = start.max(lower);
+        let kept_end = end.min(upper);
+        if kept_end <= kept_start {
+            continue;
+        }
+        let kept = (kept_end - kept_start) as i128;
+        sum_weighted_votes += (*vote as i128) * kept;
+        sum_weights += kept;
+    }
+    if sum_weights == 0 {
+        return calculate_weighted_median(votes);
+    }
+    (sum_weighted_votes / sum_weights) as i64
+}
+
+/// Calculate the consensus using the given estimator
+///
+/// ### Arguments
+/// * estimator: the consensus estimator
+/// * votes: (vote: I32.32, weight: Q64.0)
+/// * trim_rate: only used by the trimmed mean
+///
+/// ### Returns
+/// * consensus: I32.32
+pub fn estimate_consensus(
+    estimator: ConsensusEstimator,
+    votes: &mut [(i64, u64)],
+    trim_rate: u32,
+) -> i64 {
+    match estimator {
+        ConsensusEstimator::Mean => calculate_weighted_mean(votes),
+        ConsensusEstimator::WeightedMedian => calculate_weighted_median(votes),
+        ConsensusEstimator::TrimmedMean => calculate_trimmed_mean(votes, trim_rate),
+    }
+}
+
+#[cfg(test)]
+pub mod test_consensus {
+    use super::*;
+    use crate::utils::{convert_f32_i64, convert_ix32_f64};
+
+    fn honest_votes() -> Vec<(i64, u64)> {
+        [2.0, 2.5, 3.0, 3.5, 4.0, 2.0, 2.5, 3.0, 3.5, 4.0]
+            .iter()
+            .map(|vote| (convert_f32_i64(*vote), 10))
+            .collect()
+    }
+
+    /// A whale voting far away from the honest
+    /// voters drags the mean but not the robust estimators
+    #[test]
+    pub fn test_whale_resistance() {
+        pub struct Test {
+            name: String,
+            estimator: ConsensusEstimator,
+            trim_rate: u32,
+            whale_weight: u64,
+            expected_consensus: f64,
+        }
+
+        let tests = [
+            Test {
+                name: "1. mean is dragged by the whale".to_string(),
+                estimator: ConsensusEstimator::Mean,
+                trim_rate: 0,
+                whale_weight: 30,
+                expected_consensus: 25.384615384,
+            },
+            Test {
+                name: "2. weighted median resists the whale".to_string(),
+                estimator: ConsensusEstimator::WeightedMedian,
+                trim_rate: 0,
+                whale_weight: 30,
+                expected_consensus: 3.5,
+            },
+            Test {
+                name: "3. trimmed mean removes the whale".to_string(),
+                estimator: ConsensusEstimator::TrimmedMean,
+                trim_rate: 4,
+                whale_weight: 30,
+                expected_consensus: 3.363636363,
+            },
+            Test {
+                name: "4. majority whale decides the median".to_string(),
+                estimator: ConsensusEstimator::WeightedMedian,
+                trim_rate: 0,
+                whale_weight: 101,
+                expected_consensus: 100.0,
+            },
+        ];
+
+        for test in tests {
+            let mut votes = honest_votes();
+            votes.push((convert_f32_i64(100.0), test.whale_weight));
+            let consensus = estimate_consensus(test.estimator, &mut votes, test.trim_rate);
+            assert!(
+                (convert_ix32_f64(consensus) - test.expected_consensus).abs() < 1e-6,
+                "{}: expected {} got {}",
+                test.name,
+                test.expected_consensus,
+                convert_ix32_f64(consensus)
+            );
+        }
+    }
+
+    /// Without outliers all estimators agree
+    #[test]
+    pub fn test_estimators_agree_on_symmetric_votes() {
+        let estimators = [
+            ConsensusEstimator::Mean,
+            ConsensusEstimator::WeightedMedian,
+            ConsensusEstimator::TrimmedMean,
+        ];
+        for estimator in estimators {
+            let mut votes = honest_votes();
+            votes.push((convert_f32_i64(3.0), 10));
+            let consensus = estimate_consensus(estimator, &mut votes, 4);
+            assert_eq!(convert_ix32_f64(consensus), 3.0, "{:?}", estimator);
+        }
+    }
+
+    #[test]
+    pub fn test_empty_votes() {
+        assert_eq!(calculate_weighted_mean(&[]), 0);
+        assert_eq!(calculate_weighted_median(&mut []), 0);
+        assert_eq!(calculate_trimmed_mean(&mut [], 4), 0);
+    }
+
+    #[test]
+    pub fn test_estimator_ids() {
+        assert_eq!(
+            ConsensusEstimator::from_id(ConsensusEstimator::TrimmedMean.get_id()).unwrap(),
+            ConsensusEstimator::TrimmedMean
+        );
+        assert!(ConsensusEstimator::from_id(3).is_err());
+        assert!(ConsensusEstimator::TrimmedMean
+            .validate_trim_rate(2)
+            .is_err());
+        assert!(ConsensusEstimator::WeightedMedian
+            .validate_trim_rate(0)
+            .is_ok());
+    }
+}
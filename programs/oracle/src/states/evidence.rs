@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::SureError;
+
+/// max length of the evidence uri in bytes
+pub const MAX_EVIDENCE_URI_LENGTH: usize = 200;
+
+/// Proposal evidence
+///
+/// structured reference to the off-chain incident report
+/// so that voters and keepers can verify what they vote on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProposalEvidence {
+    /// where to find the incident report
+    pub uri: String, // 4 + 200 bytes
+
+    /// sha3-256 hash of the incident report
+    pub content_hash: [u8; 32], // 32 bytes
+
+    /// program affected by the incident
+    pub affected_program: Pubkey, // 32 bytes
+
+    /// first slot of the incident
+    pub start_slot: u64, // 8 bytes
+
+    /// last slot of the incident
+    pub end_slot: u64, // 8 bytes
+}
+
+impl ProposalEvidence {
+    pub const SPACE: usize = 4 + MAX_EVIDENCE_URI_LENGTH + 32 + 32 + 8 + 8;
+
+    /// validate that the evidence fits into the proposal
+    /// and describes a valid slot range
+    pub fn validate(&self) -> Result<()> {
+        if self.uri.len() > MAX_EVIDENCE_URI_LENGTH {
+            return Err(SureError::ProposalContentTooLong.into());
+        }
+        if self.start_slot > self.end_slot {
+            return Err(SureError::InvalidEvidenceSlotRange.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test_evidence {
+    use super::*;
+
+    #[test]
+    pub fn test_validate_evidence() {
+        pub struct Test {
+            name: String,
+            evidence: ProposalEvidence,
+            expected_error: Option<SureError>,
+        }
+        let tests = [
+            Test {
+                name: "1. valid evidence".to_string(),
+                evidence: ProposalEvidence {
+                    uri: "https://arweave.net/report".to_string(),
+                    content_hash: [1; 32],
+                    affected_program: Pubkey::new_unique(),
+                    start_slot: 100,
+                    end_slot: 120,
+                },
+                expected_error: None,
+            },
+            Test {
+                name: "2. uri too long".to_string(),
+                evidence: ProposalEvidence {
+                    uri: "a".repeat(MAX_EVIDENCE_URI_LENGTH + 1),
+                    ..Default::default()
+                },
+                expected_error: Some(SureError::ProposalContentTooLong),
+            },
+            Test {
+                name: "3. slot range ends before it starts".to_string(),
+                evidence: ProposalEvidence {
+                    start_slot: 120,
+                    end_slot: 100,
+                    ..Default::default()
+                },
+                expected_error: Some(SureError::InvalidEvidenceSlotRange),
+            },
+        ];
+
+        for test in tests {
+            let res = test.evidence.validate();
+            match test.expected_error {
+                Some(err) => {
+                    let expected_err: anchor_lang::error::Error = err.into();
+                    assert_eq!(
+                        res.unwrap_err().to_string(),
+                        expected_err.to_string(),
+                        "{}",
+                        test.name
+                    );
+                }
+                None => assert!(res.is_ok(), "{}", test.name),
+            }
+        }
+    }
+}
@@ -1,9 +1,15 @@
 pub mod config;
+pub mod consensus;
+pub mod evidence;
 pub mod proposal;
+pub mod reputation;
 pub mod vote;
 pub mod vote_array;
 
 pub use config::*;
+pub use consensus::*;
+pub use evidence::*;
 pub use proposal::*;
+pub use reputation::*;
 pub use vote::*;
 pub use vote_array::*;
@@ -4,14 +4,21 @@ use std::{
 };
 
 use crate::{
-    factory::{calculate_stake, calculate_stake_x32},
+    factory::calculate_stake,
     instructions::validate_stake,
     utils::{uint::U256, *},
 };
 
 use anchor_lang::{prelude::*, solana_program::clock};
+use sha3::{Digest, Sha3_256};
+use sure_common::fixed::{I32x32, Q16x16, Q64x64};
 
-use super::{Config, RevealedVoteArray, VoteAccount};
+use super::{Config, ConsensusEstimator, ProposalEvidence, RevealedVoteArray, VoteAccount};
+
+/// max length of the proposal name in bytes
+pub const MAX_PROPOSAL_NAME_LENGTH: usize = 4 * 64;
+/// max length of the proposal description in bytes
+pub const MAX_PROPOSAL_DESCRIPTION_LENGTH: usize = 4 * 140;
 
 #[derive(Debug, Eq, PartialEq, PartialOrd)]
 #[repr(u8)]
@@ -59,12 +66,34 @@ pub struct Proposal {
     /// name of vote
     pub name: String, // 4 + 4*64 bytes
 
-    /// id - hashed name
+    /// id - hash of the proposal content
     pub id: [u8; 16], // 16 bytes
 
     /// description of vote
     pub description: String, // 4 + 4*140 (140chars) bytes
 
+    /// reference to the off-chain evidence
+    pub evidence: ProposalEvidence, // ProposalEvidence::SPACE
+
+    /// insurance pool the proposal claims against.
+    /// Pubkey::default() if not bound to a pool
+    pub pool: Pubkey, // 32 bytes
+
+    /// tokens bonded by the proposer when binding
+    /// the pool. Slashed if the claim is rejected
+    pub claim_bond: u64, // 8
+
+    /// coverage position the claim is paid to
+    pub coverage_position: Pubkey, // 32 bytes
+
+    /// amount claimed for the coverage position.
+    /// The payout is the share of the claim
+    /// voted on by the voters
+    pub claim_amount: u64, // 8
+
+    /// whether the accepted claim is paid out
+    pub claim_paid: bool, // 1
+
     /// Proposed result
     pub proposed_result: i64, // 8
 
@@ -109,19 +138,64 @@ pub struct Proposal {
     /// Q64.64
     pub earned_rewards: u128, // 16
 
-    /// protocol fees
+    /// protocol fees accrued
+    /// Q64.64
     pub protocol_fees: u128, // 16
 
+    /// total protocol fees sent to the
+    /// protocol treasury
+    pub collected_protocol_fees: u64, // 8
+
     /// Scale parameter in exp(L)
     /// Q16.16
     pub scale_parameter: u32, // 4
 
     pub scale_parameter_calculated: bool, // 1
 
-    pub vote_factor_sum: u64,   // 8
+    /// sum of the vote factors X_i
+    /// Q32.32
+    pub vote_factor_sum: u64, // 8
+
+    /// sum of the reward weights F_i = V_i * X_i
+    /// used to normalize the voter rewards
+    /// Q64.32
     pub distribution_sum: u128, // 16
 
+    /// vote power of the revealed votes that
+    /// have calculated their vote factor
+    /// Q64.0
+    pub finalized_votes: u64, // 8
+
+    /// amount of tokens to be distributed
+    /// among the revealed votes
+    pub reward_pool: u64, // 8
+
+    /// optional mint of the incentive rewards
+    /// Pubkey::default() if no incentives are attached
+    pub incentive_mint: Pubkey, // 32 bytes
+
+    /// vault holding the incentive rewards
+    pub incentive_vault: Pubkey, // 32 bytes
+
+    /// account that attached the incentive vault. Receives
+    /// the incentives back if they cannot be distributed
+    pub incentive_sponsor: Pubkey, // 32 bytes
+
+    /// amount of incentive tokens to be distributed
+    /// among the revealed votes
+    pub incentive_pool: u64, // 8
+
+    /// consensus reached after the reveal
+    /// I32.32
     pub consensus: i64, // 8
+
+    /// estimator used to calculate the consensus
+    /// see ConsensusEstimator
+    pub consensus_estimator: u8, // 1
+
+    /// 1/x of the vote power trimmed from each
+    /// tail when using the trimmed mean
+    pub consensus_trim_rate: u32, // 4
 }
 
 impl Default for Proposal {
@@ -135,6 +209,12 @@ impl Default for Proposal {
             id: [0; 16],
             optimistic: false,
             description: "test descr".to_string(),
+            evidence: ProposalEvidence::default(),
+            pool: Pubkey::default(),
+            claim_bond: 0,
+            coverage_position: Pubkey::default(),
+            claim_amount: 0,
+            claim_paid: false,
             proposed_result: 0,
             proposer: Pubkey::default(),
             stake_rate: 10,
@@ -150,6 +230,7 @@ impl Default for Proposal {
             vote_end_reveal_at: 0,
             earned_rewards: 0,
             protocol_fees: 0,
+            collected_protocol_fees: 0,
             scale_parameter: 0,
             protocol_fee_rate: 50,
             scale_parameter_calculated: false,
@@ -157,7 +238,15 @@ impl Default for Proposal {
             status: ProposalStatus::Proposed.get_id(),
             distribution_sum: 0,
             vote_factor_sum: 0,
+            finalized_votes: 0,
+            reward_pool: 0,
+            incentive_mint: Pubkey::default(),
+            incentive_vault: Pubkey::default(),
+            incentive_sponsor: Pubkey::default(),
+            incentive_pool: 0,
             consensus: 0,
+            consensus_estimator: ConsensusEstimator::Mean.get_id(),
+            consensus_trim_rate: 0,
         }
     }
 }
@@ -165,7 +254,16 @@ impl Default for Proposal {
 pub struct FinalizeVoteResult {}
 
 impl Proposal {
-    pub const SPACE: usize = 1 * 6 + 4 * 3 + 8 * 12 + 16 * 4 + 32 * 3 + 4 + 4 * 64 + 4 + 4 * 140;
+    pub const SPACE: usize = 1 * 8
+        + 4 * 4
+        + 8 * 18
+        + 16 * 4
+        + 32 * 8
+        + 4
+        + MAX_PROPOSAL_NAME_LENGTH
+        + 4
+        + MAX_PROPOSAL_DESCRIPTION_LENGTH
+        + ProposalEvidence::SPACE;
 
     pub fn seeds(&self) -> [&[u8]; 3] {
         [
@@ -175,6 +273,52 @@ impl Proposal {
         ]
     }
 
+    /// Calculate the proposal id
+    ///
+    /// the id is the truncated sha3-256 hash of the proposer
+    /// and the proposal content. Each field is length prefixed
+    /// so that different contents can't produce the same preimage
+    ///
+    /// ### Returns
+    /// * id: first 16 bytes of the hash
+    pub fn calculate_id(
+        proposer: &Pubkey,
+        name: &str,
+        description: &str,
+        evidence: &ProposalEvidence,
+    ) -> [u8; 16] {
+        let mut hasher = Sha3_256::new();
+        hasher.update(proposer.as_ref());
+        hasher.update((name.len() as u32).to_le_bytes());
+        hasher.update(name.as_bytes());
+        hasher.update((description.len() as u32).to_le_bytes());
+        hasher.update(description.as_bytes());
+        hasher.update(evidence.try_to_vec().unwrap());
+        let hash: [u8; 32] = hasher.finalize().into();
+        let mut id = [0; 16];
+        id.copy_from_slice(&hash[..16]);
+        id
+    }
+
+    /// validate that the proposal content fits into the account
+    pub fn validate_content(
+        name: &str,
+        description: &str,
+        evidence: &ProposalEvidence,
+    ) -> Result<()> {
+        if name.len() > MAX_PROPOSAL_NAME_LENGTH
+            || description.len() > MAX_PROPOSAL_DESCRIPTION_LENGTH
+        {
+            return Err(SureError::ProposalContentTooLong.into());
+        }
+        evidence.validate()
+    }
+
+    /// initialize the proposal
+    ///
+    /// the vote starts at the given time and lasts for
+    /// the voting and reveal periods of the config unless
+    /// an end time is given
     pub fn initialize(
         &mut self,
         config: &Account<Config>,
@@ -182,12 +326,15 @@ impl Proposal {
         name: String,
         id: &[u8; 16],
         description: &str,
+        evidence: &ProposalEvidence,
         proposer: &Pubkey,
         proposed_staked: u64,
         vault: &Pubkey,
         end_time_ts: Option<i64>,
+        time: i64,
     ) -> Result<()> {
         validate_stake(proposed_staked)?;
+        Self::validate_content(&name, description, evidence)?;
 
         // initialize account
         self.bump = bump;
@@ -195,6 +342,8 @@ impl Proposal {
         self.name = name;
         self.id = *id;
         self.description = String::from(description);
+        self.evidence = evidence.clone();
+        self.pool = Pubkey::default();
         self.proposer = *proposer;
         self.status = ProposalStatus::Proposed.get_id();
         if proposed_staked < config.minimum_proposal_stake {
@@ -204,28 +353,38 @@ impl Proposal {
         self.vault = *vault;
         self.stake_rate = config.vote_stake_rate;
         self.protocol_fee_rate = config.protocol_fee_rate;
+        self.consensus_estimator = config.consensus_estimator;
+        self.consensus_trim_rate = config.consensus_trim_rate;
         self.optimistic = false;
         self.config = config.key();
         // set end of
-        let current_time = Clock::get()?.unix_timestamp;
-        self.vote_start_at = current_time;
+        self.vote_start_at = time;
         self.vote_end_at = match end_time_ts {
             Some(t) => t,
-            None => current_time
+            None => time
                 .checked_add(config.voting_length_seconds)
                 .ok_or(SureError::InvalidVoteEndTime)?,
         };
 
         self.vote_end_reveal_at = match end_time_ts {
             Some(t) => t,
-            None => current_time
-                .checked_add(self.vote_end_at + config.reveal_length_seconds)
+            None => self
+                .vote_end_at
+                .checked_add(config.reveal_length_seconds)
                 .ok_or(SureError::InvalidVoteEndTime)?,
         };
 
         self.required_votes = config.default_required_votes;
         self.votes = 0;
         self.protocol_fees = 0;
+        self.collected_protocol_fees = 0;
+        self.distribution_sum = 0;
+        self.finalized_votes = 0;
+        self.reward_pool = 0;
+        self.incentive_mint = Pubkey::default();
+        self.incentive_vault = Pubkey::default();
+        self.incentive_sponsor = Pubkey::default();
+        self.incentive_pool = 0;
         Ok(())
     }
 
@@ -250,9 +409,31 @@ impl Proposal {
         timeouted | enough_votes
     }
 
-    pub fn has_ended(&self) -> Result<bool> {
-        let current_time = Clock::get()?.unix_timestamp;
-        Ok(self.has_ended_at_time(current_time))
+    /// Reveal a vote
+    ///
+    /// reveals the vote in the vote account, charges the
+    /// protocol fee on the stake and adds the vote to the
+    /// running consensus and the revealed votes
+    ///
+    /// ### Arguments
+    /// * vote_account: the vote to reveal
+    /// * revealed_votes: array of the revealed votes
+    /// * salt: salt used to hash the vote
+    /// * vote: the revealed vote
+    /// * time: current time
+    pub fn reveal_vote(
+        &mut self,
+        vote_account: &mut VoteAccount,
+        revealed_votes: &mut RevealedVoteArray,
+        salt: &str,
+        vote: i64,
+        time: i64,
+    ) -> Result<()> {
+        self.can_reveal_vote(time)?;
+        vote_account.reveal_vote(self, salt, vote, time)?;
+        self.update_protocol_fee(vote_account.staked);
+        self.update_running_sum_weighted_vote(*vote_account);
+        revealed_votes.reveal_vote(vote_account)
     }
 
     /// Update the weighted vote sum and the weight sum
@@ -274,6 +455,9 @@ impl Proposal {
     }
 
     pub fn calculate_consensus_(&self, running_sum_weighted_vote: i64, running_weight: u64) -> i64 {
+        if running_weight == 0 {
+            return 0;
+        }
         // Convert i64 -> i64.64
         let positive = running_sum_weighted_vote > 0;
         // convert u64 -> Q64.64
@@ -301,24 +485,46 @@ impl Proposal {
         self.calculate_consensus_(self.running_sum_weighted_vote, self.running_weight)
     }
 
+    /// Consensus using the estimator of the proposal
+    ///
+    /// the weighted mean uses the running sums while the
+    /// robust estimators are calculated from the revealed votes
+    ///
+    /// ## Returns:
+    ///  * consensus: I32.32
+    pub fn estimate_consensus(&self, revealed_votes: &RevealedVoteArray) -> Result<i64> {
+        let estimator = ConsensusEstimator::from_id(self.consensus_estimator)?;
+        match estimator {
+            ConsensusEstimator::Mean => Ok(self.calculate_consensus()),
+            _ => Ok(revealed_votes.calculate_consensus(estimator, self.consensus_trim_rate)),
+        }
+    }
+
     /// Estimate the scale parameter used in the
     /// exponential model
     /// Estimate:
-    ///     L_n = W_N / sum_i^n (w_i x v_i - X_n)^2
+    ///     L_n = W_N / sum_i^n w_i x (v_i - X_n)^2
     pub fn estimate_scale_parameter(
         &self,
         consensus: i64,
         revealed_votes: &RevealedVoteArray,
-    ) -> u32 {
-        let sum_squared = revealed_votes.calculate_sum_squared_difference(consensus);
+    ) -> Result<u32> {
+        let sum_squared = revealed_votes.calculate_sum_squared_difference(consensus)?;
+        if sum_squared == 0 {
+            // all votes agree, any scale gives equal vote factors
+            return Ok(1 << 16);
+        }
 
-        // Q32.32 -> Q64.64
-        let running_weight_Q64 = (self.running_weight as u128) << 32;
-        // Q64.64
-        let lambda = running_weight_Q64.div(sum_squared as u128);
-        // get rid of precision: Q64.64 >> 48 -> Q16.16
-        let lambda_q16 = (lambda >> 48) as u32;
-        lambda_q16
+        // Q64.0 -> Q16.48, fits in 112 bits
+        let running_weight_x48 = (self.running_weight as u128) << 48;
+        // Q16.48 / Q32.32 -> Q16.16
+        let lambda = running_weight_x48.div(sum_squared as u128);
+        // votes that almost agree are capped at the largest
+        // representable scale instead of failing the vote
+        let lambda_q16 = u32::try_from(lambda).unwrap_or(u32::MAX);
+        // widely spread votes would round the scale down to
+        // zero and leave every vote without a reward
+        Ok(lambda_q16.max(1))
     }
 
     /// Calculate
@@ -329,7 +535,11 @@ impl Proposal {
     ///
     pub fn calculate_consensus_distance(&self, vote_account: &VoteAccount) -> i64 {
         // i32.32
-        let consensus = self.calculate_consensus();
+        let consensus = if self.scale_parameter_calculated {
+            self.consensus
+        } else {
+            self.calculate_consensus()
+        };
         println!("consensus: {}", convert_ix32_f64(consensus));
         // i32.32 - i32.32 => i32.32
         println!("vote: {}", convert_ix32_f64(vote_account.vote));
@@ -337,73 +547,59 @@ impl Proposal {
     }
 
     /// Calculate the remainder of the
-    /// reward pool after a reward is subtracted
+    /// reward pool after the protocol fees and
+    /// the proposer reward are subtracted
+    ///
     /// # Input
-    /// - reward: Q64.64
+    /// - vault_amount: tokens held by the proposal vault
+    /// - proposer_reward: Q64.64
     /// # Output
-    /// - remaining_reward: Q64.64
-    pub fn calculate_reward_pool_remainder(&self, reward: u128) -> u128 {
-        println!("votes: {}", (self.votes as u128) << 64);
-        ((self.votes as u128) << 64) - reward
+    /// - remaining_reward: tokens available to the voters
+    pub fn calculate_reward_pool_remainder(&self, vault_amount: u64, proposer_reward: u128) -> u64 {
+        let proposer_reward = (proposer_reward >> 64) as u64;
+        vault_amount
+            .saturating_sub(self.accrued_protocol_fees())
+            .saturating_sub(proposer_reward)
     }
 
     /// Calculate: X = l * exp(-l*(x-x^bar)^2)
     ///
+    /// exp(-l*(x-x^bar)^2) vanishes for votes too far
+    /// from the consensus to be represented
+    ///
     /// ### Output
     /// - X : Q32.32
     ///
-    /// TODO: scale down variables earlier
     /// TODO: move out to manager
     pub fn calculate_vote_factor(&self, vote_account: &VoteAccount) -> Result<u64> {
-        // validate that voting is done
-        // Q64.0
-        let proposer_reward = self.calculate_proposer_reward();
-        println!("proposer_reward: {}", proposer_reward);
-        // Q64.64
-        let reward_pool = self.calculate_reward_pool_remainder(proposer_reward);
-        println!("reward_pool: {}", reward_pool);
-
         // calculate distance
         if !vote_account.revealed_vote {
             return Err(SureError::VoteNotRevealed.into());
         }
-        // I32.32
-        let distance = self.calculate_consensus_distance(vote_account);
-        println!("distance f64: {}", convert_ix32_f64(distance));
-        // I32.32 x I32.32 -> Q64.64
-        let sqrd_distance = (distance as i128).mul(distance as i128) as u128;
-        println!("sqrd_distance u128: {}", sqrd_distance);
-        // Q64.64 >> 48 -> Q48.16
-        if (sqrd_distance >> 48) > u64::MAX as u128 {
-            return Err(SureError::OverflowU64.into());
-        }
-        let sqrd_distance_q48_16 = (sqrd_distance >> 48) as u64;
+        // |x - x^bar|: I32.32 -> Q64.64
+        let distance = I32x32::from_raw(self.calculate_consensus_distance(vote_account))
+            .abs()
+            .to_q64x64();
+        let scale_parameter = Q16x16::from_raw(self.scale_parameter);
+
+        // l*(x-x^bar)^2: Q64.64 x Q64.64 -> Q64.64 -> Q32.32
+        let exponent = match distance
+            .checked_mul(distance)
+            .and_then(|sqrd_distance| sqrd_distance.to_q32x32())
+            .and_then(|sqrd_distance| scale_parameter.to_q32x32().checked_mul(sqrd_distance))
+        {
+            Some(exponent) => exponent,
+            None => return Ok(0),
+        };
+
+        // exp(-l*(x-x^bar)^2): Q64.64 -> Q16.16
+        let exp_x16 = Q64x64::from_raw(calculate_exp(exponent.raw(), true))
+            .to_q32x32()
+            .and_then(|exp| exp.to_q16x16())
+            .ok_or(SureError::OverflowU64)?;
 
-        // Q16.16 x Q48.16  -> Q64.32 => Q128.0
-        println!("scale parameter: {}", convert_q16_f16(self.scale_parameter));
-        let exponent = (self.scale_parameter as u128).mul(sqrd_distance_q48_16 as u128);
-        if exponent > u64::MAX as u128 {
-            return Err(SureError::OverflowU64.into());
-        }
-        let exponent_q64 = exponent as u64;
-        println!("exponent: {}", exponent_q64);
-        println!("exponent f64: {}", convert_ix32_f64(exponent_q64 as i64));
-        let pt2_x64 = calculate_exp(exponent_q64, true);
-        println!("pt2_x64: {}", pt2_x64);
-        println!("pt2_x64 as f64: {}", convert_q64_to_f64(pt2_x64));
-        let pt2_x32 = (pt2_x64 >> 48) as u64;
-        // CAST DOWN: Q64.64 >> 32 -> Q32.32
-        if pt2_x32 > u32::MAX as u64 {
-            return Err(SureError::OverflowU64.into());
-        }
-        // Q64.64 << 48 -> Q48.16 -> Q16.16
-        let pt2_q16 = pt2_x32 as u32;
-        println!("pt2_q16 as u32: {}", convert_q16_f16(pt2_q16));
         // Q16.16 x Q16.16 -> Q32.32
-        let exp_factor = self.scale_parameter.mul(pt2_q16) as u64;
-        println!("exp_factor as u32: {}", convert_ix32_f64(exp_factor as i64));
-        // Q64.64 >> 64 => u64
-        Ok(exp_factor)
+        Ok(scale_parameter.widening_mul(exp_x16).raw())
     }
 
     /// Updates the vote factor sum
@@ -412,66 +608,87 @@ impl Proposal {
         self.vote_factor_sum += vote_factor;
     }
 
+    /// Update the distribution sum
+    ///
+    /// adds the reward weight F_i = V_i * X_i of a
+    /// finalized vote to sum F. When all the revealed
+    /// votes are accounted for the proposal is locked
+    /// and the rewards can be paid out
+    pub fn update_distribution_sum(&mut self, vote_account: &VoteAccount) -> Result<()> {
+        let reward_weight = vote_account.calculate_reward_weight();
+        self.distribution_sum = self
+            .distribution_sum
+            .checked_add(reward_weight)
+            .ok_or(SureError::OverflowU128)?;
+        self.update_vote_factor_sum(vote_account.vote_factor);
+        self.finalized_votes = self
+            .finalized_votes
+            .checked_add(vote_account.vote_power as u64)
+            .ok_or(SureError::OverflowU64)?;
+        if self.finalized_votes >= self.running_weight {
+            self.locked = true;
+        }
+        Ok(())
+    }
+
     /// Calculate and update the scale parameter
     pub fn update_scale_parameter(&mut self, revealed_votes: &RevealedVoteArray) -> Result<()> {
-        let consensus = self.calculate_consensus();
-        self.scale_parameter = self.estimate_scale_parameter(consensus, revealed_votes);
+        let consensus = self.estimate_consensus(revealed_votes)?;
+        self.scale_parameter = self.estimate_scale_parameter(consensus, revealed_votes)?;
         self.consensus = consensus;
         Ok(())
     }
 
     /// try to finalize the vote after reveal
+    ///
+    /// sets the proposer reward and fixes the reward pool
+    /// that is distributed among the revealed votes
+    ///
+    /// ### Arguments
+    /// * revealed_votes: the revealed vote array
+    /// * vault_amount: tokens held by the proposal vault
+    /// * time: current time
     pub fn try_finalize_vote_after_reveal(
         &mut self,
         revealed_votes: &RevealedVoteArray,
+        vault_amount: u64,
         time: i64,
     ) -> Result<()> {
         if self.get_status(time).unwrap() == ProposalStatus::VoteRevealFinished {
             // distribute reward to proposer
-            let rewards = self.calculate_proposer_reward();
+            let rewards = self.calculate_proposer_reward(vault_amount);
             self.earned_rewards = rewards;
+            self.reward_pool = self.calculate_reward_pool_remainder(vault_amount, rewards);
 
             // calculate scale parameter
             self.update_scale_parameter(revealed_votes)?;
             self.scale_parameter_calculated = true;
+
+            // nothing to distribute if no votes were revealed
+            if self.running_weight == 0 {
+                self.locked = true;
+            }
         } else {
             return Err(SureError::RevealPeriodNotActive.into());
         }
         Ok(())
     }
 
-    /// try finalize vote
-    /// Only finalize vote if either the
-    /// quorum is reached or if it timed out
-    pub fn try_finalize_blind_vote(&mut self, time: i64) -> Result<u128> {
-        if self.get_status(time).unwrap() == ProposalStatus::RevealVote {
-            // distribute rewards
-            let rewards = self.calculate_proposer_reward();
-            self.earned_rewards = rewards;
-
-            // Initiate instruction
-            return Ok(rewards);
-        }
-
-        Ok(0)
-    }
-
     /// get the payout earned by the proposer
     ///
     /// ### Arguments
-    /// * decimals: number of decimals in the mint
     /// * time: current time used to check if it's possible to payout
-    pub fn payout_earned_rewards_at_time(&mut self, decimals: u8, time: i64) -> Result<u64> {
+    pub fn payout_earned_rewards_at_time(&mut self, time: i64) -> Result<u64> {
         if self.get_status(time).unwrap() > ProposalStatus::RevealVote {
-            // Q64.64 -> Q64.32
-            let rewards_x32 = self.earned_rewards >> 32;
-            if rewards_x32 > u64::MAX as u128 {
+            // Q64.64 -> Q64.0
+            let rewards = self.earned_rewards >> 64;
+            if rewards > u64::MAX as u128 {
                 return Err(SureError::OverflowU64.into());
             }
 
             self.earned_rewards = 0;
 
-            return Ok(convert_x32_to_u64(rewards_x32 as u64, decimals));
+            return Ok(rewards as u64);
         } else {
             return Err(SureError::RevealPeriodIsNotFinished.into());
         }
@@ -480,31 +697,212 @@ impl Proposal {
     /// update protocol fee
     ///
     /// when a user reveals the vote the protocol takes a cut
+    ///
+    /// ### Arguments
+    /// * amount: tokens staked by the voter
     pub fn update_protocol_fee(&mut self, amount: u64) {
-        let protocol_fee = amount.div(self.protocol_fee_rate as u64);
-        self.protocol_fees += protocol_fee as u128;
+        // Q64.0 << 64 -> Q64.64
+        let protocol_fee = ((amount as u128) << 64).div(self.protocol_fee_rate as u128);
+        self.protocol_fees += protocol_fee;
+    }
+
+    /// checks if the proposal claims against a pool
+    pub fn is_bound_to_pool(&self) -> bool {
+        self.pool != Pubkey::default()
+    }
+
+    /// Bind the proposal to an insurance pool
+    ///
+    /// the proposal becomes a claim against the pool. The
+    /// evidence has to reference the insured smart contract
+    ///
+    /// ### Arguments
+    /// * pool: the insurance pool
+    /// * smart_contract: smart contract insured by the pool
+    /// * coverage_position: coverage position to pay the claim to
+    /// * claim_amount: amount claimed for the coverage position
+    /// * claim_bond: tokens bonded by the proposer
+    /// * time: current time
+    pub fn bind_pool(
+        &mut self,
+        pool: &Pubkey,
+        smart_contract: &Pubkey,
+        coverage_position: &Pubkey,
+        claim_amount: u64,
+        claim_bond: u64,
+        time: i64,
+    ) -> Result<()> {
+        if self.is_bound_to_pool() {
+            return Err(SureError::ProposalAlreadyBoundToPool.into());
+        }
+        if self.evidence.affected_program != *smart_contract {
+            return Err(SureError::InvalidPoolForProposal.into());
+        }
+        if self.get_status(time).unwrap() != ProposalStatus::Voting {
+            return Err(SureError::VotingPeriodEnded.into());
+        }
+        if claim_amount == 0 {
+            return Err(SureError::InvalidClaimAmount.into());
+        }
+        self.pool = *pool;
+        self.coverage_position = *coverage_position;
+        self.claim_amount = claim_amount;
+        self.claim_bond = claim_bond;
+        Ok(())
+    }
+
+    /// checks if the claim against the pool is accepted
+    ///
+    /// voters vote on the share of the claim to pay out.
+    /// The claim is accepted if the consensus is at least
+    /// CLAIM_ACCEPTANCE_THRESHOLD_X32. A failed vote rejects
+    /// the claim
+    pub fn is_claim_accepted(&self, time: i64) -> bool {
+        self.get_status(time).unwrap() >= ProposalStatus::RewardCalculation
+            && self.consensus >= CLAIM_ACCEPTANCE_THRESHOLD_X32
+    }
+
+    /// calculate the claim payout
+    ///
+    /// the share of the claim amount resolved by the
+    /// consensus. The share is capped at the full claim
+    ///
+    /// ### Returns
+    /// * payout: tokens to pay to the coverage position
+    pub fn calculate_claim_payout(&self) -> u64 {
+        // I32.32 -> Q0.32 capped at 1
+        let share_x32 = self.consensus.clamp(0, 1 << 32) as u128;
+        ((self.claim_amount as u128 * share_x32) >> 32) as u64
+    }
+
+    /// pay claim
+    ///
+    /// mark the accepted claim as paid so that it
+    /// can only be paid out once
+    ///
+    /// ### Returns
+    /// * payout: tokens to pay to the coverage position
+    pub fn pay_claim(&mut self, time: i64) -> Result<u64> {
+        if !self.is_bound_to_pool() {
+            return Err(SureError::ProposalNotBoundToPool.into());
+        }
+        if !self.is_claim_accepted(time) {
+            return Err(SureError::ClaimNotAccepted.into());
+        }
+        if self.claim_paid {
+            return Err(SureError::ClaimAlreadyPaid.into());
+        }
+        self.claim_paid = true;
+        Ok(self.calculate_claim_payout())
+    }
+
+    /// checks if an incentive vault is attached to the proposal
+    pub fn has_incentives(&self) -> bool {
+        self.incentive_vault != Pubkey::default()
+    }
+
+    /// initialize incentives
+    ///
+    /// attach a vault in a separate mint to the proposal. The
+    /// incentives are distributed among the revealed votes by
+    /// the same reward weights as the voter rewards
+    ///
+    /// ### Arguments
+    /// * incentive_mint: mint of the incentive rewards
+    /// * incentive_vault: vault holding the incentive rewards
+    /// * incentive_sponsor: receiver of undistributed incentives,
+    ///     has to be the proposer or the pool founder
+    /// * pool_founder: founder of the pool bound to the proposal
+    /// * time: current time
+    pub fn initialize_incentives(
+        &mut self,
+        incentive_mint: &Pubkey,
+        incentive_vault: &Pubkey,
+        incentive_sponsor: &Pubkey,
+        pool_founder: Option<&Pubkey>,
+        time: i64,
+    ) -> Result<()> {
+        if self.has_incentives() {
+            return Err(SureError::IncentiveVaultAlreadyInitialized.into());
+        }
+        let is_pool_founder = self.is_bound_to_pool() && pool_founder == Some(incentive_sponsor);
+        if *incentive_sponsor != self.proposer && !is_pool_founder {
+            return Err(SureError::InvalidIncentiveSponsor.into());
+        }
+        self.can_fund_incentives(time)?;
+        self.incentive_mint = *incentive_mint;
+        self.incentive_vault = *incentive_vault;
+        self.incentive_sponsor = *incentive_sponsor;
+        self.incentive_pool = 0;
+        Ok(())
+    }
+
+    /// fund incentives
+    ///
+    /// add incentive tokens to the incentive pool
+    pub fn fund_incentives(&mut self, amount: u64, time: i64) -> Result<()> {
+        if !self.has_incentives() {
+            return Err(SureError::IncentiveVaultNotInitialized.into());
+        }
+        self.can_fund_incentives(time)?;
+        self.incentive_pool = self
+            .incentive_pool
+            .checked_add(amount)
+            .ok_or(SureError::OverflowU64)?;
+        Ok(())
+    }
+
+    /// Reclaim the incentive pool
+    ///
+    /// the incentives can be reclaimed if the vote failed
+    /// or there are no rewards to distribute
+    ///
+    /// ### Returns
+    /// * incentive tokens to be returned
+    pub fn reclaim_incentives_at_time(&mut self, time: i64) -> Result<u64> {
+        self.can_reclaim_incentives(time)?;
+        let incentives = self.incentive_pool;
+        self.incentive_pool = 0;
+        Ok(incentives)
+    }
+
+    /// protocol fees accrued but not yet collected
+    pub fn accrued_protocol_fees(&self) -> u64 {
+        u64::try_from(self.protocol_fees >> 64).unwrap_or(u64::MAX)
     }
 
     /// payout protocol fees
     ///
-    /// get the accrued protocol fees and
-    /// set it to zero
+    /// get the accrued protocol fees in tokens and
+    /// keep the fractional part for the next payout
     pub fn payout_accrued_protocol_fees(&mut self) -> Result<u64> {
-        let protocol_fees = (self.protocol_fees >> 64);
+        // Q64.64 -> Q64.0
+        let protocol_fees = self.protocol_fees >> 64;
         if protocol_fees > u64::MAX as u128 {
             return Err(SureError::OverflowU64.into());
         }
-        self.protocol_fees = 0;
+        self.protocol_fees = self.protocol_fees.bitand(u64::MAX as u128);
+        self.collected_protocol_fees = self
+            .collected_protocol_fees
+            .checked_add(protocol_fees as u64)
+            .ok_or(SureError::OverflowU64)?;
         Ok(protocol_fees as u64)
     }
 
     /// Calculate the reward from the votes
     ///
-    /// 0.1% = 10bp of the total votes
+    /// 1/stake_rate of the tokens staked by the voters
+    /// after protocol fees
+    ///
+    /// ### Arguments
+    /// * vault_amount: tokens held by the proposal vault
     ///
     /// Returns Q64.64
-    fn calculate_reward_from_revealed_votes(&self) -> u128 {
-        (calculate_stake_x32(self.revealed_votes, self.stake_rate) as u128) << 64
+    fn calculate_reward_from_vote_stake(&self, vault_amount: u64) -> u128 {
+        let vote_stake = vault_amount
+            .saturating_sub(self.staked)
+            .saturating_sub(self.accrued_protocol_fees());
+        (vote_stake.div(self.stake_rate as u64) as u128) << 64
     }
 
     /// Update status callback
@@ -517,11 +915,14 @@ impl Proposal {
     ///
     /// if the vote has ended calculate reward
     ///
+    /// ### Arguments
+    /// * vault_amount: tokens held by the proposal vault
+    ///
     /// Returns
     /// - proposer reward as Q64.64
-    pub fn calculate_proposer_reward(&self) -> u128 {
+    pub fn calculate_proposer_reward(&self, vault_amount: u64) -> u128 {
         // if vote is successful
-        return (self.staked as u128) << 64 + self.calculate_reward_from_revealed_votes();
+        ((self.staked as u128) << 64) + self.calculate_reward_from_vote_stake(vault_amount)
     }
 
     pub fn is_blind_vote_ongoing_at_time(&self, time: i64) -> bool {
@@ -541,6 +942,13 @@ impl Proposal {
         time >= self.vote_end_reveal_at
     }
 
+    /// the reward calculation period lasts as long
+    /// as the reveal period
+    pub fn is_reward_calculation_over_at_time(&self, time: i64) -> bool {
+        let reveal_length = self.vote_end_reveal_at - self.vote_end_at;
+        time >= self.vote_end_reveal_at + reveal_length
+    }
+
     pub fn has_reached_quorum(&self) -> bool {
         self.votes >= self.required_votes
     }
@@ -548,18 +956,18 @@ impl Proposal {
     pub fn get_status(&self, time: i64) -> Option<ProposalStatus> {
         if self.is_blind_vote_ongoing_at_time(time) && !self.has_reached_quorum() {
             return Some(ProposalStatus::Voting);
-        } else if self.is_blind_vote_finished_at_time(time) && self.has_reached_quorum() {
+        } else if !self.has_reached_quorum() {
+            return Some(ProposalStatus::Failed);
+        } else if !self.is_blind_vote_finished_at_time(time) {
             return Some(ProposalStatus::ReachedQuorum);
-        } else if self.has_reached_quorum() && self.is_vote_reveal_ongoing_at_time(time) {
+        } else if self.is_vote_reveal_ongoing_at_time(time) {
             return Some(ProposalStatus::RevealVote);
-        } else if self.is_vote_revealed_over(time) {
+        } else if !self.scale_parameter_calculated {
             return Some(ProposalStatus::VoteRevealFinished);
-        } else if self.scale_parameter_calculated {
+        } else if !self.locked && !self.is_reward_calculation_over_at_time(time) {
             return Some(ProposalStatus::RewardCalculation);
-        } else if self.locked {
-            return Some(ProposalStatus::RewardPayout);
         } else {
-            return Some(ProposalStatus::Failed);
+            return Some(ProposalStatus::RewardPayout);
         }
     }
 
@@ -618,6 +1026,46 @@ impl Proposal {
         Ok(())
     }
 
+    /// the incentive pool can be funded until the
+    /// vote results are finalized
+    pub fn can_fund_incentives(&self, time: i64) -> Result<()> {
+        let status = self.get_status(time).unwrap();
+        if status == ProposalStatus::Failed || status >= ProposalStatus::VoteRevealFinished {
+            return Err(SureError::NotPossibleToFundIncentives.into());
+        }
+        Ok(())
+    }
+
+    /// the pool is released when the vote failed
+    /// or the vote results reject the claim
+    pub fn can_release_pool(&self, time: i64) -> Result<()> {
+        if !self.is_bound_to_pool() {
+            return Err(SureError::ProposalNotBoundToPool.into());
+        }
+        let status = self.get_status(time).unwrap();
+        if status != ProposalStatus::Failed && status < ProposalStatus::RewardCalculation {
+            return Err(SureError::NotPossibleToReleasePool.into());
+        }
+        // accepted claims release the pool when paid
+        if self.is_claim_accepted(time) {
+            return Err(SureError::ClaimIsAccepted.into());
+        }
+        Ok(())
+    }
+
+    pub fn can_reclaim_incentives(&self, time: i64) -> Result<()> {
+        if !self.has_incentives() {
+            return Err(SureError::IncentiveVaultNotInitialized.into());
+        }
+        let status = self.get_status(time).unwrap();
+        let nothing_distributed =
+            status == ProposalStatus::RewardPayout && self.distribution_sum == 0;
+        if status != ProposalStatus::Failed && !nothing_distributed {
+            return Err(SureError::NotPossibleToReclaimIncentives.into());
+        }
+        Ok(())
+    }
+
     /// can a vote be cancelled
     ///
     /// a vote can be cancelled when in voting period
@@ -679,6 +1127,7 @@ pub mod test_proposal_proto {
         pub protocol_fee_rate: u32,
 
         pub protocol_fees: u128,
+        pub collected_protocol_fees: u64,
         /// Scale parameter in exp(L)
         /// Q16.16
         pub scale_parameter: u32,
@@ -691,8 +1140,17 @@ pub mod test_proposal_proto {
 
         pub vote_factor_sum: u64,
         pub consensus: i64,
+        pub consensus_estimator: u8,
+        pub consensus_trim_rate: u32,
 
         pub distribution_sum: u128,
+        pub finalized_votes: u64,
+        pub reward_pool: u64,
+
+        pub incentive_mint: Pubkey,
+        pub incentive_vault: Pubkey,
+        pub incentive_sponsor: Pubkey,
+        pub incentive_pool: u64,
     }
 
     impl ProposalProto {
@@ -717,15 +1175,34 @@ pub mod test_proposal_proto {
                 vote_end_reveal_at: TEST_START_TIME + 86400 * 2,
                 earned_rewards: 0,
                 protocol_fees: 0,
+                collected_protocol_fees: 0,
                 scale_parameter: 0,
                 scale_parameter_calculated: false,
                 distribution_sum: 0,
+                finalized_votes: 0,
+                reward_pool: 0,
+                incentive_mint: Pubkey::default(),
+                incentive_vault: Pubkey::default(),
+                incentive_sponsor: Pubkey::default(),
+                incentive_pool: 0,
                 locked: false,
                 consensus: 0,
+                consensus_estimator: ConsensusEstimator::Mean.get_id(),
+                consensus_trim_rate: 0,
                 vote_factor_sum: 0,
             }
         }
 
+        pub fn set_consensus_estimator(
+            mut self,
+            estimator: ConsensusEstimator,
+            trim_rate: u32,
+        ) -> Self {
+            self.consensus_estimator = estimator.get_id();
+            self.consensus_trim_rate = trim_rate;
+            self
+        }
+
         pub fn set_scale_parameter(mut self, scale_parameter: f32) -> Self {
             self.scale_parameter = convert_f32_x16(scale_parameter) as u32;
             self
@@ -761,6 +1238,12 @@ pub mod test_proposal_proto {
                 name: self.name,
                 id: self.id,
                 description: self.description,
+                evidence: ProposalEvidence::default(),
+                pool: Pubkey::default(),
+                claim_bond: 0,
+                coverage_position: Pubkey::default(),
+                claim_amount: 0,
+                claim_paid: false,
                 proposer: Pubkey::default(),
                 proposed_result: self.proposed_result,
                 staked: self.staked,
@@ -779,12 +1262,21 @@ pub mod test_proposal_proto {
                 vote_end_reveal_at: self.vote_end_reveal_at,
                 earned_rewards: self.earned_rewards,
                 protocol_fees: self.protocol_fees,
+                collected_protocol_fees: self.collected_protocol_fees,
                 scale_parameter: self.scale_parameter,
                 scale_parameter_calculated: self.scale_parameter_calculated,
                 locked: self.locked,
                 consensus: self.consensus,
                 distribution_sum: self.distribution_sum,
                 vote_factor_sum: self.vote_factor_sum,
+                finalized_votes: self.finalized_votes,
+                reward_pool: self.reward_pool,
+                incentive_mint: self.incentive_mint,
+                incentive_vault: self.incentive_vault,
+                incentive_sponsor: self.incentive_sponsor,
+                incentive_pool: self.incentive_pool,
+                consensus_estimator: self.consensus_estimator,
+                consensus_trim_rate: self.consensus_trim_rate,
             }
         }
     }
@@ -807,21 +1299,35 @@ pub mod test_propose_vote {
         pub struct Test {
             name: String,
             votes: Vec<VoteAccount>,
+            vault_amount: u64,
             expected_result: ExpectedResult,
         }
-        let tests = [Test {
-            name: "Calculate_rewards. 1. vanilla".to_string(),
-            votes: [
-                vote_account_proto::VoteAccountProto::initialize()
-                    .set_vote_power(3_000_000, 6)
-                    .build(),
-                vote_account_proto::VoteAccountProto::initialize()
-                    .set_vote_power(4_000_000, 6)
-                    .build(),
-            ]
-            .to_vec(),
-            expected_result: ExpectedResult { reward: 0.007 },
-        }];
+        let tests = [
+            Test {
+                name: "Calculate_rewards. 1. vanilla".to_string(),
+                votes: [
+                    vote_account_proto::VoteAccountProto::initialize()
+                        .set_vote_power(3_000_000, 6)
+                        .build(),
+                    vote_account_proto::VoteAccountProto::initialize()
+                        .set_vote_power(4_000_000, 6)
+                        .build(),
+                ]
+                .to_vec(),
+                vault_amount: 1_070_000,
+                expected_result: ExpectedResult {
+                    reward: 1_007_000.0,
+                },
+            },
+            Test {
+                name: "Calculate_rewards. 2. no votes staked".to_string(),
+                votes: [].to_vec(),
+                vault_amount: 1_000_000,
+                expected_result: ExpectedResult {
+                    reward: 1_000_000.0,
+                },
+            },
+        ];
         for test in tests {
             let mut proposal = ProposalProto::initialize().build();
             let mut current_time = START_TIME;
@@ -834,7 +1340,7 @@ pub mod test_propose_vote {
             }
 
             // test calculations
-            let proposal_rewards = proposal.calculate_proposer_reward();
+            let proposal_rewards = proposal.calculate_proposer_reward(test.vault_amount);
             assert_eq!(
                 convert_q64_to_f64(proposal_rewards),
                 test.expected_result.reward,
@@ -844,6 +1350,465 @@ pub mod test_propose_vote {
         }
     }
 
+    #[test]
+    pub fn test_protocol_fees() {
+        pub struct ExpectedResult {
+            fees: u64,
+            collected_fees: u64,
+        }
+        pub struct Test {
+            name: String,
+            revealed_stakes: Vec<u64>,
+            expected_result: ExpectedResult,
+        }
+        let tests = [
+            Test {
+                name: "1. collect whole tokens".to_string(),
+                revealed_stakes: [1_000_000, 25, 25].to_vec(),
+                expected_result: ExpectedResult {
+                    fees: 20_001,
+                    collected_fees: 20_001,
+                },
+            },
+            Test {
+                name: "2. keep fractional fees for next payout".to_string(),
+                revealed_stakes: [75].to_vec(),
+                expected_result: ExpectedResult {
+                    fees: 1,
+                    collected_fees: 20_002,
+                },
+            },
+            Test {
+                name: "3. collect fractional fees".to_string(),
+                revealed_stakes: [25].to_vec(),
+                expected_result: ExpectedResult {
+                    fees: 1,
+                    collected_fees: 20_003,
+                },
+            },
+        ];
+
+        let mut proposal = ProposalProto::initialize().build();
+        for test in tests {
+            for stake in test.revealed_stakes {
+                proposal.update_protocol_fee(stake);
+            }
+            assert_eq!(
+                proposal.accrued_protocol_fees(),
+                test.expected_result.fees,
+                "{}: accrued protocol fees",
+                test.name
+            );
+            let fees = proposal.payout_accrued_protocol_fees().unwrap();
+            assert_eq!(fees, test.expected_result.fees, "{}: payout", test.name);
+            assert_eq!(
+                proposal.collected_protocol_fees, test.expected_result.collected_fees,
+                "{}: collected protocol fees",
+                test.name
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_calculate_id() {
+        let proposer = Pubkey::new_unique();
+        let evidence = ProposalEvidence {
+            uri: "https://arweave.net/report".to_string(),
+            content_hash: [1; 32],
+            affected_program: Pubkey::new_unique(),
+            start_slot: 100,
+            end_slot: 120,
+        };
+        let id = Proposal::calculate_id(&proposer, "hack", "exploit of pool", &evidence);
+        assert_eq!(
+            id,
+            Proposal::calculate_id(&proposer, "hack", "exploit of pool", &evidence),
+            "same content gives the same id"
+        );
+
+        let other_ids = [
+            Proposal::calculate_id(&Pubkey::new_unique(), "hack", "exploit of pool", &evidence),
+            Proposal::calculate_id(&proposer, "hac", "kexploit of pool", &evidence),
+            Proposal::calculate_id(
+                &proposer,
+                "hack",
+                "exploit of pool",
+                &ProposalEvidence {
+                    end_slot: 121,
+                    ..evidence.clone()
+                },
+            ),
+        ];
+        for other_id in other_ids {
+            assert_ne!(id, other_id, "different content gives a different id");
+        }
+
+        assert!(Proposal::validate_content("hack", "exploit of pool", &evidence).is_ok());
+        assert!(Proposal::validate_content(
+            &"a".repeat(MAX_PROPOSAL_NAME_LENGTH + 1),
+            "exploit of pool",
+            &evidence
+        )
+        .is_err());
+    }
+
+    #[test]
+    pub fn test_bind_pool() {
+        let smart_contract = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let coverage_position = Pubkey::new_unique();
+        let mut proposal = ProposalProto::initialize().build();
+        proposal.evidence.affected_program = smart_contract;
+
+        assert!(
+            proposal
+                .bind_pool(
+                    &pool,
+                    &Pubkey::new_unique(),
+                    &coverage_position,
+                    1_000,
+                    10,
+                    START_TIME
+                )
+                .is_err(),
+            "pool must insure the affected program"
+        );
+        assert!(
+            proposal
+                .bind_pool(
+                    &pool,
+                    &smart_contract,
+                    &coverage_position,
+                    1_000,
+                    10,
+                    proposal.vote_end_at + 1
+                )
+                .is_err(),
+            "cannot bind after the vote"
+        );
+        assert_eq!(
+            proposal
+                .bind_pool(
+                    &pool,
+                    &smart_contract,
+                    &coverage_position,
+                    0,
+                    10,
+                    START_TIME
+                )
+                .unwrap_err(),
+            SureError::InvalidClaimAmount.into(),
+            "claim amount has to be positive"
+        );
+        proposal
+            .bind_pool(
+                &pool,
+                &smart_contract,
+                &coverage_position,
+                1_000,
+                10,
+                START_TIME,
+            )
+            .unwrap();
+        assert_eq!(proposal.pool, pool);
+        assert_eq!(proposal.coverage_position, coverage_position);
+        assert_eq!(proposal.claim_amount, 1_000);
+        assert_eq!(proposal.claim_bond, 10);
+        assert!(
+            proposal
+                .bind_pool(
+                    &pool,
+                    &smart_contract,
+                    &coverage_position,
+                    1_000,
+                    10,
+                    START_TIME
+                )
+                .is_err(),
+            "can only bind once"
+        );
+
+        assert!(
+            proposal.can_release_pool(START_TIME).is_err(),
+            "pool is locked during the vote"
+        );
+        assert!(
+            proposal.can_release_pool(proposal.vote_end_at + 1).is_ok(),
+            "release pool when the vote failed"
+        );
+        assert!(
+            !proposal.is_claim_accepted(proposal.vote_end_at + 1),
+            "failed vote rejects the claim"
+        );
+        assert_eq!(
+            proposal.pay_claim(proposal.vote_end_at + 1).unwrap_err(),
+            SureError::ClaimNotAccepted.into(),
+            "failed vote pays nothing"
+        );
+    }
+
+    #[test]
+    pub fn test_pay_claim() {
+        pub struct Test {
+            name: String,
+            consensus: f64,
+            expected_payout: Result<u64>,
+        }
+        let tests = [
+            Test {
+                name: "1. pay out the full claim".to_string(),
+                consensus: 1.0,
+                expected_payout: Ok(1_000),
+            },
+            Test {
+                name: "2. pay out half of the claim".to_string(),
+                consensus: 0.5,
+                expected_payout: Ok(500),
+            },
+            Test {
+                name: "3. payout is capped at the claim".to_string(),
+                consensus: 1.5,
+                expected_payout: Ok(1_000),
+            },
+            Test {
+                name: "4. rejected claim".to_string(),
+                consensus: 0.4,
+                expected_payout: Err(SureError::ClaimNotAccepted.into()),
+            },
+        ];
+
+        for test in tests {
+            let mut proposal = ProposalProto::initialize()
+                .set_required_voted(1_000)
+                .set_votes(1_000)
+                .build();
+            proposal.pool = Pubkey::new_unique();
+            proposal.claim_amount = 1_000;
+            proposal.scale_parameter_calculated = true;
+            proposal.consensus = (test.consensus * (1_u64 << 32) as f64) as i64;
+            let time = proposal.vote_end_reveal_at + 1;
+            assert_eq!(
+                proposal.pay_claim(proposal.vote_end_at - 1).unwrap_err(),
+                SureError::ClaimNotAccepted.into(),
+                "{}: claim is pending during the vote",
+                test.name
+            );
+            assert_eq!(
+                proposal.pay_claim(time),
+                test.expected_payout,
+                "{}",
+                test.name
+            );
+            if test.expected_payout.is_ok() {
+                assert_eq!(
+                    proposal.pay_claim(time).unwrap_err(),
+                    SureError::ClaimAlreadyPaid.into(),
+                    "{}: claim is paid once",
+                    test.name
+                );
+                assert_eq!(
+                    proposal.can_release_pool(time).unwrap_err(),
+                    SureError::ClaimIsAccepted.into(),
+                    "{}: accepted claims are released when paid",
+                    test.name
+                );
+            } else {
+                assert!(proposal.can_release_pool(time).is_ok(), "{}", test.name);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_is_claim_accepted() {
+        pub struct Test {
+            name: String,
+            consensus: f64,
+            accepted: bool,
+        }
+        let tests = [
+            Test {
+                name: "1. pay out the full claim".to_string(),
+                consensus: 1.0,
+                accepted: true,
+            },
+            Test {
+                name: "2. pay out half of the claim".to_string(),
+                consensus: 0.5,
+                accepted: true,
+            },
+            Test {
+                name: "3. below the acceptance threshold".to_string(),
+                consensus: 0.4,
+                accepted: false,
+            },
+            Test {
+                name: "4. reject the claim".to_string(),
+                consensus: 0.0,
+                accepted: false,
+            },
+        ];
+
+        for test in tests {
+            let mut proposal = ProposalProto::initialize()
+                .set_required_voted(1_000)
+                .set_votes(1_000)
+                .build();
+            proposal.scale_parameter_calculated = true;
+            proposal.consensus = (test.consensus * (1_u64 << 32) as f64) as i64;
+            let time = proposal.vote_end_reveal_at + 1;
+            assert_eq!(
+                proposal.is_claim_accepted(time),
+                test.accepted,
+                "{}",
+                test.name
+            );
+            assert!(
+                !proposal.is_claim_accepted(proposal.vote_end_at - 1),
+                "{}: claim is pending during the vote",
+                test.name
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_incentives() {
+        pub struct Test {
+            name: String,
+            proposal: Proposal,
+            time: i64,
+            can_fund: bool,
+            can_reclaim: bool,
+        }
+        let proposal = ProposalProto::initialize().build();
+        let reveal_time = proposal.vote_end_at;
+        let tests = [
+            Test {
+                name: "1. fund while voting".to_string(),
+                proposal: ProposalProto::initialize().build(),
+                time: START_TIME,
+                can_fund: true,
+                can_reclaim: false,
+            },
+            Test {
+                name: "2. reclaim when vote failed".to_string(),
+                proposal: ProposalProto::initialize().build(),
+                time: reveal_time,
+                can_fund: false,
+                can_reclaim: true,
+            },
+            Test {
+                name: "3. fund while revealing".to_string(),
+                proposal: ProposalProto::initialize().set_in_reveal_state().build(),
+                time: reveal_time,
+                can_fund: true,
+                can_reclaim: false,
+            },
+            Test {
+                name: "4. reclaim if nothing is distributed".to_string(),
+                proposal: {
+                    let mut proposal = ProposalProto::initialize().set_in_reveal_state().build();
+                    proposal.scale_parameter_calculated = true;
+                    proposal
+                },
+                time: proposal.vote_end_reveal_at * 2,
+                can_fund: false,
+                can_reclaim: true,
+            },
+        ];
+
+        for test in tests {
+            let mut proposal = test.proposal;
+            assert!(
+                proposal.fund_incentives(10, START_TIME).is_err(),
+                "{}: fund without vault",
+                test.name
+            );
+            let proposer = proposal.proposer;
+            let pool_founder = Pubkey::new_unique();
+            assert!(
+                proposal
+                    .initialize_incentives(
+                        &Pubkey::new_unique(),
+                        &Pubkey::new_unique(),
+                        &Pubkey::new_unique(),
+                        None,
+                        START_TIME,
+                    )
+                    .is_err(),
+                "{}: only the proposer can sponsor the incentives",
+                test.name
+            );
+            assert!(
+                proposal
+                    .initialize_incentives(
+                        &Pubkey::new_unique(),
+                        &Pubkey::new_unique(),
+                        &pool_founder,
+                        Some(&pool_founder),
+                        START_TIME,
+                    )
+                    .is_err(),
+                "{}: the pool founder can only sponsor a bound proposal",
+                test.name
+            );
+            proposal
+                .initialize_incentives(
+                    &Pubkey::new_unique(),
+                    &Pubkey::new_unique(),
+                    &proposer,
+                    None,
+                    START_TIME,
+                )
+                .unwrap();
+            assert!(
+                proposal
+                    .initialize_incentives(
+                        &Pubkey::new_unique(),
+                        &Pubkey::new_unique(),
+                        &proposer,
+                        None,
+                        START_TIME
+                    )
+                    .is_err(),
+                "{}: vault can only be attached once",
+                test.name
+            );
+            proposal.fund_incentives(1_000, START_TIME).unwrap();
+            assert_eq!(
+                proposal.fund_incentives(10, test.time).is_ok(),
+                test.can_fund,
+                "{}: fund incentives",
+                test.name
+            );
+            let reclaimed = proposal.reclaim_incentives_at_time(test.time);
+            assert_eq!(
+                reclaimed.is_ok(),
+                test.can_reclaim,
+                "{}: reclaim incentives",
+                test.name
+            );
+            if test.can_reclaim {
+                assert_eq!(reclaimed.unwrap(), 1_000, "{}: reclaimed", test.name);
+                assert_eq!(proposal.incentive_pool, 0, "{}: pool empty", test.name);
+            }
+        }
+
+        // the founder of the bound pool can sponsor the incentives
+        let mut proposal = ProposalProto::initialize().build();
+        let pool_founder = Pubkey::new_unique();
+        proposal.pool = Pubkey::new_unique();
+        proposal
+            .initialize_incentives(
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &pool_founder,
+                Some(&pool_founder),
+                START_TIME,
+            )
+            .unwrap();
+        assert_eq!(proposal.incentive_sponsor, pool_founder);
+    }
+
     #[test]
     pub fn test_calculate_consensus() {
         pub struct Test {
@@ -881,6 +1846,75 @@ pub mod test_propose_vote {
         }
     }
 
+    /// A whale voting far from the other voters
+    /// only moves the consensus of the mean
+    #[test]
+    pub fn test_estimate_consensus() {
+        pub struct Test {
+            name: String,
+            estimator: ConsensusEstimator,
+            trim_rate: u32,
+            expected_consensus: f64,
+        }
+        let tests = [
+            Test {
+                name: "1. mean".to_string(),
+                estimator: ConsensusEstimator::Mean,
+                trim_rate: 0,
+                expected_consensus: 25.384615384,
+            },
+            Test {
+                name: "2. weighted median".to_string(),
+                estimator: ConsensusEstimator::WeightedMedian,
+                trim_rate: 0,
+                expected_consensus: 3.5,
+            },
+            Test {
+                name: "3. trimmed mean".to_string(),
+                estimator: ConsensusEstimator::TrimmedMean,
+                trim_rate: 4,
+                expected_consensus: 3.363636363,
+            },
+        ];
+
+        let mut votes: Vec<VoteAccount> = [2.0, 2.5, 3.0, 3.5, 4.0, 2.0, 2.5, 3.0, 3.5, 4.0]
+            .iter()
+            .map(|vote| {
+                vote_account_proto::VoteAccountProto::initialize()
+                    .set_vote_power(10_000_000, 6)
+                    .set_vote_raw(*vote)
+                    .build()
+            })
+            .collect();
+        votes.push(
+            vote_account_proto::VoteAccountProto::initialize()
+                .set_vote_power(30_000_000, 6)
+                .set_vote_raw(100.0)
+                .build(),
+        );
+
+        for test in tests {
+            let mut proposal = ProposalProto::initialize()
+                .set_consensus_estimator(test.estimator, test.trim_rate)
+                .build();
+            let mut vote_array = RevealedVoteArray::default();
+            for vote in votes.iter() {
+                proposal.update_running_sum_weighted_vote(*vote);
+                vote_array.reveal_vote(vote).unwrap();
+            }
+
+            proposal.update_scale_parameter(&vote_array).unwrap();
+            let consensus = convert_ix32_f64(proposal.consensus);
+            assert!(
+                (consensus - test.expected_consensus).abs() < 1e-6,
+                "{}: expected consensus {} got {}",
+                test.name,
+                test.expected_consensus,
+                consensus
+            );
+        }
+    }
+
     /// Calculate the vote reward received
     /// based on consensus and the vote
     #[test]
@@ -936,3 +1970,172 @@ pub mod test_propose_vote {
         }
     }
 }
+
+/// Invariant tests for the reward distribution
+///
+/// simulates random votes through the proposal lifecycle
+/// and checks that the payouts never exceed the vault
+#[cfg(test)]
+pub mod test_reward_distribution {
+    use std::cell::RefCell;
+
+    use crate::states::{test_proposal_proto::ProposalProto, vote_account_proto};
+
+    use super::*;
+    use crate::simulation::Rng;
+
+    #[test]
+    pub fn test_payouts_never_exceed_vault() {
+        const DECIMALS: u8 = 6;
+        const NUM_PROPOSALS: usize = 16;
+        const MAX_VOTES: u64 = 512;
+        let salt = "a23sw23";
+        let mut rng = Rng(0x5eed_5eed);
+
+        for round in 0..NUM_PROPOSALS {
+            let mut proposal = ProposalProto::initialize().set_required_voted(1).build();
+            proposal.stake_rate = rng.range(2, 200) as u32;
+            proposal.protocol_fee_rate = rng.range(2, 200) as u32;
+            proposal.consensus_estimator = rng.range(0, 3) as u8;
+            proposal.consensus_trim_rate = rng.range(3, 20) as u32;
+            let mut vault_amount = proposal.staked;
+            let incentive_vault = Pubkey::new_unique();
+            let proposer = proposal.proposer;
+            proposal
+                .initialize_incentives(
+                    &Pubkey::new_unique(),
+                    &incentive_vault,
+                    &proposer,
+                    None,
+                    proposal.vote_start_at,
+                )
+                .unwrap();
+            proposal
+                .fund_incentives(rng.range(0, 1_000_000_000), proposal.vote_start_at)
+                .unwrap();
+            let true_result = rng.range(0, 20) as i64 - 10;
+
+            // submit votes
+            let num_votes = rng.range(1, MAX_VOTES);
+            let mut votes = Vec::with_capacity(num_votes as usize);
+            let vote_time = proposal.vote_start_at;
+            for _ in 0..num_votes {
+                let vote_power = rng.range(1, 100) * 10_u64.pow(DECIMALS as u32);
+                // I32.32 vote scattered around the true result
+                let vote = ((true_result << 32) + rng.range(0, 8 << 32) as i64) - (4 << 32);
+                let vote_hash = vote_account_proto::hash_vote(vote, salt);
+                let mut vote_account = VoteAccount::default();
+                vote_account
+                    .initialize(
+                        proposal.stake_rate,
+                        0,
+                        &Pubkey::default(),
+                        &Pubkey::default(),
+                        &vote_hash,
+                        Pubkey::default(),
+                        vote_power,
+                        DECIMALS,
+                    )
+                    .unwrap();
+                vault_amount += vote_account.staked;
+                proposal
+                    .cast_vote_at_time(RefCell::new(vote_account).borrow_mut(), vote_time)
+                    .unwrap();
+                votes.push((vote_account, vote));
+            }
+
+            // reveal most of the votes
+            let reveal_time = proposal.vote_end_at;
+            let mut revealed_votes = RevealedVoteArray::default();
+            for (vote_account, vote) in votes.iter_mut() {
+                if rng.range(0, 10) < 8 {
+                    vote_account
+                        .reveal_vote(&proposal, salt, *vote, reveal_time)
+                        .unwrap();
+                    proposal.update_protocol_fee(vote_account.staked);
+                    proposal.update_running_sum_weighted_vote(*vote_account);
+                    revealed_votes.reveal_vote(vote_account).unwrap();
+                }
+            }
+
+            // finalize results and calculate vote factors
+            let finalize_time = proposal.vote_end_reveal_at;
+            proposal
+                .try_finalize_vote_after_reveal(&revealed_votes, vault_amount, finalize_time)
+                .unwrap();
+            for (vote_account, _) in votes.iter_mut() {
+                if vote_account.revealed_vote && rng.range(0, 10) < 9 {
+                    if vote_account.calculate_vote_factor(&proposal).is_ok() {
+                        proposal.update_distribution_sum(vote_account).unwrap();
+                    }
+                }
+            }
+
+            // payout
+            let payout_time = finalize_time + (proposal.vote_end_reveal_at - proposal.vote_end_at);
+            assert_eq!(
+                proposal.get_status(payout_time).unwrap(),
+                ProposalStatus::RewardPayout,
+                "{}: proposal is in payout",
+                round
+            );
+            let proposer_reward = proposal.payout_earned_rewards_at_time(payout_time).unwrap();
+            let protocol_fees = proposal.accrued_protocol_fees();
+            let mut payouts: u64 = 0;
+            let mut incentive_payouts: u64 = 0;
+            for (vote_account, _) in votes.iter_mut() {
+                if let Ok(reward) =
+                    vote_account.calculate_token_reward_at_time(&proposal, payout_time)
+                {
+                    payouts += reward;
+                }
+                if let Ok(reward) =
+                    vote_account.calculate_incentive_reward_at_time(&proposal, payout_time)
+                {
+                    incentive_payouts += reward;
+                }
+                // rewards can only be collected once
+                assert!(vote_account
+                    .calculate_token_reward_at_time(&proposal, payout_time)
+                    .is_err());
+                assert!(vote_account
+                    .calculate_incentive_reward_at_time(&proposal, payout_time)
+                    .is_err());
+            }
+            assert!(
+                incentive_payouts <= proposal.incentive_pool,
+                "{}: incentive payouts {} exceed incentive pool {}",
+                round,
+                incentive_payouts,
+                proposal.incentive_pool
+            );
+
+            assert!(
+                payouts + protocol_fees + proposer_reward <= vault_amount,
+                "{}: payouts {} + protocol fees {} + proposer reward {} exceed vault {}",
+                round,
+                payouts,
+                protocol_fees,
+                proposer_reward,
+                vault_amount
+            );
+            assert!(
+                payouts <= proposal.reward_pool,
+                "{}: payouts {} exceed reward pool {}",
+                round,
+                payouts,
+                proposal.reward_pool
+            );
+            // only rounding dust is left when every revealed vote is finalized
+            if proposal.locked {
+                assert!(
+                    proposal.reward_pool - payouts <= num_votes,
+                    "{}: reward pool {} leaves too much dust after payouts {}",
+                    round,
+                    proposal.reward_pool,
+                    payouts
+                );
+            }
+        }
+    }
+}
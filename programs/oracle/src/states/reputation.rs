@@ -0,0 +1,333 @@
+use std::ops::{Div, Mul};
+
+use anchor_lang::prelude::*;
+
+use crate::utils::{
+    SureError, MAX_REPUTATION_FACTOR_X16, MIN_REPUTATION_FACTOR_X16, MIN_REPUTATION_VOTES,
+    SURE_ORACLE_REPUTATION_SEED,
+};
+
+use super::{Proposal, VoteAccount};
+
+/// Voter reputation
+///
+/// persistent record of the voting history of a voter
+/// for a given config. Updated when the vote is submitted
+/// and when the vote is finalized
+#[account]
+#[derive(Default, Debug)]
+pub struct VoterReputation {
+    pub bump: u8,            //     1 byte
+    pub bump_array: [u8; 1], //     1 byte
+
+    pub config: Pubkey, //          32 bytes
+    pub owner: Pubkey,  //          32 bytes
+
+    /// number of proposals in the config when
+    /// the reputation was created
+    pub proposals_at_start: u64, // 8 bytes
+
+    /// number of submitted votes
+    pub votes: u64, //              8 bytes
+
+    /// number of revealed and finalized votes
+    pub finalized_votes: u64, //    8 bytes
+
+    /// sum of accuracy scores exp(-l*(x-X)^2)
+    /// Q32.32
+    pub accuracy_sum: u64, //       8 bytes
+
+    /// sum of absolute distances to consensus
+    /// Q64.32
+    pub consensus_distance_sum: u128, // 16 bytes
+}
+
+impl VoterReputation {
+    pub const SPACE: usize = 1 + 1 + 32 + 32 + 8 * 5 + 16;
+
+    pub fn seeds(&self) -> [&[u8]; 4] {
+        [
+            SURE_ORACLE_REPUTATION_SEED.as_bytes() as &[u8],
+            self.config.as_ref(),
+            self.owner.as_ref(),
+            self.bump_array.as_ref(),
+        ]
+    }
+
+    pub fn initialize(&mut self, bump: u8, config: &Pubkey, owner: &Pubkey, proposals: u64) {
+        self.bump = bump;
+        self.bump_array = [bump; 1];
+        self.config = *config;
+        self.owner = *owner;
+        self.proposals_at_start = proposals;
+        self.votes = 0;
+        self.finalized_votes = 0;
+        self.accuracy_sum = 0;
+        self.consensus_distance_sum = 0;
+    }
+
+    /// record a submitted vote
+    pub fn record_vote(&mut self) -> Result<()> {
+        self.votes = self.votes.checked_add(1).ok_or(SureError::OverflowU64)?;
+        Ok(())
+    }
+
+    /// Record a finalized vote
+    ///
+    /// the accuracy score exp(-l*(x-X)^2) is recovered from the
+    /// vote factor X = l*exp(-l*(x-X)^2) so that it is normalized
+    /// by the spread of the votes on the proposal
+    pub fn record_finalized_vote(
+        &mut self,
+        proposal: &Proposal,
+        vote_account: &VoteAccount,
+    ) -> Result<()> {
+        let accuracy = Self::calculate_accuracy(vote_account.vote_factor, proposal.scale_parameter);
+        let distance = proposal
+            .calculate_consensus_distance(vote_account)
+            .unsigned_abs();
+
+        self.finalized_votes = self
+            .finalized_votes
+            .checked_add(1)
+            .ok_or(SureError::OverflowU64)?;
+        self.accuracy_sum = self
+            .accuracy_sum
+            .checked_add(accuracy)
+            .ok_or(SureError::OverflowU64)?;
+        self.consensus_distance_sum = self
+            .consensus_distance_sum
+            .checked_add(distance as u128)
+            .ok_or(SureError::OverflowU128)?;
+        Ok(())
+    }
+
+    /// Calculate accuracy
+    ///
+    /// ### Arguments
+    /// * vote_factor: Q32.32
+    /// * scale_parameter: Q16.16
+    ///
+    /// ### Returns
+    /// * accuracy in [0,1]: Q32.32
+    pub fn calculate_accuracy(vote_factor: u64, scale_parameter: u32) -> u64 {
+        if scale_parameter == 0 {
+            return 0;
+        }
+        // Q32.32 << 16 / Q16.16 -> Q32.32
+        let accuracy = ((vote_factor as u128) << 16).div(scale_parameter as u128);
+        accuracy.min(1 << 32) as u64
+    }
+
+    /// average accuracy of the finalized votes
+    /// Q32.32
+    pub fn average_accuracy(&self) -> u64 {
+        if self.finalized_votes == 0 {
+            return 0;
+        }
+        self.accuracy_sum / self.finalized_votes
+    }
+
+    /// average distance to consensus of the finalized votes
+    /// Q32.32
+    pub fn average_consensus_distance(&self) -> u64 {
+        if self.finalized_votes == 0 {
+            return 0;
+        }
+        (self.consensus_distance_sum / self.finalized_votes as u128) as u64
+    }
+
+    /// fraction of the submitted votes that were revealed and finalized
+    /// Q32.32
+    pub fn reveal_rate(&self) -> u64 {
+        if self.votes == 0 {
+            return 0;
+        }
+        ((self.finalized_votes.min(self.votes) as u128) << 32).div(self.votes as u128) as u64
+    }
+
+    /// fraction of the proposals since the reputation was
+    /// created that the voter voted on
+    /// Q32.32
+    pub fn participation_rate(&self, proposals: u64) -> u64 {
+        let eligible_proposals = proposals.saturating_sub(self.proposals_at_start);
+        if eligible_proposals == 0 {
+            return 0;
+        }
+        ((self.votes.min(eligible_proposals) as u128) << 32).div(eligible_proposals as u128) as u64
+    }
+
+    /// Calculate the reputation factor
+    ///
+    /// f = f_min + (f_max - f_min) * accuracy * reveal rate * participation rate
+    /// voters with too short history get a neutral factor
+    ///
+    /// ### Arguments
+    /// * proposals: number of proposals in the config
+    ///
+    /// ### Returns
+    /// * reputation factor: Q16.16
+    pub fn calculate_reputation_factor(&self, proposals: u64) -> u32 {
+        if self.finalized_votes < MIN_REPUTATION_VOTES {
+            return 1 << 16;
+        }
+        // Q32.32 x Q32.32 -> Q64.64 >> 32 -> Q32.32
+        let score = (self.average_accuracy() as u128).mul(self.reveal_rate() as u128) >> 32;
+        // Q32.32 x Q32.32 -> Q64.64 >> 48 -> Q16.16
+        let score = score.mul(self.participation_rate(proposals) as u128) >> 48;
+        let range = (MAX_REPUTATION_FACTOR_X16 - MIN_REPUTATION_FACTOR_X16) as u128;
+        // Q16.16 x Q16.16 -> Q32.32 >> 16 -> Q16.16
+        MIN_REPUTATION_FACTOR_X16 + (range.mul(score) >> 16) as u32
+    }
+
+    /// Apply the reputation factor to the vote power
+    ///
+    /// ### Arguments
+    /// * vote_power: in tokens
+    /// * proposals: number of proposals in the config
+    pub fn apply_reputation_factor(&self, vote_power: u64, proposals: u64) -> Result<u64> {
+        let vote_power =
+            (vote_power as u128).mul(self.calculate_reputation_factor(proposals) as u128) >> 16;
+        u64::try_from(vote_power).map_err(|_| SureError::OverflowU64.into())
+    }
+
+    /// Load the optional voter reputation
+    ///
+    /// the reputation is passed as the first remaining account
+    /// and is only required if the config applies the reputation
+    /// factor to the vote power
+    ///
+    /// ### Arguments
+    /// * remaining_accounts: remaining accounts of the instruction
+    /// * config: config the reputation belongs to
+    /// * voter: owner of the reputation
+    /// * required: fail if the reputation is not passed
+    pub fn load_optional<'info>(
+        remaining_accounts: &[AccountInfo<'info>],
+        config: &Pubkey,
+        voter: &Pubkey,
+        required: bool,
+    ) -> Result<Option<Account<'info, VoterReputation>>> {
+        let reputation_info = match remaining_accounts.first() {
+            Some(reputation_info) => reputation_info,
+            None if required => return Err(SureError::MissingVoterReputation.into()),
+            None => return Ok(None),
+        };
+        let reputation = Account::<VoterReputation>::try_from(reputation_info)?;
+        if reputation.config != *config || reputation.owner != *voter {
+            return Err(SureError::InvalidVoterReputation.into());
+        }
+        Ok(Some(reputation))
+    }
+}
+
+#[cfg(test)]
+pub mod test_reputation {
+    use super::*;
+
+    #[test]
+    pub fn test_reputation_factor() {
+        pub struct Test {
+            name: String,
+            votes: u64,
+            finalized_votes: u64,
+            /// average accuracy as fraction
+            accuracy: f64,
+            proposals: u64,
+            expected_factor: f64,
+        }
+        let tests = [
+            Test {
+                name: "1. new voter gets a neutral factor".to_string(),
+                votes: 2,
+                finalized_votes: 2,
+                accuracy: 0.0,
+                proposals: 2,
+                expected_factor: 1.0,
+            },
+            Test {
+                name: "2. accurate voter that always reveals".to_string(),
+                votes: 10,
+                finalized_votes: 10,
+                accuracy: 1.0,
+                proposals: 10,
+                expected_factor: 1.5,
+            },
+            Test {
+                name: "3. inaccurate voter".to_string(),
+                votes: 10,
+                finalized_votes: 10,
+                accuracy: 0.0,
+                proposals: 10,
+                expected_factor: 0.5,
+            },
+            Test {
+                name: "4. accurate voter that reveals half of the votes".to_string(),
+                votes: 20,
+                finalized_votes: 10,
+                accuracy: 1.0,
+                proposals: 20,
+                expected_factor: 1.0,
+            },
+            Test {
+                name: "5. accurate voter that votes on half of the proposals".to_string(),
+                votes: 10,
+                finalized_votes: 10,
+                accuracy: 1.0,
+                proposals: 20,
+                expected_factor: 1.0,
+            },
+        ];
+
+        for test in tests {
+            let reputation = VoterReputation {
+                votes: test.votes,
+                finalized_votes: test.finalized_votes,
+                accuracy_sum: ((test.accuracy * (1u64 << 32) as f64) as u64) * test.finalized_votes,
+                ..Default::default()
+            };
+            let factor =
+                reputation.calculate_reputation_factor(test.proposals) as f64 / (1 << 16) as f64;
+            assert!(
+                (factor - test.expected_factor).abs() < 0.001,
+                "{}: factor {} expected {}",
+                test.name,
+                factor,
+                test.expected_factor
+            );
+            assert!(factor >= 0.5 && factor <= 1.5, "{}: bounded", test.name);
+            assert_eq!(
+                reputation
+                    .apply_reputation_factor(1_000_000, test.proposals)
+                    .unwrap(),
+                (1_000_000 as f64 * factor) as u64,
+                "{}: vote power",
+                test.name
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_rates() {
+        let reputation = VoterReputation {
+            proposals_at_start: 10,
+            votes: 4,
+            finalized_votes: 3,
+            ..Default::default()
+        };
+        assert_eq!(reputation.reveal_rate(), (3 << 32) / 4);
+        assert_eq!(reputation.participation_rate(18), 1 << 31);
+        assert_eq!(reputation.participation_rate(10), 0);
+
+        // the accuracy is bounded by 1
+        assert_eq!(
+            VoterReputation::calculate_accuracy(3 << 32, 2 << 16),
+            1 << 32
+        );
+        assert_eq!(
+            VoterReputation::calculate_accuracy(1 << 32, 2 << 16),
+            1 << 31
+        );
+        assert_eq!(VoterReputation::calculate_accuracy(1 << 32, 0), 0);
+    }
+}
@@ -1,14 +1,16 @@
 use crate::{
     factory::calculate_stake,
-    utils::{convert_x32_to_u64, SureError, SURE_ORACLE_VOTE_SEED, VOTE_STAKE_RATE},
+    utils::{uint::U256, SureError, SURE_ORACLE_VOTE_SEED, VOTE_STAKE_RATE},
 };
 
 use super::{Config, Proposal, ProposalStatus};
 use anchor_lang::{prelude::*, solana_program::pubkey};
+use locked_voter::{Escrow, LockerParams};
 use std::ops::{Add, BitAnd, BitOr, Div, Mul, Shl, Shr, Sub};
 
 use hex_literal::hex;
 use sha3::{Digest, Keccak256FullCore, Sha3_256, Sha3_256Core};
+use sure_common::fixed::I32x32;
 pub const MINT_FACTOR: u32 = 1_000;
 
 pub struct VoteAccountUpdate {
@@ -45,8 +47,16 @@ pub struct VoteAccount {
     // Q32.0 - assume rounded
     pub vote_power: u32, //         8  bytes
 
+    /// tokens locked in the escrow when the vote
+    /// was submitted. Caps later vote power updates
+    pub escrow_amount: u64, //      8 bytes
+
     pub revealed_vote: bool, //     1 bytes
 
+    pub vote_factor_calculated: bool, // 1 bytes
+
+    pub incentive_collected: bool, //  1 bytes
+
     pub locked: bool, //            1 bytes
 }
 
@@ -65,14 +75,17 @@ impl Default for VoteAccount {
             vote_factor: 0,
             earned_rewards: 0,
             vote_power: 0,
+            escrow_amount: 0,
             revealed_vote: false,
+            vote_factor_calculated: false,
+            incentive_collected: false,
             locked: false,
         }
     }
 }
 
 impl VoteAccount {
-    pub const SPACE: usize = 1 + 1 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
+    pub const SPACE: usize = 1 + 1 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 1;
 
     pub fn seeds(&self) -> [&[u8]; 4] {
         [
@@ -83,6 +96,30 @@ impl VoteAccount {
         ]
     }
 
+    /// Vote power snapshot
+    ///
+    /// calculates the vote power of the escrow as of the start
+    /// of the vote. Every lock resets the start of the escrow so
+    /// an escrow started after the proposal went live might have
+    /// been topped up to inflate the vote power and is rejected.
+    ///
+    /// ### Arguments
+    /// * escrow: the voter escrow
+    /// * locker_params: parameters of the locker of the escrow
+    /// * vote_start_at: start of the vote
+    pub fn calculate_vote_power_snapshot(
+        escrow: &Escrow,
+        locker_params: &LockerParams,
+        vote_start_at: i64,
+    ) -> Result<u64> {
+        if escrow.escrow_started_at > vote_start_at {
+            return Err(SureError::EscrowLockedAfterVoteStart.into());
+        }
+        escrow
+            .voting_power_at_time(locker_params, vote_start_at)
+            .ok_or(SureError::InvalidVotePower.into())
+    }
+
     pub fn initialize(
         &mut self,
         stake_rate: u32,
@@ -136,6 +173,72 @@ impl VoteAccount {
         vote_power_proto << 32
     }
 
+    /// Vote power cap
+    ///
+    /// the vote power can follow an extended escrow lock but
+    /// tokens added to the escrow after the vote was submitted
+    /// are not counted. The cap is measured at the start of the
+    /// vote like the snapshot of the submitted vote
+    ///
+    /// ### Arguments
+    /// * escrow: the voter escrow
+    /// * locker_params: parameters of the locker of the escrow
+    /// * vote_start_at: start of the vote
+    pub fn calculate_vote_power_cap(
+        &self,
+        escrow: &Escrow,
+        locker_params: &LockerParams,
+        vote_start_at: i64,
+    ) -> Result<u64> {
+        let mut escrow = escrow.clone();
+        escrow.amount = escrow.amount.min(self.escrow_amount);
+        escrow
+            .voting_power_at_time(locker_params, vote_start_at)
+            .ok_or(SureError::InvalidVotePower.into())
+    }
+
+    /// Update vote power
+    ///
+    /// top up or decrease the vote power in the voting period.
+    /// The stake follows the vote power.
+    ///
+    /// ### Arguments
+    /// * proposal: proposal voted on
+    /// * vote_power: new vote power in tokens
+    /// * vote_power_cap: max vote power in tokens
+    /// * decimals: decimals of the vote power
+    /// * time: current time
+    ///
+    /// ### Returns
+    /// * stake change and direction
+    pub fn update_vote_power_at_time(
+        &mut self,
+        proposal: &Proposal,
+        vote_power: u64,
+        vote_power_cap: u64,
+        decimals: u8,
+        time: i64,
+    ) -> Result<VoteAccountUpdate> {
+        if !(proposal.get_status(time).unwrap() == ProposalStatus::Voting) {
+            return Err(SureError::VotingPeriodEnded.into());
+        }
+        if vote_power > vote_power_cap {
+            return Err(SureError::VotePowerExceedsEscrow.into());
+        }
+        let vote_power = Self::calculate_vote_power_x32_from_tokens(vote_power, decimals as u32)?;
+        if vote_power == 0 {
+            return Err(SureError::InvalidVotePower.into());
+        }
+        let staked = calculate_stake((vote_power as u64) << 32, decimals, proposal.stake_rate);
+        let previous_stake = self.staked;
+        self.vote_power = vote_power;
+        self.staked = staked;
+        Ok(VoteAccountUpdate {
+            stake_change: staked.abs_diff(previous_stake),
+            increase_stake: staked >= previous_stake,
+        })
+    }
+
     pub fn update_vote_at_time(
         &mut self,
         proposal: &Proposal,
@@ -185,34 +288,59 @@ impl VoteAccount {
     /// Calculate the vote factor
     ///
     /// when votes are revealed the vote factor can be calculated
-    /// calculates and sets V = l*exp(-l*x)
+    /// calculates and sets X = l*exp(-l*x)
     pub fn calculate_vote_factor(&mut self, proposal: &Proposal) -> Result<u64> {
+        if self.vote_factor_calculated {
+            return Err(SureError::FailedToFinalizeVote.into());
+        }
         let vote_factor = proposal.calculate_vote_factor(&self)?;
         self.vote_factor = vote_factor;
+        self.vote_factor_calculated = true;
         Ok(vote_factor)
     }
 
+    /// Calculate the reward weight
+    ///
+    /// F = V * X where
+    /// V is the vote_power
+    /// X is the vote factor
+    ///
+    /// ### Returns
+    /// - F: Q64.32
+    pub fn calculate_reward_weight(&self) -> u128 {
+        // Q32.0 x Q32.32 -> Q64.32
+        (self.vote_power as u128).mul(self.vote_factor as u128)
+    }
+
     /// Calculate expected reward
     /// Upon an ended vote the voters should
     /// get rewarded or slashed
     ///
     /// Reward is
-    /// V * X where
-    /// V is the vote_power
-    /// X is the exponential result
+    /// R * F / sum F where
+    /// R is the reward pool
+    /// F is the reward weight of the vote
     ///
     /// ### Returns
-    /// - mint reward in Q32.32
+    /// - reward in tokens
     pub fn calculate_token_reward_at_time(
         &mut self,
         proposal: &Proposal,
-        mint_decimals: u8,
         time: i64,
     ) -> Result<u64> {
+        if self.locked {
+            return Err(SureError::NotPossibleToCollectVoterReward.into());
+        }
         let status = proposal.get_status(time).unwrap();
-        if self.revealed_vote && self.vote_factor > 0 && status == ProposalStatus::RewardCalculation
+        if self.revealed_vote
+            && self.vote_factor_calculated
+            && status == ProposalStatus::RewardPayout
         {
-            self.calculate_token_reward_(self.vote_factor, mint_decimals)
+            let reward =
+                self.calculate_token_reward_(proposal.reward_pool, proposal.distribution_sum)?;
+            self.earned_rewards = reward;
+            self.locked = true;
+            Ok(reward)
         } else if status == ProposalStatus::Failed {
             return self.cancel_vote();
         } else {
@@ -220,42 +348,68 @@ impl VoteAccount {
         }
     }
 
-    /// helper for the calculate_token_rewards method
-    pub fn calculate_token_reward_(
-        &self,
-        exponential_value: u64,
-        mint_decimals: u8,
+    /// Calculate the incentive reward
+    ///
+    /// the incentive pool is distributed by the same
+    /// reward weights as the voter rewards
+    ///
+    /// ### Returns
+    /// - reward in incentive tokens
+    pub fn calculate_incentive_reward_at_time(
+        &mut self,
+        proposal: &Proposal,
+        time: i64,
     ) -> Result<u64> {
+        if !proposal.has_incentives() {
+            return Err(SureError::IncentiveVaultNotInitialized.into());
+        }
+        if self.incentive_collected {
+            return Err(SureError::IncentiveAlreadyCollected.into());
+        }
+        let status = proposal.get_status(time).unwrap();
+        if self.revealed_vote
+            && self.vote_factor_calculated
+            && status == ProposalStatus::RewardPayout
+        {
+            let reward =
+                self.calculate_token_reward_(proposal.incentive_pool, proposal.distribution_sum)?;
+            self.incentive_collected = true;
+            Ok(reward)
+        } else {
+            return Err(SureError::NotPossibleToCalculateVoteReward.into());
+        }
+    }
+
+    /// helper for the calculate_token_rewards method
+    ///
+    /// ### Arguments
+    /// * reward_pool: tokens to be distributed among the voters
+    /// * distribution_sum: sum F of all the finalized votes. Q64.32
+    pub fn calculate_token_reward_(&self, reward_pool: u64, distribution_sum: u128) -> Result<u64> {
         if self.revealed_vote {
-            // Q32.0 x Q32.32 -> Q64.32
-            let reward_x64 = (self.vote_power as u128).mul(exponential_value as u128);
-            if reward_x64 > u64::MAX as u128 {
-                return Err(SureError::OverflowU64.into());
+            if distribution_sum == 0 {
+                return Ok(0);
             }
-            let reward_x32 = reward_x64 as u64;
-
-            // convert to token mint
-            let reward_10 = convert_x32_to_u64(reward_x32, mint_decimals);
-            Ok(reward_10)
+            // R x F / sum F <= R
+            let reward = U256::from(reward_pool)
+                .mul(U256::from(self.calculate_reward_weight()))
+                .div(U256::from(distribution_sum));
+            Ok(reward.as_u64())
         } else {
             return Err(SureError::VoteNotRevealed.into());
         }
     }
 
     /// Calculate the weigted vote
+    ///
+    /// ### Returns
+    /// * vote_power x vote: I32.32
     pub fn calculate_weighted_vote(self) -> Result<i64> {
-        // Q32.32 x Q32.32 -> Q64.32
-        let weigted_vote_abs = (self.vote_power as u64).mul(self.vote.abs() as u64) as u128;
-        // convert Q64.32 -> Q64.0
-        let weigted_vote_abs_x32 = (weigted_vote_abs >> 32) as u64;
-        if weigted_vote_abs_x32 > i64::MAX as u64 {
-            return Err(SureError::OverflowU64.into());
-        }
-        if self.vote > 0 {
-            return Ok(weigted_vote_abs as i64);
-        } else {
-            return Ok(-(weigted_vote_abs as i64));
-        }
+        // Q32.0 x I32.32 -> I32.32
+        let weighted_vote = I32x32::from_raw(self.vote)
+            .checked_mul_int(self.vote_power as u64)
+            .ok_or(SureError::OverflowU64)?;
+        Ok(weighted_vote.raw())
     }
 
     //
@@ -296,8 +450,14 @@ pub mod vote_account_proto {
         // how many votes put on the vote_hash
         pub vote_power: u32, // 8  bytes
 
+        pub escrow_amount: u64,
+
         pub revealed_vote: bool, // 1 bytes
 
+        pub vote_factor_calculated: bool,
+
+        pub incentive_collected: bool,
+
         pub locked: bool,
     }
     impl VoteAccountProto {
@@ -310,7 +470,10 @@ pub mod vote_account_proto {
                 earned_rewards: 0,
                 staked: 0,
                 vote_power: 0,
+                escrow_amount: 0,
                 revealed_vote: false,
+                vote_factor_calculated: false,
+                incentive_collected: false,
                 locked: false,
             }
         }
@@ -337,6 +500,12 @@ pub mod vote_account_proto {
             self
         }
 
+        pub fn set_vote_factor(mut self, vote_factor: f32) -> Self {
+            self.vote_factor = convert_f32_i64(vote_factor) as u64;
+            self.vote_factor_calculated = true;
+            self
+        }
+
         pub fn build(self) -> VoteAccount {
             VoteAccount {
                 bump: self.bump,
@@ -348,7 +517,10 @@ pub mod vote_account_proto {
                 staked: self.staked,
                 earned_rewards: self.earned_rewards,
                 vote_power: self.vote_power,
+                escrow_amount: self.escrow_amount,
                 revealed_vote: self.revealed_vote,
+                vote_factor_calculated: self.vote_factor_calculated,
+                incentive_collected: self.incentive_collected,
                 owner: Pubkey::default(),
                 proposal: Pubkey::default(),
                 locked: self.locked,
@@ -359,6 +531,8 @@ pub mod vote_account_proto {
 
 #[cfg(test)]
 pub mod test_vote {
+    use std::cell::RefCell;
+
     use proposal::test_proposal_proto::{self, ProposalProto};
 
     use crate::{states::proposal, utils::convert_f32_i64};
@@ -607,49 +781,56 @@ pub mod test_vote {
             vote: i64,
             vote_power: u64,
             decimals: u8,
-            vote_updated: i64,
             salt_true: String,
-            salt_provided: String,
-            start_time: i64,
             proposal: ProposalProto,
-            vote_account: VoteAccount,
-            exponential_value: f32,
+            vote_factor: f32,
+            reward_pool: u64,
+            /// sum F, Q64.32
+            distribution_sum: Option<u128>,
             expected_value: ExpectedValue,
         }
-        let mut vote_account = VoteAccount::default();
 
         let tests = [
             Test {
-                name: "1. ".to_string(),
+                name: "1. share the reward pool with other votes".to_string(),
                 vote: 400,
-                vote_updated: 450,
                 vote_power: 3_000_000,
                 decimals: 6,
                 salt_true: "a23sw23".to_string(),
-                salt_provided: "a23sw23".to_string(),
-                start_time: START_TIME,
                 proposal: test_proposal_proto::ProposalProto::initialize(),
-                vote_account: VoteAccount::default(),
-                exponential_value: 1.2,
-                expected_value: ExpectedValue { reward: 3_600_000 },
+                vote_factor: 1.2,
+                reward_pool: 10_000_000,
+                distribution_sum: Some(6 << 32),
+                expected_value: ExpectedValue { reward: 6_000_000 },
             },
             Test {
-                name: "2. ".to_string(),
+                name: "2. only vote takes the whole reward pool".to_string(),
                 vote: 400,
-                vote_updated: 450,
                 vote_power: 3_000_000,
                 decimals: 6,
                 salt_true: "a23sw23".to_string(),
-                salt_provided: "a23sw23".to_string(),
-                start_time: START_TIME,
                 proposal: test_proposal_proto::ProposalProto::initialize(),
-                vote_account: VoteAccount::default(),
-                exponential_value: 0.98989898989898,
-                expected_value: ExpectedValue { reward: 2_969_696 },
+                vote_factor: 0.98989898989898,
+                reward_pool: 10_000_000,
+                distribution_sum: None,
+                expected_value: ExpectedValue { reward: 10_000_000 },
+            },
+            Test {
+                name: "3. empty distribution".to_string(),
+                vote: 400,
+                vote_power: 3_000_000,
+                decimals: 6,
+                salt_true: "a23sw23".to_string(),
+                proposal: test_proposal_proto::ProposalProto::initialize(),
+                vote_factor: 0.0,
+                reward_pool: 10_000_000,
+                distribution_sum: Some(0),
+                expected_value: ExpectedValue { reward: 0 },
             },
         ];
 
         for test in tests {
+            let mut vote_account = VoteAccount::default();
             let vote_hash = vote_account_proto::hash_vote(test.vote, &test.salt_true);
             vote_account
                 .initialize(
@@ -668,17 +849,205 @@ pub mod test_vote {
             vote_account
                 .reveal_vote(&proposal, &test.salt_true, test.vote, reveal_time)
                 .unwrap();
+            vote_account.vote_factor = convert_f32_i64(test.vote_factor) as u64;
+            let distribution_sum = test
+                .distribution_sum
+                .unwrap_or(vote_account.calculate_reward_weight());
             let reward = vote_account
-                .calculate_token_reward_(
-                    convert_f32_i64(test.exponential_value) as u64,
-                    test.decimals,
-                )
+                .calculate_token_reward_(test.reward_pool, distribution_sum)
                 .unwrap();
             assert_eq!(
                 reward, test.expected_value.reward,
-                "{} |  equal reward. exponential value: {}, decimals: {}",
-                test.name, test.exponential_value, test.decimals
+                "{} |  equal reward. vote factor: {}, reward pool: {}",
+                test.name, test.vote_factor, test.reward_pool
             );
         }
     }
+
+    #[test]
+    pub fn test_vote_power_snapshot() {
+        pub struct Test {
+            name: String,
+            escrow_started_at: i64,
+            expected_error: Option<SureError>,
+        }
+        let locker_params = LockerParams {
+            max_stake_vote_multiplier: 1,
+            max_stake_duration: 365 * 86400,
+            ..Default::default()
+        };
+        let tests = [
+            Test {
+                name: "1. escrow locked before the vote".to_string(),
+                escrow_started_at: START_TIME - 86400,
+                expected_error: None,
+            },
+            Test {
+                name: "2. escrow locked at the start of the vote".to_string(),
+                escrow_started_at: START_TIME,
+                expected_error: None,
+            },
+            Test {
+                name: "3. escrow topped up after the start of the vote".to_string(),
+                escrow_started_at: START_TIME + 1,
+                expected_error: Some(SureError::EscrowLockedAfterVoteStart),
+            },
+        ];
+
+        for test in tests {
+            let escrow = Escrow {
+                amount: 3_000_000,
+                escrow_started_at: test.escrow_started_at,
+                escrow_ends_at: START_TIME + 2 * 365 * 86400,
+                ..Default::default()
+            };
+            let res =
+                VoteAccount::calculate_vote_power_snapshot(&escrow, &locker_params, START_TIME);
+            match test.expected_error {
+                Some(err) => {
+                    let expected_err: anchor_lang::error::Error = err.into();
+                    assert_eq!(
+                        res.unwrap_err().to_string(),
+                        expected_err.to_string(),
+                        "{}",
+                        test.name
+                    );
+                }
+                None => assert_eq!(res.unwrap(), escrow.amount, "{}", test.name),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_update_vote_power() {
+        pub struct ExpectedValue {
+            vote_power: u32,
+            stake_change: u64,
+            increase_stake: bool,
+            votes: u64,
+        }
+        pub struct Test {
+            name: String,
+            vote_power: u64,
+            escrow_amount: u64,
+            time: i64,
+            expected_value: Option<ExpectedValue>,
+        }
+        const DECIMALS: u8 = 6;
+        let locker_params = LockerParams {
+            max_stake_vote_multiplier: 1,
+            max_stake_duration: 365 * 86400,
+            ..Default::default()
+        };
+        let tests = [
+            Test {
+                name: "1. top up after extending the lock".to_string(),
+                vote_power: 3_000_000,
+                escrow_amount: 3_000_000,
+                time: START_TIME,
+                expected_value: Some(ExpectedValue {
+                    vote_power: 3,
+                    stake_change: 100_000,
+                    increase_stake: true,
+                    votes: 3,
+                }),
+            },
+            Test {
+                name: "2. decrease vote power".to_string(),
+                vote_power: 1_000_000,
+                escrow_amount: 3_000_000,
+                time: START_TIME,
+                expected_value: Some(ExpectedValue {
+                    vote_power: 1,
+                    stake_change: 100_000,
+                    increase_stake: false,
+                    votes: 1,
+                }),
+            },
+            Test {
+                name: "3. tokens locked after the vote are not counted".to_string(),
+                vote_power: 5_000_000,
+                escrow_amount: 5_000_000,
+                time: START_TIME,
+                expected_value: None,
+            },
+            Test {
+                name: "4. voting period is over".to_string(),
+                vote_power: 3_000_000,
+                escrow_amount: 3_000_000,
+                time: START_TIME + 86400 + 1,
+                expected_value: None,
+            },
+        ];
+
+        for test in tests {
+            let mut proposal = ProposalProto::initialize().build();
+            let vote_hash = vote_account_proto::hash_vote(400, "a23sw23");
+            let mut vote_account = VoteAccount::default();
+            vote_account
+                .initialize(
+                    proposal.stake_rate,
+                    0,
+                    &Pubkey::default(),
+                    &Pubkey::default(),
+                    &vote_hash,
+                    Pubkey::default(),
+                    2_000_000,
+                    DECIMALS,
+                )
+                .unwrap();
+            // vote power of the escrow decayed before the vote
+            vote_account.escrow_amount = 3_000_000;
+            proposal
+                .cast_vote_at_time(RefCell::new(vote_account).borrow_mut(), START_TIME)
+                .unwrap();
+
+            let escrow = Escrow {
+                amount: test.escrow_amount,
+                escrow_started_at: START_TIME + 1,
+                escrow_ends_at: START_TIME + 2 * 365 * 86400,
+                ..Default::default()
+            };
+            let vote_power_cap = vote_account
+                .calculate_vote_power_cap(&escrow, &locker_params, proposal.vote_start_at)
+                .unwrap();
+            proposal
+                .cancel_vote_at_time(RefCell::new(vote_account).borrow_mut(), test.time)
+                .unwrap();
+            let res = vote_account.update_vote_power_at_time(
+                &proposal,
+                test.vote_power,
+                vote_power_cap,
+                DECIMALS,
+                test.time,
+            );
+            proposal
+                .cast_vote_at_time(RefCell::new(vote_account).borrow_mut(), test.time)
+                .unwrap();
+
+            match test.expected_value {
+                Some(expected) => {
+                    let update = res.unwrap();
+                    let vote_power = vote_account.vote_power;
+                    assert_eq!(vote_power, expected.vote_power, "{}: vote power", test.name);
+                    assert_eq!(
+                        update.stake_change, expected.stake_change,
+                        "{}: stake change",
+                        test.name
+                    );
+                    assert_eq!(
+                        update.increase_stake, expected.increase_stake,
+                        "{}: increase stake",
+                        test.name
+                    );
+                    assert_eq!(proposal.votes, expected.votes, "{}: votes", test.name);
+                }
+                None => {
+                    assert!(res.is_err(), "{}: expected error", test.name);
+                    let vote_power = vote_account.vote_power;
+                    assert_eq!(vote_power, 2, "{}: vote power unchanged", test.name);
+                }
+            }
+        }
+    }
 }
@@ -7,9 +7,11 @@ use anchor_lang::prelude::*;
 
 use crate::utils::SureError;
 
-use super::VoteAccount;
-pub const NUM_VOTES_IN_ARRAY_USIZE: usize = 1024;
-pub const NUM_VOTES_IN_ARRAY: u16 = 1024;
+use super::{estimate_consensus, ConsensusEstimator, VoteAccount};
+/// accounts created through a CPI are limited to 10KiB
+/// so the array is sized to fit within that limit
+pub const NUM_VOTES_IN_ARRAY_USIZE: usize = 768;
+pub const NUM_VOTES_IN_ARRAY: u16 = 768;
 
 #[account(zero_copy)]
 #[repr(packed)]
@@ -19,6 +21,8 @@ pub struct RevealedVoteArray {
     pub proposal: Pubkey, // 32
     /// Q32.32
     pub weighted_votes: [i64; NUM_VOTES_IN_ARRAY_USIZE], // 8*
+    /// vote power of each revealed vote
+    pub vote_powers: [u32; NUM_VOTES_IN_ARRAY_USIZE], // 4*
     pub last_index: i16,
 }
 
@@ -29,13 +33,15 @@ impl Default for RevealedVoteArray {
             bump: 0,
             proposal: Pubkey::default(),
             weighted_votes: [0; NUM_VOTES_IN_ARRAY_USIZE],
+            vote_powers: [0; NUM_VOTES_IN_ARRAY_USIZE],
             last_index: -1,
         }
     }
 }
 
 impl RevealedVoteArray {
-    pub const SPACE: usize = 1 + 32 + 8 * NUM_VOTES_IN_ARRAY_USIZE + 2;
+    pub const SPACE: usize =
+        1 + 32 + 8 * NUM_VOTES_IN_ARRAY_USIZE + 4 * NUM_VOTES_IN_ARRAY_USIZE + 2;
 
     pub fn initialize(&mut self, proposal: Pubkey, bump: u8) {
         self.bump = bump;
@@ -47,7 +53,7 @@ impl RevealedVoteArray {
     /// NOTE: tested
     pub fn reveal_vote(&mut self, vote: &VoteAccount) -> Result<()> {
         let next_index = self.last_index + 1;
-        if next_index.abs() as u16 > NUM_VOTES_IN_ARRAY {
+        if next_index.abs() as u16 >= NUM_VOTES_IN_ARRAY {
             return Err(SureError::FullRevealList.into());
         }
         if !vote.revealed_vote {
@@ -56,32 +62,66 @@ impl RevealedVoteArray {
 
         let weighted_vote = vote.calculate_weighted_vote()?;
         self.weighted_votes[next_index as usize] = weighted_vote;
+        self.vote_powers[next_index as usize] = vote.vote_power;
         self.last_index = next_index;
         Ok(())
     }
 
+    /// Get the revealed votes and their weights
+    ///
+    /// ### Returns
+    /// * votes: (vote: I32.32, weight: Q64.0)
+    pub fn get_votes(&self) -> Vec<(i64, u64)> {
+        let mut votes = Vec::new();
+        let mut i = 0;
+        while i <= self.last_index {
+            let vote_power = self.vote_powers[i as usize];
+            if vote_power > 0 {
+                let vote = self.weighted_votes[i as usize] / vote_power as i64;
+                votes.push((vote, vote_power as u64));
+            }
+            i += 1;
+        }
+        votes
+    }
+
+    /// Calculate the consensus of the revealed votes
+    ///
+    /// ### Arguments
+    /// * estimator: the consensus estimator
+    /// * trim_rate: 1/x of the vote power trimmed from each tail
+    ///
+    /// ### Returns
+    /// * consensus: I32.32
+    pub fn calculate_consensus(&self, estimator: ConsensusEstimator, trim_rate: u32) -> i64 {
+        let mut votes = self.get_votes();
+        estimate_consensus(estimator, &mut votes, trim_rate)
+    }
+
     /// Calculate
-    /// sum_i^n (w_i - x^bar)^2
+    /// sum_i^n w_i x (v_i - x^bar)^2
     /// NOTE: tested
     ///
     /// ### Arguments
     /// * consensus: i32.32
     ///
     /// ### Returns
-    /// * sum_i^n (w_i - x^bar)^2:  Q32.32
-    pub fn calculate_sum_squared_difference(&self, consensus: i64) -> u64 {
-        let mut ssd = 0;
-        let mut i = 0;
-        while i <= self.last_index + 1 {
-            let sub = self.weighted_votes[i as usize].sub(consensus) as i128;
-            // i32.32 x i32.32 -> i64.64
-            let sub_squared_ix64 = sub.mul(sub) as u128;
-            // Q64.64 >> 32 -> Q32.32
-            let sub_squared_ix32 = (sub_squared_ix64 >> 32) as u64;
-            ssd += sub_squared_ix32;
-            i += 1;
+    /// * sum_i^n w_i x (v_i - x^bar)^2:  Q32.32
+    pub fn calculate_sum_squared_difference(&self, consensus: i64) -> Result<u64> {
+        let mut ssd: u128 = 0;
+        for (vote, weight) in self.get_votes() {
+            let difference = (vote as i128).sub(consensus as i128).unsigned_abs();
+            // i32.32 x i32.32 -> Q64.64 >> 32 -> Q64.32
+            let difference_squared = difference
+                .checked_mul(difference)
+                .ok_or(SureError::OverflowU128)?
+                >> 32;
+            ssd = difference_squared
+                .checked_mul(weight as u128)
+                .and_then(|weighted_difference| ssd.checked_add(weighted_difference))
+                .ok_or(SureError::OverflowU128)?;
         }
-        ssd
+        u64::try_from(ssd).map_err(|_| SureError::OverflowU64.into())
     }
 }
 
@@ -89,8 +129,12 @@ impl RevealedVoteArray {
 pub mod test_revealed_vote_array {
     use anchor_lang::prelude::Pubkey;
 
-    use crate::states::{
-        vote_account_proto, RevealedVoteArray, VoteAccount, NUM_VOTES_IN_ARRAY_USIZE,
+    use crate::{
+        states::{
+            vote_account_proto, RevealedVoteArray, VoteAccount, NUM_VOTES_IN_ARRAY,
+            NUM_VOTES_IN_ARRAY_USIZE,
+        },
+        utils::SureError,
     };
 
     /// Happy path
@@ -144,7 +188,7 @@ pub mod test_revealed_vote_array {
                 consensus: (300 as i64) << 32,
                 expected_result: ExpectedResult::initialize()
                     .set_weighted_vote(0 as usize, 900)
-                    .set_sum_squared_difference(360000)
+                    .set_sum_squared_difference(1159641169380000)
                     .set_last_index(0),
             },
             Test {
@@ -164,7 +208,7 @@ pub mod test_revealed_vote_array {
                 expected_result: ExpectedResult::initialize()
                     .set_weighted_vote(0 as usize, 900)
                     .set_weighted_vote(1, 1_600)
-                    .set_sum_squared_difference(1839898)
+                    .set_sum_squared_difference(2705829394980000)
                     .set_last_index(1),
             },
         ];
@@ -190,13 +234,61 @@ pub mod test_revealed_vote_array {
             );
 
             assert_eq!(
-                vote_array.calculate_sum_squared_difference(test.consensus),
+                vote_array
+                    .calculate_sum_squared_difference(test.consensus)
+                    .unwrap(),
                 test.expected_result.sum_squared_difference,
                 "{}: test_expected_ssd",
                 test.name
             );
         }
     }
+
+    /// The sum of squared differences fails
+    /// instead of wrapping around
+    #[test]
+    pub fn test_sum_squared_difference_overflow() {
+        let mut vote_array = RevealedVoteArray::default();
+        vote_array.weighted_votes[0] = i64::MAX;
+        vote_array.vote_powers[0] = 1;
+        vote_array.last_index = 0;
+
+        let err = vote_array
+            .calculate_sum_squared_difference(i64::MIN)
+            .unwrap_err();
+        let expected_err: anchor_lang::error::Error = SureError::OverflowU64.into();
+        assert_eq!(err.to_string(), expected_err.to_string());
+        assert_eq!(
+            vote_array
+                .calculate_sum_squared_difference(i64::MAX)
+                .unwrap(),
+            0
+        );
+    }
+
+    /// The last slot of the array can be filled
+    /// but no vote can be revealed beyond it
+    #[test]
+    pub fn test_reveal_vote_at_capacity() {
+        let vote = vote_account_proto::VoteAccountProto::initialize()
+            .set_vote_power(3_000_000, 6)
+            .set_vote(300)
+            .build();
+        let mut vote_array = RevealedVoteArray::default();
+        vote_array.last_index = NUM_VOTES_IN_ARRAY as i16 - 2;
+
+        vote_array.reveal_vote(&vote).unwrap();
+        let last_index = vote_array.last_index;
+        assert_eq!(last_index, NUM_VOTES_IN_ARRAY as i16 - 1);
+        let vote_powers = vote_array.vote_powers;
+        assert_eq!(vote_powers[NUM_VOTES_IN_ARRAY_USIZE - 1], 3);
+
+        let err = vote_array.reveal_vote(&vote).unwrap_err();
+        let expected_err: anchor_lang::error::Error = SureError::FullRevealList.into();
+        assert_eq!(err.to_string(), expected_err.to_string());
+        let last_index = vote_array.last_index;
+        assert_eq!(last_index, NUM_VOTES_IN_ARRAY as i16 - 1);
+    }
 }
 
 pub struct VoteArrayList<'info> {
@@ -6,12 +6,27 @@ pub const SURE_ORACLE_VOTE_SEED: &str = "sure-oracle-vote";
 pub const SURE_ORACLE_REVEAL_ARRAY_SEED: &str = "sure-oracle-reveal-array";
 pub const SURE_ORACLE_PROPOSAL_VAULT_SEED: &str = "sure-oracle-propsal-vault";
 pub const SURE_ORACLE_CONFIG_SEED: &str = "sure-oracle-config";
+pub const SURE_ORACLE_TREASURY_SEED: &str = "sure-oracle-treasury";
+pub const SURE_ORACLE_INCENTIVE_VAULT_SEED: &str = "sure-oracle-incentive-vault";
+pub const SURE_ORACLE_REPUTATION_SEED: &str = "sure-oracle-reputation";
+pub const SURE_ORACLE_CLAIM_BOND_SEED: &str = "sure-oracle-claim-bond";
 // voting fraction required in 1/x
 pub const VOTING_FRACTION_REQUIRED: u64 = 10;
 pub const MIN_VOTING_LENGTH_SECONDS: i64 = 60 * 60;
+pub const MAX_VOTING_LENGTH_SECONDS: i64 = 14 * SECONDS_PER_DAY as i64;
 pub const MIN_PROPOSAL_STAKE: u64 = 1_000_000;
 
 // vote stake calculation as 1/x
 pub const VOTE_STAKE_RATE: u32 = 100; // 1%
 
+// bounds of the reputation factor. Q16.16
+pub const MIN_REPUTATION_FACTOR_X16: u32 = 1 << 15; // 0.5
+pub const MAX_REPUTATION_FACTOR_X16: u32 = 3 << 15; // 1.5
+// finalized votes before the reputation factor applies
+pub const MIN_REPUTATION_VOTES: u64 = 5;
+
+// share of the claim the consensus has to pay out
+// for the claim to be accepted. I32.32
+pub const CLAIM_ACCEPTANCE_THRESHOLD_X32: i64 = 1 << 31; // 0.5
+
 pub const TEST_START_TIME: i64 = 1660681219;
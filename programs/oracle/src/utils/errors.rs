@@ -37,6 +37,9 @@ pub enum SureError {
     #[msg("U32 overflow")]
     OverflowU32,
 
+    #[msg("U128 overflow")]
+    OverflowU128,
+
     #[msg("Could not calculate the vote reward at this time")]
     NotPossibleToCalculateVoteReward,
 
@@ -82,6 +85,99 @@ pub enum SureError {
 
     #[msg("Unauthorized signer")]
     UnauthorizedSigner,
+
+    #[msg("Remaining accounts must be pairs of proposal and proposal vault")]
+    InvalidRemainingAccounts,
+
+    #[msg("Proposal does not belong to the config")]
+    InvalidProposalConfig,
+
+    #[msg("Proposal vault does not match the proposal")]
+    InvalidProposalVault,
+
+    #[msg("Incentive vault is already attached to the proposal")]
+    IncentiveVaultAlreadyInitialized,
+
+    #[msg("Proposal has no incentive vault")]
+    IncentiveVaultNotInitialized,
+
+    #[msg("Cannot fund the incentives at this time")]
+    NotPossibleToFundIncentives,
+
+    #[msg("Cannot reclaim the incentives at this time")]
+    NotPossibleToReclaimIncentives,
+
+    #[msg("Incentive reward is already collected")]
+    IncentiveAlreadyCollected,
+
+    #[msg("Signer is not the sponsor of the incentives")]
+    InvalidIncentiveSponsor,
+
+    #[msg("Locker does not match the config locker")]
+    InvalidLocker,
+
+    #[msg("Escrow was locked after the vote started")]
+    EscrowLockedAfterVoteStart,
+
+    #[msg("Could not calculate the vote power of the escrow")]
+    InvalidVotePower,
+
+    #[msg("Vote power exceeds the vote power of the escrow")]
+    VotePowerExceedsEscrow,
+
+    #[msg("Voter reputation is required when the reputation is enabled")]
+    MissingVoterReputation,
+
+    #[msg("Voter reputation does not belong to the voter")]
+    InvalidVoterReputation,
+
+    #[msg("Proposal name, description or evidence is too long")]
+    ProposalContentTooLong,
+
+    #[msg("Evidence slot range ends before it starts")]
+    InvalidEvidenceSlotRange,
+
+    #[msg("Proposal is already bound to a pool")]
+    ProposalAlreadyBoundToPool,
+
+    #[msg("Proposal is not bound to a pool")]
+    ProposalNotBoundToPool,
+
+    #[msg("Pool does not insure the program affected by the proposal")]
+    InvalidPoolForProposal,
+
+    #[msg("Cannot release the pool before the vote is over")]
+    NotPossibleToReleasePool,
+
+    #[msg("Claim amount is zero or larger than the coverage")]
+    InvalidClaimAmount,
+
+    #[msg("Claim is not accepted")]
+    ClaimNotAccepted,
+
+    #[msg("Claim is already paid")]
+    ClaimAlreadyPaid,
+
+    #[msg("Accepted claims release the pool when they are paid")]
+    ClaimIsAccepted,
+
+    #[msg("Token pool does not hold the coverage of the claim")]
+    InvalidTokenPool,
+
+    #[msg("Claimant is not the owner of the coverage position")]
+    InvalidClaimant,
+
+    #[msg("Pool is paused")]
+    PoolIsPaused,
+
+    #[msg("Pool is already locked for a claim")]
+    PoolIsLocked,
+
+    #[msg("Unknown consensus estimator")]
+    InvalidConsensusEstimator,
+
+    #[msg("Trim rate must be larger than 2")]
+    InvalidConsensusTrimRate,
 }
 
 impl From<TryFromIntError> for SureError {
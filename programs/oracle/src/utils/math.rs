@@ -15,7 +15,12 @@ use crate::{
 pub fn calculate_exp(x: u64, negative: bool) -> u128 {
     println!("calculate_exp");
     // Q32.32 x Q16.16 -> Q48.48 >> 32 => Q16.16
-    let exponent = (x.mul(DIV_LN2_X64) >> 32) as u32;
+    let exponent_x16 = (x as u128).mul(DIV_LN2_X64 as u128) >> 32;
+    if exponent_x16 > u32::MAX as u128 {
+        // exp(-x) vanishes and exp(x) saturates
+        return if negative { 0 } else { u128::MAX };
+    }
+    let exponent = exponent_x16 as u32;
     println!("exponent: {}", exponent);
     let exponent_f = convert_q16_f16(exponent);
     println!("exponentf: {}", exponent_f);
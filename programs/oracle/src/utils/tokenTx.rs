@@ -58,6 +58,28 @@ pub fn withdraw_from_vault<'info>(
     )
 }
 
+/// Withdraw from the protocol treasury
+pub fn withdraw_from_treasury<'info>(
+    authority: &Account<'info, Config>,
+    treasury: &Account<'info, TokenAccount>,
+    destination_account: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: treasury.to_account_info(),
+                to: destination_account.to_account_info(),
+                authority: authority.to_account_info(),
+            },
+            &[&authority.seeds()],
+        ),
+        amount,
+    )
+}
+
 // /// Burn the NFT and close the nft token account
 // pub fn burn_liquidity_position_nft<'info>(
 //     authority: &Account<'info, Pool>,
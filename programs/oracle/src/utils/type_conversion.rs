@@ -1,8 +1,8 @@
-use std::ops::{BitAnd, BitOr, Div, Mul};
+use std::ops::Mul;
 
-/// Convert from Q64.64 -> Q64
-///
-/// TODO: move to common lib
+use sure_common::fixed::{I32x32, Q16x16, Q32x32, Q64x64};
+
+/// Convert from Q64.64 -> Q64 as 10^decimals
 pub fn convert_x64_to_u64(reward: u128, decimals: u8) -> u64 {
     let reward_f = convert_q64_to_f64(reward);
     reward_f.mul(10_u64.pow(decimals as u32) as f64) as u64
@@ -10,77 +10,57 @@ pub fn convert_x64_to_u64(reward: u128, decimals: u8) -> u64 {
 
 /// Convert Q32.32 -> Q64 as 10^decimals
 pub fn convert_x32_to_u64(reward: u64, decimals: u8) -> u64 {
-    let reward_f = convert_ix32_f64(reward as i64);
+    let reward_f = Q32x32::from_raw(reward).to_f64();
     reward_f.mul(10_u64.pow(decimals as u32) as f64) as u64
 }
 
 /// Q64.64 -> f64
 pub fn convert_q64_to_f64(num: u128) -> f64 {
-    let fractional_part = num.bitand(u64::MAX as u128) as u128;
-    let integer_part = num.bitor(u64::MAX as u128) as u128;
-
-    let fraction = (fractional_part as f64)
-        .div(2_u64.pow(32) as f64)
-        .div(2_u64.pow(32) as f64);
-    let integer = integer_part >> 64;
-    (integer as f64) + fraction
+    Q64x64::from_raw(num).to_f64()
 }
 
-/// Q64.64 -> f64
+/// Q64.64 -> Q64.0
 pub fn convert_q64_to_u64(num: u128) -> u64 {
-    // Q64.64 _> Q0.64
-    let fractional_part = num.bitand(u64::MAX as u128) as u64;
-    // Q64.64 -> Q64.0
-    let integer_part = num.bitor(u64::MAX as u128) as u128;
-
-    let fraction = (fractional_part).div(2_u64.pow(32)).div(2_u64.pow(32));
-    let integer = (integer_part >> 64) as u64;
-    (integer) + (fraction)
+    Q64x64::from_raw(num).floor()
 }
 
 /// Convert a f64 to Q64.64
+///
+/// saturates like an `as` cast if the float
+/// is out of range
 pub fn convert_f64_q64(float: f64) -> u128 {
-    float
-        .mul(2_u64.pow(32) as f64) // Q32.32
-        .mul(2_u64.pow(32) as f64) //Q32.64
-        .round() as u128
+    match Q64x64::from_f64(float) {
+        Some(num) => num.raw(),
+        None if float > 0.0 => u128::MAX,
+        None => 0,
+    }
 }
 
+/// Convert a f32 to I32.32
 pub fn convert_f32_i64(float: f32) -> i64 {
-    float
-        .mul(2_u64.pow(32) as f32) // Q32.32
-        .round() as i64
+    match I32x32::from_f64(float as f64) {
+        Some(num) => num.raw(),
+        None if float > 0.0 => i64::MAX,
+        None if float < 0.0 => i64::MIN,
+        None => 0,
+    }
 }
 
+/// Convert a f32 to I16.16
 pub fn convert_f32_x16(float: f32) -> i32 {
     float
-        .mul(2_u32.pow(16) as f32) // Q32.32
+        .mul(2_u32.pow(16) as f32) // Q16.16
         .round() as i32
 }
 
+/// Q16.16 -> f32
 pub fn convert_q16_f16(num: u32) -> f32 {
-    let fractional_part = num.bitand(u16::MAX as u32) as u32;
-    let integer_part = num.bitor(u16::MAX as u32) as u32;
-
-    let fraction = (fractional_part as f32).div(2_u32.pow(16) as f32);
-    let integer = integer_part >> 16;
-    (integer as f32) + fraction
+    Q16x16::from_raw(num).to_f64() as f32
 }
 
+/// I32.32 -> f64
 pub fn convert_ix32_f64(num: i64) -> f64 {
-    let positive = num > 0;
-    let num_q32 = num.abs() as u64;
-
-    let fractional_part = num_q32.bitand(u32::MAX as u64) as u64;
-    let integer_part = num_q32.bitor(u32::MAX as u64) as u64;
-
-    let fraction = (fractional_part as f64).div(2_u64.pow(32) as f64);
-    let integer = integer_part >> 32;
-    if positive {
-        (integer as f64) + fraction
-    } else {
-        -((integer as f64) + fraction)
-    }
+    I32x32::from_raw(num).to_f64()
 }
 
 #[cfg(test)]
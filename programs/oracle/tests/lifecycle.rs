@@ -0,0 +1,357 @@
+#![cfg(feature = "test-bpf")]
+
+mod utils;
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use oracle::{
+    factory::calculate_stake,
+    instructions::{
+        CollectProposerRewardEvent, CollectVoteRewardEvent, FinalizedVoteResultsEvent,
+        ProposeVoteEvent, RevealedVoteEvent, SubmittedVoteEvent,
+    },
+    states::{Proposal, ProposalStatus},
+    utils::{convert_f32_i64, convert_ix32_f64, SureError},
+};
+use sha3::{Digest, Sha3_256};
+use solana_sdk::{
+    instruction::InstructionError, signature::Signer, transaction::TransactionError,
+    transport::TransportError,
+};
+use utils::{get_events, OracleTest, ProposalKeys, DECIMALS};
+
+const TOKENS: u64 = 10_u64.pow(DECIMALS as u32);
+
+fn hash_vote(vote: i64, salt: &str) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(format!("{}{}", vote, salt).as_bytes());
+    hasher.finalize().into()
+}
+
+fn assert_sure_error(result: Result<(), TransportError>, expected_error: SureError) {
+    let expected_code = expected_error as u32 + ERROR_CODE_OFFSET;
+    match result.unwrap_err() {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => assert_eq!(code, expected_code),
+        err => panic!("expected error code {} got {:?}", expected_code, err),
+    }
+}
+
+/// Run a proposal from the proposal to the payout
+/// and check that no tokens are lost on the way
+#[tokio::test]
+async fn test_oracle_lifecycle() {
+    let escrow_amounts = [10_000 * TOKENS, 10_000 * TOKENS, 20_000 * TOKENS];
+    let votes = [10.0, 10.5, 12.0];
+    let stake = 10 * TOKENS;
+    let test = OracleTest::start(&escrow_amounts, 1_000 * TOKENS).await;
+
+    // initialize config, quorum is reached when everyone voted
+    let mut instructions = vec![
+        test.initialize_config_ix(),
+        test.update_required_votes_ix(40_000),
+    ];
+    for voter in &test.voters {
+        instructions.push(test.initialize_voter_reputation_ix(voter));
+    }
+    let voter_keypairs: Vec<_> = test.voters.iter().map(|v| &v.keypair).collect();
+    test.process(&instructions, &voter_keypairs).await.unwrap();
+
+    // propose vote
+    let proposal = ProposalKeys::new(
+        &test.proposer.keypair.pubkey(),
+        "Hack of protocol",
+        "An exploit drained the lending pool",
+    );
+    test.process(
+        &[test.propose_vote_ix(&proposal, stake)],
+        &[&test.proposer.keypair],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        test.get_token_balance(&proposal.proposal_vault).await,
+        stake
+    );
+    assert_eq!(
+        test.get_token_balance(&test.proposer.token_account).await,
+        990 * TOKENS
+    );
+    let propose_event = get_events::<ProposeVoteEvent>()
+        .into_iter()
+        .find(|event| event.proposer == test.proposer.keypair.pubkey())
+        .expect("propose vote event");
+    assert_eq!(propose_event.stake, stake);
+
+    let proposal_state: Proposal = test.get_anchor_account(&proposal.proposal).await;
+    let vote_end_at = proposal_state.vote_end_at;
+    let vote_end_reveal_at = proposal_state.vote_end_reveal_at;
+    let reward_payout_at = vote_end_reveal_at + (vote_end_reveal_at - vote_end_at);
+
+    // submit votes
+    let mut vote_stakes = Vec::new();
+    for (idx, voter) in test.voters.iter().enumerate() {
+        let vote_hash = hash_vote(convert_f32_i64(votes[idx]), "salt");
+        test.process(
+            &[test.submit_vote_ix(&proposal, voter, vote_hash, true)],
+            &[&voter.keypair],
+        )
+        .await
+        .unwrap();
+        let vote_power = escrow_amounts[idx] / TOKENS;
+        vote_stakes.push(calculate_stake(vote_power << 32, DECIMALS, 100));
+        assert_eq!(
+            test.get_token_balance(&voter.token_account).await,
+            1_000 * TOKENS - vote_stakes[idx]
+        );
+    }
+    let vault_amount = stake + vote_stakes.iter().sum::<u64>();
+    assert_eq!(
+        test.get_token_balance(&proposal.proposal_vault).await,
+        vault_amount
+    );
+    let submitted_events: Vec<_> = get_events::<SubmittedVoteEvent>()
+        .into_iter()
+        .filter(|event| event.proposal == proposal.proposal)
+        .collect();
+    assert_eq!(submitted_events.len(), 3);
+    assert_eq!(
+        submitted_events
+            .iter()
+            .map(|event| event.vote_power)
+            .sum::<u64>(),
+        escrow_amounts.iter().sum::<u64>()
+    );
+
+    // votes cannot be revealed before the voting period is over.
+    // A different salt keeps the transaction from being a duplicate
+    // of the reveal below, which would return this cached error
+    assert_sure_error(
+        test.process(
+            &[test.reveal_vote_ix(
+                &proposal,
+                &test.voters[0],
+                "early salt",
+                convert_f32_i64(votes[0]),
+            )],
+            &[&test.voters[0].keypair],
+        )
+        .await,
+        SureError::RevealPeriodNotActive,
+    );
+
+    // reveal votes
+    test.warp_to_timestamp(vote_end_at).await;
+    for (idx, voter) in test.voters.iter().enumerate() {
+        test.process(
+            &[test.reveal_vote_ix(&proposal, voter, "salt", convert_f32_i64(votes[idx]))],
+            &[&voter.keypair],
+        )
+        .await
+        .unwrap();
+    }
+    let revealed_events: Vec<_> = get_events::<RevealedVoteEvent>()
+        .into_iter()
+        .filter(|event| event.proposal == proposal.proposal)
+        .collect();
+    assert_eq!(revealed_events.len(), 3);
+
+    // finalize vote results
+    test.warp_to_timestamp(vote_end_reveal_at).await;
+    test.process(&[test.finalize_vote_results_ix(&proposal)], &[])
+        .await
+        .unwrap();
+    let results_event = get_events::<FinalizedVoteResultsEvent>()
+        .into_iter()
+        .find(|event| event.proposal == proposal.proposal)
+        .expect("finalized vote results event");
+    assert_eq!(
+        results_event.status,
+        ProposalStatus::RewardCalculation as u8
+    );
+    // weighted mean of the votes
+    assert!((convert_ix32_f64(results_event.consensus) - 11.125).abs() < 1e-6);
+
+    // finalize the votes and collect the proposer reward
+    for voter in &test.voters {
+        test.process(
+            &[test.finalize_vote_ix(&proposal, voter, true)],
+            &[&voter.keypair],
+        )
+        .await
+        .unwrap();
+    }
+    test.process(
+        &[test.collect_proposer_reward_ix(&proposal)],
+        &[&test.proposer.keypair],
+    )
+    .await
+    .unwrap();
+    let proposer_reward = get_events::<CollectProposerRewardEvent>()
+        .into_iter()
+        .find(|event| event.proposal == proposal.proposal)
+        .expect("collect proposer reward event")
+        .reward;
+    assert!(proposer_reward >= stake);
+    assert_eq!(
+        test.get_token_balance(&test.proposer.token_account).await,
+        990 * TOKENS + proposer_reward
+    );
+
+    // collect vote rewards
+    test.warp_to_timestamp(reward_payout_at).await;
+    let mut vote_rewards = Vec::new();
+    for voter in &test.voters {
+        test.process(
+            &[test.collect_vote_reward_ix(&proposal, voter)],
+            &[&voter.keypair],
+        )
+        .await
+        .unwrap();
+        let vote_account = proposal.vote_account(voter);
+        let reward = get_events::<CollectVoteRewardEvent>()
+            .into_iter()
+            .find(|event| event.vote == vote_account)
+            .expect("collect vote reward event")
+            .reward;
+        assert!(reward > 0);
+        vote_rewards.push(reward);
+    }
+    for (idx, voter) in test.voters.iter().enumerate() {
+        assert_eq!(
+            test.get_token_balance(&voter.token_account).await,
+            1_000 * TOKENS - vote_stakes[idx] + vote_rewards[idx]
+        );
+    }
+    // the vote closest to the consensus earns the most per vote power
+    let reward_per_vote_power: Vec<_> = vote_rewards
+        .iter()
+        .zip(escrow_amounts.iter())
+        .map(|(reward, amount)| *reward as f64 / *amount as f64)
+        .collect();
+    assert!(reward_per_vote_power[1] > reward_per_vote_power[0]);
+
+    // only the protocol fees and rounding dust are left in the vault
+    let proposal_state: Proposal = test.get_anchor_account(&proposal.proposal).await;
+    let vault_remainder = test.get_token_balance(&proposal.proposal_vault).await;
+    let paid_out = proposer_reward + vote_rewards.iter().sum::<u64>();
+    assert_eq!(vault_remainder, vault_amount - paid_out);
+    assert!(vault_remainder >= proposal_state.accrued_protocol_fees());
+    assert!(vault_remainder - proposal_state.accrued_protocol_fees() <= votes.len() as u64);
+}
+
+/// A proposal that does not reach quorum refunds
+/// the voters and keeps the stake of the proposer.
+/// The voter has no reputation since it is disabled
+#[tokio::test]
+async fn test_oracle_failed_quorum() {
+    let escrow_amounts = [10_000 * TOKENS];
+    let stake = 10 * TOKENS;
+    let test = OracleTest::start(&escrow_amounts, 1_000 * TOKENS).await;
+
+    let instructions = vec![
+        test.initialize_config_ix(),
+        test.update_required_votes_ix(40_000),
+    ];
+    test.process(&instructions, &[])
+        .await
+        .unwrap();
+
+    let proposal = ProposalKeys::new(
+        &test.proposer.keypair.pubkey(),
+        "Oracle failure",
+        "The price feed reported a stale price",
+    );
+    test.process(
+        &[test.propose_vote_ix(&proposal, stake)],
+        &[&test.proposer.keypair],
+    )
+    .await
+    .unwrap();
+    let voter = &test.voters[0];
+    test.process(
+        &[test.submit_vote_ix(
+            &proposal,
+            voter,
+            hash_vote(convert_f32_i64(1.0), "salt"),
+            false,
+        )],
+        &[&voter.keypair],
+    )
+    .await
+    .unwrap();
+
+    // the proposal fails when the voting period is over
+    let proposal_state: Proposal = test.get_anchor_account(&proposal.proposal).await;
+    test.warp_to_timestamp(proposal_state.vote_end_at).await;
+    test.process(
+        &[test.collect_vote_reward_ix(&proposal, voter)],
+        &[&voter.keypair],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        test.get_token_balance(&voter.token_account).await,
+        1_000 * TOKENS
+    );
+    assert_sure_error(
+        test.process(
+            &[test.collect_proposer_reward_ix(&proposal)],
+            &[&test.proposer.keypair],
+        )
+        .await,
+        SureError::NotPossibleToCollectProposerReward,
+    );
+    assert_eq!(
+        test.get_token_balance(&proposal.proposal_vault).await,
+        stake
+    );
+}
+
+/// The voter reputation is only required
+/// if the reputation factor is enabled
+#[tokio::test]
+async fn test_oracle_reputation_required_when_enabled() {
+    let escrow_amounts = [10_000 * TOKENS];
+    let test = OracleTest::start(&escrow_amounts, 1_000 * TOKENS).await;
+
+    let instructions = vec![
+        test.initialize_config_ix(),
+        test.update_reputation_enabled_ix(true),
+    ];
+    test.process(&instructions, &[]).await.unwrap();
+
+    let proposal = ProposalKeys::new(
+        &test.proposer.keypair.pubkey(),
+        "Bridge exploit",
+        "The bridge minted unbacked tokens",
+    );
+    test.process(
+        &[test.propose_vote_ix(&proposal, 10 * TOKENS)],
+        &[&test.proposer.keypair],
+    )
+    .await
+    .unwrap();
+
+    let voter = &test.voters[0];
+    let vote_hash = hash_vote(convert_f32_i64(1.0), "salt");
+    assert_sure_error(
+        test.process(
+            &[test.submit_vote_ix(&proposal, voter, vote_hash, false)],
+            &[&voter.keypair],
+        )
+        .await,
+        SureError::MissingVoterReputation,
+    );
+
+    test.process(
+        &[
+            test.initialize_voter_reputation_ix(voter),
+            test.submit_vote_ix(&proposal, voter, vote_hash, true),
+        ],
+        &[&voter.keypair],
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,618 @@
+use std::sync::Mutex;
+
+use anchor_lang::{
+    prelude::*, solana_program::program_pack::Pack, AccountSerialize, Discriminator,
+    InstructionData, ToAccountMetas,
+};
+use locked_voter::{Escrow, Locker, LockerParams};
+use oracle::utils::{
+    SURE_ORACLE_CONFIG_SEED, SURE_ORACLE_PROPOSAL_VAULT_SEED, SURE_ORACLE_REPUTATION_SEED,
+    SURE_ORACLE_REVEAL_ARRAY_SEED, SURE_ORACLE_SEED, SURE_ORACLE_TREASURY_SEED,
+    SURE_ORACLE_VOTE_SEED,
+};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+    transport::TransportError,
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+pub const DECIMALS: u8 = 6;
+
+/// Program logs captured from the runtime
+///
+/// anchor events are logged as `Program data: <base64>`
+static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static LOG_CAPTURE: LogCapture = LogCapture;
+
+struct LogCapture;
+
+impl log::Log for LogCapture {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        let message = record.args().to_string();
+        if message.starts_with("Program data: ") {
+            LOGS.lock().unwrap().push(message);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Get all the events of type E emitted so far
+///
+/// the tests run in parallel so the events of
+/// other tests have to be filtered out by the caller
+pub fn get_events<E: Discriminator + AnchorDeserialize>() -> Vec<E> {
+    LOGS.lock()
+        .unwrap()
+        .iter()
+        .filter_map(|log| base64::decode(log.trim_start_matches("Program data: ")).ok())
+        .filter(|data| data.len() >= 8 && data[..8] == E::discriminator())
+        .filter_map(|data| E::deserialize(&mut &data[8..]).ok())
+        .collect()
+}
+
+pub struct Voter {
+    pub keypair: Keypair,
+    pub token_account: Pubkey,
+    pub escrow: Pubkey,
+}
+
+/// Oracle test environment
+///
+/// the locker and the escrows of the voters are written
+/// directly into the genesis accounts so that the
+/// locked_voter program does not have to run
+pub struct OracleTest {
+    pub context: ProgramTestContext,
+    pub mint: Pubkey,
+    pub locker: Pubkey,
+    pub config: Pubkey,
+    pub protocol_treasury: Pubkey,
+    pub proposer: Voter,
+    pub voters: Vec<Voter>,
+}
+
+impl OracleTest {
+    /// Start the test validator
+    ///
+    /// ### Arguments
+    /// * escrow_amounts: tokens locked by each voter
+    /// * token_amount: tokens held by each user
+    pub async fn start(escrow_amounts: &[u64], token_amount: u64) -> Self {
+        // the logger has to be set before the program test
+        // sets up the solana logger
+        let _ = log::set_logger(&LOG_CAPTURE);
+        log::set_max_level(log::LevelFilter::Debug);
+
+        // events are only logged by the bpf program
+        let mut program_test = ProgramTest::new("oracle", oracle::id(), None);
+
+        let mint = Pubkey::new_unique();
+        let mint_authority = Pubkey::new_unique();
+        add_mint(&mut program_test, &mint, &mint_authority);
+
+        let locker = Pubkey::new_unique();
+        add_anchor_account(
+            &mut program_test,
+            &locker,
+            &Locker {
+                base: Pubkey::new_unique(),
+                bump: 255,
+                token_mint: mint,
+                locked_supply: escrow_amounts.iter().sum(),
+                governor: Pubkey::new_unique(),
+                params: LockerParams {
+                    whitelist_enabled: false,
+                    max_stake_vote_multiplier: 1,
+                    min_stake_duration: 0,
+                    max_stake_duration: 365 * 86400,
+                    proposal_activation_min_votes: 0,
+                },
+            },
+        );
+
+        let proposer = add_user(&mut program_test, &mint, &locker, 0, token_amount);
+        let voters = escrow_amounts
+            .iter()
+            .map(|amount| add_user(&mut program_test, &mint, &locker, *amount, token_amount))
+            .collect();
+
+        let context = program_test.start_with_context().await;
+        let (config, _) = Pubkey::find_program_address(
+            &[SURE_ORACLE_CONFIG_SEED.as_bytes(), mint.as_ref()],
+            &oracle::id(),
+        );
+        let (protocol_treasury, _) = Pubkey::find_program_address(
+            &[SURE_ORACLE_TREASURY_SEED.as_bytes(), config.as_ref()],
+            &oracle::id(),
+        );
+
+        Self {
+            context,
+            mint,
+            locker,
+            config,
+            protocol_treasury,
+            proposer,
+            voters,
+        }
+    }
+
+    /// Sign and process the instructions. The payer
+    /// is always a signer
+    pub async fn process(
+        &self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), TransportError> {
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            self.context.last_blockhash,
+        );
+        self.context
+            .banks_client
+            .clone()
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Move the clock to the given unix timestamp
+    pub async fn warp_to_timestamp(&self, unix_timestamp: i64) {
+        let mut clock: Clock = self
+            .context
+            .banks_client
+            .clone()
+            .get_sysvar()
+            .await
+            .unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn get_token_balance(&self, token_account: &Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .clone()
+            .get_account(*token_account)
+            .await
+            .unwrap()
+            .unwrap();
+        TokenAccount::unpack(&account.data).unwrap().amount
+    }
+
+    pub async fn get_anchor_account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .clone()
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap();
+        T::try_deserialize(&mut account.data.as_ref()).unwrap()
+    }
+
+    pub fn initialize_config_ix(&self) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::InitializeConfig {
+                signer: self.context.payer.pubkey(),
+                config: self.config,
+                token_mint: self.mint,
+                locker: self.locker,
+                protocol_treasury: self.protocol_treasury,
+                token_program: spl_token::id(),
+                rent: sysvar::rent::id(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::InitializeConfig {
+                protocol_authority: self.context.payer.pubkey(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn update_required_votes_ix(&self, required_votes: u64) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::UpdateConfig {
+                protocol_authority: self.context.payer.pubkey(),
+                config: self.config,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::UpdateRequiredVotes { required_votes }.data(),
+        }
+    }
+
+    pub fn update_reputation_enabled_ix(&self, reputation_enabled: bool) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::UpdateConfig {
+                protocol_authority: self.context.payer.pubkey(),
+                config: self.config,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::UpdateReputationEnabled { reputation_enabled }.data(),
+        }
+    }
+
+    pub fn initialize_voter_reputation_ix(&self, voter: &Voter) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::InitializeVoterReputation {
+                voter: voter.keypair.pubkey(),
+                config: self.config,
+                voter_reputation: self.voter_reputation(voter),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::InitializeVoterReputation {}.data(),
+        }
+    }
+
+    pub fn propose_vote_ix(&self, proposal: &ProposalKeys, stake: u64) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::ProposeVote {
+                proposer: self.proposer.keypair.pubkey(),
+                config: self.config,
+                proposal: proposal.proposal,
+                reveal_vote_array: proposal.reveal_vote_array,
+                proposer_account: self.proposer.token_account,
+                proposal_vault_mint: self.mint,
+                proposal_vault: proposal.proposal_vault,
+                token_program: spl_token::id(),
+                associated_token_program: anchor_spl::associated_token::ID,
+                rent: sysvar::rent::id(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::ProposeVote {
+                name: proposal.name.clone(),
+                description: proposal.description.clone(),
+                evidence: proposal.evidence.clone(),
+                stake,
+            }
+            .data(),
+        }
+    }
+
+    /// the voter reputation is passed as the
+    /// optional remaining account
+    pub fn submit_vote_ix(
+        &self,
+        proposal: &ProposalKeys,
+        voter: &Voter,
+        vote_hash: [u8; 32],
+        with_reputation: bool,
+    ) -> Instruction {
+        let mut accounts = oracle::accounts::SubmitVote {
+            voter: voter.keypair.pubkey(),
+            voter_account: voter.token_account,
+            config: self.config,
+            locker: self.locker,
+            user_escrow: voter.escrow,
+            proposal: proposal.proposal,
+            proposal_vault: proposal.proposal_vault,
+            proposal_vault_mint: self.mint,
+            vote_account: proposal.vote_account(voter),
+            token_program: spl_token::id(),
+            rent: sysvar::rent::id(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        if with_reputation {
+            accounts.push(AccountMeta::new(self.voter_reputation(voter), false));
+        }
+        Instruction {
+            program_id: oracle::id(),
+            accounts,
+            data: oracle::instruction::SubmitVote {
+                vote_hash: vote_hash.to_vec(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn reveal_vote_ix(
+        &self,
+        proposal: &ProposalKeys,
+        voter: &Voter,
+        salt: &str,
+        vote: i64,
+    ) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::RevealVote {
+                voter: voter.keypair.pubkey(),
+                proposal: proposal.proposal,
+                reveal_vote_array: proposal.reveal_vote_array,
+                vote_account: proposal.vote_account(voter),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::RevealVote {
+                salt: salt.to_string(),
+                vote,
+            }
+            .data(),
+        }
+    }
+
+    pub fn finalize_vote_results_ix(&self, proposal: &ProposalKeys) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::FinalizeVoteResults {
+                finalizer: self.context.payer.pubkey(),
+                proposal: proposal.proposal,
+                revealed_votes: proposal.reveal_vote_array,
+                proposal_vault: proposal.proposal_vault,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::FinalizeVoteResults {}.data(),
+        }
+    }
+
+    pub fn finalize_vote_ix(
+        &self,
+        proposal: &ProposalKeys,
+        voter: &Voter,
+        with_reputation: bool,
+    ) -> Instruction {
+        let mut accounts = oracle::accounts::FinalizeVote {
+            signer: voter.keypair.pubkey(),
+            vote_account: proposal.vote_account(voter),
+            proposal: proposal.proposal,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None);
+        if with_reputation {
+            accounts.push(AccountMeta::new(self.voter_reputation(voter), false));
+        }
+        Instruction {
+            program_id: oracle::id(),
+            accounts,
+            data: oracle::instruction::FinalizeVote {}.data(),
+        }
+    }
+
+    pub fn collect_proposer_reward_ix(&self, proposal: &ProposalKeys) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::CollectProposerReward {
+                proposer: self.proposer.keypair.pubkey(),
+                config: self.config,
+                proposer_token_account: self.proposer.token_account,
+                proposal: proposal.proposal,
+                proposal_vault: proposal.proposal_vault,
+                proposal_vault_mint: self.mint,
+                system_program: system_program::ID,
+                token_program: spl_token::id(),
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::CollectProposerReward {}.data(),
+        }
+    }
+
+    pub fn collect_vote_reward_ix(&self, proposal: &ProposalKeys, voter: &Voter) -> Instruction {
+        Instruction {
+            program_id: oracle::id(),
+            accounts: oracle::accounts::CollectVoteReward {
+                voter: voter.keypair.pubkey(),
+                config: self.config,
+                voter_account: voter.token_account,
+                vote_account: proposal.vote_account(voter),
+                proposal: proposal.proposal,
+                proposal_vault_mint: self.mint,
+                proposal_vault: proposal.proposal_vault,
+                system_program: system_program::ID,
+                token_program: spl_token::id(),
+            }
+            .to_account_metas(None),
+            data: oracle::instruction::CollectVoteReward {}.data(),
+        }
+    }
+
+    pub fn voter_reputation(&self, voter: &Voter) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                SURE_ORACLE_REPUTATION_SEED.as_bytes(),
+                self.config.as_ref(),
+                voter.keypair.pubkey().as_ref(),
+            ],
+            &oracle::id(),
+        )
+        .0
+    }
+}
+
+/// Addresses of a proposal and the content
+/// used to derive its id
+pub struct ProposalKeys {
+    pub name: String,
+    pub description: String,
+    pub evidence: oracle::states::ProposalEvidence,
+    pub proposal: Pubkey,
+    pub reveal_vote_array: Pubkey,
+    pub proposal_vault: Pubkey,
+}
+
+impl ProposalKeys {
+    pub fn new(proposer: &Pubkey, name: &str, description: &str) -> Self {
+        let evidence = oracle::states::ProposalEvidence {
+            uri: "https://arweave.net/incident-report".to_string(),
+            content_hash: [7; 32],
+            affected_program: Pubkey::new_unique(),
+            start_slot: 100,
+            end_slot: 120,
+        };
+        let id = oracle::states::Proposal::calculate_id(proposer, name, description, &evidence);
+        let (proposal, _) = Pubkey::find_program_address(
+            &[SURE_ORACLE_SEED.as_bytes(), id.as_ref()],
+            &oracle::id(),
+        );
+        let (reveal_vote_array, _) = Pubkey::find_program_address(
+            &[SURE_ORACLE_REVEAL_ARRAY_SEED.as_bytes(), proposal.as_ref()],
+            &oracle::id(),
+        );
+        let (proposal_vault, _) = Pubkey::find_program_address(
+            &[
+                SURE_ORACLE_PROPOSAL_VAULT_SEED.as_bytes(),
+                proposal.as_ref(),
+            ],
+            &oracle::id(),
+        );
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            evidence,
+            proposal,
+            reveal_vote_array,
+            proposal_vault,
+        }
+    }
+
+    pub fn vote_account(&self, voter: &Voter) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                SURE_ORACLE_VOTE_SEED.as_bytes(),
+                self.proposal.as_ref(),
+                voter.keypair.pubkey().as_ref(),
+            ],
+            &oracle::id(),
+        )
+        .0
+    }
+}
+
+fn add_mint(program_test: &mut ProgramTest, mint: &Pubkey, mint_authority: &Pubkey) {
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(
+        Mint {
+            mint_authority: Some(*mint_authority).into(),
+            supply: u64::MAX / 2,
+            decimals: DECIMALS,
+            is_initialized: true,
+            freeze_authority: None.into(),
+        },
+        &mut data,
+    )
+    .unwrap();
+    program_test.add_account(
+        *mint,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+fn add_anchor_account<T: AccountSerialize + Owner>(
+    program_test: &mut ProgramTest,
+    address: &Pubkey,
+    account: &T,
+) {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    program_test.add_account(
+        *address,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: T::owner(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+/// Add a user with SOL, a token account and an escrow
+/// that has been locked since before any proposal
+fn add_user(
+    program_test: &mut ProgramTest,
+    mint: &Pubkey,
+    locker: &Pubkey,
+    escrow_amount: u64,
+    token_amount: u64,
+) -> Voter {
+    let keypair = Keypair::new();
+    program_test.add_account(
+        keypair.pubkey(),
+        Account {
+            lamports: 10_000_000_000,
+            data: vec![],
+            owner: system_program::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let token_account = Pubkey::new_unique();
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount::pack(
+        TokenAccount {
+            mint: *mint,
+            owner: keypair.pubkey(),
+            amount: token_amount,
+            delegate: None.into(),
+            state: AccountState::Initialized,
+            is_native: None.into(),
+            delegated_amount: 0,
+            close_authority: None.into(),
+        },
+        &mut data,
+    )
+    .unwrap();
+    program_test.add_account(
+        token_account,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: spl_token::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let escrow = Pubkey::new_unique();
+    add_anchor_account(
+        program_test,
+        &escrow,
+        &Escrow {
+            locker: *locker,
+            owner: keypair.pubkey(),
+            bump: 255,
+            tokens: Pubkey::new_unique(),
+            amount: escrow_amount,
+            escrow_started_at: 1,
+            escrow_ends_at: i64::MAX / 2,
+            vote_delegate: keypair.pubkey(),
+        },
+    );
+
+    Voter {
+        keypair,
+        token_account,
+        escrow,
+    }
+}
@@ -0,0 +1,35 @@
+use crate::states::owner::ChangeProtocolOwner;
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Accept pool manager
+///
+/// the pending owner takes over the pool manager
+#[derive(Accounts)]
+pub struct AcceptPoolManager<'info> {
+    /// Owner proposed by the current owner
+    pub pending_owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = pending_owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+}
+
+pub fn handler(ctx: Context<AcceptPoolManager>) -> Result<()> {
+    let pool_manager = &mut ctx.accounts.pool_manager;
+    let new_owner = ctx.accounts.pending_owner.key();
+    let old_owner = pool_manager.accept_owner(&new_owner)?;
+
+    emit!(ChangeProtocolOwner {
+        owner: new_owner,
+        old_owner,
+    });
+    Ok(())
+}
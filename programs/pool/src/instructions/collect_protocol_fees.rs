@@ -0,0 +1,75 @@
+use crate::states::pool::*;
+use crate::states::token_pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Collect protocol fees
+///
+/// withdraws the protocol fees owed by a token pool.
+/// Only the owner of the pool manager can collect
+#[derive(Accounts)]
+pub struct CollectProtocolFees<'info> {
+    /// Owner of the pool manager
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Token pool owing the fees
+    #[account(
+        mut,
+        has_one = pool @ SureError::InvalidTokenPool,
+        has_one = premium_vault @ SureError::InvalidTokenPool,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
+    /// Premium vault of the token pool holding the fees
+    #[account(mut)]
+    pub premium_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = destination.mint == premium_vault.mint @ SureError::InvalidMint
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CollectProtocolFees>) -> Result<()> {
+    ctx.accounts.pool.can_collect_fees()?;
+    let amount = ctx.accounts.token_pool.collect_protocol_fees();
+    if amount > 0 {
+        withdraw_from_vault(
+            &ctx.accounts.pool,
+            &ctx.accounts.premium_vault,
+            &ctx.accounts.destination,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+    }
+
+    emit!(CollectedProtocolFees {
+        pool: ctx.accounts.pool.key(),
+        token_pool: ctx.accounts.token_pool.key(),
+        amount,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct CollectedProtocolFees {
+    #[index]
+    pub pool: Pubkey,
+    pub token_pool: Pubkey,
+    pub amount: u64,
+}
@@ -1,6 +1,6 @@
-use std::borrow::Borrow;
-
 use super::increase_coverage_position::*;
+use crate::states::tick_array::TickArrayPool;
+use crate::utils::*;
 use anchor_lang::prelude::*;
 
 /// Decrease Coverage Position handler
@@ -8,10 +8,11 @@ use anchor_lang::prelude::*;
 /// Decrease the amount coveraged by moving from upper to
 /// the lower part of coverage position
 ///
-/// Assume that current price is at the first available tick array
+/// The ticks are taken from the tick ledger of the position,
+/// the tick arrays have to hold the released ticks
 ///
-/// Premium is paid into seperate premium vault.  
-/// The premium can be collected at any time
+/// The premium for the remaining period of the released
+/// coverage is refunded from the premium vault
 ///
 /// * parameters
 /// - coverage_amount: the amount to decrease the position with
@@ -24,47 +25,93 @@ pub fn handler(
     expiry_ts: i64,
     is_target_amount: bool,
 ) -> Result<()> {
-    let pool = ctx.accounts.pool.as_mut();
-    let coverage_buyer = &ctx.accounts.owner;
-    let premium_vault = &ctx.accounts.token_vault_1;
-    let coverage_buyer_account = &ctx.accounts.token_account_0;
-    let coverage_position = ctx.accounts.coverage_position.load_mut()?;
+    let pool = ctx.accounts.pool.as_ref();
+    let token_pool = ctx.accounts.token_pool.as_mut();
+    let mut coverage_position = ctx.accounts.coverage_position.load_mut()?;
+    let current_ts = Clock::get()?.unix_timestamp;
+
+    // no coverage changes while the pool is paused
+    pool.can_decrease_coverage()?;
 
-    // Validate the coverage position
-    // account::validate_token_account_ownership(
-    //     &ctx.accounts.position_token_account,
-    //     &ctx.accounts.owner,
-    // )?;
+    // expired coverage is released by the expire_coverage crank
+    if !coverage_position.active {
+        return Err(SureError::InsuranceContractIsNotActive.into());
+    }
+    if coverage_position.is_expired(current_ts) {
+        return Err(SureError::InsuranceContractExpired.into());
+    }
+    if coverage_position.pool != pool.key() {
+        return Err(SureError::InvalidCoveragePool.into());
+    }
+    if coverage_position.token_mint != token_pool.token_mint {
+        return Err(SureError::InvalidMint.into());
+    }
+    if coverage_position.expiry_ts != expiry_ts {
+        return Err(SureError::InvalidCoverageExpiry.into());
+    }
+    let insured_amount = coverage_position.insured_amount as u128;
+    let coverage_delta = if is_target_amount {
+        insured_amount
+            .checked_sub(coverage_amount)
+            .ok_or(SureError::InvalidAmount)?
+    } else {
+        coverage_amount
+    };
 
-    // let tick_array_pool = TickArrayPool::new(
-    //     ctx.accounts.tick_array_0.load_mut().unwrap(),
-    //     ctx.accounts.tick_array_1.load_mut().ok(),
-    //     ctx.accounts.tick_array_2.load_mut().ok(),
-    // );
+    let mut tick_array_pool = TickArrayPool::load_mut(
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+    )?;
 
-    // Calculate the coverage
-    // let coverage_result = pool.update_coverage(
-    //     tick_array_pool,
-    //     coverage_position,
-    //     coverage_amount,
-    //     expiry_ts,
-    //     false,
-    //     false,
-    // )?;
+    // Release the coverage from the tick ledger of the position,
+    // most expensive ticks first
+    let quote = tick_array_pool.quote_coverage_decrease(
+        pool,
+        &coverage_position.get_coverage_ticks(),
+        coverage_delta,
+        current_ts,
+        expiry_ts,
+    )?;
+    tick_array_pool.update_coverage(&quote, pool.tick_spacing, false)?;
+    coverage_position.reduce_coverage(&quote)?;
+    let insured_amount = coverage_position.insured_amount as u128;
 
-    // // update pool
-    // pool.update_after_coverage_change(coverage_result.borrow())?;
+    // update token pool
+    token_pool.update_used_liquidity(
+        -i128::try_from(coverage_delta).map_err(|_| SureError::LiquidityTooLarge)?,
+    )?;
+    drop(coverage_position);
+    drop(tick_array_pool);
 
-    // // ---
-    // // deposit premium and fees into vault
-    // let premium_plus_cost = coverage_result.get_total_cost_of_coverage()?;
-    // deposit_into_vault(
-    //     coverage_buyer,
-    //     premium_vault,
-    //     coverage_buyer_account,
-    //     &ctx.accounts.token_program,
-    //     premium_plus_cost,
-    // )?;
+    // refund the premium for the released coverage
+    let refund = u64::try_from(quote.premium).map_err(|_| SureError::OverflowU64)?;
+    if refund > 0 {
+        withdraw_from_vault(
+            &ctx.accounts.pool,
+            &ctx.accounts.token_vault_1,
+            &ctx.accounts.token_account_0,
+            &ctx.accounts.token_program,
+            refund,
+        )?;
+    }
 
+    emit!(DecreasedCoveragePosition {
+        pool: ctx.accounts.pool.key(),
+        coverage_position: ctx.accounts.coverage_position.key(),
+        insured_amount,
+        refund,
+        expiry_ts,
+    });
     Ok(())
 }
+
+#[event]
+pub struct DecreasedCoveragePosition {
+    #[index]
+    pub pool: Pubkey,
+    pub coverage_position: Pubkey,
+    pub insured_amount: u128,
+    pub refund: u64,
+    pub expiry_ts: i64,
+}
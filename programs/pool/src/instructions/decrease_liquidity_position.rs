@@ -1,8 +1,40 @@
+use crate::utils::*;
 use anchor_lang::prelude::*;
 
-use super::increase_liquidity_position::UpdateLiquidity;
+use super::increase_liquidity_position::{update_position_liquidity, UpdateLiquidity};
 
-pub fn handler(ctx: Context<UpdateLiquidity>) -> Result<()> {
+/// Decrease liquidity Position
+///
+/// Withdraws up to liquidity_amount from each tick of
+/// the position after the claim losses are settled.
+/// Only liquidity that is not used for coverage can
+/// be withdrawn
+pub fn handler(ctx: Context<UpdateLiquidity>, liquidity_amount: u128) -> Result<()> {
+    // withdrawals are frozen while the pool is paused
+    // or a claim is voted on
+    ctx.accounts.pool.can_decrease_liquidity()?;
+    if liquidity_amount == 0 {
+        return Err(SureError::LiquidityHaveToBeGreaterThan0.into());
+    }
+    let liquidity_delta =
+        i128::try_from(liquidity_amount).map_err(|_| SureError::LiquidityTooLarge)?;
+
+    let (amount, haircut) = update_position_liquidity(ctx.accounts, -liquidity_delta)?;
+    withdraw_from_vault(
+        &ctx.accounts.pool,
+        &ctx.accounts.vault,
+        &ctx.accounts.liquidity_provider_token_account,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(DecreasedLiquidityPosition {
+        pool: ctx.accounts.pool.key(),
+        liquidity_position: ctx.accounts.liquidity_position.key(),
+        liquidity: ctx.accounts.liquidity_position.liquidity,
+        amount,
+        haircut,
+    });
     Ok(())
 }
 
@@ -11,3 +43,13 @@ pub struct NewLiquidityPosition {
     pub tick: u16,
     pub liquidity: u64,
 }
+
+#[event]
+pub struct DecreasedLiquidityPosition {
+    #[index]
+    pub pool: Pubkey,
+    pub liquidity_position: Pubkey,
+    pub liquidity: u128,
+    pub amount: u64,
+    pub haircut: u128,
+}
@@ -0,0 +1,176 @@
+use crate::states::coverage::CoveragePosition;
+use crate::states::pool::Pool;
+use crate::states::tick_array::{TickArray, TickArrayPool};
+use crate::states::token_pool::TokenPool;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Expire coverage
+///
+/// Permissionless crank that releases the liquidity
+/// used by an expired coverage position
+#[derive(Accounts)]
+pub struct ExpireCoverage<'info> {
+    /// Keeper cranking the position
+    pub keeper: Signer<'info>,
+
+    /// Keeper account to receive the tip
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == premium_vault.mint @ SureError::InvalidMint,
+    )]
+    pub keeper_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Pool the coverage is bought from
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Token pool the coverage is bought from
+    #[account(
+        mut,
+        has_one = pool @ SureError::InvalidTokenPool,
+        has_one = premium_vault @ SureError::InvalidTokenPool,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
+    /// Expired coverage position
+    #[account(
+        mut,
+        constraint = coverage_position.load()?.pool == pool.key() @ SureError::InvalidCoveragePool,
+        constraint = coverage_position.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub coverage_position: AccountLoader<'info, CoveragePosition>,
+
+    /// Premium vault of the token pool
+    #[account(mut)]
+    pub premium_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Tick array 0
+    /// Array holding liquidity used by the position
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_0.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_0: AccountLoader<'info, TickArray>,
+
+    /// Tick array 1
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_1.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_1: AccountLoader<'info, TickArray>,
+
+    /// Tick array 2
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_2.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_2: AccountLoader<'info, TickArray>,
+
+    /// Token program to transfer the tip
+    pub token_program: Program<'info, Token>,
+}
+
+/// Expire coverage handler
+///
+/// Releases the liquidity of the ticks in the given
+/// tick arrays. A position with coverage in more than three
+/// tick arrays is expired over several cranks. When all
+/// the liquidity is released the position is marked
+/// inactive and the keeper is paid a tip out of the
+/// protocol fees owed by the token pool. Positions can
+/// not expire while a claim against the pool is open.
+pub fn handler(ctx: Context<ExpireCoverage>) -> Result<()> {
+    let pool = ctx.accounts.pool.as_ref();
+    let token_pool = ctx.accounts.token_pool.as_mut();
+    pool.can_expire_coverage()?;
+    let tick_spacing = pool.tick_spacing;
+    let mut coverage_position = ctx.accounts.coverage_position.load_mut()?;
+    let current_ts = Clock::get()?.unix_timestamp;
+
+    if !coverage_position.active {
+        return Err(SureError::InsuranceContractIsNotActive.into());
+    }
+    if !coverage_position.is_expired(current_ts) {
+        return Err(SureError::CoverageNotExpired.into());
+    }
+
+    let mut tick_array_pool = TickArrayPool::load_mut(
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+    )?;
+
+    // release the liquidity of the ticks in the tick arrays
+    let released_ticks = coverage_position.remove_ticks(|tick_index| {
+        tick_array_pool
+            .find_array_index(tick_index, tick_spacing)
+            .is_some()
+    });
+    let mut released_liquidity: u128 = 0;
+    for coverage_tick in released_ticks.iter() {
+        let tick_index = coverage_tick.tick_index;
+        let amount = coverage_tick.amount as u128;
+        let array_index = tick_array_pool
+            .find_array_index(tick_index, tick_spacing)
+            .ok_or(SureError::TickOutOfRange)?;
+        tick_array_pool.update_tick_coverage(
+            array_index,
+            tick_index,
+            tick_spacing,
+            amount,
+            false,
+        )?;
+        released_liquidity = released_liquidity
+            .checked_add(amount)
+            .ok_or(SureError::LiquidityOverflow)?;
+    }
+    token_pool.update_used_liquidity(
+        -i128::try_from(released_liquidity).map_err(|_| SureError::LiquidityTooLarge)?,
+    )?;
+
+    // mark the position inactive when all ticks are released
+    let expired = coverage_position.tick_count == 0;
+    let keeper_tip = if expired {
+        token_pool.take_keeper_tip(coverage_position.expire()?)
+    } else {
+        0
+    };
+    drop(coverage_position);
+    drop(tick_array_pool);
+
+    if keeper_tip > 0 {
+        withdraw_from_vault(
+            &ctx.accounts.pool,
+            &ctx.accounts.premium_vault,
+            &ctx.accounts.keeper_token_account,
+            &ctx.accounts.token_program,
+            keeper_tip,
+        )?;
+    }
+
+    emit!(ExpiredCoverage {
+        pool: ctx.accounts.pool.key(),
+        coverage_position: ctx.accounts.coverage_position.key(),
+        keeper: ctx.accounts.keeper.key(),
+        released_liquidity,
+        keeper_tip,
+        expired,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct ExpiredCoverage {
+    #[index]
+    pub pool: Pubkey,
+    pub coverage_position: Pubkey,
+    pub keeper: Pubkey,
+    pub released_liquidity: u128,
+    pub keeper_tip: u64,
+    /// whether all the liquidity is released
+    pub expired: bool,
+}
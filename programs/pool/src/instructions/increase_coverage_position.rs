@@ -3,6 +3,7 @@ use std::borrow::Borrow;
 use crate::states::coverage::CoveragePosition;
 use crate::states::pool::Pool;
 use crate::states::tick_array::{TickArray, TickArrayPool};
+use crate::states::token_pool::TokenPool;
 use crate::utils::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount, ID};
@@ -23,9 +24,15 @@ pub struct UpdateCoveragePosition<'info> {
     pub token_account_0: Account<'info, TokenAccount>,
 
     /// Pool to buy insurance from
-    #[account(mut)]
     pub pool: Box<Account<'info, Pool>>,
 
+    /// Token pool of the mint to buy insurance in
+    #[account(
+        mut,
+        has_one = pool @ SureError::InvalidTokenPool,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
     /// Position Mint
     pub position_mint: Account<'info, Mint>,
 
@@ -44,7 +51,7 @@ pub struct UpdateCoveragePosition<'info> {
     #[account(
         mut,
         constraint = token_vault_0.mint == token_account_0.mint,
-        constraint = token_vault_0.key() == pool.vault_0
+        constraint = token_vault_0.key() == token_pool.vault @ SureError::InvalidTokenPool,
     )]
     pub token_vault_0: Box<Account<'info, TokenAccount>>,
 
@@ -52,24 +59,36 @@ pub struct UpdateCoveragePosition<'info> {
     /// Constraint: should be of same mint as token vault 0
     #[account(
         mut,
-        constraint =token_vault_1.key() == pool.vault_1 
+        constraint = token_vault_1.key() == token_pool.premium_vault @ SureError::InvalidTokenPool,
     )]
     pub token_vault_1: Box<Account<'info, TokenAccount>>,
 
     /// Tick array 0
     /// First array to buy insurance from and
     /// where the current price is located
-    #[account(mut,has_one = pool)]
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_0.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
     pub tick_array_0: AccountLoader<'info, TickArray>,
 
     /// Tick array 1
     /// Array after tick array 0 to buy from
-    #[account(mut,has_one = pool)]
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_1.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
     pub tick_array_1: AccountLoader<'info, TickArray>,
 
     /// Tick array 2
     /// Array after tick array 1 to buy from
-    #[account(mut,has_one = pool)]
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_2.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
     pub tick_array_2: AccountLoader<'info, TickArray>,
 
     /// Token program to transfer tokens
@@ -92,48 +111,91 @@ pub fn handler(
     expiry_ts: i64,
     is_target_amount: bool,
 ) -> Result<()> {
-    let pool = ctx.accounts.pool.as_mut();
+    let pool = ctx.accounts.pool.as_ref();
+    let token_pool = ctx.accounts.token_pool.as_mut();
     let coverage_buyer = &ctx.accounts.owner;
     let premium_vault = &ctx.accounts.token_vault_1;
     let coverage_buyer_account = &ctx.accounts.token_account_0;
-    let coverage_position = ctx.accounts.coverage_position.load_mut()?;
-
-    // Validate the coverage position
-    // account::validate_token_account_ownership(
-    //     &ctx.accounts.position_token_account,
-    //     &ctx.accounts.owner,
-    // )?;
-
-    // // Combine input tick arrays into a tick array pool to buy insurance from
-    // let tick_array_pool = TickArrayPool::new(
-    //     ctx.accounts.tick_array_0.load_mut().unwrap(),
-    //     ctx.accounts.tick_array_1.load_mut().ok(),
-    //     ctx.accounts.tick_array_2.load_mut().ok(),
-    // );
-
-    // // Calculate the coverage
-    // let coverage_result = pool.update_coverage(
-    //     tick_array_pool,
-    //     coverage_position,
-    //     coverage_amount,
-    //     expiry_ts,
-    //     true,
-    //     false,
-    // )?;
-
-    // // update pool
-    // pool.update_after_coverage_change(&coverage_result.borrow())?;
-
-    // // ---
-    // // deposit premium and fees into vault
-    // let premium_plus_cost = coverage_result.get_total_cost_of_coverage()?;
-    // deposit_into_vault(
-    //     coverage_buyer,
-    //     premium_vault,
-    //     coverage_buyer_account,
-    //     &ctx.accounts.token_program,
-    //     premium_plus_cost,
-    // )?;
-
+    let mut coverage_position = ctx.accounts.coverage_position.load_mut()?;
+    let current_ts = Clock::get()?.unix_timestamp;
+
+    // no new coverage while the pool is paused or a claim is voted on
+    pool.can_increase_coverage()?;
+
+    // the coverage in a position expires at the same time.
+    // Expired coverage has to be released first
+    let insured_amount = coverage_position.insured_amount as u128;
+    if coverage_position.active {
+        if coverage_position.is_expired(current_ts) {
+            return Err(SureError::InsuranceContractExpired.into());
+        }
+        if coverage_position.expiry_ts != expiry_ts {
+            return Err(SureError::InvalidCoverageExpiry.into());
+        }
+    }
+    let coverage_delta = if is_target_amount {
+        coverage_amount
+            .checked_sub(insured_amount)
+            .ok_or(SureError::InvalidAmount)?
+    } else {
+        coverage_amount
+    };
+
+    // Combine input tick arrays into a tick array pool to buy insurance from
+    let mut tick_array_pool = TickArrayPool::load_mut(
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+    )?;
+
+    // Calculate the coverage, same as quote_coverage
+    let quote = tick_array_pool.quote_coverage(pool, coverage_delta, current_ts, expiry_ts)?;
+    if !quote.is_filled(coverage_delta) {
+        return Err(SureError::LiquidityFilled.into());
+    }
+    tick_array_pool.update_coverage(&quote, pool.tick_spacing, true)?;
+
+    // update token pool
+    token_pool.update_used_liquidity(
+        i128::try_from(coverage_delta).map_err(|_| SureError::LiquidityTooLarge)?,
+    )?;
+    token_pool.accrue_fees(
+        u64::try_from(quote.protocol_fee).map_err(|_| SureError::OverflowU64)?,
+        u64::try_from(quote.founders_fee).map_err(|_| SureError::OverflowU64)?,
+    )?;
+
+    // record the ticks the coverage is bought from
+    coverage_position.add_coverage(&pool.key(), &token_pool.token_mint, &quote, expiry_ts)?;
+    let insured_amount = coverage_position.insured_amount as u128;
+
+    // deposit premium and fees into vault
+    let premium_plus_cost = quote.total_cost()?;
+    deposit_into_vault(
+        coverage_buyer,
+        premium_vault,
+        coverage_buyer_account,
+        &ctx.accounts.token_program,
+        premium_plus_cost,
+    )?;
+
+    emit!(IncreasedCoveragePosition {
+        pool: pool.key(),
+        coverage_position: ctx.accounts.coverage_position.key(),
+        insured_amount,
+        premium: quote.premium,
+        fee_amount: quote.fee_amount,
+        expiry_ts,
+    });
     Ok(())
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct IncreasedCoveragePosition {
+    #[index]
+    pub pool: Pubkey,
+    pub coverage_position: Pubkey,
+    pub insured_amount: u128,
+    pub premium: u128,
+    pub fee_amount: u128,
+    pub expiry_ts: i64,
+}
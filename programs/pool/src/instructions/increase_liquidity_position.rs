@@ -2,85 +2,182 @@ use crate::states::pool::Pool;
 use crate::states::*;
 use crate::utils::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token::{Mint, Token, TokenAccount, ID};
 
-/// --- Deposit Liquidity ---
+/// --- Update Liquidity ---
 ///
-/// Deposits liquidity into a
+/// Deposits, or withdraws, liquidity of a
+/// liquidity position
 ///
 /// Liquidity Positions on Sure is represented as an NFT.
 /// The holder has the right to manage the liquidity position
 ///
-/// The associated method does
-///     - Mint a new Liquidity Position NFT
-///     - Transfer capital to pool vault
-///     - Creates liquidity position
-///     - Updates
-///
-/// Initializes:
-///     - nft_mint: Mint associated with the liquidity NFT position
-///     - liquidity_position: keeps a summary of liquidity position
-///     - nft_account: nft account to hold the newly minted Liquidity position NFT
+/// The same amount of liquidity is held in each tick
+/// in the range [tick_index_lower, tick_index_upper)
+/// of the position
 ///
 #[derive(Accounts)]
-#[instruction(tick: u16,tick_pos: u64)]
 pub struct UpdateLiquidity<'info> {
     /// Liquidity provider
-    #[account(mut)]
     pub liquidity_provider: Signer<'info>,
 
-    /// Associated token account to credit
-    #[account(mut)]
+    /// Associated token account to debit or credit
+    #[account(
+        mut,
+        constraint = liquidity_provider_token_account.mint == vault.mint @ SureError::InvalidMint,
+    )]
     pub liquidity_provider_token_account: Box<Account<'info, TokenAccount>>,
 
     /// Pool which owns token account
-    #[account(mut)]
     pub pool: Box<Account<'info, Pool>>,
 
-    /// Pool Vault account to deposit liquidity to
-    #[account(mut)]
-    pub premium_vault: Account<'info, TokenAccount>,
+    /// Token pool holding the liquidity of the position
+    #[account(
+        mut,
+        has_one = pool @ SureError::InvalidTokenPool,
+        has_one = vault @ SureError::InvalidTokenPool,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
 
-    /// Create Liquidity position
-    /// HASH: [sure-lp,liquidity-provider,pool,token,tick]
+    /// Liquidity position to update
     #[account(
-        init,
-        payer = liquidity_provider,
+        mut,
         seeds = [
-            SURE_LIQUIDITY_POSITION.as_bytes(),
-            liquidity_position_nft_mint.key().as_ref()
+            SURE_DOMAIN.as_bytes(),
+            position_mint.key().as_ref()
         ],
-        space = 8 + LiquidityPosition::SPACE,
-        bump,
+        bump = liquidity_position.bump,
+        has_one = pool,
+        has_one = position_mint,
+        constraint = liquidity_position.token_mint == token_pool.token_mint @ SureError::InvalidMint,
     )]
     pub liquidity_position: Box<Account<'info, LiquidityPosition>>,
 
-    pub liquidity_position_nft_mint: Box<Account<'info, Mint>>,
+    /// Mint of the NFT representing the
+    /// liquidity position
+    pub position_mint: Box<Account<'info, Mint>>,
 
-    /// CHECK: done in method
+    /// Token account holding the NFT.
+    /// The holder manages the position
+    #[account(
+        constraint = position_token_account.mint == position_mint.key() @ SureError::InvalidMint,
+        constraint = position_token_account.owner == liquidity_provider.key() @ SureError::InvalidOwner,
+        constraint = position_token_account.amount == 1 @ SureError::InvalidOwner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Vault of the token pool holding the liquidity
     #[account(mut)]
-    pub metadata_account: UncheckedAccount<'info>,
+    pub vault: Box<Account<'info, TokenAccount>>,
 
-    /// Program id for metadata program
-    /// CHECK: checks that the address matches the mpl token metadata id
-    //#[account(address =mpl_token_metadata::ID )]
-    pub metadata_program: UncheckedAccount<'info>,
+    /// Tick array holding the lower tick
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_0.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_0: AccountLoader<'info, TickArray>,
 
-    /// Sysvar for token mint and ATA creation
-    pub rent: Sysvar<'info, Rent>,
+    /// Tick array after tick array 0
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_1.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_1: AccountLoader<'info, TickArray>,
 
-    // Token program that executes the transfer
+    /// Tick array after tick array 1
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_2.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_2: AccountLoader<'info, TickArray>,
+
+    /// Token program that executes the transfer
+    #[account(address = ID)]
     pub token_program: Program<'info, Token>,
+}
 
-    /// Provide the system program
-    pub system_program: Program<'info, System>,
+/// Update the liquidity in the ticks of the position
+///
+/// The claim losses of the position are settled
+/// before the liquidity is changed
+///
+/// # Returns
+/// * amount: tokens to transfer for the liquidity delta
+/// * haircut: liquidity the position lost to claims
+pub fn update_position_liquidity(
+    accounts: &mut UpdateLiquidity,
+    liquidity_delta: i128,
+) -> Result<(u64, u128)> {
+    let tick_spacing = accounts.pool.tick_spacing;
+    let liquidity_position = accounts.liquidity_position.as_mut();
+
+    let mut tick_array_pool = TickArrayPool::load_mut(
+        &accounts.tick_array_0,
+        &accounts.tick_array_1,
+        &accounts.tick_array_2,
+    )?;
+    // the token pool liquidity is reduced when the claim
+    // is paid, so the haircut only updates the ticks
+    let haircut = tick_array_pool.settle_position_loss(liquidity_position, tick_spacing)?;
+    let amount = tick_array_pool.update_position_liquidity(
+        liquidity_position,
+        tick_spacing,
+        liquidity_delta,
+    )?;
+
+    // the token pool holds the liquidity of all the ticks
+    let amount_delta = i128::try_from(amount).map_err(|_| SureError::LiquidityTooLarge)?;
+    if liquidity_delta < 0 {
+        accounts.token_pool.update_liquidity(-amount_delta)?;
+    } else {
+        accounts.token_pool.update_liquidity(amount_delta)?;
+    }
+    Ok((
+        u64::try_from(amount).map_err(|_| SureError::OverflowU64)?,
+        haircut,
+    ))
 }
 
 /// Increase liquidity Position
+///
+/// Deposits liquidity_amount into each tick of the position
 pub fn handler(ctx: Context<UpdateLiquidity>, liquidity_amount: u128) -> Result<()> {
-    // TODO: validation
+    // no liquidity changes while the pool is paused
+    ctx.accounts.pool.can_increase_liquidity()?;
+    if liquidity_amount == 0 {
+        return Err(SureError::LiquidityHaveToBeGreaterThan0.into());
+    }
+    let liquidity_delta =
+        i128::try_from(liquidity_amount).map_err(|_| SureError::LiquidityTooLarge)?;
 
-    // create update
+    let (amount, haircut) = update_position_liquidity(ctx.accounts, liquidity_delta)?;
+    deposit_into_vault(
+        &ctx.accounts.liquidity_provider,
+        &ctx.accounts.vault,
+        &ctx.accounts.liquidity_provider_token_account,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
 
+    emit!(IncreasedLiquidityPosition {
+        pool: ctx.accounts.pool.key(),
+        liquidity_position: ctx.accounts.liquidity_position.key(),
+        liquidity: ctx.accounts.liquidity_position.liquidity,
+        amount,
+        haircut,
+    });
     Ok(())
 }
+
+#[event]
+pub struct IncreasedLiquidityPosition {
+    #[index]
+    pub pool: Pubkey,
+    pub liquidity_position: Pubkey,
+    pub liquidity: u128,
+    pub amount: u64,
+    pub haircut: u128,
+}
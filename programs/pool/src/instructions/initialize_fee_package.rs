@@ -0,0 +1,78 @@
+use crate::states::fee_package::*;
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Initialize fee package
+///
+/// creates a fee package owned by the protocol.
+/// Only the owner of the pool manager can create fee packages.
+/// Pools created with the fee package copy its rates
+#[derive(Accounts)]
+#[instruction(fee_package_id: u16)]
+pub struct InitializeFeePackage<'info> {
+    /// Owner of the pool manager
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            SURE_FEE_PACKAGE_SEED.as_bytes(),
+            fee_package_id.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 8 + FeePackage::SIZE,
+    )]
+    pub fee_package: Account<'info, FeePackage>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeFeePackage>,
+    fee_package_id: u16,
+    fee_rate: u16,
+    protocol_fee: u16,
+    founders_fee: u16,
+) -> Result<()> {
+    let fee_package = &mut ctx.accounts.fee_package;
+    fee_package.initialize(
+        *ctx.bumps.get("fee_package").unwrap(),
+        fee_package_id,
+        fee_rate,
+        protocol_fee,
+        founders_fee,
+    )?;
+
+    emit!(InitializedFeePackage {
+        fee_package: fee_package.key(),
+        owner: ctx.accounts.owner.key(),
+        fee_package_id,
+        fee_rate,
+        protocol_fee,
+        founders_fee,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct InitializedFeePackage {
+    #[index]
+    pub fee_package: Pubkey,
+    pub owner: Pubkey,
+    pub fee_package_id: u16,
+    pub fee_rate: u16,
+    pub protocol_fee: u16,
+    pub founders_fee: u16,
+}
@@ -1,5 +1,6 @@
 use crate::states::liquidity::*;
 use crate::states::pool::*;
+use crate::states::token_pool::*;
 use crate::utils::SURE_NFT_UPDATE_AUTH;
 use crate::utils::*;
 use anchor_lang::prelude::*;
@@ -9,13 +10,19 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount, ID},
 };
 #[derive(Accounts)]
-#[instruction(tick_upper: i32, tick_lower: i32)]
+#[instruction(tick_lower: i32, tick_upper: i32)]
 pub struct InitializeLiquidityPosition<'info> {
     #[account(mut)]
     liquidity_provider: Signer<'info>,
 
     pool: Box<Account<'info, Pool>>,
 
+    /// Token pool of the mint to provide liquidity in
+    #[account(
+        has_one = pool @ SureError::InvalidTokenPool,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
     #[account(
         init,
         payer = liquidity_provider,
@@ -92,5 +99,50 @@ pub fn handler(
     tick_lower: i32,
     tick_upper: i32,
 ) -> Result<()> {
+    ctx.accounts.pool.can_increase_liquidity()?;
+    LiquidityPosition::validate_tick_range(tick_lower, tick_upper, ctx.accounts.pool.tick_spacing)?;
+
+    let liquidity_position = ctx.accounts.liquidity_position.as_mut();
+    liquidity_position.initialize(
+        *ctx.bumps.get("liquidity_position").unwrap(),
+        ctx.accounts.pool.key(),
+        ctx.accounts.token_pool.token_mint,
+        ctx.accounts.position_mint.key(),
+        tick_lower,
+        tick_upper,
+        ctx.accounts.pool.tick_spacing,
+    );
+
+    // Mint the position NFT to the liquidity provider
+    create_liquidity_position_with_metadata(
+        &ctx.accounts.metadata_account,
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.metadata_update_authority,
+        &ctx.accounts.pool,
+        &ctx.accounts.liquidity_provider,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.rent,
+    )?;
+
+    emit!(InitializedLiquidityPosition {
+        pool: ctx.accounts.pool.key(),
+        liquidity_position: ctx.accounts.liquidity_position.key(),
+        position_mint: ctx.accounts.position_mint.key(),
+        tick_index_lower: tick_lower,
+        tick_index_upper: tick_upper,
+    });
     Ok(())
 }
+
+#[event]
+pub struct InitializedLiquidityPosition {
+    #[index]
+    pub pool: Pubkey,
+    pub liquidity_position: Pubkey,
+    pub position_mint: Pubkey,
+    pub tick_index_lower: i32,
+    pub tick_index_upper: i32,
+}
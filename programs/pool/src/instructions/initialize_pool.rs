@@ -1,7 +1,11 @@
+use super::initialize_token_pool::InitializedTokenPool;
 use crate::states::fee_package::FeePackage;
 use crate::states::pool::*;
+use crate::states::registry::*;
+use crate::states::token_pool::*;
 use crate::utils::*;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_spl::token::Token;
 use anchor_spl::{token::Mint, token::TokenAccount};
 #[derive(Accounts)]
@@ -23,8 +27,47 @@ pub struct InitializePool<'info> {
     )]
     pub pool: Box<Account<'info, Pool>>,
 
+    /// Pool manager deciding who can create pools
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    /// Fee package to copy the fee rates from
+    /// Fee packages can only be created by the pool manager
     pub fee_package: Account<'info, FeePackage>,
 
+    /// Registry of all the pools
+    #[account(
+        mut,
+        seeds = [
+            SURE_INSURANCE_CONTRACTS.as_bytes(),
+        ],
+        bump = sure_pools.bump,
+    )]
+    pub sure_pools: Box<Account<'info, SurePools>>,
+
+    /// Registry page with a free slot
+    #[account(mut)]
+    pub registry_page: AccountLoader<'info, SurePoolsPage>,
+
+    /// Registry entry used to look up the pool
+    /// by the insured program
+    #[account(
+        init,
+        space = 8 + InsuredContractInfo::SPACE,
+        payer = creator,
+        seeds = [
+            SURE_INSURANCE_CONTRACTS_INFO.as_bytes(),
+            smart_contract.key().as_ref(),
+        ],
+        bump
+    )]
+    pub insured_contract_info: Box<Account<'info, InsuredContractInfo>>,
+
     // Assume the contract being hacked is a token account
     /// CHECK: This accounts represents the executable contract
     /// that is to be insured.
@@ -34,8 +77,28 @@ pub struct InitializePool<'info> {
     pub smart_contract: UncheckedAccount<'info>,
 
     pub token_mint_0: Account<'info, Mint>,
+
+    /// Mint of the premiums. Premiums are paid
+    /// in the mint of the coverage
+    #[account(
+        constraint = token_mint_1.key() == token_mint_0.key() @ SureError::InvalidMint,
+    )]
     pub token_mint_1: Account<'info, Mint>,
 
+    /// Token pool of the primary mint
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            SURE_TOKEN_POOL_SEED.as_bytes(),
+            pool.key().as_ref(),
+            token_mint_0.key().as_ref(),
+        ],
+        bump,
+        space = 8 + TokenPool::SPACE,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
     // Pool Vault used to hold tokens from token_mint
     #[account(
         init,
@@ -79,11 +142,23 @@ pub struct InitializePool<'info> {
 pub fn handler(ctx: Context<InitializePool>, name: String, tick_spacing: u16) -> Result<()> {
     // ________________ Validation ________________
     // Only allow the owner of the protocol to create pools
-    // let protocol_owner = &ctx.accounts.protocol_owner.load()?;
-    // require!(
-    //     ctx.accounts.pool_creator.key() == protocol_owner.owner,
-    //     SureError::InvalidPoolCreator
-    // );
+    // unless pool creation is permissionless
+    let creation_bond = ctx
+        .accounts
+        .pool_manager
+        .validate_pool_creator(&ctx.accounts.creator.key())?;
+    if creation_bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.pool.to_account_info(),
+                },
+            ),
+            creation_bond,
+        )?;
+    }
 
     // Load Accounts
     let pool = &mut ctx.accounts.pool;
@@ -93,15 +168,56 @@ pub fn handler(ctx: Context<InitializePool>, name: String, tick_spacing: u16) ->
         ctx.accounts.creator.key(),
         tick_spacing,
         &ctx.accounts.fee_package,
+        ctx.accounts.smart_contract.key(),
         ctx.accounts.token_mint_0.key(),
         ctx.accounts.token_mint_1.key(),
         ctx.accounts.vault_0.key(),
         ctx.accounts.vault_1.key(),
+    )?;
+    pool.creation_bond = creation_bond;
+
+    // The primary mint is held by a token pool
+    // using the vaults of the pool
+    let token_pool = ctx.accounts.token_pool.as_mut();
+    token_pool.initialize(
+        *ctx.bumps.get("token_pool").unwrap(),
+        &pool.key(),
+        &ctx.accounts.token_mint_0.key(),
+        &ctx.accounts.vault_0.key(),
+        &ctx.accounts.vault_1.key(),
     );
+    pool.add_token_pool(&token_pool.key())?;
+
+    // Register the pool
+    let mut registry_page = ctx.accounts.registry_page.load_mut()?;
+    let slot = registry_page.register_pool(&pool.key())?;
+    ctx.accounts.sure_pools.record_pool()?;
+
+    let insured_contract_info = &mut ctx.accounts.insured_contract_info;
+    insured_contract_info.initialize(
+        *ctx.bumps.get("insured_contract_info").unwrap(),
+        &ctx.accounts.smart_contract.key(),
+        &pool.key(),
+        registry_page.page,
+        slot,
+        Clock::get()?.unix_timestamp,
+    );
+
     emit!(CreatePool {
         name: name,
         smart_contract: ctx.accounts.smart_contract.key(),
     });
+    emit!(RegisteredPool {
+        pool: pool.key(),
+        smart_contract: ctx.accounts.smart_contract.key(),
+        page: registry_page.page,
+        slot,
+    });
+    emit!(InitializedTokenPool {
+        pool: pool.key(),
+        token_pool: ctx.accounts.token_pool.key(),
+        token_mint: ctx.accounts.token_mint_0.key(),
+    });
 
     Ok(())
 }
@@ -112,3 +228,12 @@ pub struct CreatePool {
     pub name: String,
     pub smart_contract: Pubkey,
 }
+
+#[event]
+pub struct RegisteredPool {
+    #[index]
+    pub pool: Pubkey,
+    pub smart_contract: Pubkey,
+    pub page: u16,
+    pub slot: u8,
+}
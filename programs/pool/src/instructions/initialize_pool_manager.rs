@@ -0,0 +1,53 @@
+use crate::states::pool::*;
+use crate::utils::events::InitializedManager;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+/// Initialize pool manager
+///
+/// creates the pool manager owned by the upgrade
+/// authority of the program
+#[derive(Accounts)]
+pub struct InitializePoolManager<'info> {
+    /// Upgrade authority of the program
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump,
+        space = 8 + PoolManager::SIZE,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    /// Program data of the program
+    #[account(
+        seeds = [
+            crate::ID.as_ref(),
+        ],
+        bump,
+        seeds::program = bpf_loader_upgradeable::id(),
+        constraint = program_data.upgrade_authority_address == Some(owner.key()) @ SureError::InvalidOwner,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializePoolManager>) -> Result<()> {
+    let pool_manager = &mut ctx.accounts.pool_manager;
+    pool_manager.initialize(
+        *ctx.bumps.get("pool_manager").unwrap(),
+        &ctx.accounts.owner.key(),
+    );
+
+    emit!(InitializedManager {
+        owner: pool_manager.owner,
+    });
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::states::registry::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Initialize the pool registry
+///
+/// creates the registry header and the first
+/// registry page
+#[derive(Accounts)]
+pub struct InitializePoolRegistry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            SURE_INSURANCE_CONTRACTS.as_bytes(),
+        ],
+        bump,
+        space = 8 + SurePools::SPACE,
+    )]
+    pub sure_pools: Box<Account<'info, SurePools>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            SURE_INSURANCE_CONTRACTS_BITMAP.as_bytes(),
+            0_u16.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 8 + SurePoolsPage::SPACE,
+    )]
+    pub registry_page: AccountLoader<'info, SurePoolsPage>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializePoolRegistry>) -> Result<()> {
+    let sure_pools = ctx.accounts.sure_pools.as_mut();
+    sure_pools.initialize(*ctx.bumps.get("sure_pools").unwrap());
+    let page = sure_pools.add_page()?;
+
+    let mut registry_page = ctx.accounts.registry_page.load_init()?;
+    registry_page.initialize(*ctx.bumps.get("registry_page").unwrap(), page);
+
+    emit!(InitializedPoolRegistry {
+        sure_pools: sure_pools.key(),
+        registry_page: ctx.accounts.registry_page.key(),
+    });
+    Ok(())
+}
+
+#[event]
+pub struct InitializedPoolRegistry {
+    pub sure_pools: Pubkey,
+    pub registry_page: Pubkey,
+}
@@ -0,0 +1,56 @@
+use crate::states::registry::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Initialize registry page
+///
+/// adds a page to the registry when the
+/// previous pages are full
+#[derive(Accounts)]
+pub struct InitializePoolRegistryPage<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SURE_INSURANCE_CONTRACTS.as_bytes(),
+        ],
+        bump = sure_pools.bump,
+    )]
+    pub sure_pools: Box<Account<'info, SurePools>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            SURE_INSURANCE_CONTRACTS_BITMAP.as_bytes(),
+            sure_pools.pages.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 8 + SurePoolsPage::SPACE,
+    )]
+    pub registry_page: AccountLoader<'info, SurePoolsPage>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializePoolRegistryPage>) -> Result<()> {
+    let sure_pools = ctx.accounts.sure_pools.as_mut();
+    let page = sure_pools.add_page()?;
+
+    let mut registry_page = ctx.accounts.registry_page.load_init()?;
+    registry_page.initialize(*ctx.bumps.get("registry_page").unwrap(), page);
+
+    emit!(InitializedPoolRegistryPage {
+        registry_page: ctx.accounts.registry_page.key(),
+        page,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct InitializedPoolRegistryPage {
+    pub registry_page: Pubkey,
+    pub page: u16,
+}
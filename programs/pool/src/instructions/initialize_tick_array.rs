@@ -0,0 +1,67 @@
+use crate::states::pool::*;
+use crate::states::tick_array::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+/// Initialize tick array
+///
+/// creates the tick array of the token mint starting at
+/// start_tick_index. Tick arrays are created on demand by
+/// anyone who needs them to provide liquidity or buy coverage
+#[derive(Accounts)]
+#[instruction(start_tick_index: i32)]
+pub struct InitializeTickArray<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Pool the tick array belongs to
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Mint of the liquidity in the tick array.
+    /// Has to be accepted by the pool
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            SURE_TICK_SEED.as_bytes(),
+            pool.key().as_ref(),
+            token_mint.key().as_ref(),
+            start_tick_index.to_le_bytes().as_ref(),
+        ],
+        bump,
+        space = 8 + TickArray::SIZE,
+    )]
+    pub tick_array: AccountLoader<'info, TickArray>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTickArray>, start_tick_index: i32) -> Result<()> {
+    ctx.accounts.pool.can_extend()?;
+    let mut tick_array = ctx.accounts.tick_array.load_init()?;
+    tick_array.initialize(
+        &ctx.accounts.pool,
+        &ctx.accounts.token_mint.key(),
+        start_tick_index,
+    )?;
+
+    emit!(InitializedTickArray {
+        pool: ctx.accounts.pool.key(),
+        tick_array: ctx.accounts.tick_array.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        start_tick_index,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct InitializedTickArray {
+    #[index]
+    pub pool: Pubkey,
+    pub tick_array: Pubkey,
+    pub token_mint: Pubkey,
+    pub start_tick_index: i32,
+}
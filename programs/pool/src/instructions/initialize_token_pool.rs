@@ -0,0 +1,104 @@
+use crate::states::pool::*;
+use crate::states::token_pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Initialize token pool
+///
+/// adds a collateral mint to the pool. The token pool
+/// holds the liquidity and premium vaults of the mint
+#[derive(Accounts)]
+pub struct InitializeTokenPool<'info> {
+    /// Founder of the pool
+    #[account(mut)]
+    pub founder: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = founder @ SureError::InvalidOwner,
+        constraint = pool.token_mint_0 != token_mint.key() @ SureError::TokenPoolAlreadyExists,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Mint of the collateral
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = founder,
+        seeds = [
+            SURE_TOKEN_POOL_SEED.as_bytes(),
+            pool.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        space = 8 + TokenPool::SPACE,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
+    // Vault holding the liquidity of the token pool
+    #[account(
+        init,
+        payer = founder,
+        seeds = [
+            SURE_VAULT_POOL_SEED.as_bytes(),
+            token_pool.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    // Vault holding the premiums of the token pool
+    #[account(
+        init,
+        payer = founder,
+        seeds = [
+            SURE_PREMIUM_POOL_SEED.as_bytes(),
+            token_pool.key().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        token::mint = token_mint,
+        token::authority = pool,
+    )]
+    pub premium_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeTokenPool>) -> Result<()> {
+    let pool = ctx.accounts.pool.as_mut();
+    pool.can_extend()?;
+    let token_pool = ctx.accounts.token_pool.as_mut();
+    pool.add_token_pool(&token_pool.key())?;
+    token_pool.initialize(
+        *ctx.bumps.get("token_pool").unwrap(),
+        &pool.key(),
+        &ctx.accounts.token_mint.key(),
+        &ctx.accounts.vault.key(),
+        &ctx.accounts.premium_vault.key(),
+    );
+
+    emit!(InitializedTokenPool {
+        pool: pool.key(),
+        token_pool: token_pool.key(),
+        token_mint: token_pool.token_mint,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct InitializedTokenPool {
+    #[index]
+    pub pool: Pubkey,
+    pub token_pool: Pubkey,
+    pub token_mint: Pubkey,
+}
@@ -0,0 +1,48 @@
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Lock pool for claim
+///
+/// Called by the oracle through CPI signed by the
+/// proposal that claims against the pool
+#[derive(Accounts)]
+pub struct LockPoolForClaim<'info> {
+    /// Oracle proposal claiming against the pool
+    #[account(owner = SURE_ORACLE @ SureError::InvalidClaimAuthority)]
+    pub claim: Signer<'info>,
+
+    /// Pool manager holding the trusted oracle config
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+    )]
+    pub pool_manager: Box<Account<'info, PoolManager>>,
+
+    /// Pool to claim against
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+}
+
+pub fn handler(ctx: Context<LockPoolForClaim>) -> Result<()> {
+    ctx.accounts
+        .pool_manager
+        .validate_claim(&ctx.accounts.claim.to_account_info())?;
+    let pool = ctx.accounts.pool.as_mut();
+    pool.lock_for_claim(&ctx.accounts.claim.key())?;
+
+    emit!(LockedPoolForClaim {
+        pool: pool.key(),
+        claim: ctx.accounts.claim.key(),
+    });
+    Ok(())
+}
+
+#[event]
+pub struct LockedPoolForClaim {
+    #[index]
+    pub pool: Pubkey,
+    pub claim: Pubkey,
+}
@@ -1,15 +1,61 @@
+pub mod accept_pool_manager;
+pub mod collect_founders_fees;
+pub mod collect_protocol_fees;
 pub mod decrease_coverage_position;
 pub mod decrease_liquidity_position;
+pub mod expire_coverage;
 pub mod increase_coverage_position;
 pub mod increase_liquidity_position;
 pub mod initialize_coverage_position;
+pub mod initialize_fee_package;
 pub mod initialize_liquidity_position;
 pub mod initialize_pool;
+pub mod initialize_pool_manager;
+pub mod initialize_pool_registry;
+pub mod initialize_pool_registry_page;
+pub mod initialize_tick_array;
+pub mod initialize_token_pool;
+pub mod lock_pool_for_claim;
+pub mod pay_claim;
+pub mod quote_coverage;
+pub mod release_creation_bond;
+pub mod release_pool_claim;
+pub mod slash_creation_bond;
+pub mod transfer_pool_manager;
+pub mod update_fee_package;
+pub mod update_insured_contract_info;
+pub mod update_oracle_config;
+pub mod update_pool_fee_package;
+pub mod update_pool_manager_policy;
+pub mod update_pool_status;
 
+pub use accept_pool_manager::*;
+pub use collect_founders_fees::*;
+pub use collect_protocol_fees::*;
 pub use decrease_coverage_position::*;
 pub use decrease_liquidity_position::*;
+pub use expire_coverage::*;
 pub use increase_coverage_position::*;
 pub use increase_liquidity_position::*;
 pub use initialize_coverage_position::*;
+pub use initialize_fee_package::*;
 pub use initialize_liquidity_position::*;
 pub use initialize_pool::*;
+pub use initialize_pool_manager::*;
+pub use initialize_pool_registry::*;
+pub use initialize_pool_registry_page::*;
+pub use initialize_tick_array::*;
+pub use initialize_token_pool::*;
+pub use lock_pool_for_claim::*;
+pub use pay_claim::*;
+pub use quote_coverage::*;
+pub use release_creation_bond::*;
+pub use release_pool_claim::*;
+pub use slash_creation_bond::*;
+pub use transfer_pool_manager::*;
+pub use update_fee_package::*;
+pub use update_insured_contract_info::*;
+pub use update_oracle_config::*;
+pub use update_pool_fee_package::*;
+pub use update_pool_manager_policy::*;
+pub use update_pool_status::*;
@@ -0,0 +1,177 @@
+use crate::states::coverage::CoveragePosition;
+use crate::states::pool::{Pool, PoolManager};
+use crate::states::tick_array::{TickArray, TickArrayPool};
+use crate::states::token_pool::TokenPool;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Pay claim
+///
+/// Called by the oracle through CPI when the claim
+/// against the pool is accepted
+#[derive(Accounts)]
+pub struct PayClaim<'info> {
+    /// Oracle proposal holding the claim
+    #[account(owner = SURE_ORACLE @ SureError::InvalidClaimAuthority)]
+    pub claim: Signer<'info>,
+
+    /// Pool manager holding the trusted oracle config
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+    )]
+    pub pool_manager: Box<Account<'info, PoolManager>>,
+
+    /// Pool that is claimed against
+    #[account(
+        constraint = pool.active_claim == claim.key() @ SureError::InvalidClaim,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Token pool the coverage is bought from
+    #[account(
+        mut,
+        has_one = pool @ SureError::InvalidTokenPool,
+        constraint = token_pool.vault == liquidity_vault.key() @ SureError::InvalidTokenPool,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
+    /// Coverage position paid out
+    #[account(
+        mut,
+        constraint = coverage_position.load()?.pool == pool.key() @ SureError::InvalidCoveragePool,
+        constraint = coverage_position.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub coverage_position: AccountLoader<'info, CoveragePosition>,
+
+    /// Token account of the coverage owner to pay to
+    #[account(
+        mut,
+        constraint = claimant_token_account.owner == coverage_position.load()?.owner @ SureError::InvalidOwner,
+        constraint = claimant_token_account.mint == liquidity_vault.mint @ SureError::InvalidMint,
+    )]
+    pub claimant_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Liquidity vault of the token pool
+    #[account(mut)]
+    pub liquidity_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Tick array 0
+    /// Array holding liquidity used by the position
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_0.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_0: AccountLoader<'info, TickArray>,
+
+    /// Tick array 1
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_1.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_1: AccountLoader<'info, TickArray>,
+
+    /// Tick array 2
+    #[account(
+        mut,
+        has_one = pool,
+        constraint = tick_array_2.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_2: AccountLoader<'info, TickArray>,
+
+    /// Token program to transfer the payout
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pay claim handler
+///
+/// Pays the claim from the liquidity vault and charges
+/// the loss to the ticks the coverage is bought from.
+/// The loss is spread over the liquidity providers of each
+/// tick through the loss growth and is applied to the
+/// liquidity positions the next time they are changed.
+///
+/// The tick arrays have to hold all the ticks of the
+/// coverage position
+///
+/// # Arguments
+/// * claim_amount: the amount to pay to the coverage owner
+pub fn handler(ctx: Context<PayClaim>, claim_amount: u64) -> Result<()> {
+    ctx.accounts
+        .pool_manager
+        .validate_claim(&ctx.accounts.claim.to_account_info())?;
+    let pool = ctx.accounts.pool.as_ref();
+    let token_pool = ctx.accounts.token_pool.as_mut();
+    pool.can_pay_claim()?;
+    let tick_spacing = pool.tick_spacing;
+    let mut coverage_position = ctx.accounts.coverage_position.load_mut()?;
+
+    if !coverage_position.active {
+        return Err(SureError::InsuranceContractIsNotActive.into());
+    }
+    if claim_amount > ctx.accounts.liquidity_vault.amount {
+        return Err(SureError::InvalidAmount.into());
+    }
+
+    let mut tick_array_pool = TickArrayPool::load_mut(
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+    )?;
+
+    // charge the claim to the ticks in the tick ledger
+    let tick_losses = coverage_position.claim_coverage(claim_amount)?;
+    for tick_loss in tick_losses.iter() {
+        let tick_index = tick_loss.tick_index;
+        let array_index = tick_array_pool
+            .find_array_index(tick_index, tick_spacing)
+            .ok_or(SureError::TickOutOfRange)?;
+        tick_array_pool.record_tick_loss(
+            array_index,
+            tick_index,
+            tick_spacing,
+            tick_loss.amount as u128,
+        )?;
+    }
+    // the payout leaves the token pool
+    token_pool.update_used_liquidity(-(claim_amount as i128))?;
+    token_pool.update_liquidity(-(claim_amount as i128))?;
+    let owner = coverage_position.owner;
+    drop(coverage_position);
+    drop(tick_array_pool);
+
+    withdraw_from_vault(
+        &ctx.accounts.pool,
+        &ctx.accounts.liquidity_vault,
+        &ctx.accounts.claimant_token_account,
+        &ctx.accounts.token_program,
+        claim_amount,
+    )?;
+
+    emit!(PaidClaim {
+        pool: ctx.accounts.pool.key(),
+        claim: ctx.accounts.claim.key(),
+        coverage_position: ctx.accounts.coverage_position.key(),
+        owner,
+        claim_amount,
+        ticks_charged: tick_losses.len() as u32,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct PaidClaim {
+    #[index]
+    pub pool: Pubkey,
+    pub claim: Pubkey,
+    pub coverage_position: Pubkey,
+    pub owner: Pubkey,
+    pub claim_amount: u64,
+    /// number of ticks the loss is charged to
+    pub ticks_charged: u32,
+}
@@ -0,0 +1,116 @@
+use crate::managers::coverage::get_coverage_quote;
+use crate::states::pool::Pool;
+use crate::states::tick_array::TickArray;
+use crate::states::token_pool::TokenPool;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Quote coverage
+///
+/// Read-only instruction that prices coverage in the
+/// pool. Meant to be simulated by buyers before
+/// buying coverage.
+#[derive(Accounts)]
+pub struct QuoteCoverage<'info> {
+    /// Pool to quote coverage from
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Token pool of the mint to quote coverage in
+    #[account(
+        has_one = pool @ SureError::InvalidTokenPool,
+    )]
+    pub token_pool: Box<Account<'info, TokenPool>>,
+
+    /// Tick array 0
+    /// First array to buy insurance from
+    #[account(
+        has_one = pool,
+        constraint = tick_array_0.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_0: AccountLoader<'info, TickArray>,
+
+    /// Tick array 1
+    /// Array after tick array 0 to buy from
+    #[account(
+        has_one = pool,
+        constraint = tick_array_1.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_1: AccountLoader<'info, TickArray>,
+
+    /// Tick array 2
+    /// Array after tick array 1 to buy from
+    #[account(
+        has_one = pool,
+        constraint = tick_array_2.load()?.token_mint == token_pool.token_mint @ SureError::InvalidMint,
+    )]
+    pub tick_array_2: AccountLoader<'info, TickArray>,
+}
+
+/// Quote coverage handler
+///
+/// Calculates the coverage in the same way as when
+/// increasing a coverage position and emits the quote.
+/// The same tick array can be passed several times
+/// at the end of the tick range.
+pub fn handler(ctx: Context<QuoteCoverage>, coverage_amount: u128, expiry_ts: i64) -> Result<()> {
+    let pool = ctx.accounts.pool.as_ref();
+    let current_ts = Clock::get()?.unix_timestamp;
+
+    let mut tick_array_keys = Vec::with_capacity(3);
+    let mut tick_arrays = Vec::with_capacity(3);
+    for tick_array in [
+        &ctx.accounts.tick_array_0,
+        &ctx.accounts.tick_array_1,
+        &ctx.accounts.tick_array_2,
+    ] {
+        if !tick_array_keys.contains(&tick_array.key()) {
+            tick_array_keys.push(tick_array.key());
+            tick_arrays.push(tick_array.load()?);
+        }
+    }
+    let tick_array_refs: Vec<&TickArray> = tick_arrays.iter().map(|ta| &**ta).collect();
+
+    let quote = get_coverage_quote(
+        pool,
+        &tick_array_refs,
+        coverage_amount,
+        current_ts,
+        expiry_ts,
+    )?;
+
+    emit!(QuotedCoverage {
+        pool: pool.key(),
+        token_mint: ctx.accounts.token_pool.token_mint,
+        requested_amount: coverage_amount,
+        coverage_amount: quote.coverage_amount,
+        premium: quote.premium,
+        fee_amount: quote.fee_amount,
+        protocol_fee: quote.protocol_fee,
+        founders_fee: quote.founders_fee,
+        ticks_crossed: quote.ticks_crossed(),
+        start_tick_index: quote.ticks.first().map_or(0, |tick| tick.tick_index),
+        end_tick_index: quote.ticks.last().map_or(0, |tick| tick.tick_index),
+        expiry_ts,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct QuotedCoverage {
+    #[index]
+    pub pool: Pubkey,
+    pub token_mint: Pubkey,
+    pub requested_amount: u128,
+    /// amount that can be covered
+    pub coverage_amount: u128,
+    pub premium: u128,
+    /// total fee including protocol and founders fees
+    pub fee_amount: u128,
+    pub protocol_fee: u128,
+    pub founders_fee: u128,
+    pub ticks_crossed: u32,
+    /// first and last tick coverage is bought from
+    pub start_tick_index: i32,
+    pub end_tick_index: i32,
+    pub expiry_ts: i64,
+}
@@ -0,0 +1,56 @@
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Release creation bond
+///
+/// returns the creation bond to the founder
+/// once the pool manager approves the pool
+#[derive(Accounts)]
+pub struct ReleaseCreationBond<'info> {
+    /// Owner of the pool manager
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    #[account(
+        mut,
+        has_one = founder @ SureError::InvalidOwner,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Founder of the pool receiving the bond
+    #[account(mut)]
+    pub founder: SystemAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ReleaseCreationBond>) -> Result<()> {
+    let creation_bond = ctx.accounts.pool.take_creation_bond()?;
+    withdraw_lamports_from_pool(
+        &ctx.accounts.pool,
+        &ctx.accounts.founder.to_account_info(),
+        creation_bond,
+    )?;
+
+    emit!(ReleasedCreationBond {
+        pool: ctx.accounts.pool.key(),
+        founder: ctx.accounts.founder.key(),
+        creation_bond,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct ReleasedCreationBond {
+    #[index]
+    pub pool: Pubkey,
+    pub founder: Pubkey,
+    pub creation_bond: u64,
+}
@@ -0,0 +1,50 @@
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Slash creation bond
+///
+/// moves the creation bond to the owner of the
+/// pool manager, e.g. for a spam or malicious pool
+#[derive(Accounts)]
+pub struct SlashCreationBond<'info> {
+    /// Owner of the pool manager receiving the bond
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+}
+
+pub fn handler(ctx: Context<SlashCreationBond>) -> Result<()> {
+    let creation_bond = ctx.accounts.pool.take_creation_bond()?;
+    withdraw_lamports_from_pool(
+        &ctx.accounts.pool,
+        &ctx.accounts.owner.to_account_info(),
+        creation_bond,
+    )?;
+
+    emit!(SlashedCreationBond {
+        pool: ctx.accounts.pool.key(),
+        founder: ctx.accounts.pool.founder,
+        creation_bond,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct SlashedCreationBond {
+    #[index]
+    pub pool: Pubkey,
+    pub founder: Pubkey,
+    pub creation_bond: u64,
+}
@@ -0,0 +1,40 @@
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Transfer pool manager
+///
+/// proposes a new owner of the pool manager. The
+/// new owner has to accept the pool manager
+#[derive(Accounts)]
+pub struct TransferPoolManager<'info> {
+    /// Current owner of the pool manager
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+}
+
+pub fn handler(ctx: Context<TransferPoolManager>, new_owner: Pubkey) -> Result<()> {
+    let pool_manager = &mut ctx.accounts.pool_manager;
+    pool_manager.propose_owner(&new_owner);
+
+    emit!(ProposedPoolManagerOwner {
+        owner: pool_manager.owner,
+        pending_owner: new_owner,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct ProposedPoolManagerOwner {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
@@ -0,0 +1,55 @@
+use crate::states::fee_package::*;
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Update fee package
+///
+/// updates the rates of the fee package. Only the owner of
+/// the pool manager can update fee packages. Existing pools
+/// keep their rates until they are migrated
+#[derive(Accounts)]
+pub struct UpdateFeePackage<'info> {
+    /// Owner of the pool manager
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    #[account(mut)]
+    pub fee_package: Account<'info, FeePackage>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateFeePackage>,
+    fee_rate: u16,
+    protocol_fee: u16,
+    founders_fee: u16,
+) -> Result<()> {
+    let fee_package = &mut ctx.accounts.fee_package;
+    fee_package.update_fee_package(fee_rate, protocol_fee, founders_fee);
+    fee_package.validate_fee_rates()?;
+
+    emit!(UpdatedFeePackage {
+        fee_package: fee_package.key(),
+        fee_rate,
+        protocol_fee,
+        founders_fee,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedFeePackage {
+    #[index]
+    pub fee_package: Pubkey,
+    pub fee_rate: u16,
+    pub protocol_fee: u16,
+    pub founders_fee: u16,
+}
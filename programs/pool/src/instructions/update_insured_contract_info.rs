@@ -0,0 +1,88 @@
+use crate::states::pool::*;
+use crate::states::registry::*;
+use crate::states::token_pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+/// Update insured contract info
+///
+/// crank that updates the TVL and coverage of
+/// each token pool of a registry entry. Anyone can call it
+#[derive(Accounts)]
+pub struct UpdateInsuredContractInfo<'info> {
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        has_one = pool @ SureError::InvalidInsuredContractInfo,
+        seeds = [
+            SURE_INSURANCE_CONTRACTS_INFO.as_bytes(),
+            insured_contract_info.smart_contract.as_ref(),
+        ],
+        bump = insured_contract_info.bump,
+    )]
+    pub insured_contract_info: Box<Account<'info, InsuredContractInfo>>,
+}
+
+/// Update insured contract info handler
+///
+/// The TVL is the liquidity in the vault of each token
+/// pool, including the token pool of the primary mint. The
+/// coverage is the liquidity used in the token pool. Both
+/// are kept per mint since the mints have different decimals
+///
+/// ### remaining accounts
+/// pairs of, for every token pool of the pool
+/// * token_pool: token pool of the pool
+/// * vault: the liquidity vault of the token pool
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, UpdateInsuredContractInfo<'info>>,
+) -> Result<()> {
+    let time = Clock::get()?.unix_timestamp;
+    let pool = ctx.accounts.pool.as_ref();
+    let remaining_accounts = ctx.remaining_accounts;
+    if remaining_accounts.len() % 2 != 0 {
+        return Err(SureError::MissingTokenPools.into());
+    }
+
+    let mut token_pools = Vec::with_capacity(remaining_accounts.len() / 2);
+    let mut token_pool_infos = Vec::with_capacity(remaining_accounts.len() / 2);
+    for accounts in remaining_accounts.chunks(2) {
+        let token_pool = Account::<TokenPool>::try_from(&accounts[0])?;
+        let vault = Account::<TokenAccount>::try_from(&accounts[1])?;
+        if token_pool.pool != pool.key() {
+            return Err(SureError::InvalidTokenPool.into());
+        }
+        if token_pool.vault != vault.key() {
+            return Err(SureError::InvalidTokenPool.into());
+        }
+
+        token_pools.push(token_pool.key());
+        token_pool_infos.push(TokenPoolInfo {
+            token_mint: token_pool.token_mint,
+            tvl: vault.amount,
+            coverage: token_pool.used_liquidity,
+        });
+    }
+    pool.validate_token_pools(&token_pools)?;
+
+    let insured_contract_info = ctx.accounts.insured_contract_info.as_mut();
+    insured_contract_info.update_aggregates(token_pool_infos.clone(), time)?;
+
+    emit!(UpdatedInsuredContractInfo {
+        pool: pool.key(),
+        smart_contract: insured_contract_info.smart_contract,
+        token_pools: token_pool_infos,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedInsuredContractInfo {
+    #[index]
+    pub pool: Pubkey,
+    pub smart_contract: Pubkey,
+    /// TVL and coverage of each token pool
+    pub token_pools: Vec<TokenPoolInfo>,
+}
@@ -0,0 +1,41 @@
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Update oracle config
+///
+/// sets the oracle config whose proposals
+/// can claim against the pools
+#[derive(Accounts)]
+pub struct UpdateOracleConfig<'info> {
+    /// Owner of the pool manager
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+}
+
+pub fn handler(ctx: Context<UpdateOracleConfig>, oracle_config: Pubkey) -> Result<()> {
+    let pool_manager = &mut ctx.accounts.pool_manager;
+    let old_oracle_config = pool_manager.oracle_config;
+    pool_manager.update_oracle_config(&oracle_config);
+
+    emit!(UpdatedOracleConfig {
+        old_oracle_config,
+        oracle_config,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedOracleConfig {
+    pub old_oracle_config: Pubkey,
+    pub oracle_config: Pubkey,
+}
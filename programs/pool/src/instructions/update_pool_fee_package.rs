@@ -0,0 +1,56 @@
+use crate::states::fee_package::*;
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Update pool fee package
+///
+/// migrates the pool to a new fee package. Only the
+/// owner of the pool manager can migrate pools
+#[derive(Accounts)]
+pub struct UpdatePoolFeePackage<'info> {
+    /// Owner of the pool manager
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Fee package to migrate the pool to
+    pub fee_package: Account<'info, FeePackage>,
+}
+
+pub fn handler(ctx: Context<UpdatePoolFeePackage>) -> Result<()> {
+    let pool = ctx.accounts.pool.as_mut();
+    let old_fee_package = pool.fee_package;
+    pool.set_fee_package(&ctx.accounts.fee_package);
+
+    emit!(UpdatedPoolFeePackage {
+        pool: pool.key(),
+        old_fee_package,
+        fee_package: pool.fee_package,
+        fee_rate: pool.fee_rate,
+        protocol_fee: pool.protocol_fee,
+        founders_fee: pool.founders_fee,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedPoolFeePackage {
+    #[index]
+    pub pool: Pubkey,
+    pub old_fee_package: Pubkey,
+    pub fee_package: Pubkey,
+    pub fee_rate: u16,
+    pub protocol_fee: u16,
+    pub founders_fee: u16,
+}
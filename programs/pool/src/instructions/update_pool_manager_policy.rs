@@ -0,0 +1,44 @@
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Update pool manager policy
+///
+/// sets who can create pools and the bond
+/// required to create one
+#[derive(Accounts)]
+pub struct UpdatePoolManagerPolicy<'info> {
+    /// Owner of the pool manager
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+}
+
+pub fn handler(
+    ctx: Context<UpdatePoolManagerPolicy>,
+    permissionless: bool,
+    creation_bond: u64,
+) -> Result<()> {
+    let pool_manager = &mut ctx.accounts.pool_manager;
+    pool_manager.update_policy(permissionless, creation_bond);
+
+    emit!(UpdatedPoolManagerPolicy {
+        permissionless,
+        creation_bond,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedPoolManagerPolicy {
+    pub permissionless: bool,
+    pub creation_bond: u64,
+}
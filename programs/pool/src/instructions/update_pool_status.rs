@@ -0,0 +1,45 @@
+use crate::states::pool::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+/// Update pool status
+///
+/// pauses the pool or puts it in claims only mode,
+/// e.g. when an incident is reported
+#[derive(Accounts)]
+pub struct UpdatePoolStatus<'info> {
+    /// Owner of the pool manager
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [
+            SURE_PROTOCOL_OWNER.as_bytes(),
+        ],
+        bump = pool_manager.bump,
+        has_one = owner @ SureError::InvalidOwner,
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    #[account(mut)]
+    pub pool: Box<Account<'info, Pool>>,
+}
+
+pub fn handler(ctx: Context<UpdatePoolStatus>, paused: bool, claims_only: bool) -> Result<()> {
+    let pool = ctx.accounts.pool.as_mut();
+    pool.set_status(paused, claims_only);
+
+    emit!(UpdatedPoolStatus {
+        pool: pool.key(),
+        paused,
+        claims_only,
+    });
+    Ok(())
+}
+
+#[event]
+pub struct UpdatedPoolStatus {
+    #[index]
+    pub pool: Pubkey,
+    pub paused: bool,
+    pub claims_only: bool,
+}
@@ -21,6 +21,155 @@ pub mod sure_pool {
 
     use super::*;
 
+    // ------------ Pool Manager -----------------------------------------------
+    /// Initialize the pool manager
+    ///
+    /// Only callable by the upgrade authority of the program.
+    /// Pool creation is permissioned by default
+    pub fn initialize_pool_manager(ctx: Context<InitializePoolManager>) -> Result<()> {
+        instructions::initialize_pool_manager::handler(ctx)
+    }
+
+    /// Transfer the pool manager to a new owner
+    ///
+    /// The new owner has to accept the pool manager
+    /// before the ownership is transferred
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * new_owner: the proposed owner of the pool manager
+    pub fn transfer_pool_manager(
+        ctx: Context<TransferPoolManager>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        instructions::transfer_pool_manager::handler(ctx, new_owner)
+    }
+
+    /// Accept the ownership of the pool manager
+    ///
+    /// Only callable by the proposed owner
+    pub fn accept_pool_manager(ctx: Context<AcceptPoolManager>) -> Result<()> {
+        instructions::accept_pool_manager::handler(ctx)
+    }
+
+    /// Update who can create pools
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * permissionless: allow anyone to create pools
+    /// * creation_bond: lamports locked in the pool when created
+    ///     by someone else than the owner
+    pub fn update_pool_manager_policy(
+        ctx: Context<UpdatePoolManagerPolicy>,
+        permissionless: bool,
+        creation_bond: u64,
+    ) -> Result<()> {
+        instructions::update_pool_manager_policy::handler(ctx, permissionless, creation_bond)
+    }
+
+    /// Set the oracle config whose proposals can claim
+    /// against the pools
+    ///
+    /// Only callable by the owner of the pool manager
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * oracle_config: config of the oracle proposals
+    pub fn update_oracle_config(
+        ctx: Context<UpdateOracleConfig>,
+        oracle_config: Pubkey,
+    ) -> Result<()> {
+        instructions::update_oracle_config::handler(ctx, oracle_config)
+    }
+
+    /// Return the creation bond of a pool to its founder
+    ///
+    /// Only callable by the owner of the pool manager
+    pub fn release_creation_bond(ctx: Context<ReleaseCreationBond>) -> Result<()> {
+        instructions::release_creation_bond::handler(ctx)
+    }
+
+    /// Slash the creation bond of a pool
+    ///
+    /// Only callable by the owner of the pool manager.
+    /// The bond is sent to the owner
+    pub fn slash_creation_bond(ctx: Context<SlashCreationBond>) -> Result<()> {
+        instructions::slash_creation_bond::handler(ctx)
+    }
+
+    // ------------ Registry -----------------------------------------------
+    /// Initialize the pool registry
+    ///
+    /// Creates the registry header and the first page.
+    /// Has to be called once before any pool is created
+    pub fn initialize_pool_registry(ctx: Context<InitializePoolRegistry>) -> Result<()> {
+        instructions::initialize_pool_registry::handler(ctx)
+    }
+
+    /// Add a page to the pool registry
+    ///
+    /// Each page holds 256 pools
+    pub fn initialize_pool_registry_page(ctx: Context<InitializePoolRegistryPage>) -> Result<()> {
+        instructions::initialize_pool_registry_page::handler(ctx)
+    }
+
+    /// Update the TVL and coverage of a registry entry
+    ///
+    /// Permissionless crank. The token pools of the pool
+    /// and their vaults are passed as remaining accounts
+    pub fn update_insured_contract_info<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateInsuredContractInfo<'info>>,
+    ) -> Result<()> {
+        instructions::update_insured_contract_info::handler(ctx)
+    }
+
+    // ------------ Fee Package -----------------------------------------------
+    /// Create a fee package owned by the protocol
+    ///
+    /// Only callable by the owner of the pool manager
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * fee_package_id: id used to derive the fee package address
+    /// * fee_rate: fee taken on coverage bought
+    /// * protocol_fee: share of the fee going to the protocol. In basis points
+    /// * founders_fee: share of the fee going to the pool founder. In basis points
+    pub fn initialize_fee_package(
+        ctx: Context<InitializeFeePackage>,
+        fee_package_id: u16,
+        fee_rate: u16,
+        protocol_fee: u16,
+        founders_fee: u16,
+    ) -> Result<()> {
+        instructions::initialize_fee_package::handler(
+            ctx,
+            fee_package_id,
+            fee_rate,
+            protocol_fee,
+            founders_fee,
+        )
+    }
+
+    /// Update the rates of a fee package
+    ///
+    /// Only callable by the owner of the pool manager.
+    /// Pools keep their rates until they are migrated
+    pub fn update_fee_package(
+        ctx: Context<UpdateFeePackage>,
+        fee_rate: u16,
+        protocol_fee: u16,
+        founders_fee: u16,
+    ) -> Result<()> {
+        instructions::update_fee_package::handler(ctx, fee_rate, protocol_fee, founders_fee)
+    }
+
+    /// Migrate a pool to a new fee package
+    ///
+    /// Only callable by the owner of the pool manager
+    pub fn update_pool_fee_package(ctx: Context<UpdatePoolFeePackage>) -> Result<()> {
+        instructions::update_pool_fee_package::handler(ctx)
+    }
+
     // ------------ Pool -----------------------------------------------
     /// Create an insurance pool for a smart contract
     /// also create an associated vault to hold the tokens
@@ -37,4 +186,167 @@ pub mod sure_pool {
     ) -> Result<()> {
         instructions::initialize_pool::handler(ctx, name, tick_spacing)
     }
+
+    /// Add a collateral mint to the pool
+    ///
+    /// Only callable by the founder of the pool.
+    /// Creates a token pool with its own vaults so that
+    /// a protocol can be insured with liquidity in several mints
+    pub fn initialize_token_pool(ctx: Context<InitializeTokenPool>) -> Result<()> {
+        instructions::initialize_token_pool::handler(ctx)
+    }
+
+    // ------------ Ticks -----------------------------------------------
+    /// Initialize a tick array for a collateral mint of the pool
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * start_tick_index: first tick in the array. Has to be a multiple
+    ///     of NUM_TICKS_IN_TICK_ARRAY*tick_spacing
+    pub fn initialize_tick_array(
+        ctx: Context<InitializeTickArray>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        instructions::initialize_tick_array::handler(ctx, start_tick_index)
+    }
+
+    // ------------ Liquidity -----------------------------------------------
+    /// Initialize a liquidity position
+    ///
+    /// Mints an NFT representing the position over
+    /// the ticks [tick_lower, tick_upper)
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * tick_lower: first tick of the position
+    /// * tick_upper: tick after the last tick of the position
+    pub fn initialize_liquidity_position(
+        ctx: Context<InitializeLiquidityPosition>,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<()> {
+        instructions::initialize_liquidity_position::handler(ctx, tick_lower, tick_upper)
+    }
+
+    /// Increase liquidity position
+    ///
+    /// Settles the claim losses of the position
+    /// before the liquidity is deposited
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * liquidity_amount: liquidity to deposit into each tick of the position
+    pub fn increase_liquidity_position(
+        ctx: Context<UpdateLiquidity>,
+        liquidity_amount: u128,
+    ) -> Result<()> {
+        instructions::increase_liquidity_position::handler(ctx, liquidity_amount)
+    }
+
+    /// Decrease liquidity position
+    ///
+    /// Settles the claim losses of the position before
+    /// the liquidity is withdrawn. Only the liquidity not
+    /// used for coverage can be withdrawn
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * liquidity_amount: liquidity to withdraw from each tick of the position.
+    ///     Capped at the liquidity left in the tick
+    pub fn decrease_liquidity_position(
+        ctx: Context<UpdateLiquidity>,
+        liquidity_amount: u128,
+    ) -> Result<()> {
+        instructions::decrease_liquidity_position::handler(ctx, liquidity_amount)
+    }
+
+    // ------------ Coverage -----------------------------------------------
+    /// Quote coverage
+    ///
+    /// Read-only instruction that emits the coverage that can
+    /// be bought from the tick arrays. Simulate the transaction
+    /// to get the price of coverage before buying it.
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * coverage_amount: the amount to cover
+    /// * expiry_ts: the expiry of the coverage
+    pub fn quote_coverage(
+        ctx: Context<QuoteCoverage>,
+        coverage_amount: u128,
+        expiry_ts: i64,
+    ) -> Result<()> {
+        instructions::quote_coverage::handler(ctx, coverage_amount, expiry_ts)
+    }
+
+    /// Expire coverage
+    ///
+    /// Permissionless crank that releases the liquidity used
+    /// by an expired coverage position. The keeper is paid a
+    /// tip out of the protocol fees owed by the token pool
+    /// once the position is expired
+    pub fn expire_coverage(ctx: Context<ExpireCoverage>) -> Result<()> {
+        instructions::expire_coverage::handler(ctx)
+    }
+
+    // ------------ Fees -----------------------------------------------
+    /// Collect the protocol fees owed by a token pool
+    ///
+    /// Only callable by the owner of the pool manager
+    pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+        instructions::collect_protocol_fees::handler(ctx)
+    }
+
+    /// Collect the founders fees owed by a token pool
+    ///
+    /// Only callable by the founder of the pool
+    pub fn collect_founders_fees(ctx: Context<CollectFoundersFees>) -> Result<()> {
+        instructions::collect_founders_fees::handler(ctx)
+    }
+
+    // ------------ Pool Status -----------------------------------------------
+    /// Pause a pool or put it in claims only mode
+    ///
+    /// Only callable by the owner of the pool manager
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * paused: no coverage or liquidity changes
+    /// * claims_only: no new coverage and no liquidity withdrawals
+    pub fn update_pool_status(
+        ctx: Context<UpdatePoolStatus>,
+        paused: bool,
+        claims_only: bool,
+    ) -> Result<()> {
+        instructions::update_pool_status::handler(ctx, paused, claims_only)
+    }
+
+    // ------------ Claims -----------------------------------------------
+    /// Lock the pool for a claim
+    ///
+    /// Only callable by an oracle proposal through CPI.
+    /// Locks the pool against new coverage during the vote
+    pub fn lock_pool_for_claim(ctx: Context<LockPoolForClaim>) -> Result<()> {
+        instructions::lock_pool_for_claim::handler(ctx)
+    }
+
+    /// Pay the claim to a coverage position
+    ///
+    /// Only callable by the oracle proposal holding the claim.
+    /// The loss is charged to the liquidity providers of the
+    /// ticks the coverage is bought from
+    ///
+    /// # Arguments
+    /// * ctx:
+    /// * claim_amount: the amount to pay out
+    pub fn pay_claim(ctx: Context<PayClaim>, claim_amount: u64) -> Result<()> {
+        instructions::pay_claim::handler(ctx, claim_amount)
+    }
+
+    /// Release the claim on the pool
+    ///
+    /// Only callable by the oracle proposal holding the claim
+    pub fn release_pool_claim(ctx: Context<ReleasePoolClaim>) -> Result<()> {
+        instructions::release_pool_claim::handler(ctx)
+    }
 }
@@ -0,0 +1,465 @@
+use anchor_lang::prelude::*;
+
+use crate::states::{
+    coverage::CoverageTick,
+    pool::Pool,
+    tick_array::{calculate_sub_fee, TickArray, NUM_TICKS_IN_TICK_ARRAY},
+};
+use crate::utils::tick_math::{MAX_TICK_INDEX, MIN_TICK_INDEX};
+use crate::utils::*;
+
+/// Coverage bought from a single tick
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TickCoverage {
+    /// index of the tick array in the sequence
+    pub array_index: usize,
+    pub tick_index: i32,
+    /// liquidity used from the tick
+    pub amount: u128,
+    /// premium paid to the tick
+    pub premium: u128,
+    pub fee_amount: u128,
+}
+
+/// Coverage quote
+///
+/// The result of buying coverage from a
+/// sequence of tick arrays
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoverageQuote {
+    /// amount that can be covered by the
+    /// liquidity in the tick arrays
+    pub coverage_amount: u128,
+    pub premium: u128,
+    /// total fee including protocol and founders fees
+    pub fee_amount: u128,
+    pub protocol_fee: u128,
+    pub founders_fee: u128,
+    /// ticks coverage is bought from, lowest price first
+    pub ticks: Vec<TickCoverage>,
+}
+
+impl CoverageQuote {
+    /// Number of ticks crossed to cover the amount
+    pub fn ticks_crossed(&self) -> u32 {
+        self.ticks.len() as u32
+    }
+
+    /// Is the full amount covered
+    pub fn is_filled(&self, coverage_amount: u128) -> bool {
+        self.coverage_amount >= coverage_amount
+    }
+
+    /// Total amount the buyer pays, premium + fees
+    pub fn total_cost(&self) -> Result<u64> {
+        let total_cost = self
+            .premium
+            .checked_add(self.fee_amount)
+            .ok_or(SureError::AdditionQ3232OverflowError)?;
+        u64::try_from(total_cost).map_err(|_| SureError::OverflowU64.into())
+    }
+
+    fn add_tick_coverage(&mut self, tick_coverage: TickCoverage) -> Result<()> {
+        self.coverage_amount = self
+            .coverage_amount
+            .checked_add(tick_coverage.amount)
+            .ok_or(SureError::AdditionQ3232OverflowError)?;
+        self.premium = self
+            .premium
+            .checked_add(tick_coverage.premium)
+            .ok_or(SureError::AdditionQ3232OverflowError)?;
+        self.fee_amount = self
+            .fee_amount
+            .checked_add(tick_coverage.fee_amount)
+            .ok_or(SureError::AdditionQ3232OverflowError)?;
+        self.ticks.push(tick_coverage);
+        Ok(())
+    }
+}
+
+/// Get coverage quote
+///
+/// Buy coverage from the ticks with available liquidity,
+/// starting at the lowest tick in the first tick array. Each
+/// tick is priced with Tick::calculate_coverage_delta, the same
+/// calculation used when the coverage is bought on-chain.
+///
+/// # Arguments
+/// * pool: the pool to buy coverage from
+/// * tick_arrays: consecutive tick arrays of the pool
+/// * coverage_amount: the amount to cover
+/// * current_ts: the current time
+/// * expiry_ts: the expiry of the coverage
+///
+/// # Returns
+/// * quote: the coverable amount might be less than
+///   coverage_amount if the tick arrays run out of liquidity
+pub fn get_coverage_quote(
+    pool: &Pool,
+    tick_arrays: &[&TickArray],
+    coverage_amount: u128,
+    current_ts: i64,
+    expiry_ts: i64,
+) -> Result<CoverageQuote> {
+    if expiry_ts <= current_ts {
+        return Err(SureError::InvalidTimestamp.into());
+    }
+    let tick_spacing = pool.tick_spacing;
+    if tick_spacing == 0 {
+        return Err(SureError::InvalidTickSpacing.into());
+    }
+    let tick_array_width = NUM_TICKS_IN_TICK_ARRAY * tick_spacing as i32;
+
+    let mut quote = CoverageQuote::default();
+    let mut remaining_amount = coverage_amount;
+    for (array_index, tick_array) in tick_arrays.iter().enumerate() {
+        let start_tick_index = tick_array.start_tick_index;
+        if array_index > 0
+            && start_tick_index != tick_arrays[array_index - 1].start_tick_index + tick_array_width
+        {
+            return Err(SureError::InvalidTickArraySequence.into());
+        }
+
+        let ticks = tick_array.ticks;
+        for (tick_location, tick) in ticks.iter().enumerate() {
+            if remaining_amount == 0 {
+                break;
+            }
+            let tick_index = start_tick_index + tick_location as i32 * tick_spacing as i32;
+            if tick_index < MIN_TICK_INDEX || tick_index > MAX_TICK_INDEX {
+                continue;
+            }
+            if !tick.is_available_liquidity() {
+                continue;
+            }
+
+            let amount = remaining_amount.min(tick.get_available_liquidity());
+            let (fee_amount, premium, _) = tick.calculate_coverage_delta(
+                tick_index,
+                tick_index,
+                amount,
+                0,
+                pool.fee_rate,
+                current_ts,
+                expiry_ts,
+                true,
+            )?;
+            quote.add_tick_coverage(TickCoverage {
+                array_index,
+                tick_index,
+                amount,
+                premium,
+                fee_amount,
+            })?;
+            remaining_amount -= amount;
+        }
+    }
+
+    quote.protocol_fee = calculate_sub_fee(quote.fee_amount, pool.protocol_fee)?;
+    quote.founders_fee = calculate_sub_fee(quote.fee_amount, pool.founders_fee)?;
+    Ok(quote)
+}
+
+/// Get coverage decrease
+///
+/// Release coverage from the tick ledger of a position,
+/// starting with the most expensive tick. The refund of each
+/// tick is calculated with Tick::calculate_coverage_delta as
+/// the premium for the remaining period that is no longer covered.
+///
+/// # Arguments
+/// * pool: the pool the coverage is bought from
+/// * tick_arrays: tick arrays holding the ticks in the ledger
+/// * coverage_ticks: the tick ledger of the position
+/// * coverage_amount: the amount to release
+/// * current_ts: the current time
+/// * expiry_ts: the expiry of the coverage
+///
+/// # Returns
+/// * quote: the released coverage where the premium is the refund
+pub fn get_coverage_decrease(
+    pool: &Pool,
+    tick_arrays: &[&TickArray],
+    coverage_ticks: &[CoverageTick],
+    coverage_amount: u128,
+    current_ts: i64,
+    expiry_ts: i64,
+) -> Result<CoverageQuote> {
+    if expiry_ts <= current_ts {
+        return Err(SureError::InvalidTimestamp.into());
+    }
+    let tick_spacing = pool.tick_spacing;
+    let mut coverage_ticks = coverage_ticks.to_vec();
+    coverage_ticks.sort_by_key(|coverage_tick| std::cmp::Reverse(coverage_tick.tick_index));
+
+    let mut quote = CoverageQuote::default();
+    let mut remaining_amount = coverage_amount;
+    for coverage_tick in coverage_ticks {
+        if remaining_amount == 0 {
+            break;
+        }
+        let tick_index = coverage_tick.tick_index;
+        let covered_amount = coverage_tick.amount as u128;
+        let amount = remaining_amount.min(covered_amount);
+
+        let array_index = tick_arrays
+            .iter()
+            .position(|tick_array| tick_array.contains_tick_index(tick_index, tick_spacing))
+            .ok_or(SureError::TickOutOfRange)?;
+        let tick = tick_arrays[array_index].get_tick(tick_index, tick_spacing)?;
+        let (_, _, refund) = tick.calculate_coverage_delta(
+            tick_index,
+            tick_index,
+            amount,
+            covered_amount,
+            pool.fee_rate,
+            current_ts,
+            expiry_ts,
+            false,
+        )?;
+        quote.add_tick_coverage(TickCoverage {
+            array_index,
+            tick_index,
+            amount,
+            premium: refund,
+            fee_amount: 0,
+        })?;
+        remaining_amount -= amount;
+    }
+
+    if remaining_amount > 0 {
+        return Err(SureError::InvalidAmount.into());
+    }
+    Ok(quote)
+}
+
+#[cfg(test)]
+pub mod test_coverage_quote {
+    use super::*;
+    use crate::states::tick_array::Tick;
+    use crate::utils::tick_math::UNIX_TIME_IN_YEARS;
+
+    fn tick_array(start_tick_index: i32, liquidity: &[(usize, u128)]) -> TickArray {
+        let mut tick_array = TickArray::default();
+        tick_array.start_tick_index = start_tick_index;
+        let mut ticks = tick_array.ticks;
+        for (tick_location, liquidity_gross) in liquidity {
+            ticks[*tick_location] = Tick {
+                bump: 0,
+                liquidity_gross: *liquidity_gross,
+                liquidity_used: 0,
+                ..Tick::default()
+            };
+        }
+        tick_array.ticks = ticks;
+        tick_array
+    }
+
+    fn pool(tick_spacing: u16) -> Pool {
+        Pool {
+            tick_spacing,
+            fee_rate: 1_000,
+            protocol_fee: 2_000,
+            founders_fee: 1_000,
+            ..Pool::default()
+        }
+    }
+
+    #[test]
+    pub fn test_get_coverage_quote() {
+        pub struct Test<'a> {
+            name: &'a str,
+            coverage_amount: u128,
+            expected_coverage_amount: u128,
+            expected_ticks: Vec<(i32, u128)>,
+        }
+        let year = UNIX_TIME_IN_YEARS as i64;
+        let pool = pool(10);
+        let ta0 = tick_array(0, &[(0, 1_000_000), (3, 2_000_000)]);
+        let ta1 = tick_array(640, &[(1, 5_000_000)]);
+
+        let test_data = [
+            Test {
+                name: "1. covered by the first tick",
+                coverage_amount: 500_000,
+                expected_coverage_amount: 500_000,
+                expected_ticks: vec![(0, 500_000)],
+            },
+            Test {
+                name: "2. cross ticks in the first array",
+                coverage_amount: 2_000_000,
+                expected_coverage_amount: 2_000_000,
+                expected_ticks: vec![(0, 1_000_000), (30, 1_000_000)],
+            },
+            Test {
+                name: "3. cross into the next array",
+                coverage_amount: 4_000_000,
+                expected_coverage_amount: 4_000_000,
+                expected_ticks: vec![(0, 1_000_000), (30, 2_000_000), (650, 1_000_000)],
+            },
+            Test {
+                name: "4. not enough liquidity",
+                coverage_amount: 10_000_000,
+                expected_coverage_amount: 8_000_000,
+                expected_ticks: vec![(0, 1_000_000), (30, 2_000_000), (650, 5_000_000)],
+            },
+        ];
+
+        for test in test_data {
+            let quote =
+                get_coverage_quote(&pool, &[&ta0, &ta1], test.coverage_amount, 0, year).unwrap();
+            assert_eq!(
+                quote.coverage_amount, test.expected_coverage_amount,
+                "{}: coverage amount",
+                test.name
+            );
+            assert_eq!(
+                quote.is_filled(test.coverage_amount),
+                test.coverage_amount == test.expected_coverage_amount,
+                "{}: is filled",
+                test.name
+            );
+            let ticks: Vec<(i32, u128)> = quote
+                .ticks
+                .iter()
+                .map(|tick| (tick.tick_index, tick.amount))
+                .collect();
+            assert_eq!(ticks, test.expected_ticks, "{}: ticks", test.name);
+            assert_eq!(quote.ticks_crossed() as usize, test.expected_ticks.len());
+
+            // the quote is the sum of the ticks
+            let mut expected_premium = 0;
+            let mut expected_fee_amount = 0;
+            for tick_coverage in quote.ticks.iter() {
+                let tick_array = [&ta0, &ta1][tick_coverage.array_index];
+                let tick = tick_array
+                    .get_tick(tick_coverage.tick_index, pool.tick_spacing)
+                    .unwrap();
+                let (fee_amount, premium, _) = tick
+                    .calculate_coverage_delta(
+                        tick_coverage.tick_index,
+                        tick_coverage.tick_index,
+                        tick_coverage.amount,
+                        0,
+                        pool.fee_rate,
+                        0,
+                        year,
+                        true,
+                    )
+                    .unwrap();
+                assert_eq!(tick_coverage.premium, premium);
+                expected_premium += premium;
+                expected_fee_amount += fee_amount;
+            }
+            assert_eq!(quote.premium, expected_premium, "{}: premium", test.name);
+            assert_eq!(quote.fee_amount, expected_fee_amount, "{}: fee", test.name);
+            assert_eq!(quote.protocol_fee, quote.fee_amount * 2_000 / 10_000);
+            assert_eq!(quote.founders_fee, quote.fee_amount * 1_000 / 10_000);
+            assert_eq!(
+                quote.total_cost().unwrap() as u128,
+                quote.premium + quote.fee_amount
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_coverage_quote_premium() {
+        let year = UNIX_TIME_IN_YEARS as i64;
+        let pool = Pool {
+            tick_spacing: 10,
+            ..Pool::default()
+        };
+        // 1bp at tick 0
+        let ta0 = tick_array(0, &[(0, 1_000_000)]);
+        let quote = get_coverage_quote(&pool, &[&ta0], 1_000_000, 0, year).unwrap();
+        assert_eq!(quote.premium, 100);
+        assert_eq!(quote.fee_amount, 0);
+
+        // half the time, half the premium
+        let quote = get_coverage_quote(&pool, &[&ta0], 1_000_000, 0, year / 2).unwrap();
+        assert_eq!(quote.premium, 50);
+
+        // higher ticks are more expensive
+        let ta1 = tick_array(640, &[(0, 1_000_000)]);
+        let expensive_quote = get_coverage_quote(&pool, &[&ta1], 1_000_000, 0, year).unwrap();
+        assert!(expensive_quote.premium > 100);
+    }
+
+    #[test]
+    pub fn test_coverage_quote_errors() {
+        let pool = pool(10);
+        let ta0 = tick_array(0, &[(0, 1_000_000)]);
+        let ta2 = tick_array(1280, &[(0, 1_000_000)]);
+        assert!(
+            get_coverage_quote(&pool, &[&ta0, &ta2], 1_000, 0, 100).is_err(),
+            "tick arrays are not consecutive"
+        );
+        assert!(
+            get_coverage_quote(&pool, &[&ta0], 1_000, 100, 100).is_err(),
+            "coverage has expired"
+        );
+        let empty_quote = get_coverage_quote(&pool, &[&ta0], 0, 0, 100).unwrap();
+        assert_eq!(empty_quote, CoverageQuote::default());
+    }
+
+    #[test]
+    pub fn test_get_coverage_decrease() {
+        let year = UNIX_TIME_IN_YEARS as i64;
+        let pool = pool(10);
+        let ta0 = tick_array(0, &[(0, 1_000_000), (3, 2_000_000)]);
+        let ta1 = tick_array(640, &[(1, 5_000_000)]);
+        let quote = get_coverage_quote(&pool, &[&ta0, &ta1], 4_000_000, 0, year).unwrap();
+        let coverage_ticks: Vec<CoverageTick> = quote
+            .ticks
+            .iter()
+            .map(|tick| CoverageTick {
+                tick_index: tick.tick_index,
+                amount: tick.amount as u64,
+                premium: tick.premium as u64,
+            })
+            .collect();
+
+        // release from the most expensive tick first
+        let decrease =
+            get_coverage_decrease(&pool, &[&ta0, &ta1], &coverage_ticks, 1_500_000, 0, year)
+                .unwrap();
+        let ticks: Vec<(usize, i32, u128)> = decrease
+            .ticks
+            .iter()
+            .map(|tick| (tick.array_index, tick.tick_index, tick.amount))
+            .collect();
+        assert_eq!(ticks, vec![(1, 650, 1_000_000), (0, 30, 500_000)]);
+        assert_eq!(decrease.coverage_amount, 1_500_000);
+        assert_eq!(decrease.fee_amount, 0);
+
+        // a full refund at the start of the coverage, the
+        // premium is rounded up and the refund down
+        let refund = decrease.ticks[0].premium;
+        assert!(refund <= quote.ticks[2].premium && refund + 1 >= quote.ticks[2].premium);
+        assert!(decrease.premium <= quote.premium);
+
+        // half the refund halfway through the coverage
+        let half_way_decrease = get_coverage_decrease(
+            &pool,
+            &[&ta0, &ta1],
+            &coverage_ticks[2..],
+            1_000_000,
+            year / 2,
+            year,
+        )
+        .unwrap();
+        let half_premium = quote.ticks[2].premium / 2;
+        assert!(
+            half_way_decrease.premium + 1 >= half_premium
+                && half_way_decrease.premium <= half_premium
+        );
+
+        // more than the position holds
+        assert!(
+            get_coverage_decrease(&pool, &[&ta0, &ta1], &coverage_ticks, 4_000_001, 0, year)
+                .is_err()
+        );
+        // the tick array is missing
+        assert!(get_coverage_decrease(&pool, &[&ta0], &coverage_ticks, 1_000, 0, year).is_err());
+    }
+}
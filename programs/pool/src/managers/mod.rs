@@ -1,3 +1,5 @@
+pub mod coverage;
 pub mod liquidity;
 
+pub use coverage::*;
 pub use liquidity::*;
@@ -3,6 +3,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
 
+use crate::managers::coverage::CoverageQuote;
 use crate::states::*;
 use crate::utils::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
@@ -11,6 +12,36 @@ use vipers::{assert_is_ata, prelude::*};
 
 const SURE_TIME_LOCK_IN_SECONDS: u64 = solana_program::clock::SECONDS_PER_DAY;
 
+/// Max number of ticks in the tick ledger
+/// of a coverage position
+pub const MAX_COVERAGE_TICKS: usize = 32;
+
+/// Keeper tip for expiring a coverage position
+/// in basis points of the premium
+pub const EXPIRY_KEEPER_TIP_BP: u64 = 100;
+
+/// Tick ledger entry
+///
+/// Coverage bought from a tick. Used to attribute
+/// expiries, refunds and claims to the liquidity
+/// providers of the tick
+#[zero_copy]
+#[repr(packed)]
+#[derive(Default, Debug, PartialEq)]
+pub struct CoverageTick {
+    pub tick_index: i32, // 4 bytes
+
+    /// liquidity used from the tick
+    pub amount: u64, // 8 bytes
+
+    /// premium paid to the tick, net of refunds
+    pub premium: u64, // 8 bytes
+}
+
+impl CoverageTick {
+    pub const SPACE: usize = 4 + 8 + 8;
+}
+
 /// --- Pool insurance contract ---
 /// <POOL>
 /// Accumulation of all insurance contracts for a user in  
@@ -35,10 +66,285 @@ pub struct CoveragePosition {
 
     /// Owner of contract
     pub owner: Pubkey, // 32 byte
+
+    /// Pool the coverage is bought from
+    pub pool: Pubkey, // 32 bytes
+
+    /// Whether the position holds unexpired coverage
+    pub active: bool, // 1 byte
+
+    /// Premium paid for the coverage
+    pub premium: u64, // 8 bytes
+
+    /// Number of ticks in use
+    pub tick_count: u8, // 1 byte
+
+    /// Tick ledger, the coverage bought from each tick
+    pub ticks: [CoverageTick; MAX_COVERAGE_TICKS], // 20*32 = 640 bytes
+}
+
+impl Default for CoveragePosition {
+    #[inline]
+    fn default() -> CoveragePosition {
+        CoveragePosition {
+            bump: 0,
+            expiry_ts: 0,
+            insured_amount: 0,
+            position_mint: Pubkey::default(),
+            token_mint: Pubkey::default(),
+            owner: Pubkey::default(),
+            pool: Pubkey::default(),
+            active: false,
+            premium: 0,
+            tick_count: 0,
+            ticks: [CoverageTick::default(); MAX_COVERAGE_TICKS],
+        }
+    }
 }
 
 impl CoveragePosition {
-    pub const SPACE: usize = 1 + 8 + 8 + 32 + 32;
+    pub const SPACE: usize =
+        1 + 8 + 8 + 32 + 32 + 32 + 32 + 1 + 8 + 1 + CoverageTick::SPACE * MAX_COVERAGE_TICKS;
+
+    /// Check if the coverage has expired
+    pub fn is_expired(&self, current_ts: i64) -> bool {
+        current_ts >= self.expiry_ts
+    }
+
+    /// Get the ticks the coverage is bought from
+    pub fn get_coverage_ticks(&self) -> Vec<CoverageTick> {
+        let ticks = self.ticks;
+        ticks[..self.tick_count as usize].to_vec()
+    }
+
+    /// Set the tick ledger
+    fn set_coverage_ticks(&mut self, coverage_ticks: &[CoverageTick]) {
+        let mut ticks = [CoverageTick::default(); MAX_COVERAGE_TICKS];
+        ticks[..coverage_ticks.len()].copy_from_slice(coverage_ticks);
+        self.ticks = ticks;
+        self.tick_count = coverage_ticks.len() as u8;
+    }
+
+    /// Add coverage
+    ///
+    /// Records the liquidity used from and the
+    /// premium paid to each tick
+    ///
+    /// # Arguments
+    /// * pool: the pool the coverage is bought from
+    /// * token_mint: mint of the token pool the coverage is bought from
+    /// * quote: the coverage bought
+    /// * expiry_ts: expiry of the coverage
+    pub fn add_coverage(
+        &mut self,
+        pool: &Pubkey,
+        token_mint: &Pubkey,
+        quote: &CoverageQuote,
+        expiry_ts: i64,
+    ) -> Result<()> {
+        if self.active && self.pool != *pool {
+            return Err(SureError::InvalidCoveragePool.into());
+        }
+        if self.active && self.token_mint != *token_mint {
+            return Err(SureError::InvalidMint.into());
+        }
+
+        let mut ticks = self.ticks;
+        let mut tick_count = self.tick_count as usize;
+        for tick_coverage in quote.ticks.iter() {
+            let amount = u64::try_from(tick_coverage.amount).map_err(|_| SureError::OverflowU64)?;
+            let premium =
+                u64::try_from(tick_coverage.premium).map_err(|_| SureError::OverflowU64)?;
+            match ticks[..tick_count]
+                .iter()
+                .position(|tick| tick.tick_index == tick_coverage.tick_index)
+            {
+                Some(location) => {
+                    ticks[location].amount = ticks[location]
+                        .amount
+                        .checked_add(amount)
+                        .ok_or(SureError::OverflowU64)?;
+                    ticks[location].premium = ticks[location]
+                        .premium
+                        .checked_add(premium)
+                        .ok_or(SureError::OverflowU64)?;
+                }
+                None => {
+                    if tick_count >= MAX_COVERAGE_TICKS {
+                        return Err(SureError::MaxCoverageTicksExceeded.into());
+                    }
+                    ticks[tick_count] = CoverageTick {
+                        tick_index: tick_coverage.tick_index,
+                        amount,
+                        premium,
+                    };
+                    tick_count += 1;
+                }
+            }
+        }
+
+        let coverage_amount =
+            u64::try_from(quote.coverage_amount).map_err(|_| SureError::OverflowU64)?;
+        let premium = u64::try_from(quote.premium).map_err(|_| SureError::OverflowU64)?;
+        self.ticks = ticks;
+        self.tick_count = tick_count as u8;
+        self.insured_amount = self
+            .insured_amount
+            .checked_add(coverage_amount)
+            .ok_or(SureError::OverflowU64)?;
+        self.premium = self
+            .premium
+            .checked_add(premium)
+            .ok_or(SureError::OverflowU64)?;
+        self.pool = *pool;
+        self.token_mint = *token_mint;
+        self.expiry_ts = expiry_ts;
+        self.active = true;
+        Ok(())
+    }
+
+    /// Remove ticks
+    ///
+    /// Removes the ticks that are released from
+    /// the position
+    ///
+    /// # Arguments
+    /// * is_released: whether the tick at the index is released
+    ///
+    /// # Returns
+    /// * the removed ticks
+    pub fn remove_ticks(&mut self, is_released: impl Fn(i32) -> bool) -> Vec<CoverageTick> {
+        let (released_ticks, remaining_ticks): (Vec<CoverageTick>, Vec<CoverageTick>) = self
+            .get_coverage_ticks()
+            .into_iter()
+            .partition(|tick| is_released(tick.tick_index));
+
+        self.set_coverage_ticks(&remaining_ticks);
+        released_ticks
+    }
+
+    /// Reduce coverage
+    ///
+    /// Removes the released coverage and the refunded
+    /// premium from the tick ledger. The position is
+    /// inactive when all the coverage is released
+    ///
+    /// # Arguments
+    /// * quote: the released coverage, the premium is the refund
+    pub fn reduce_coverage(&mut self, quote: &CoverageQuote) -> Result<()> {
+        let mut ticks = self.get_coverage_ticks();
+        for tick_coverage in quote.ticks.iter() {
+            let amount = u64::try_from(tick_coverage.amount).map_err(|_| SureError::OverflowU64)?;
+            let refund =
+                u64::try_from(tick_coverage.premium).map_err(|_| SureError::OverflowU64)?;
+            let tick = ticks
+                .iter_mut()
+                .find(|tick| tick.tick_index == tick_coverage.tick_index)
+                .ok_or(SureError::InvalidTick)?;
+            tick.amount = tick
+                .amount
+                .checked_sub(amount)
+                .ok_or(SureError::InvalidAmount)?;
+            tick.premium = tick.premium.saturating_sub(refund);
+        }
+        ticks.retain(|tick| tick.amount > 0);
+        self.set_coverage_ticks(&ticks);
+
+        let coverage_amount =
+            u64::try_from(quote.coverage_amount).map_err(|_| SureError::OverflowU64)?;
+        let refund = u64::try_from(quote.premium).map_err(|_| SureError::OverflowU64)?;
+        self.insured_amount = self
+            .insured_amount
+            .checked_sub(coverage_amount)
+            .ok_or(SureError::InvalidAmount)?;
+        self.premium = self.premium.saturating_sub(refund);
+        if self.insured_amount == 0 {
+            self.active = false;
+            self.premium = 0;
+        }
+        Ok(())
+    }
+
+    /// Claim coverage
+    ///
+    /// Pays out the claim from the coverage of the
+    /// position. The claim is split over the ticks pro
+    /// rata to the coverage bought from each tick, the
+    /// rounding remainder is taken from the highest ticks.
+    /// The premium of the claimed coverage is not refunded.
+    ///
+    /// # Arguments
+    /// * claim_amount: the amount paid out to the owner
+    ///
+    /// # Returns
+    /// * the loss of each tick
+    pub fn claim_coverage(&mut self, claim_amount: u64) -> Result<Vec<CoverageTick>> {
+        let mut ticks = self.get_coverage_ticks();
+        let covered_amount = ticks.iter().map(|tick| tick.amount as u128).sum::<u128>();
+        if claim_amount as u128 > covered_amount {
+            return Err(SureError::ClaimExceedsCoverage.into());
+        }
+
+        let mut losses: Vec<u64> = ticks
+            .iter()
+            .map(|tick| (tick.amount as u128 * claim_amount as u128 / covered_amount) as u64)
+            .collect();
+        let mut remainder = claim_amount - losses.iter().sum::<u64>();
+        for (tick, loss) in ticks.iter().zip(losses.iter_mut()).rev() {
+            if remainder == 0 {
+                break;
+            }
+            if tick.amount > *loss {
+                *loss += 1;
+                remainder -= 1;
+            }
+        }
+
+        let mut tick_losses = Vec::with_capacity(ticks.len());
+        for (tick, loss) in ticks.iter_mut().zip(losses.into_iter()) {
+            if loss == 0 {
+                continue;
+            }
+            tick.amount -= loss;
+            tick_losses.push(CoverageTick {
+                tick_index: tick.tick_index,
+                amount: loss,
+                premium: 0,
+            });
+        }
+        ticks.retain(|tick| tick.amount > 0);
+        self.set_coverage_ticks(&ticks);
+
+        self.insured_amount = self.insured_amount.saturating_sub(claim_amount);
+        if self.tick_count == 0 {
+            self.active = false;
+            self.insured_amount = 0;
+            self.premium = 0;
+        }
+        Ok(tick_losses)
+    }
+
+    /// Expire the position
+    ///
+    /// Marks the position inactive once all
+    /// the ticks are released
+    ///
+    /// # Returns
+    /// * keeper_tip: the tip for the keeper
+    pub fn expire(&mut self) -> Result<u64> {
+        if self.tick_count > 0 {
+            return Err(SureError::CoverageTicksNotReleased.into());
+        }
+        let keeper_tip = self
+            .premium
+            .checked_mul(EXPIRY_KEEPER_TIP_BP)
+            .ok_or(SureError::OverflowU64)?
+            / 10_000;
+        self.active = false;
+        self.insured_amount = 0;
+        self.premium = 0;
+        Ok(keeper_tip)
+    }
 }
 
 /// --- Insurance Contract --
@@ -127,6 +433,7 @@ impl InsuranceTickContract {
     /// * tick: the current tick used for the premium rate
     /// * new_insured_amount: The new amount to be insured
     /// * new_end_ts: the updated end time for the contract
+    /// * current_time: the time of the update
     ///
     /// # Returns Result<increasePremium,premiumIncrease>
     /// * increasePremium<bool>: Should premium be increased?
@@ -136,8 +443,8 @@ impl InsuranceTickContract {
         tick: u16,
         new_insured_amount: u64,
         new_end_ts: i64,
+        current_time: i64,
     ) -> Result<(bool, u64)> {
-        let current_time = Clock::get()?.unix_timestamp;
         let remaining_premium =
             self.calculate_premium(tick, self.insured_amount, current_time, self.end_ts)?;
         let new_premium =
@@ -158,6 +465,7 @@ impl InsuranceTickContract {
     /// * tick: the current tick used for the premium rate
     /// * new_insured_amount: The new amount to be insured
     /// * new_end_ts: the updated end time for the contract
+    /// * current_time: the time of the update
     ///
     /// # Returns Result<increasePremium,premiumIncrease>
     /// * increasePremium<bool>: Should premium be increased?
@@ -167,11 +475,11 @@ impl InsuranceTickContract {
         tick: u16,
         new_insured_amount: u64,
         new_end_ts: i64,
+        current_time: i64,
     ) -> Result<(bool, u64)> {
         let (increase_premium, premium) =
-            self.increase_premium(tick, new_insured_amount, new_end_ts)?;
+            self.increase_premium(tick, new_insured_amount, new_end_ts, current_time)?;
 
-        let current_time = Clock::get()?.unix_timestamp;
         // Update insurance position
         let time_lock = false;
         if new_insured_amount > self.insured_amount && time_lock {
@@ -213,3 +521,259 @@ impl InsuranceTickContract {
         Ok(())
     }
 }
+
+#[cfg(test)]
+pub mod test_coverage_position {
+    use super::*;
+    use crate::managers::coverage::TickCoverage;
+
+    /// Quote where the premium is split evenly over the ticks
+    fn quote(ticks: &[(i32, u128)], premium: u128) -> CoverageQuote {
+        CoverageQuote {
+            coverage_amount: ticks.iter().map(|(_, amount)| amount).sum(),
+            premium,
+            ticks: ticks
+                .iter()
+                .map(|(tick_index, amount)| TickCoverage {
+                    tick_index: *tick_index,
+                    amount: *amount,
+                    premium: premium / ticks.len() as u128,
+                    ..TickCoverage::default()
+                })
+                .collect(),
+            ..CoverageQuote::default()
+        }
+    }
+
+    #[test]
+    pub fn test_add_coverage() {
+        let pool = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let mut position = CoveragePosition::default();
+        position
+            .add_coverage(
+                &pool,
+                &token_mint,
+                &quote(&[(10, 1_000), (20, 500)], 10),
+                100,
+            )
+            .unwrap();
+        position
+            .add_coverage(&pool, &token_mint, &quote(&[(20, 500), (30, 200)], 6), 100)
+            .unwrap();
+
+        assert!(position.active);
+        assert_eq!({ position.insured_amount }, 2_200);
+        assert_eq!({ position.premium }, 16);
+        assert_eq!(
+            position.get_coverage_ticks(),
+            vec![
+                CoverageTick {
+                    tick_index: 10,
+                    amount: 1_000,
+                    premium: 5,
+                },
+                CoverageTick {
+                    tick_index: 20,
+                    amount: 1_000,
+                    premium: 8,
+                },
+                CoverageTick {
+                    tick_index: 30,
+                    amount: 200,
+                    premium: 3,
+                },
+            ]
+        );
+
+        // coverage from another pool
+        assert!(position
+            .add_coverage(
+                &Pubkey::new_unique(),
+                &token_mint,
+                &quote(&[(10, 1)], 1),
+                100
+            )
+            .is_err());
+
+        // coverage from another token pool
+        assert_eq!(
+            position
+                .add_coverage(&pool, &Pubkey::new_unique(), &quote(&[(10, 1)], 1), 100)
+                .unwrap_err(),
+            SureError::InvalidMint.into()
+        );
+
+        // too many ticks
+        let ticks: Vec<(i32, u128)> = (0..MAX_COVERAGE_TICKS as i32)
+            .map(|tick| (100 + tick, 1))
+            .collect();
+        assert!(position
+            .add_coverage(&pool, &token_mint, &quote(&ticks, 1), 100)
+            .is_err());
+    }
+
+    #[test]
+    pub fn test_expire_coverage() {
+        let pool = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let mut position = CoveragePosition::default();
+        position
+            .add_coverage(
+                &pool,
+                &token_mint,
+                &quote(&[(10, 1_000), (700, 500)], 2_000),
+                100,
+            )
+            .unwrap();
+        assert!(!position.is_expired(99));
+        assert!(position.is_expired(100));
+
+        // release the ticks in the first tick array
+        let released_ticks = position.remove_ticks(|tick_index| tick_index < 640);
+        assert_eq!(
+            released_ticks,
+            vec![CoverageTick {
+                tick_index: 10,
+                amount: 1_000,
+                premium: 1_000,
+            }]
+        );
+        assert!(position.expire().is_err(), "ticks are not released");
+
+        position.remove_ticks(|_| true);
+        assert_eq!(position.expire().unwrap(), 20);
+        assert!(!position.active);
+        assert_eq!({ position.insured_amount }, 0);
+        assert_eq!(position.get_coverage_ticks(), vec![]);
+    }
+
+    #[test]
+    pub fn test_reduce_coverage() {
+        let pool = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        let mut position = CoveragePosition::default();
+        position
+            .add_coverage(
+                &pool,
+                &token_mint,
+                &quote(&[(10, 1_000), (20, 500)], 100),
+                100,
+            )
+            .unwrap();
+
+        // release part of the most expensive tick
+        position.reduce_coverage(&quote(&[(20, 200)], 20)).unwrap();
+        assert!(position.active);
+        assert_eq!({ position.insured_amount }, 1_300);
+        assert_eq!({ position.premium }, 80);
+        assert_eq!(
+            position.get_coverage_ticks()[1],
+            CoverageTick {
+                tick_index: 20,
+                amount: 300,
+                premium: 30,
+            }
+        );
+
+        // releasing more than the tick holds
+        assert!(position.reduce_coverage(&quote(&[(20, 301)], 0)).is_err());
+        assert!(position.reduce_coverage(&quote(&[(30, 1)], 0)).is_err());
+
+        // the tick is removed from the ledger when empty
+        position.reduce_coverage(&quote(&[(20, 300)], 30)).unwrap();
+        assert_eq!(position.get_coverage_ticks().len(), 1);
+
+        // release all of the coverage
+        position
+            .reduce_coverage(&quote(&[(10, 1_000)], 50))
+            .unwrap();
+        assert!(!position.active);
+        assert_eq!({ position.insured_amount }, 0);
+        assert_eq!(position.get_coverage_ticks(), vec![]);
+    }
+
+    #[test]
+    pub fn test_claim_coverage() {
+        pub struct Test<'a> {
+            name: &'a str,
+            claim_amount: u64,
+            expected_losses: Vec<(i32, u64)>,
+            expected_ticks: Vec<(i32, u64)>,
+            expected_active: bool,
+        }
+        let tests = [
+            Test {
+                name: "split pro rata over the ticks",
+                claim_amount: 750,
+                expected_losses: vec![(10, 500), (20, 250)],
+                expected_ticks: vec![(10, 500), (20, 250)],
+                expected_active: true,
+            },
+            Test {
+                name: "remainder is taken from the highest tick",
+                claim_amount: 100,
+                expected_losses: vec![(10, 66), (20, 34)],
+                expected_ticks: vec![(10, 934), (20, 466)],
+                expected_active: true,
+            },
+            Test {
+                name: "claim all of the coverage",
+                claim_amount: 1_500,
+                expected_losses: vec![(10, 1_000), (20, 500)],
+                expected_ticks: vec![],
+                expected_active: false,
+            },
+        ];
+
+        let pool = Pubkey::new_unique();
+        let token_mint = Pubkey::new_unique();
+        for test in tests {
+            let mut position = CoveragePosition::default();
+            position
+                .add_coverage(
+                    &pool,
+                    &token_mint,
+                    &quote(&[(10, 1_000), (20, 500)], 100),
+                    100,
+                )
+                .unwrap();
+            let losses = position.claim_coverage(test.claim_amount).unwrap();
+            assert_eq!(
+                losses
+                    .iter()
+                    .map(|tick| (tick.tick_index, tick.amount))
+                    .collect::<Vec<(i32, u64)>>(),
+                test.expected_losses,
+                "{}",
+                test.name
+            );
+            assert_eq!(
+                position
+                    .get_coverage_ticks()
+                    .iter()
+                    .map(|tick| (tick.tick_index, tick.amount))
+                    .collect::<Vec<(i32, u64)>>(),
+                test.expected_ticks,
+                "{}",
+                test.name
+            );
+            assert_eq!(
+                { position.insured_amount },
+                1_500 - test.claim_amount,
+                "{}",
+                test.name
+            );
+            assert_eq!(position.active, test.expected_active, "{}", test.name);
+        }
+
+        let mut position = CoveragePosition::default();
+        position
+            .add_coverage(&pool, &token_mint, &quote(&[(10, 1_000)], 10), 100)
+            .unwrap();
+        assert!(
+            position.claim_coverage(1_001).is_err(),
+            "claim exceeds coverage"
+        );
+    }
+}
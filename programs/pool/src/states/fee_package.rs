@@ -1,10 +1,17 @@
 use crate::utils::errors::*;
 use anchor_lang::prelude::*;
 
+/// Fee Package
+///
+/// set of fee rates owned by the protocol. Only the owner
+/// of the pool manager can create and update fee packages.
+/// Pools copy the rates of the fee package at creation
 #[account]
+#[derive(Default)]
 pub struct FeePackage {
     pub bump: u8,
-    pub owner: Pubkey,
+    /// id used to derive the fee package address
+    pub fee_package_id: u16,
     pub fee_rate: u16,
     pub founders_fee: u16,
     pub protocol_fee: u16,
@@ -15,18 +22,20 @@ impl FeePackage {
     pub const MAX_PROTOCOL_FEE_RATE_BP: u16 = 3_200;
     pub const MAX_FOUNDERS_FEE_RATE_BP: u16 = 1_000;
     pub const MIN_LIQUIDITY_PROVIDER_FEE_RATE_BP: u16 = 1_000;
-    pub const SIZE: usize = 1 + 32 + 2 + 2 + 2;
+    pub const SIZE: usize = 1 + 2 + 2 + 2 + 2;
 
-    pub fn initialize<'info>(
+    pub fn initialize(
         &mut self,
-        owner: &Signer<'info>,
+        bump: u8,
+        fee_package_id: u16,
         fee_rate: u16,
         protocol_fee_rate: u16,
         founders_fee_rate: u16,
     ) -> Result<()> {
+        self.bump = bump;
         self.update_fee_package(fee_rate, protocol_fee_rate, founders_fee_rate);
         self.validate_fee_rates()?;
-        self.owner = owner.key.clone();
+        self.fee_package_id = fee_package_id;
 
         Ok(())
     }
@@ -37,16 +46,93 @@ impl FeePackage {
         self.founders_fee = founders_fee;
     }
 
+    /// Validate the fee rates
+    ///
+    /// protocol and founders fee are shares of the
+    /// fee rate in basis points. The rest of the fee
+    /// goes to the liquidity providers
     pub fn validate_fee_rates(&self) -> Result<()> {
         if self.fee_rate > FeePackage::MAX_FEE_RATE_BP {
             return Err(SureError::MaxFeeRateExceeded.into());
         }
 
-        // 1/x + 1/y <= 1 <=> (x+y) <= xy
-        if self.protocol_fee + self.founders_fee > self.protocol_fee * self.founders_fee {
-            return Err(SureError::InvalidSubFeeRates.into());
+        if self.protocol_fee > FeePackage::MAX_PROTOCOL_FEE_RATE_BP {
+            return Err(SureError::MaxProtocolFeeRateExceeded.into());
+        }
+
+        if self.founders_fee > FeePackage::MAX_FOUNDERS_FEE_RATE_BP {
+            return Err(SureError::MaxFoundersFeeRateExceeded.into());
+        }
+
+        let liquidity_provider_fee =
+            FeePackage::MAX_FEE_RATE_BP - self.protocol_fee - self.founders_fee;
+        if liquidity_provider_fee < FeePackage::MIN_LIQUIDITY_PROVIDER_FEE_RATE_BP {
+            return Err(SureError::TooLowLiquidityProviderFeeRate.into());
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+pub mod test_fee_package {
+    use super::*;
+
+    #[test]
+    pub fn test_validate_fee_rates() {
+        pub struct Test<'a> {
+            name: &'a str,
+            fee_rate: u16,
+            protocol_fee: u16,
+            founders_fee: u16,
+            expected_error: Option<SureError>,
+        }
+        let tests = [
+            Test {
+                name: "valid fee rates",
+                fee_rate: 100,
+                protocol_fee: 1_000,
+                founders_fee: 500,
+                expected_error: None,
+            },
+            Test {
+                name: "max fee rates",
+                fee_rate: FeePackage::MAX_FEE_RATE_BP,
+                protocol_fee: FeePackage::MAX_PROTOCOL_FEE_RATE_BP,
+                founders_fee: FeePackage::MAX_FOUNDERS_FEE_RATE_BP,
+                expected_error: None,
+            },
+            Test {
+                name: "fee rate too high",
+                fee_rate: 10_001,
+                protocol_fee: 0,
+                founders_fee: 0,
+                expected_error: Some(SureError::MaxFeeRateExceeded),
+            },
+            Test {
+                name: "protocol fee too high",
+                fee_rate: 100,
+                protocol_fee: 3_201,
+                founders_fee: 0,
+                expected_error: Some(SureError::MaxProtocolFeeRateExceeded),
+            },
+            Test {
+                name: "founders fee too high",
+                fee_rate: 100,
+                protocol_fee: 0,
+                founders_fee: 1_001,
+                expected_error: Some(SureError::MaxFoundersFeeRateExceeded),
+            },
+        ];
+
+        for test in tests {
+            let mut fee_package = FeePackage::default();
+            fee_package.update_fee_package(test.fee_rate, test.protocol_fee, test.founders_fee);
+            let res = fee_package.validate_fee_rates();
+            match test.expected_error {
+                Some(err) => assert_eq!(res.unwrap_err(), err.into(), "{}", test.name),
+                None => assert!(res.is_ok(), "{}", test.name),
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@
 use anchor_lang::prelude::*;
 
 use crate::states::*;
+use crate::utils::uint::U256;
 use crate::utils::*;
 
 use anchor_spl::{
@@ -12,6 +13,46 @@ use anchor_spl::{
 
 use vipers::{assert_is_ata, prelude::*};
 
+/// Max number of ticks in the tick ledger
+/// of a liquidity position
+pub const MAX_LIQUIDITY_TICKS: usize = 64;
+
+/// Tick ledger entry
+///
+/// Liquidity provided to a tick and the loss
+/// growth of the tick when the claim losses were
+/// last settled
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct LiquidityTick {
+    pub tick_index: i32, // 4 bytes
+
+    /// liquidity provided to the tick
+    pub liquidity: u128, // 16 bytes
+
+    /// Loss growth of the tick when the losses
+    /// were last settled, Q64.64
+    pub loss_growth_checkpoint_x64: u128, // 16 bytes
+}
+
+impl LiquidityTick {
+    pub const SPACE: usize = 4 + 16 + 16;
+
+    /// Calculate loss
+    ///
+    /// The liquidity lost in the tick to claims
+    /// since the last settlement, rounded down
+    ///
+    /// # Arguments
+    /// * loss_growth_x64: the current loss growth of the tick
+    pub fn calculate_loss(&self, loss_growth_x64: u128) -> Result<u128> {
+        let loss_growth_delta_x64 = loss_growth_x64
+            .checked_sub(self.loss_growth_checkpoint_x64)
+            .ok_or(SureError::InvalidLossGrowthSubtraction)?;
+        let loss = (U256::from(self.liquidity) * U256::from(loss_growth_delta_x64)) >> 64;
+        Ok(loss.as_u128().min(self.liquidity))
+    }
+}
+
 /// -- Liquidity Position --
 ///
 /// Holds information about liquidity at a given tick
@@ -23,6 +64,7 @@ pub struct LiquidityPosition {
     pub bump: u8, // 1byte
 
     /// The amount of liquidity provided in lamports
+    /// summed over the ticks of the position
     pub liquidity: u128, // 8 bytes
 
     /// the amount of liquidity used
@@ -31,6 +73,10 @@ pub struct LiquidityPosition {
     /// Liquidity Pool
     pub pool: Pubkey, // 32 bytes
 
+    /// Mint of the liquidity. The liquidity is
+    /// held by the token pool of the mint
+    pub token_mint: Pubkey, // 32 bytes
+
     /// NFT mint. The mint representing the position
     /// The NFT is the owner of the position.
     pub position_mint: Pubkey, // 32 bytes
@@ -44,25 +90,347 @@ pub struct LiquidityPosition {
     /// Outstanding Rewards
     pub owed_fees: u32, // 4 bytes
     pub owed_premium: u32,
+
+    /// Tick ledger, the liquidity provided to each
+    /// tick in [tick_index_lower, tick_index_upper)
+    pub ticks: Vec<LiquidityTick>, // 4 + 36*64 bytes
 }
 
 impl LiquidityPosition {
-    pub const SPACE: usize = 1 + 8 + 8 + 32 + 32 + 32 + 32 + 8 + 1 + 8 + 4;
+    pub const SPACE: usize = 1
+        + 8
+        + 8
+        + 32
+        + 32
+        + 32
+        + 32
+        + 32
+        + 8
+        + 1
+        + 8
+        + 4
+        + LiquidityTick::SPACE * MAX_LIQUIDITY_TICKS;
 
     pub fn initialize(
         &mut self,
         bump: u8,
-        liquidity_delta: u128,
         pool: Pubkey,
+        token_mint: Pubkey,
         position_mint: Pubkey,
         tick_index_lower: i32,
         tick_index_upper: i32,
+        tick_spacing: u16,
     ) {
         self.bump = bump;
-        self.liquidity = liquidity_delta;
+        self.liquidity = 0;
         self.pool = pool;
+        self.token_mint = token_mint;
         self.position_mint = position_mint;
         self.tick_index_lower = tick_index_lower;
         self.tick_index_upper = tick_index_upper;
+        self.ticks = (tick_index_lower..tick_index_upper)
+            .step_by(tick_spacing as usize)
+            .map(|tick_index| LiquidityTick {
+                tick_index,
+                ..LiquidityTick::default()
+            })
+            .collect();
+    }
+
+    /// Validate the tick range of a position
+    ///
+    /// The range [tick_index_lower, tick_index_upper) has
+    /// to hold at least one tick and fit in the tick ledger
+    pub fn validate_tick_range(
+        tick_index_lower: i32,
+        tick_index_upper: i32,
+        tick_spacing: u16,
+    ) -> Result<()> {
+        if !Tick::is_valid_tick(tick_index_lower, tick_spacing) {
+            return Err(SureError::InvalidLowerTickIndexProvided.into());
+        }
+        if !Tick::is_valid_tick(tick_index_upper, tick_spacing) {
+            return Err(SureError::InvalidUpperTickIndexProvided.into());
+        }
+        if tick_index_lower >= tick_index_upper {
+            return Err(SureError::LowerTickgtUpperTick.into());
+        }
+        let tick_count = (tick_index_upper - tick_index_lower) / tick_spacing as i32;
+        if tick_count as usize > MAX_LIQUIDITY_TICKS {
+            return Err(SureError::MaxLiquidityTicksExceeded.into());
+        }
+        Ok(())
+    }
+
+    /// Get the ticks the liquidity is provided to
+    pub fn get_tick_indexes(&self) -> Vec<i32> {
+        self.ticks.iter().map(|tick| tick.tick_index).collect()
+    }
+
+    /// Get the liquidity provided to a tick
+    pub fn get_tick_liquidity(&self, tick_index: i32) -> Result<u128> {
+        Ok(self.get_liquidity_tick(tick_index)?.liquidity)
+    }
+
+    fn get_liquidity_tick(&self, tick_index: i32) -> Result<&LiquidityTick> {
+        self.ticks
+            .iter()
+            .find(|tick| tick.tick_index == tick_index)
+            .ok_or_else(|| SureError::InvalidLiquidityPositionTick.into())
+    }
+
+    fn get_liquidity_tick_mut(&mut self, tick_index: i32) -> Result<&mut LiquidityTick> {
+        self.ticks
+            .iter_mut()
+            .find(|tick| tick.tick_index == tick_index)
+            .ok_or_else(|| SureError::InvalidLiquidityPositionTick.into())
+    }
+
+    /// Update tick liquidity
+    ///
+    /// Add, or remove if negative, liquidity
+    /// in a tick of the position
+    pub fn update_tick_liquidity(&mut self, tick_index: i32, liquidity_delta: i128) -> Result<()> {
+        let liquidity_tick = self.get_liquidity_tick_mut(tick_index)?;
+        liquidity_tick.liquidity = add_liquidity_delta(liquidity_tick.liquidity, liquidity_delta)?;
+        self.liquidity = add_liquidity_delta(self.liquidity, liquidity_delta)?;
+        Ok(())
+    }
+
+    /// Settle loss
+    ///
+    /// Applies the claim losses of a tick to the
+    /// position. Has to be called before the liquidity of
+    /// the position is changed so that withdrawals
+    /// reflect the haircut
+    ///
+    /// # Arguments
+    /// * tick_index: the index of the tick
+    /// * tick: the tick the liquidity is at
+    ///
+    /// # Returns
+    /// * haircut: the liquidity lost by the position
+    pub fn settle_loss(&mut self, tick_index: i32, tick: &mut Tick) -> Result<u128> {
+        let loss_growth_x64 = tick.loss_growth_x64;
+        let liquidity_tick = self.get_liquidity_tick_mut(tick_index)?;
+        // the rounded up loss growth can charge the
+        // positions dust more than the loss
+        let haircut = liquidity_tick
+            .calculate_loss(loss_growth_x64)?
+            .min(tick.liquidity_lost);
+        tick.settle_loss(haircut)?;
+        liquidity_tick.liquidity -= haircut;
+        liquidity_tick.loss_growth_checkpoint_x64 = loss_growth_x64;
+        self.liquidity = self
+            .liquidity
+            .checked_sub(haircut)
+            .ok_or(SureError::LiquidityUnderflow)?;
+        Ok(haircut)
+    }
+}
+
+#[cfg(test)]
+pub mod test_liquidity_position {
+    use super::*;
+
+    #[test]
+    pub fn test_validate_tick_range() {
+        pub struct Test<'a> {
+            name: &'a str,
+            tick_index_lower: i32,
+            tick_index_upper: i32,
+            expected: Result<()>,
+        }
+        let tests = [
+            Test {
+                name: "single tick",
+                tick_index_lower: 10,
+                tick_index_upper: 20,
+                expected: Ok(()),
+            },
+            Test {
+                name: "range fills the tick ledger",
+                tick_index_lower: 0,
+                tick_index_upper: 640,
+                expected: Ok(()),
+            },
+            Test {
+                name: "range exceeds the tick ledger",
+                tick_index_lower: 0,
+                tick_index_upper: 650,
+                expected: Err(SureError::MaxLiquidityTicksExceeded.into()),
+            },
+            Test {
+                name: "lower tick between tick spaces",
+                tick_index_lower: 5,
+                tick_index_upper: 20,
+                expected: Err(SureError::InvalidLowerTickIndexProvided.into()),
+            },
+            Test {
+                name: "upper tick between tick spaces",
+                tick_index_lower: 10,
+                tick_index_upper: 25,
+                expected: Err(SureError::InvalidUpperTickIndexProvided.into()),
+            },
+            Test {
+                name: "empty range",
+                tick_index_lower: 20,
+                tick_index_upper: 20,
+                expected: Err(SureError::LowerTickgtUpperTick.into()),
+            },
+        ];
+
+        for test in tests {
+            assert_eq!(
+                LiquidityPosition::validate_tick_range(
+                    test.tick_index_lower,
+                    test.tick_index_upper,
+                    10
+                ),
+                test.expected,
+                "{}",
+                test.name
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_settle_loss() {
+        pub struct Test<'a> {
+            name: &'a str,
+            liquidity: u128,
+            loss: u128,
+            expected_haircut: u128,
+        }
+        let tests = [
+            Test {
+                name: "position holds all of the liquidity",
+                liquidity: 1_000,
+                loss: 300,
+                expected_haircut: 300,
+            },
+            Test {
+                name: "loss is shared with the other positions",
+                liquidity: 250,
+                loss: 300,
+                expected_haircut: 75,
+            },
+            Test {
+                name: "haircut is rounded down",
+                liquidity: 333,
+                loss: 100,
+                expected_haircut: 33,
+            },
+            Test {
+                name: "no loss",
+                liquidity: 500,
+                loss: 0,
+                expected_haircut: 0,
+            },
+        ];
+
+        for test in tests {
+            let mut tick = Tick {
+                liquidity_gross: 1_000,
+                liquidity_used: 600,
+                ..Tick::default()
+            };
+            let mut position = LiquidityPosition::default();
+            position.initialize(
+                0,
+                Pubkey::default(),
+                Pubkey::default(),
+                Pubkey::default(),
+                0,
+                10,
+                10,
+            );
+            position
+                .update_tick_liquidity(0, test.liquidity as i128)
+                .unwrap();
+            tick.record_loss(test.loss).unwrap();
+            assert_eq!({ tick.liquidity_used }, 600 - test.loss, "{}", test.name);
+
+            let haircut = position.settle_loss(0, &mut tick).unwrap();
+            assert_eq!(haircut, test.expected_haircut, "{}", test.name);
+            assert_eq!(
+                position.liquidity,
+                test.liquidity - test.expected_haircut,
+                "{}",
+                test.name
+            );
+            assert_eq!(
+                position.get_tick_liquidity(0).unwrap(),
+                position.liquidity,
+                "{}",
+                test.name
+            );
+            assert_eq!(
+                { tick.liquidity_lost },
+                test.loss - test.expected_haircut,
+                "{}",
+                test.name
+            );
+
+            // the loss is only applied once
+            assert_eq!(
+                position.settle_loss(0, &mut tick).unwrap(),
+                0,
+                "{}",
+                test.name
+            );
+            assert_eq!(
+                position.settle_loss(10, &mut tick).unwrap_err(),
+                SureError::InvalidLiquidityPositionTick.into(),
+                "{}",
+                test.name
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_settle_loss_all_positions() {
+        let mut tick = Tick {
+            liquidity_gross: 1_000,
+            liquidity_used: 900,
+            ..Tick::default()
+        };
+        let mut positions: Vec<LiquidityPosition> = [500, 300, 200]
+            .iter()
+            .map(|liquidity| {
+                let mut position = LiquidityPosition::default();
+                position.initialize(
+                    0,
+                    Pubkey::default(),
+                    Pubkey::default(),
+                    Pubkey::default(),
+                    0,
+                    10,
+                    10,
+                );
+                position.update_tick_liquidity(0, *liquidity).unwrap();
+                position
+            })
+            .collect();
+
+        tick.record_loss(400).unwrap();
+        assert_eq!(tick.get_available_liquidity(), 100);
+        let haircuts: Vec<u128> = positions
+            .iter_mut()
+            .map(|position| position.settle_loss(0, &mut tick).unwrap())
+            .collect();
+        assert_eq!(haircuts, vec![200, 120, 80]);
+        assert_eq!({ tick.liquidity_lost }, 0);
+        assert_eq!(
+            { tick.liquidity_gross },
+            positions
+                .iter()
+                .map(|position| position.liquidity)
+                .sum::<u128>()
+        );
+        assert_eq!(tick.get_available_liquidity(), 100);
+
+        // a loss larger than the used liquidity
+        assert!(tick.record_loss(501).is_err());
     }
 }
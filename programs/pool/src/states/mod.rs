@@ -4,8 +4,10 @@ pub mod fee_package;
 pub mod liquidity;
 pub mod owner;
 pub mod pool;
+pub mod registry;
 pub mod tick;
 pub mod tick_array;
+pub mod token_pool;
 
 pub use bitmap::*;
 pub use coverage::*;
@@ -13,5 +15,7 @@ pub use fee_package::*;
 pub use liquidity::*;
 pub use owner::*;
 pub use pool::*;
+pub use registry::*;
 pub use tick::*;
 pub use tick_array::*;
+pub use token_pool::*;
@@ -1,4 +1,5 @@
 use crate::states::fee_package::*;
+use crate::states::token_pool::find_token_pool_address;
 use crate::utils::*;
 use anchor_lang::prelude::*;
 
@@ -11,29 +12,110 @@ pub struct PoolManager {
     pub owner: Pubkey, // 32 bytes
     // bump to identify the PDA
     pub bump: u8, // 1 byte
+
+    /// Whether anyone can create a pool
+    pub permissionless: bool, // 1 byte
+
+    /// Lamports locked in the pool when it is
+    /// created by someone else than the owner
+    pub creation_bond: u64, // 8 bytes
+
+    /// Owner proposed by the current owner. Becomes the
+    /// owner once it accepts the pool manager
+    pub pending_owner: Pubkey, // 32 bytes
+
+    /// Oracle config whose proposals can claim
+    /// against the pools
+    pub oracle_config: Pubkey, // 32 bytes
 }
 
 impl PoolManager {
-    pub const SIZE: usize = 32 + 1;
-}
+    pub const SIZE: usize = 32 + 1 + 1 + 8 + 32 + 32;
 
-/// SurePools holds information on which programs are
-/// insured by Sure
-#[account]
-pub struct SurePools {
-    pub bump: u8, // 1 byte
+    pub fn initialize(&mut self, bump: u8, owner: &Pubkey) {
+        self.bump = bump;
+        self.owner = *owner;
+        self.permissionless = false;
+        self.creation_bond = 0;
+        self.pending_owner = Pubkey::default();
+        self.oracle_config = Pubkey::default();
+    }
 
-    /// Vec of insured programs
-    pub pools: Vec<Pubkey>, // 4 + 32*256 = 8196, 256 insured contracts
-}
+    /// Propose a new owner of the pool manager
+    ///
+    /// # Arguments
+    /// * new_owner: the owner that has to accept the pool manager
+    pub fn propose_owner(&mut self, new_owner: &Pubkey) {
+        self.pending_owner = *new_owner;
+    }
+
+    /// Accept the ownership of the pool manager
+    ///
+    /// # Arguments
+    /// * new_owner: the pending owner accepting the pool manager
+    ///
+    /// # Returns
+    /// * old_owner: the previous owner
+    pub fn accept_owner(&mut self, new_owner: &Pubkey) -> Result<Pubkey> {
+        if self.pending_owner == Pubkey::default() || self.pending_owner != *new_owner {
+            return Err(SureError::InvalidOwner.into());
+        }
+        let old_owner = self.owner;
+        self.owner = *new_owner;
+        self.pending_owner = Pubkey::default();
+        Ok(old_owner)
+    }
+
+    /// Update the pool creation policy
+    ///
+    /// # Arguments
+    /// * permissionless: allow anyone to create pools
+    /// * creation_bond: lamports to lock when creating a pool
+    pub fn update_policy(&mut self, permissionless: bool, creation_bond: u64) {
+        self.permissionless = permissionless;
+        self.creation_bond = creation_bond;
+    }
 
-impl SurePools {
-    pub const SPACE: usize = 1 + 4 + 32 * 256;
+    /// Update the oracle config trusted with claims
+    ///
+    /// # Arguments
+    /// * oracle_config: config of the oracle proposals
+    pub fn update_oracle_config(&mut self, oracle_config: &Pubkey) {
+        self.oracle_config = *oracle_config;
+    }
+
+    /// Check that the claim is made through the
+    /// oracle config of the pool manager
+    ///
+    /// # Arguments
+    /// * claim: the oracle proposal claiming against a pool
+    pub fn validate_claim(&self, claim: &AccountInfo) -> Result<()> {
+        if self.oracle_config == Pubkey::default() || get_claim_config(claim)? != self.oracle_config
+        {
+            return Err(SureError::InvalidOracleConfig.into());
+        }
+        Ok(())
+    }
+
+    /// Check if the creator is allowed to create a pool
+    ///
+    /// # Returns
+    /// * creation_bond: lamports the creator has to lock
+    pub fn validate_pool_creator(&self, creator: &Pubkey) -> Result<u64> {
+        if *creator == self.owner {
+            return Ok(0);
+        }
+        if !self.permissionless {
+            return Err(SureError::InvalidPoolCreator.into());
+        }
+        Ok(self.creation_bond)
+    }
 }
 
 /// Pool Account (PDA) contains information describing the
 /// insurance pool
 #[account]
+#[derive(Default)]
 pub struct Pool {
     /// Bump to identify the PDA
     pub bump: u8, // 1 byte
@@ -50,10 +132,13 @@ pub struct Pool {
     // founder
     pub founder: Pubkey,
 
+    /// Fee package the fee rates are copied from
+    pub fee_package: Pubkey, // 32 bytes
+
     /// fees
     /// 100th of a basis point
     pub fee_rate: u16,
-    /// (1/x)% of fee_rate
+    /// share of fee_rate in basis points
     pub protocol_fee: u16,
     pub founders_fee: u16,
 
@@ -69,8 +154,21 @@ pub struct Pool {
     pub token_pools: Vec<Pubkey>, // 4 + 32*64, 64 tokens for each pool
 
     /// Whether the insurance pool is locked
+    /// by an active claim
     pub locked: bool, // 1 byte
 
+    /// Paused by the pool manager. No coverage or
+    /// liquidity changes are allowed
+    pub paused: bool, // 1 byte
+
+    /// Claims only mode set by the pool manager.
+    /// No new coverage and no liquidity withdrawals
+    pub claims_only: bool, // 1 byte
+
+    /// Oracle proposal currently claiming against the pool.
+    /// Pubkey::default() if there is no active claim
+    pub active_claim: Pubkey, // 32 bytes
+
     // Mint of
     pub token_mint_0: Pubkey, // 32 bytes
     pub vault_0: Pubkey,      //32 bytes
@@ -79,16 +177,33 @@ pub struct Pool {
     pub token_mint_1: Pubkey, // 32 bytes
     pub vault_1: Pubkey, //32 bytes
 
-    /// Used liquidity
-    pub used_liquidity: u128, // 8 bytes
+    /// Lamports locked in the pool by the creator
+    pub creation_bond: u64, // 8 bytes
 }
 
 impl Pool {
-    pub const SPACE: usize = 1 + 4 + 200 + 4 + 32 + 4 + 32 * 64 + 1;
+    pub const MAX_TOKEN_POOLS: usize = 64;
+    // bumps, name, tick spacing, founder, fee package, fees,
+    // smart contract, token pools, status, active claim,
+    // mints and vaults and bond
+    pub const SPACE: usize = 2
+        + 4
+        + 200
+        + 4
+        + 32
+        + 32
+        + 4 * 2
+        + 32
+        + 4
+        + 32 * Pool::MAX_TOKEN_POOLS
+        + 3
+        + 32
+        + 4 * 32
+        + 8;
 
     pub fn seeds(&self) -> [&[u8]; 3] {
         [
-            &SURE_PREMIUM_POOL_SEED.as_bytes() as &[u8],
+            &SURE_PRIMARY_POOL_SEED.as_bytes() as &[u8],
             self.smart_contract.as_ref(),
             self.bump_array.as_ref(),
         ]
@@ -101,6 +216,7 @@ impl Pool {
         founder: Pubkey,
         tick_spacing: u16,
         fee_package: &Account<FeePackage>,
+        smart_contract: Pubkey,
         token_mint_0: Pubkey,
         token_mint_1: Pubkey,
         vault_0: Pubkey,
@@ -112,10 +228,199 @@ impl Pool {
         self.founder = founder;
         self.tick_spacing = tick_spacing;
         self.tick_spacing_array = tick_spacing.to_le_bytes();
+        self.smart_contract = smart_contract;
         self.token_mint_0 = token_mint_0;
         self.token_mint_1 = token_mint_1;
         self.vault_0 = vault_0;
         self.vault_1 = vault_1;
+        self.set_fee_package(fee_package);
+        Ok(())
+    }
+
+    /// Copy the fee rates from the fee package
+    pub fn set_fee_package(&mut self, fee_package: &Account<FeePackage>) {
+        self.fee_package = fee_package.key();
+        self.fee_rate = fee_package.fee_rate;
+        self.protocol_fee = fee_package.protocol_fee;
+        self.founders_fee = fee_package.founders_fee;
+    }
+
+    /// Add a token pool to the pool
+    ///
+    /// # Arguments
+    /// * token_pool: the token pool holding the collateral
+    pub fn add_token_pool(&mut self, token_pool: &Pubkey) -> Result<()> {
+        if self.token_pools.contains(token_pool) {
+            return Err(SureError::TokenPoolAlreadyExists.into());
+        }
+        if self.token_pools.len() >= Pool::MAX_TOKEN_POOLS {
+            return Err(SureError::MaxTokenPoolsExceeded.into());
+        }
+        self.token_pools.push(*token_pool);
+        Ok(())
+    }
+
+    /// Check that the token pools are all the token
+    /// pools of the pool, each given once
+    ///
+    /// # Arguments
+    /// * token_pools: the token pools to check
+    pub fn validate_token_pools(&self, token_pools: &[Pubkey]) -> Result<()> {
+        for (i, token_pool) in token_pools.iter().enumerate() {
+            if !self.token_pools.contains(token_pool) || token_pools[..i].contains(token_pool) {
+                return Err(SureError::InvalidTokenPool.into());
+            }
+        }
+        if token_pools.len() != self.token_pools.len() {
+            return Err(SureError::MissingTokenPools.into());
+        }
+        Ok(())
+    }
+
+    /// Check if the pool accepts the mint as collateral
+    ///
+    /// Either the primary mint or the mint of one
+    /// of the token pools
+    pub fn accepts_token_mint(&self, pool: &Pubkey, token_mint: &Pubkey) -> bool {
+        if *token_mint == self.token_mint_0 {
+            return true;
+        }
+        let (token_pool, _) = find_token_pool_address(pool, token_mint);
+        self.token_pools.contains(&token_pool)
+    }
+
+    /// Take the creation bond out of the pool
+    ///
+    /// # Returns
+    /// * creation_bond: lamports to move out of the pool
+    pub fn take_creation_bond(&mut self) -> Result<u64> {
+        if self.creation_bond == 0 {
+            return Err(SureError::NoCreationBond.into());
+        }
+        let creation_bond = self.creation_bond;
+        self.creation_bond = 0;
+        Ok(creation_bond)
+    }
+
+    /// Set the pool status
+    ///
+    /// # Arguments
+    /// * paused: pause coverage and liquidity changes
+    /// * claims_only: freeze new coverage and liquidity withdrawals
+    pub fn set_status(&mut self, paused: bool, claims_only: bool) {
+        self.paused = paused;
+        self.claims_only = claims_only;
+    }
+
+    /// Whether the pool only serves claims, either
+    /// set by the pool manager or by an active claim
+    pub fn is_claims_only(&self) -> bool {
+        self.locked || self.claims_only
+    }
+
+    /// Check that the pool accepts new coverage
+    pub fn can_increase_coverage(&self) -> Result<()> {
+        if self.paused {
+            return Err(SureError::PoolIsPaused.into());
+        }
+        if self.is_claims_only() {
+            return Err(SureError::PoolIsLocked.into());
+        }
+        Ok(())
+    }
+
+    /// Check that coverage can be reduced
+    pub fn can_decrease_coverage(&self) -> Result<()> {
+        if self.paused {
+            return Err(SureError::PoolIsPaused.into());
+        }
+        Ok(())
+    }
+
+    /// Check that the pool accepts new liquidity
+    pub fn can_increase_liquidity(&self) -> Result<()> {
+        if self.paused {
+            return Err(SureError::PoolIsPaused.into());
+        }
+        Ok(())
+    }
+
+    /// Check that liquidity can be withdrawn
+    pub fn can_decrease_liquidity(&self) -> Result<()> {
+        if self.paused {
+            return Err(SureError::PoolIsPaused.into());
+        }
+        if self.is_claims_only() {
+            return Err(SureError::LiquidityIsFrozen.into());
+        }
+        Ok(())
+    }
+
+    /// Check that expired coverage can be released
+    ///
+    /// Coverage can be claimed until the claims
+    /// against the pool are resolved
+    pub fn can_expire_coverage(&self) -> Result<()> {
+        if self.paused {
+            return Err(SureError::PoolIsPaused.into());
+        }
+        if self.is_claims_only() {
+            return Err(SureError::PoolIsLocked.into());
+        }
+        Ok(())
+    }
+
+    /// Check that a claim can be paid out of the pool
+    pub fn can_pay_claim(&self) -> Result<()> {
+        if self.paused {
+            return Err(SureError::PoolIsPaused.into());
+        }
+        Ok(())
+    }
+
+    /// Check that owed fees can be collected
+    pub fn can_collect_fees(&self) -> Result<()> {
+        if self.paused {
+            return Err(SureError::PoolIsPaused.into());
+        }
+        Ok(())
+    }
+
+    /// Check that token pools and tick arrays
+    /// can be added to the pool
+    pub fn can_extend(&self) -> Result<()> {
+        if self.paused {
+            return Err(SureError::PoolIsPaused.into());
+        }
+        Ok(())
+    }
+
+    /// Lock the pool for a claim
+    ///
+    /// locks the pool against new coverage while the claim
+    /// is voted on. Only one claim can be active at a time
+    ///
+    /// # Arguments
+    /// * claim: the oracle proposal claiming against the pool
+    pub fn lock_for_claim(&mut self, claim: &Pubkey) -> Result<()> {
+        if self.active_claim != Pubkey::default() {
+            return Err(SureError::PoolHasActiveClaim.into());
+        }
+        self.active_claim = *claim;
+        self.locked = true;
+        Ok(())
+    }
+
+    /// Release the claim on the pool
+    ///
+    /// # Arguments
+    /// * claim: the oracle proposal claiming against the pool
+    pub fn release_claim(&mut self, claim: &Pubkey) -> Result<()> {
+        if self.active_claim != *claim {
+            return Err(SureError::InvalidClaim.into());
+        }
+        self.active_claim = Pubkey::default();
+        self.locked = false;
         Ok(())
     }
 }
@@ -128,5 +433,366 @@ pub struct CreatePool {
     pub insurance_fee: u16,
 }
 
-#[event]
-pub struct InitializeTokenPool {}
+#[cfg(test)]
+pub mod test_pool {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    #[test]
+    pub fn test_validate_pool_creator() {
+        pub struct Test<'a> {
+            name: &'a str,
+            permissionless: bool,
+            creation_bond: u64,
+            is_owner: bool,
+            expected: Option<u64>,
+        }
+        let tests = [
+            Test {
+                name: "owner in permissioned mode",
+                permissionless: false,
+                creation_bond: 1_000,
+                is_owner: true,
+                expected: Some(0),
+            },
+            Test {
+                name: "creator in permissioned mode",
+                permissionless: false,
+                creation_bond: 1_000,
+                is_owner: false,
+                expected: None,
+            },
+            Test {
+                name: "owner in permissionless mode",
+                permissionless: true,
+                creation_bond: 1_000,
+                is_owner: true,
+                expected: Some(0),
+            },
+            Test {
+                name: "creator in permissionless mode",
+                permissionless: true,
+                creation_bond: 1_000,
+                is_owner: false,
+                expected: Some(1_000),
+            },
+        ];
+
+        let owner = Pubkey::new_unique();
+        for test in tests {
+            let mut pool_manager = PoolManager::default();
+            pool_manager.initialize(255, &owner);
+            pool_manager.update_policy(test.permissionless, test.creation_bond);
+            let creator = if test.is_owner {
+                owner
+            } else {
+                Pubkey::new_unique()
+            };
+            let res = pool_manager.validate_pool_creator(&creator);
+            match test.expected {
+                Some(bond) => assert_eq!(res.unwrap(), bond, "{}", test.name),
+                None => assert_eq!(
+                    res.unwrap_err(),
+                    SureError::InvalidPoolCreator.into(),
+                    "{}",
+                    test.name
+                ),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_validate_claim() {
+        let oracle_config = Pubkey::new_unique();
+        let claim = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = hash(b"account:Proposal").to_bytes()[..8].to_vec();
+        data.extend_from_slice(oracle_config.as_ref());
+        let claim_info = AccountInfo::new(
+            &claim,
+            true,
+            false,
+            &mut lamports,
+            &mut data,
+            &SURE_ORACLE,
+            false,
+            0,
+        );
+
+        let mut pool_manager = PoolManager::default();
+        pool_manager.initialize(255, &Pubkey::new_unique());
+        assert_eq!(
+            pool_manager.validate_claim(&claim_info).unwrap_err(),
+            SureError::InvalidOracleConfig.into(),
+            "no oracle config is trusted by default"
+        );
+        pool_manager.update_oracle_config(&Pubkey::new_unique());
+        assert_eq!(
+            pool_manager.validate_claim(&claim_info).unwrap_err(),
+            SureError::InvalidOracleConfig.into(),
+            "proposal of another config"
+        );
+        pool_manager.update_oracle_config(&oracle_config);
+        pool_manager.validate_claim(&claim_info).unwrap();
+
+        claim_info.try_borrow_mut_data().unwrap()[0] ^= 1;
+        assert_eq!(
+            pool_manager.validate_claim(&claim_info).unwrap_err(),
+            SureError::InvalidClaimAuthority.into(),
+            "claim is not a proposal"
+        );
+    }
+
+    #[test]
+    pub fn test_transfer_pool_manager() {
+        let owner = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let mut pool_manager = PoolManager::default();
+        pool_manager.initialize(255, &owner);
+        assert_eq!(
+            pool_manager.accept_owner(&new_owner).unwrap_err(),
+            SureError::InvalidOwner.into(),
+            "no pending owner"
+        );
+
+        pool_manager.propose_owner(&new_owner);
+        assert_eq!(pool_manager.owner, owner, "owner until accepted");
+        assert_eq!(
+            pool_manager
+                .accept_owner(&Pubkey::new_unique())
+                .unwrap_err(),
+            SureError::InvalidOwner.into(),
+            "only the pending owner can accept"
+        );
+        assert_eq!(pool_manager.accept_owner(&new_owner).unwrap(), owner);
+        assert_eq!(pool_manager.owner, new_owner);
+        assert_eq!(pool_manager.pending_owner, Pubkey::default());
+        assert!(
+            pool_manager.accept_owner(&new_owner).is_err(),
+            "the proposal is used once"
+        );
+    }
+
+    #[test]
+    pub fn test_take_creation_bond() {
+        let mut pool = Pool::default();
+        assert_eq!(
+            pool.take_creation_bond().unwrap_err(),
+            SureError::NoCreationBond.into()
+        );
+        pool.creation_bond = 1_000;
+        assert_eq!(pool.take_creation_bond().unwrap(), 1_000);
+        assert_eq!(pool.creation_bond, 0);
+        assert!(
+            pool.take_creation_bond().is_err(),
+            "the bond is only released once"
+        );
+    }
+
+    #[test]
+    pub fn test_claims() {
+        let mut pool = Pool::default();
+        let claim = Pubkey::new_unique();
+        let duplicate_claim = Pubkey::new_unique();
+        assert!(pool.can_increase_coverage().is_ok());
+
+        pool.lock_for_claim(&claim).unwrap();
+        assert_eq!(pool.active_claim, claim);
+        assert!(
+            pool.can_increase_coverage().is_err(),
+            "no coverage during claim"
+        );
+        assert!(
+            pool.lock_for_claim(&duplicate_claim).is_err(),
+            "reject concurrent claim"
+        );
+        assert!(
+            pool.release_claim(&duplicate_claim).is_err(),
+            "only the active claim can release the pool"
+        );
+
+        pool.release_claim(&claim).unwrap();
+        assert!(pool.can_increase_coverage().is_ok());
+        pool.lock_for_claim(&duplicate_claim).unwrap();
+        assert_eq!(pool.active_claim, duplicate_claim);
+    }
+
+    #[test]
+    pub fn test_pool_status() {
+        pub struct Test<'a> {
+            name: &'a str,
+            paused: bool,
+            claims_only: bool,
+            active_claim: bool,
+            increase_coverage: Option<SureError>,
+            decrease_coverage: Option<SureError>,
+            increase_liquidity: Option<SureError>,
+            decrease_liquidity: Option<SureError>,
+            expire_coverage: Option<SureError>,
+            pay_claim: Option<SureError>,
+            collect_fees: Option<SureError>,
+            extend: Option<SureError>,
+        }
+        let tests = [
+            Test {
+                name: "active pool",
+                paused: false,
+                claims_only: false,
+                active_claim: false,
+                increase_coverage: None,
+                decrease_coverage: None,
+                increase_liquidity: None,
+                decrease_liquidity: None,
+                expire_coverage: None,
+                pay_claim: None,
+                collect_fees: None,
+                extend: None,
+            },
+            Test {
+                name: "paused pool",
+                paused: true,
+                claims_only: false,
+                active_claim: false,
+                increase_coverage: Some(SureError::PoolIsPaused),
+                decrease_coverage: Some(SureError::PoolIsPaused),
+                increase_liquidity: Some(SureError::PoolIsPaused),
+                decrease_liquidity: Some(SureError::PoolIsPaused),
+                expire_coverage: Some(SureError::PoolIsPaused),
+                pay_claim: Some(SureError::PoolIsPaused),
+                collect_fees: Some(SureError::PoolIsPaused),
+                extend: Some(SureError::PoolIsPaused),
+            },
+            Test {
+                name: "claims only pool",
+                paused: false,
+                claims_only: true,
+                active_claim: false,
+                increase_coverage: Some(SureError::PoolIsLocked),
+                decrease_coverage: None,
+                increase_liquidity: None,
+                decrease_liquidity: Some(SureError::LiquidityIsFrozen),
+                expire_coverage: Some(SureError::PoolIsLocked),
+                pay_claim: None,
+                collect_fees: None,
+                extend: None,
+            },
+            Test {
+                name: "pool with active claim",
+                paused: false,
+                claims_only: false,
+                active_claim: true,
+                increase_coverage: Some(SureError::PoolIsLocked),
+                decrease_coverage: None,
+                increase_liquidity: None,
+                decrease_liquidity: Some(SureError::LiquidityIsFrozen),
+                expire_coverage: Some(SureError::PoolIsLocked),
+                pay_claim: None,
+                collect_fees: None,
+                extend: None,
+            },
+            Test {
+                name: "paused pool with active claim",
+                paused: true,
+                claims_only: false,
+                active_claim: true,
+                increase_coverage: Some(SureError::PoolIsPaused),
+                decrease_coverage: Some(SureError::PoolIsPaused),
+                increase_liquidity: Some(SureError::PoolIsPaused),
+                decrease_liquidity: Some(SureError::PoolIsPaused),
+                expire_coverage: Some(SureError::PoolIsPaused),
+                pay_claim: Some(SureError::PoolIsPaused),
+                collect_fees: Some(SureError::PoolIsPaused),
+                extend: Some(SureError::PoolIsPaused),
+            },
+        ];
+
+        for test in tests {
+            let mut pool = Pool::default();
+            pool.set_status(test.paused, test.claims_only);
+            if test.active_claim {
+                pool.lock_for_claim(&Pubkey::new_unique()).unwrap();
+            }
+            let checks = [
+                (pool.can_increase_coverage(), test.increase_coverage),
+                (pool.can_decrease_coverage(), test.decrease_coverage),
+                (pool.can_increase_liquidity(), test.increase_liquidity),
+                (pool.can_decrease_liquidity(), test.decrease_liquidity),
+                (pool.can_expire_coverage(), test.expire_coverage),
+                (pool.can_pay_claim(), test.pay_claim),
+                (pool.can_collect_fees(), test.collect_fees),
+                (pool.can_extend(), test.extend),
+            ];
+            for (res, expected) in checks {
+                match expected {
+                    Some(err) => assert_eq!(res.unwrap_err(), err.into(), "{}", test.name),
+                    None => assert!(res.is_ok(), "{}", test.name),
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_claims_only_outlives_claim() {
+        let mut pool = Pool::default();
+        let claim = Pubkey::new_unique();
+        pool.set_status(false, true);
+        pool.lock_for_claim(&claim).unwrap();
+        pool.release_claim(&claim).unwrap();
+        assert!(pool.is_claims_only(), "claims only is set by the manager");
+
+        pool.set_status(true, false);
+        assert!(
+            pool.lock_for_claim(&claim).is_ok(),
+            "claims can lock a paused pool"
+        );
+    }
+
+    #[test]
+    pub fn test_token_pools() {
+        let mut pool = Pool::default();
+        let pool_key = Pubkey::new_unique();
+        pool.token_mint_0 = Pubkey::new_unique();
+        let token_mint_0 = pool.token_mint_0;
+        let usdt = Pubkey::new_unique();
+        assert!(pool.accepts_token_mint(&pool_key, &token_mint_0));
+        assert!(!pool.accepts_token_mint(&pool_key, &usdt));
+
+        let (token_pool, _) = find_token_pool_address(&pool_key, &usdt);
+        pool.add_token_pool(&token_pool).unwrap();
+        assert!(pool.accepts_token_mint(&pool_key, &usdt));
+        assert!(
+            !pool.accepts_token_mint(&Pubkey::new_unique(), &usdt),
+            "token pool of another pool"
+        );
+        assert_eq!(
+            pool.add_token_pool(&token_pool).unwrap_err(),
+            SureError::TokenPoolAlreadyExists.into()
+        );
+
+        pool.validate_token_pools(&[token_pool]).unwrap();
+        assert_eq!(
+            pool.validate_token_pools(&[]).unwrap_err(),
+            SureError::MissingTokenPools.into()
+        );
+        assert_eq!(
+            pool.validate_token_pools(&[token_pool, token_pool])
+                .unwrap_err(),
+            SureError::InvalidTokenPool.into(),
+            "token pool given twice"
+        );
+        assert_eq!(
+            pool.validate_token_pools(&[Pubkey::new_unique()])
+                .unwrap_err(),
+            SureError::InvalidTokenPool.into(),
+            "token pool of another pool"
+        );
+
+        for _ in 1..Pool::MAX_TOKEN_POOLS {
+            pool.add_token_pool(&Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(
+            pool.add_token_pool(&Pubkey::new_unique()).unwrap_err(),
+            SureError::MaxTokenPoolsExceeded.into()
+        );
+    }
+}
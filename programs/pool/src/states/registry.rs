@@ -0,0 +1,324 @@
+///! Pool registry
+///!
+///! The registry keeps track of every insured smart contract.
+///! Pools are stored in pages of 256 entries where a bitmap
+///! marks the occupied slots. Each insured contract has an
+///! info account that can be looked up by the program id
+use anchor_lang::prelude::*;
+
+use crate::states::pool::Pool;
+use crate::utils::uint::U256;
+use crate::utils::*;
+
+pub const POOLS_PER_REGISTRY_PAGE: usize = 256;
+
+/// SurePools is the header of the registry
+///
+/// keeps count of the registry pages and
+/// the registered pools
+#[account]
+#[derive(Default)]
+pub struct SurePools {
+    pub bump: u8, // 1 byte
+
+    /// number of registry pages
+    pub pages: u16, // 2 bytes
+
+    /// number of registered pools
+    pub pool_count: u64, // 8 bytes
+}
+
+impl SurePools {
+    pub const SPACE: usize = 1 + 2 + 8;
+
+    pub fn initialize(&mut self, bump: u8) {
+        self.bump = bump;
+        self.pages = 0;
+        self.pool_count = 0;
+    }
+
+    /// Add a registry page
+    ///
+    /// # Returns
+    /// * page: index of the new page
+    pub fn add_page(&mut self) -> Result<u16> {
+        let page = self.pages;
+        self.pages = page
+            .checked_add(1)
+            .ok_or(SureError::MaxRegistryPagesExceeded)?;
+        Ok(page)
+    }
+
+    /// Count a pool registered in any of the pages
+    pub fn record_pool(&mut self) -> Result<()> {
+        self.pool_count = self
+            .pool_count
+            .checked_add(1)
+            .ok_or(SureError::OverflowU64)?;
+        Ok(())
+    }
+}
+
+/// Registry page
+///
+/// holds up to 256 pools. The bitmap marks
+/// which of the slots are occupied
+#[account(zero_copy)]
+#[repr(packed)]
+pub struct SurePoolsPage {
+    pub bump: u8, // 1 byte
+
+    /// index of the page in the registry
+    pub page: u16, // 2 bytes
+
+    /// occupied slots
+    pub bitmap: [u64; 4], // 32 bytes
+
+    /// pools in the page
+    pub pools: [Pubkey; POOLS_PER_REGISTRY_PAGE], // 32*256 = 8192 bytes
+}
+
+impl Default for SurePoolsPage {
+    #[inline]
+    fn default() -> SurePoolsPage {
+        SurePoolsPage {
+            bump: 0,
+            page: 0,
+            bitmap: [0; 4],
+            pools: [Pubkey::default(); POOLS_PER_REGISTRY_PAGE],
+        }
+    }
+}
+
+impl SurePoolsPage {
+    pub const SPACE: usize = 1 + 2 + 32 + 32 * POOLS_PER_REGISTRY_PAGE;
+
+    pub fn initialize(&mut self, bump: u8, page: u16) {
+        self.bump = bump;
+        self.page = page;
+        self.bitmap = [0; 4];
+    }
+
+    /// Check if the slot holds a pool
+    pub fn is_occupied(&self, slot: u8) -> bool {
+        let bitmap = U256(self.bitmap);
+        bitmap.bit(slot as usize)
+    }
+
+    /// Get the first free slot in the page
+    ///
+    /// # Returns
+    /// * None if the page is full
+    pub fn next_free_slot(&self) -> Option<u8> {
+        let free_slots = !U256(self.bitmap);
+        if free_slots.is_zero() {
+            return None;
+        }
+        Some(free_slots.trailing_zeros() as u8)
+    }
+
+    /// Register a pool in the first free slot
+    ///
+    /// # Arguments
+    /// * pool: the pool to register
+    ///
+    /// # Returns
+    /// * slot: the slot of the pool in the page
+    pub fn register_pool(&mut self, pool: &Pubkey) -> Result<u8> {
+        let slot = self.next_free_slot().ok_or(SureError::RegistryPageIsFull)?;
+        let bitmap = U256(self.bitmap) | (U256::one() << slot);
+        self.bitmap = bitmap.0;
+        self.pools[slot as usize] = *pool;
+        Ok(slot)
+    }
+
+    /// Get the registered pools in the page
+    pub fn get_pools(&self) -> Vec<Pubkey> {
+        let pools = self.pools;
+        (0..POOLS_PER_REGISTRY_PAGE)
+            .filter(|slot| self.is_occupied(*slot as u8))
+            .map(|slot| pools[slot])
+            .collect()
+    }
+}
+
+/// Token pool aggregates of an insured contract
+///
+/// the amounts are kept per mint since the token
+/// pools hold collateral with different decimals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct TokenPoolInfo {
+    /// mint of the collateral
+    pub token_mint: Pubkey, // 32 bytes
+
+    /// tokens locked in the liquidity vault
+    pub tvl: u64, // 8 bytes
+
+    /// liquidity used for coverage
+    pub coverage: u128, // 16 bytes
+}
+
+impl TokenPoolInfo {
+    pub const SPACE: usize = 32 + 8 + 16;
+}
+
+/// Insured contract info
+///
+/// registry entry of an insured smart contract. The
+/// address is derived from the program id of the contract
+/// so that the pool can be looked up by the program.
+#[account]
+#[derive(Default)]
+pub struct InsuredContractInfo {
+    pub bump: u8, // 1 byte
+
+    /// program insured by the pool
+    pub smart_contract: Pubkey, // 32 bytes
+
+    /// pool insuring the program
+    pub pool: Pubkey, // 32 bytes
+
+    /// registry page holding the pool
+    pub page: u16, // 2 bytes
+
+    /// slot of the pool in the registry page
+    pub slot: u8, // 1 byte
+
+    /// TVL and coverage of each token pool
+    pub token_pools: Vec<TokenPoolInfo>, // 4 + 56*64 bytes
+
+    /// last time the aggregates were updated
+    pub updated_ts: i64, // 8 bytes
+}
+
+impl InsuredContractInfo {
+    pub const SPACE: usize =
+        1 + 32 + 32 + 2 + 1 + 4 + TokenPoolInfo::SPACE * Pool::MAX_TOKEN_POOLS + 8;
+
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        smart_contract: &Pubkey,
+        pool: &Pubkey,
+        page: u16,
+        slot: u8,
+        time: i64,
+    ) {
+        self.bump = bump;
+        self.smart_contract = *smart_contract;
+        self.pool = *pool;
+        self.page = page;
+        self.slot = slot;
+        self.token_pools = Vec::new();
+        self.updated_ts = time;
+    }
+
+    /// Update the aggregates of the entry
+    ///
+    /// # Arguments
+    /// * token_pools: TVL and coverage of every token pool
+    /// * time: the current time
+    pub fn update_aggregates(&mut self, token_pools: Vec<TokenPoolInfo>, time: i64) -> Result<()> {
+        if token_pools.len() > Pool::MAX_TOKEN_POOLS {
+            return Err(SureError::MaxTokenPoolsExceeded.into());
+        }
+        self.token_pools = token_pools;
+        self.updated_ts = time;
+        Ok(())
+    }
+
+    /// Get the aggregates of the token pool of the mint
+    ///
+    /// # Returns
+    /// * None if the mint has no token pool
+    pub fn get_token_pool_info(&self, token_mint: &Pubkey) -> Option<&TokenPoolInfo> {
+        self.token_pools
+            .iter()
+            .find(|info| info.token_mint == *token_mint)
+    }
+}
+
+#[cfg(test)]
+pub mod test_registry {
+    use super::*;
+
+    #[test]
+    pub fn test_register_pools() {
+        let mut page = SurePoolsPage::default();
+        page.initialize(255, 3);
+        assert_eq!(page.next_free_slot(), Some(0));
+
+        let pools: Vec<Pubkey> = (0..POOLS_PER_REGISTRY_PAGE)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        for (idx, pool) in pools.iter().enumerate() {
+            let slot = page.register_pool(pool).unwrap();
+            assert_eq!(slot as usize, idx);
+            assert!(page.is_occupied(slot));
+        }
+        assert_eq!(page.next_free_slot(), None);
+        assert_eq!(page.get_pools(), pools);
+        assert!(
+            page.register_pool(&Pubkey::new_unique()).is_err(),
+            "page is full"
+        );
+    }
+
+    #[test]
+    pub fn test_free_slot_after_gap() {
+        let mut page = SurePoolsPage::default();
+        page.register_pool(&Pubkey::new_unique()).unwrap();
+        page.register_pool(&Pubkey::new_unique()).unwrap();
+
+        // free the first slot
+        let mut bitmap = page.bitmap;
+        bitmap[0] ^= 1;
+        page.bitmap = bitmap;
+        assert!(!page.is_occupied(0));
+        assert_eq!(page.next_free_slot(), Some(0));
+        assert_eq!(page.get_pools().len(), 1);
+    }
+
+    #[test]
+    pub fn test_registry_pages() {
+        let mut sure_pools = SurePools::default();
+        sure_pools.initialize(255);
+        assert_eq!(sure_pools.add_page().unwrap(), 0);
+        assert_eq!(sure_pools.add_page().unwrap(), 1);
+        assert_eq!(sure_pools.pages, 2);
+
+        sure_pools.pages = u16::MAX;
+        assert!(sure_pools.add_page().is_err());
+
+        sure_pools.record_pool().unwrap();
+        assert_eq!(sure_pools.pool_count, 1);
+    }
+
+    #[test]
+    pub fn test_update_aggregates() {
+        let mut info = InsuredContractInfo::default();
+        info.initialize(255, &Pubkey::new_unique(), &Pubkey::new_unique(), 0, 0, 10);
+        let usdc = TokenPoolInfo {
+            token_mint: Pubkey::new_unique(),
+            tvl: 1_000_000_000,
+            coverage: 400_000_000,
+        };
+        let sol = TokenPoolInfo {
+            token_mint: Pubkey::new_unique(),
+            tvl: 5_000_000_000_000,
+            coverage: 0,
+        };
+        info.update_aggregates(vec![usdc, sol], 20).unwrap();
+        assert_eq!(info.updated_ts, 20);
+        assert_eq!(info.get_token_pool_info(&usdc.token_mint), Some(&usdc));
+        assert_eq!(info.get_token_pool_info(&sol.token_mint), Some(&sol));
+        assert_eq!(info.get_token_pool_info(&Pubkey::new_unique()), None);
+
+        let token_pools = vec![usdc; Pool::MAX_TOKEN_POOLS + 1];
+        assert_eq!(
+            info.update_aggregates(token_pools, 30).unwrap_err(),
+            SureError::MaxTokenPoolsExceeded.into()
+        );
+        assert_eq!(info.updated_ts, 20);
+    }
+}
@@ -1,9 +1,14 @@
 use anchor_lang::prelude::*;
 
 use super::*;
+use crate::managers::coverage::{get_coverage_decrease, get_coverage_quote, CoverageQuote};
 use crate::pool::*;
 use crate::utils::product::ProductType;
-use crate::utils::tick_math::{get_sqrt_ratio_at_tick, MAX_TICK_INDEX, MIN_TICK_INDEX};
+use crate::utils::tick_math::{
+    calculate_premium, calculate_premium_diff, get_sqrt_ratio_at_tick, MAX_TICK_INDEX,
+    MIN_TICK_INDEX,
+};
+use crate::utils::uint::U256;
 use crate::utils::*;
 
 use std::cell::RefMut;
@@ -37,10 +42,17 @@ pub struct Tick {
     /// Locked liquidity indicates how much of the
     /// liquidity is locked in long term commitments
     pub liquidity_used: u128, // 16 bytes
+
+    /// Liquidity paid out in claims that is not yet
+    /// charged to the liquidity positions
+    pub liquidity_lost: u128, // 16 bytes
+
+    /// Claim losses per unit of liquidity as Q64.64
+    pub loss_growth_x64: u128, // 16 bytes
 }
 
 impl Tick {
-    pub const SIZE: usize = 16 + 16 + 16 + 16 + 16;
+    pub const SIZE: usize = 1 + 16 + 16 + 16 + 16;
 
     /// Update tick with a NewTick object
     pub fn update(&mut self, new_tick: &NewTick) {
@@ -75,7 +87,81 @@ impl Tick {
     }
 
     pub fn get_available_liquidity(&self) -> u128 {
-        self.liquidity_gross - self.liquidity_used
+        self.liquidity_gross - self.liquidity_used - self.liquidity_lost
+    }
+
+    /// Record loss
+    ///
+    /// Charges a claim payout to the liquidity used
+    /// from the tick. The loss is spread over all the
+    /// liquidity in the tick through the loss growth and
+    /// is kept as lost liquidity until the liquidity
+    /// positions settle it.
+    ///
+    /// The loss growth is rounded up so the loss is
+    /// fully charged to the positions
+    ///
+    /// # Arguments
+    /// * loss: the liquidity paid out from the tick
+    pub fn record_loss(&mut self, loss: u128) -> Result<()> {
+        if loss == 0 {
+            return Ok(());
+        }
+        let liquidity_gross = self.liquidity_gross;
+        self.liquidity_used = self
+            .liquidity_used
+            .checked_sub(loss)
+            .ok_or(SureError::LossExceedsUsedLiquidity)?;
+        self.liquidity_lost = self
+            .liquidity_lost
+            .checked_add(loss)
+            .ok_or(SureError::LiquidityOverflow)?;
+
+        let liquidity_gross = U256::from(liquidity_gross);
+        let loss_growth_delta_x64 =
+            ((U256::from(loss) << 64) + liquidity_gross - U256::one()) / liquidity_gross;
+        if loss_growth_delta_x64 > U256::from(u128::MAX) {
+            return Err(SureError::MultiplictationQ3232Overflow.into());
+        }
+        self.loss_growth_x64 = self
+            .loss_growth_x64
+            .checked_add(loss_growth_delta_x64.as_u128())
+            .ok_or(SureError::AdditionQ3232OverflowError)?;
+        Ok(())
+    }
+
+    /// Settle loss
+    ///
+    /// Removes the loss charged to a liquidity
+    /// position from the tick
+    ///
+    /// # Arguments
+    /// * haircut: the liquidity the position lost
+    pub fn settle_loss(&mut self, haircut: u128) -> Result<()> {
+        self.liquidity_gross = self
+            .liquidity_gross
+            .checked_sub(haircut)
+            .ok_or(SureError::LiquidityUnderflow)?;
+        self.liquidity_lost = self
+            .liquidity_lost
+            .checked_sub(haircut)
+            .ok_or(SureError::LiquidityUnderflow)?;
+        Ok(())
+    }
+
+    /// Update liquidity
+    ///
+    /// Add, or remove if negative, liquidity to the
+    /// tick. Only the available liquidity can be removed
+    ///
+    /// # Arguments
+    /// * liquidity_delta: the liquidity to add to the tick
+    pub fn update_liquidity(&mut self, liquidity_delta: i128) -> Result<()> {
+        if liquidity_delta < 0 && liquidity_delta.unsigned_abs() > self.get_available_liquidity() {
+            return Err(SureError::LiquidityFilled.into());
+        }
+        self.liquidity_gross = add_liquidity_delta(self.liquidity_gross, liquidity_delta)?;
+        Ok(())
     }
 
     /// Calculate coverage delta
@@ -88,13 +174,16 @@ impl Tick {
     ///     - Protocol fee
     ///     - Founder fee
     ///
+    /// The premium is priced by the curve between the tick
+    /// and the target tick. When the coverage is changed
+    /// the premium for the remaining period of the current
+    /// coverage is settled against the premium of the new
+    /// coverage.
+    ///
     /// Returns:
     ///     - fee_amount: the amount to be used to pay fees
-    ///     - amount_in: the amount to pay into vault 0
-    ///     - amount_out: the amount to withdraw from vault 1
-    ///
-    /// <checkpoint>
-    /// TODO: rewrite to use amount in and amount out
+    ///     - amount_in: the premium to pay into the premium vault, rounded up
+    ///     - amount_out: the premium to refund from the premium vault, rounded down
     pub fn calculate_coverage_delta(
         &self,
         tick_index: i32,
@@ -108,25 +197,59 @@ impl Tick {
     ) -> Result<(u128, u128, u128)> {
         // available liquidity at tick
         let available_liquidity = self.get_available_liquidity();
+        let new_covered_amount = if increase {
+            if coverage_delta > available_liquidity {
+                return Err(SureError::LiquidityFilled.into());
+            }
+            current_covered_amount
+                .checked_add(coverage_delta)
+                .ok_or(SureError::AdditionQ3232OverflowError)?
+        } else {
+            current_covered_amount
+                .checked_sub(coverage_delta)
+                .ok_or(SureError::SubtractionQ3232Error)?
+        };
 
         // calculate premium
-        let sqrt_price_x64 = get_sqrt_ratio_at_tick(tick_index);
-        let sqrt_price_target = get_sqrt_ratio_at_tick(target_tick_index);
-
-        // TODO: calculate
-        let remaining_premium = 0;
-        // calculates premium
-        let (increase_premium, premium_delta) = (true, 0);
+        let sqrt_price_x64 = get_sqrt_ratio_at_tick(tick_index)?;
+        let sqrt_price_target = get_sqrt_ratio_at_tick(target_tick_index)?;
+
+        // premium left on the current coverage
+        let remaining_premium = if current_covered_amount > 0 {
+            calculate_premium(
+                sqrt_price_target,
+                sqrt_price_x64,
+                current_covered_amount,
+                current_start_ts,
+                expiry_ts,
+            )?
+        } else {
+            0
+        };
+        let (increase_premium, premium_delta_x64) = calculate_premium_diff(
+            remaining_premium,
+            sqrt_price_target,
+            sqrt_price_x64,
+            new_covered_amount,
+            current_start_ts,
+            expiry_ts,
+        )?;
 
         // calculate base fee amount of amount
         let fee_amount = coverage_delta
             .wrapping_mul(fee_rate as u128)
             .wrapping_div(MAX_100th_BP as u128 - fee_rate as u128);
 
+        // Q64.64 -> Q64.0, premium paid is rounded up
+        // and premium refunded is rounded down
         let (amount_in, amount_out) = if increase_premium {
+            let premium_delta = premium_delta_x64
+                .checked_add(u64::MAX as u128)
+                .ok_or(SureError::AdditionQ3232OverflowError)?
+                >> 64;
             (premium_delta, 0)
         } else {
-            (0, premium_delta)
+            (0, premium_delta_x64 >> 64)
         };
 
         Ok((fee_amount, amount_in, amount_out))
@@ -262,6 +385,7 @@ pub struct TickArray {
     pub start_tick_index: i32,                        // 4 bytes
     pub ticks: [Tick; NUM_TICKS_IN_TICK_ARRAY_USIZE], // NUM_TICKS_IN_TICK_ARRAY*size_of::<Tick>
     pub pool: Pubkey,                                 // 32 bytes
+    pub token_mint: Pubkey,                           // 32 bytes
 }
 
 impl Default for TickArray {
@@ -269,6 +393,7 @@ impl Default for TickArray {
     fn default() -> TickArray {
         TickArray {
             pool: Pubkey::default(),
+            token_mint: Pubkey::default(),
             ticks: [Tick::default(); NUM_TICKS_IN_TICK_ARRAY_USIZE],
             start_tick_index: 0,
         }
@@ -276,14 +401,29 @@ impl Default for TickArray {
 }
 
 impl TickArray {
-    pub const SIZE: usize = 4 + 64 * 40 + 32;
+    pub const SIZE: usize = 4 + NUM_TICKS_IN_TICK_ARRAY_USIZE * Tick::SIZE + 32 + 32;
 
-    pub fn initialize(&mut self, pool: &Account<Pool>, start_tick_index: i32) -> Result<()> {
-        if !Tick::is_valid_tick(start_tick_index, pool.tick_spacing) {
-            return Err(SureError::InvalidTick.into());
+    /// Initialize the tick array
+    ///
+    /// # Arguments
+    /// * pool: the pool the tick array belongs to
+    /// * token_mint: mint of the liquidity in the tick array
+    /// * start_tick_index: the first tick in the array
+    pub fn initialize(
+        &mut self,
+        pool: &Account<Pool>,
+        token_mint: &Pubkey,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        if !is_valid_start_tick_index(start_tick_index, pool.tick_spacing) {
+            return Err(SureError::InvalidTickArrayStartIndex.into());
+        }
+        if !pool.accepts_token_mint(&pool.key(), token_mint) {
+            return Err(SureError::InvalidMint.into());
         }
         self.start_tick_index = start_tick_index;
         self.pool = pool.key();
+        self.token_mint = *token_mint;
         Ok(())
     }
 
@@ -296,6 +436,14 @@ impl TickArray {
         self.start_tick_index
     }
 
+    /// Check if the tick is located in the array
+    ///
+    /// The upper bound is the start of the next array
+    pub fn contains_tick_index(&self, tick_index: i32, tick_spacing: u16) -> bool {
+        let tick_array_width = NUM_TICKS_IN_TICK_ARRAY * tick_spacing as i32;
+        tick_index >= self.start_tick_index && tick_index < self.start_tick_index + tick_array_width
+    }
+
     /// Check if tick is in the tick array
     pub fn validate_tick_index(&self, tick_index: i32, tick_spacing: u16) -> bool {
         let lower_tick_index = self.start_tick_index;
@@ -430,6 +578,37 @@ impl TickArray {
             .update_tick(tick_update)?;
         Ok(())
     }
+
+    /// Record a claim loss on the tick
+    pub fn record_tick_loss(
+        &mut self,
+        tick_index: i32,
+        tick_spacing: u16,
+        loss: u128,
+    ) -> Result<()> {
+        if !self.validate_tick_index(tick_index, tick_spacing)
+            || !Tick::is_valid_tick(tick_index, tick_spacing)
+        {
+            return Err(SureError::InvalidTick.into());
+        }
+        let tick_location = get_tick_location(self.start_tick_index, tick_index, tick_spacing)?;
+
+        self.ticks
+            .get_mut(tick_location as usize)
+            .unwrap()
+            .record_loss(loss)
+    }
+
+    pub fn get_tick_mut(&mut self, tick_index: i32, tick_spacing: u16) -> Result<&mut Tick> {
+        if !self.validate_tick_index(tick_index, tick_spacing)
+            || !Tick::is_valid_tick(tick_index, tick_spacing)
+        {
+            return Err(SureError::InvalidTick.into());
+        }
+
+        let tick_location = get_tick_location(self.start_tick_index, tick_index, tick_spacing)?;
+        Ok(&mut self.ticks[tick_location as usize])
+    }
 }
 
 #[derive(Default, Debug, PartialEq)]
@@ -477,6 +656,108 @@ pub fn get_tick_location(start_tick_index: i32, tick_index: i32, tick_spacing: u
     Ok(tick_location)
 }
 
+/// Get the start tick index of the tick array
+/// holding the tick
+///
+/// Tick arrays are aligned to NUM_TICKS_IN_TICK_ARRAY*tick_spacing
+pub fn get_start_tick_index(tick_index: i32, tick_spacing: u16) -> i32 {
+    let tick_array_width = NUM_TICKS_IN_TICK_ARRAY * tick_spacing as i32;
+    tick_index.div_euclid(tick_array_width) * tick_array_width
+}
+
+/// Check if the start tick index is aligned to the
+/// tick array width and holds valid ticks
+pub fn is_valid_start_tick_index(start_tick_index: i32, tick_spacing: u16) -> bool {
+    if tick_spacing == 0 {
+        return false;
+    }
+    if start_tick_index > MAX_TICK_INDEX
+        || start_tick_index < get_start_tick_index(MIN_TICK_INDEX, tick_spacing)
+    {
+        return false;
+    }
+    start_tick_index == get_start_tick_index(start_tick_index, tick_spacing)
+}
+
+/// Get the start tick indexes of the tick arrays
+/// needed for the range [lower_tick_index, upper_tick_index]
+///
+/// The first array holds the lower tick. If the range is at the
+/// end of the tick interval the last valid array is repeated.
+///
+/// # Returns
+/// * start_tick_indexes: start tick index of tick_array_0..2
+pub fn get_tick_array_start_indexes(
+    lower_tick_index: i32,
+    upper_tick_index: i32,
+    tick_spacing: u16,
+) -> Result<[i32; 3]> {
+    if tick_spacing == 0 {
+        return Err(SureError::InvalidTickSpacing.into());
+    }
+    if lower_tick_index > upper_tick_index {
+        return Err(SureError::LowerTickgtUpperTick.into());
+    }
+    let tick_array_width = NUM_TICKS_IN_TICK_ARRAY * tick_spacing as i32;
+    let first_start_tick_index = get_start_tick_index(lower_tick_index, tick_spacing);
+    if get_start_tick_index(upper_tick_index, tick_spacing)
+        > first_start_tick_index + 2 * tick_array_width
+    {
+        return Err(SureError::TickRangeExceedsTickArrays.into());
+    }
+
+    let mut start_tick_indexes = [first_start_tick_index; 3];
+    for i in 1..3 {
+        let start_tick_index = start_tick_indexes[i - 1] + tick_array_width;
+        start_tick_indexes[i] = if start_tick_index > MAX_TICK_INDEX {
+            start_tick_indexes[i - 1]
+        } else {
+            start_tick_index
+        };
+    }
+    Ok(start_tick_indexes)
+}
+
+/// Find the address of the tick array
+///
+/// # Arguments
+/// * pool: the pool the tick array belongs to
+/// * token_mint: mint of the liquidity in the tick array
+/// * start_tick_index: the first tick in the array
+pub fn find_tick_array_address(
+    pool: &Pubkey,
+    token_mint: &Pubkey,
+    start_tick_index: i32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SURE_TICK_SEED.as_bytes(),
+            pool.as_ref(),
+            token_mint.as_ref(),
+            start_tick_index.to_le_bytes().as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+/// Get the addresses of the tick arrays needed
+/// for the range [lower_tick_index, upper_tick_index]
+///
+/// The arrays might not exist yet and have to be created
+/// with initialize_tick_array before they are used
+pub fn get_tick_array_addresses(
+    pool: &Pubkey,
+    token_mint: &Pubkey,
+    lower_tick_index: i32,
+    upper_tick_index: i32,
+    tick_spacing: u16,
+) -> Result<[Pubkey; 3]> {
+    let start_tick_indexes =
+        get_tick_array_start_indexes(lower_tick_index, upper_tick_index, tick_spacing)?;
+    Ok(start_tick_indexes
+        .map(|start_tick_index| find_tick_array_address(pool, token_mint, start_tick_index).0))
+}
+
 /// Tick Array Pool
 ///
 /// Used for combining tick arrays and perform
@@ -505,6 +786,31 @@ impl<'info> TickArrayPool<'info> {
         }
     }
 
+    /// Load the tick arrays of an instruction
+    ///
+    /// The same tick array can be passed several times at
+    /// the end of the tick range, it is only loaded once
+    pub fn load_mut(
+        ta0: &'info AccountLoader<TickArray>,
+        ta1: &'info AccountLoader<TickArray>,
+        ta2: &'info AccountLoader<TickArray>,
+    ) -> Result<Self> {
+        let (key0, key1, key2) = (ta0.key(), ta1.key(), ta2.key());
+        Ok(Self::new(
+            ta0.load_mut()?,
+            if key1 != key0 {
+                Some(ta1.load_mut()?)
+            } else {
+                None
+            },
+            if key2 != key0 && key2 != key1 {
+                Some(ta2.load_mut()?)
+            } else {
+                None
+            },
+        ))
+    }
+
     /// Max tick index
     ///
     /// Find the max tick index in the array
@@ -528,7 +834,7 @@ impl<'info> TickArrayPool<'info> {
     /// return: sqrt_price: 32.32
     pub fn max_sqrt_price_x32(&self, tick_spacing: u16) -> Result<u128> {
         let tick_index = self.max_tick_index(tick_spacing)?;
-        Ok(get_sqrt_ratio_at_tick(tick_index))
+        get_sqrt_ratio_at_tick(tick_index)
     }
 
     /// Get tick
@@ -641,16 +947,182 @@ impl<'info> TickArrayPool<'info> {
         tick_array.update_tick(tick_index, tick_spacing, tick_update)
     }
 
-    /// Update Ticks with new liquidity
-    /// TODO: implement
-    pub fn update_liquidity(liquidity_target: u128, current_tick: i32) -> Result<()> {
+    /// Quote coverage
+    ///
+    /// Calculate the coverage that can be bought
+    /// from the tick arrays in the pool
+    pub fn quote_coverage(
+        &self,
+        pool: &Pool,
+        coverage_amount: u128,
+        current_ts: i64,
+        expiry_ts: i64,
+    ) -> Result<CoverageQuote> {
+        let tick_arrays: Vec<&TickArray> =
+            self.arrays.iter().map(|tick_array| &**tick_array).collect();
+        get_coverage_quote(pool, &tick_arrays, coverage_amount, current_ts, expiry_ts)
+    }
+
+    /// Quote coverage decrease
+    ///
+    /// Calculate the coverage released from the
+    /// tick ledger of a position and the refund
+    pub fn quote_coverage_decrease(
+        &self,
+        pool: &Pool,
+        coverage_ticks: &[CoverageTick],
+        coverage_amount: u128,
+        current_ts: i64,
+        expiry_ts: i64,
+    ) -> Result<CoverageQuote> {
+        let tick_arrays: Vec<&TickArray> =
+            self.arrays.iter().map(|tick_array| &**tick_array).collect();
+        get_coverage_decrease(
+            pool,
+            &tick_arrays,
+            coverage_ticks,
+            coverage_amount,
+            current_ts,
+            expiry_ts,
+        )
+    }
+
+    /// Update coverage
+    ///
+    /// Use, or release, the liquidity of
+    /// the ticks in the quote
+    pub fn update_coverage(
+        &mut self,
+        quote: &CoverageQuote,
+        tick_spacing: u16,
+        increase_coverage: bool,
+    ) -> Result<()> {
+        for tick_coverage in quote.ticks.iter() {
+            self.update_tick_coverage(
+                tick_coverage.array_index,
+                tick_coverage.tick_index,
+                tick_spacing,
+                tick_coverage.amount,
+                increase_coverage,
+            )?;
+        }
         Ok(())
     }
+
+    /// Update tick coverage
+    ///
+    /// Use, or release, the liquidity of a single tick
+    pub fn update_tick_coverage(
+        &mut self,
+        array_index: usize,
+        tick_index: i32,
+        tick_spacing: u16,
+        amount: u128,
+        increase_coverage: bool,
+    ) -> Result<()> {
+        let tick = *self.get_tick(array_index, tick_index, tick_spacing)?;
+        let (tick_update, _) =
+            tick.calculate_coverage_update(increase_coverage, 0, amount, 0, 0)?;
+        self.update_tick(array_index, tick_index, tick_spacing, &tick_update)
+    }
+
+    /// Record tick loss
+    ///
+    /// Charge a claim payout to the liquidity
+    /// used from a single tick
+    pub fn record_tick_loss(
+        &mut self,
+        array_index: usize,
+        tick_index: i32,
+        tick_spacing: u16,
+        loss: u128,
+    ) -> Result<()> {
+        let tick_array = self.arrays.get_mut(array_index).unwrap();
+        tick_array.record_tick_loss(tick_index, tick_spacing, loss)
+    }
+
+    /// Find the array in the pool containing the tick
+    ///
+    /// Returns: None if the tick is not in any of the arrays
+    pub fn find_array_index(&self, tick_index: i32, tick_spacing: u16) -> Option<usize> {
+        self.arrays
+            .iter()
+            .position(|tick_array| tick_array.contains_tick_index(tick_index, tick_spacing))
+    }
+
+    /// Get a mutable tick
+    ///
+    /// Finds the array in the pool containing the tick
+    pub fn get_tick_mut(&mut self, tick_index: i32, tick_spacing: u16) -> Result<&mut Tick> {
+        let array_index = self
+            .find_array_index(tick_index, tick_spacing)
+            .ok_or(SureError::InvalidTickArrayIndexInTickArrayPool)?;
+        self.arrays[array_index].get_tick_mut(tick_index, tick_spacing)
+    }
+
+    /// Settle position loss
+    ///
+    /// Applies the claim losses of each tick in
+    /// [tick_index_lower, tick_index_upper) to the
+    /// liquidity position
+    ///
+    /// # Returns
+    /// * haircut: the liquidity lost by the position
+    pub fn settle_position_loss(
+        &mut self,
+        liquidity_position: &mut LiquidityPosition,
+        tick_spacing: u16,
+    ) -> Result<u128> {
+        let mut haircut: u128 = 0;
+        for tick_index in liquidity_position.get_tick_indexes() {
+            let tick = self.get_tick_mut(tick_index, tick_spacing)?;
+            haircut = haircut
+                .checked_add(liquidity_position.settle_loss(tick_index, tick)?)
+                .ok_or(SureError::LiquidityOverflow)?;
+        }
+        Ok(haircut)
+    }
+
+    /// Update position liquidity
+    ///
+    /// Add the same liquidity to each tick of the
+    /// liquidity position, or remove up to the
+    /// liquidity held in each tick if negative.
+    /// The losses of the position have to be settled
+    /// first
+    ///
+    /// # Returns
+    /// * amount: the liquidity added, or removed,
+    ///     summed over the ticks
+    pub fn update_position_liquidity(
+        &mut self,
+        liquidity_position: &mut LiquidityPosition,
+        tick_spacing: u16,
+        liquidity_delta: i128,
+    ) -> Result<u128> {
+        let mut amount: u128 = 0;
+        for tick_index in liquidity_position.get_tick_indexes() {
+            let tick_liquidity_delta = if liquidity_delta < 0 {
+                let tick_liquidity = liquidity_position.get_tick_liquidity(tick_index)?;
+                -(liquidity_delta.unsigned_abs().min(tick_liquidity) as i128)
+            } else {
+                liquidity_delta
+            };
+            self.get_tick_mut(tick_index, tick_spacing)?
+                .update_liquidity(tick_liquidity_delta)?;
+            liquidity_position.update_tick_liquidity(tick_index, tick_liquidity_delta)?;
+            amount = amount
+                .checked_add(tick_liquidity_delta.unsigned_abs())
+                .ok_or(SureError::LiquidityOverflow)?;
+        }
+        Ok(amount)
+    }
 }
 
 #[cfg(test)]
 pub mod tick_testing {
     use super::*;
+    use crate::utils::tick_math::UNIX_TIME_IN_YEARS;
 
     #[derive(Default)]
     pub struct TickProto {
@@ -682,6 +1154,7 @@ pub mod tick_testing {
                 bump: 0,
                 liquidity_gross: self.liquidity_gross,
                 liquidity_used: self.liquidity_used,
+                ..Tick::default()
             }
         }
     }
@@ -831,6 +1304,7 @@ pub mod tick_testing {
             test_name: &'a str,
             tick: Tick,
             tick_index: i32,
+            target_tick_index: i32,
             coverage_delta: u128,
             current_covered_amount: u128,
             fee_rate: u16,
@@ -839,6 +1313,124 @@ pub mod tick_testing {
             increase: bool,
             expected_output: ExpectedOutput,
         }
+        let year = UNIX_TIME_IN_YEARS as i64;
+
+        let test_data = [
+            Test {
+                test_name: "1. buy coverage for a year at 1bp",
+                tick: TickProto::new().liquidity_gross(2_000_000).build(),
+                tick_index: 0,
+                target_tick_index: 0,
+                coverage_delta: 1_000_000,
+                current_covered_amount: 0,
+                fee_rate: 1_000,
+                current_start_ts: 0,
+                expiry_ts: year,
+                increase: true,
+                expected_output: ExpectedOutput {
+                    fee_amount: 1_001,
+                    amount_in: 100,
+                    amount_out: 0,
+                },
+            },
+            Test {
+                test_name: "2. increase coverage pays the premium of the delta",
+                tick: TickProto::new()
+                    .liquidity_gross(2_000_000)
+                    .liquidity_used(1_000_000)
+                    .build(),
+                tick_index: 0,
+                target_tick_index: 0,
+                coverage_delta: 1_000_000,
+                current_covered_amount: 1_000_000,
+                fee_rate: 0,
+                current_start_ts: 0,
+                expiry_ts: year,
+                increase: true,
+                expected_output: ExpectedOutput {
+                    fee_amount: 0,
+                    amount_in: 100,
+                    amount_out: 0,
+                },
+            },
+            Test {
+                test_name: "3. decrease coverage refunds the premium of the delta",
+                tick: TickProto::new()
+                    .liquidity_gross(2_000_000)
+                    .liquidity_used(1_000_000)
+                    .build(),
+                tick_index: 0,
+                target_tick_index: 0,
+                coverage_delta: 500_000,
+                current_covered_amount: 1_000_000,
+                fee_rate: 0,
+                current_start_ts: 0,
+                expiry_ts: year,
+                increase: false,
+                expected_output: ExpectedOutput {
+                    fee_amount: 0,
+                    amount_in: 0,
+                    amount_out: 50,
+                },
+            },
+            Test {
+                test_name: "4. premium is rounded up for the buyer",
+                tick: TickProto::new().liquidity_gross(2_000_000).build(),
+                tick_index: 0,
+                target_tick_index: 0,
+                coverage_delta: 10_000,
+                current_covered_amount: 0,
+                fee_rate: 0,
+                current_start_ts: 0,
+                expiry_ts: year / 2,
+                increase: true,
+                expected_output: ExpectedOutput {
+                    fee_amount: 0,
+                    amount_in: 1,
+                    amount_out: 0,
+                },
+            },
+        ];
+
+        for test in test_data {
+            let (fee_amount, amount_in, amount_out) = test
+                .tick
+                .calculate_coverage_delta(
+                    test.tick_index,
+                    test.target_tick_index,
+                    test.coverage_delta,
+                    test.current_covered_amount,
+                    test.fee_rate,
+                    test.current_start_ts,
+                    test.expiry_ts,
+                    test.increase,
+                )
+                .unwrap();
+            assert_eq!(
+                fee_amount, test.expected_output.fee_amount,
+                "{}: fee amount",
+                test.test_name
+            );
+            assert_eq!(
+                amount_in, test.expected_output.amount_in,
+                "{}: amount in",
+                test.test_name
+            );
+            assert_eq!(
+                amount_out, test.expected_output.amount_out,
+                "{}: amount out",
+                test.test_name
+            );
+        }
+
+        // not enough liquidity left in the tick
+        let tick = TickProto::new()
+            .liquidity_gross(1_000)
+            .liquidity_used(500)
+            .build();
+        assert!(tick
+            .calculate_coverage_delta(0, 0, 501, 0, 0, 0, year, true)
+            .is_err());
     }
 }
 
@@ -876,6 +1468,138 @@ pub mod tick_array_testing {
             tick_array
         }
     }
+
+    #[test]
+    pub fn test_get_start_tick_index() {
+        pub struct Test<'a> {
+            name: &'a str,
+            tick_index: i32,
+            tick_spacing: u16,
+            expected_start_tick_index: i32,
+        }
+        let tests = [
+            Test {
+                name: "start of array",
+                tick_index: 640,
+                tick_spacing: 10,
+                expected_start_tick_index: 640,
+            },
+            Test {
+                name: "inside array",
+                tick_index: 1270,
+                tick_spacing: 10,
+                expected_start_tick_index: 640,
+            },
+            Test {
+                name: "negative tick",
+                tick_index: -10,
+                tick_spacing: 10,
+                expected_start_tick_index: -640,
+            },
+            Test {
+                name: "min tick",
+                tick_index: MIN_TICK_INDEX,
+                tick_spacing: 1,
+                expected_start_tick_index: -443_648,
+            },
+        ];
+        for test in tests {
+            assert_eq!(
+                get_start_tick_index(test.tick_index, test.tick_spacing),
+                test.expected_start_tick_index,
+                "{}",
+                test.name
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_is_valid_start_tick_index() {
+        let configs = [
+            (0, 10, true),
+            (640, 10, true),
+            (-640, 10, true),
+            (320, 10, false),
+            (10, 10, false),
+            (0, 0, false),
+            (-443_648, 1, true),
+            (-443_712, 1, false),
+            (443_584, 1, true),
+            (443_648, 1, false),
+        ];
+        for (start_tick_index, tick_spacing, expected) in configs {
+            assert_eq!(
+                is_valid_start_tick_index(start_tick_index, tick_spacing),
+                expected,
+                "start tick index: {}",
+                start_tick_index
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_get_tick_array_start_indexes() {
+        pub struct Test<'a> {
+            name: &'a str,
+            lower_tick_index: i32,
+            upper_tick_index: i32,
+            tick_spacing: u16,
+            expected: Result<[i32; 3]>,
+        }
+        let tests = [
+            Test {
+                name: "range in one array",
+                lower_tick_index: 10,
+                upper_tick_index: 100,
+                tick_spacing: 10,
+                expected: Ok([0, 640, 1280]),
+            },
+            Test {
+                name: "range over three arrays",
+                lower_tick_index: -20,
+                upper_tick_index: 1270,
+                tick_spacing: 10,
+                expected: Ok([-640, 0, 640]),
+            },
+            Test {
+                name: "range too wide",
+                lower_tick_index: -20,
+                upper_tick_index: 1280,
+                tick_spacing: 10,
+                expected: Err(SureError::TickRangeExceedsTickArrays.into()),
+            },
+            Test {
+                name: "end of tick interval",
+                lower_tick_index: MAX_TICK_INDEX - 10,
+                upper_tick_index: MAX_TICK_INDEX,
+                tick_spacing: 1,
+                expected: Ok([443_584, 443_584, 443_584]),
+            },
+            Test {
+                name: "lower tick gt upper tick",
+                lower_tick_index: 100,
+                upper_tick_index: 10,
+                tick_spacing: 10,
+                expected: Err(SureError::LowerTickgtUpperTick.into()),
+            },
+        ];
+        for test in tests {
+            let start_tick_indexes = get_tick_array_start_indexes(
+                test.lower_tick_index,
+                test.upper_tick_index,
+                test.tick_spacing,
+            );
+            assert_eq!(start_tick_indexes, test.expected, "{}", test.name);
+            if let Ok(start_tick_indexes) = start_tick_indexes {
+                for start_tick_index in start_tick_indexes {
+                    assert!(is_valid_start_tick_index(
+                        start_tick_index,
+                        test.tick_spacing
+                    ));
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -909,4 +1633,222 @@ pub mod tick_array_pool_testing {
             }
         }
     }
+
+    #[test]
+    pub fn test_update_coverage() {
+        let tick_spacing = 10;
+        let tick_arrays: Vec<RefCell<TickArray>> = [0, 640]
+            .iter()
+            .map(|start_tick_index| {
+                let mut tick_array = TickArrayProto::new()
+                    .set_start_tick_index(*start_tick_index)
+                    .build();
+                let mut ticks = tick_array.ticks;
+                ticks[1].liquidity_gross = 1_000;
+                tick_array.ticks = ticks;
+                RefCell::new(tick_array)
+            })
+            .collect();
+        let pool = Pool {
+            tick_spacing,
+            ..Pool::default()
+        };
+        let mut tick_array_pool = TickArrayPool::new(
+            tick_arrays[0].borrow_mut(),
+            Some(tick_arrays[1].borrow_mut()),
+            None,
+        );
+
+        let quote = tick_array_pool
+            .quote_coverage(&pool, 1_500, 0, 1_000)
+            .unwrap();
+        assert_eq!(quote.ticks_crossed(), 2);
+        tick_array_pool
+            .update_coverage(&quote, tick_spacing, true)
+            .unwrap();
+        assert_eq!(
+            tick_array_pool
+                .get_tick(0, 10, tick_spacing)
+                .unwrap()
+                .get_available_liquidity(),
+            0
+        );
+        assert_eq!(
+            tick_array_pool
+                .get_tick(1, 650, tick_spacing)
+                .unwrap()
+                .get_available_liquidity(),
+            500
+        );
+
+        // the used liquidity is not quoted again
+        let next_quote = tick_array_pool
+            .quote_coverage(&pool, 1_500, 0, 1_000)
+            .unwrap();
+        assert_eq!(next_quote.coverage_amount, 500);
+
+        // release the coverage
+        assert_eq!(tick_array_pool.find_array_index(650, tick_spacing), Some(1));
+        assert_eq!(tick_array_pool.find_array_index(1280, tick_spacing), None);
+        tick_array_pool
+            .update_coverage(&quote, tick_spacing, false)
+            .unwrap();
+        assert_eq!(
+            tick_array_pool
+                .get_tick(0, 10, tick_spacing)
+                .unwrap()
+                .get_available_liquidity(),
+            1_000
+        );
+    }
+
+    #[test]
+    pub fn test_update_position_liquidity() {
+        let tick_spacing = 10;
+        let tick_arrays: Vec<RefCell<TickArray>> = [0, 640]
+            .iter()
+            .map(|start_tick_index| {
+                RefCell::new(
+                    TickArrayProto::new()
+                        .set_start_tick_index(*start_tick_index)
+                        .build(),
+                )
+            })
+            .collect();
+        let mut tick_array_pool = TickArrayPool::new(
+            tick_arrays[0].borrow_mut(),
+            Some(tick_arrays[1].borrow_mut()),
+            None,
+        );
+
+        // the range crosses into the second array
+        let mut position = LiquidityPosition::default();
+        position.initialize(
+            0,
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            620,
+            660,
+            tick_spacing,
+        );
+        let amount = tick_array_pool
+            .update_position_liquidity(&mut position, tick_spacing, 1_000)
+            .unwrap();
+        assert_eq!(amount, 4_000);
+        assert_eq!(position.liquidity, 4_000);
+        for (array_index, tick_index) in [(0, 620), (0, 630), (1, 640), (1, 650)] {
+            assert_eq!(
+                tick_array_pool
+                    .get_tick(array_index, tick_index, tick_spacing)
+                    .unwrap()
+                    .get_available_liquidity(),
+                1_000
+            );
+        }
+        assert_eq!(
+            tick_array_pool
+                .get_tick(1, 660, tick_spacing)
+                .unwrap()
+                .get_available_liquidity(),
+            0,
+            "the upper tick is not part of the range"
+        );
+
+        // used liquidity can not be withdrawn
+        tick_array_pool
+            .update_tick_coverage(0, 620, tick_spacing, 600, true)
+            .unwrap();
+        assert_eq!(
+            tick_array_pool
+                .update_position_liquidity(&mut position, tick_spacing, -1_000)
+                .unwrap_err(),
+            SureError::LiquidityFilled.into()
+        );
+
+        // a claim is paid out of the lower tick
+        tick_array_pool
+            .record_tick_loss(0, 620, tick_spacing, 300)
+            .unwrap();
+        let haircut = tick_array_pool
+            .settle_position_loss(&mut position, tick_spacing)
+            .unwrap();
+        assert_eq!(haircut, 300);
+        assert_eq!(position.liquidity, 3_700);
+        assert_eq!(position.get_tick_liquidity(620).unwrap(), 700);
+        assert_eq!(
+            {
+                tick_array_pool
+                    .get_tick(0, 620, tick_spacing)
+                    .unwrap()
+                    .liquidity_gross
+            },
+            700
+        );
+        assert_eq!(
+            tick_array_pool
+                .settle_position_loss(&mut position, tick_spacing)
+                .unwrap(),
+            0,
+            "the loss is only settled once"
+        );
+
+        let amount = tick_array_pool
+            .update_position_liquidity(&mut position, tick_spacing, -400)
+            .unwrap();
+        assert_eq!(amount, 1_600);
+        assert_eq!(
+            tick_array_pool
+                .get_tick(0, 620, tick_spacing)
+                .unwrap()
+                .get_available_liquidity(),
+            0
+        );
+        assert_eq!(
+            tick_array_pool
+                .get_tick(1, 650, tick_spacing)
+                .unwrap()
+                .get_available_liquidity(),
+            600
+        );
+
+        // only the liquidity held in each tick is withdrawn
+        tick_array_pool
+            .update_tick_coverage(0, 620, tick_spacing, 300, false)
+            .unwrap();
+        let amount = tick_array_pool
+            .update_position_liquidity(&mut position, tick_spacing, -10_000)
+            .unwrap();
+        assert_eq!(amount, 2_100);
+        assert_eq!(position.liquidity, 0);
+        for (array_index, tick_index) in [(0, 620), (0, 630), (1, 640), (1, 650)] {
+            assert_eq!(
+                {
+                    tick_array_pool
+                        .get_tick(array_index, tick_index, tick_spacing)
+                        .unwrap()
+                        .liquidity_gross
+                },
+                0
+            );
+        }
+
+        // the range is outside of the tick arrays
+        let mut position = LiquidityPosition::default();
+        position.initialize(
+            0,
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            1270,
+            1290,
+            tick_spacing,
+        );
+        assert_eq!(
+            tick_array_pool
+                .update_position_liquidity(&mut position, tick_spacing, 1_000)
+                .unwrap_err(),
+            SureError::InvalidTickArrayIndexInTickArrayPool.into()
+        );
+    }
 }
@@ -0,0 +1,227 @@
+///! Token pools
+///!
+///! A pool can accept coverage collateral in several mints.
+///! Each mint has its own token pool holding the vaults
+///! and the liquidity accounting of the mint. The token
+///! pool of the primary mint uses the vaults of the pool
+use anchor_lang::prelude::*;
+
+use crate::states::tick_array::add_liquidity_delta;
+use crate::utils::*;
+
+/// Token Pool
+///
+/// collateral of a pool in a single mint. Tick arrays
+/// of the token pool are derived from the pool and the mint
+#[account]
+#[derive(Default)]
+pub struct TokenPool {
+    pub bump: u8, // 1 byte
+
+    /// Pool the token pool belongs to
+    pub pool: Pubkey, // 32 bytes
+
+    /// Mint of the collateral
+    pub token_mint: Pubkey, // 32 bytes
+
+    /// Vault holding the liquidity
+    pub vault: Pubkey, // 32 bytes
+
+    /// Vault holding the premiums
+    pub premium_vault: Pubkey, // 32 bytes
+
+    /// Liquidity provided to the token pool
+    pub liquidity: u128, // 16 bytes
+
+    /// Liquidity used for coverage
+    pub used_liquidity: u128, // 16 bytes
+
+    /// Fees owed to the protocol
+    pub protocol_fees_owed: u64, // 8 bytes
+
+    /// Fees owed to the founder
+    pub founders_fees_owed: u64, // 8 bytes
+}
+
+impl TokenPool {
+    pub const SPACE: usize = 1 + 32 + 32 + 32 + 32 + 16 + 16 + 8 + 8;
+
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        pool: &Pubkey,
+        token_mint: &Pubkey,
+        vault: &Pubkey,
+        premium_vault: &Pubkey,
+    ) {
+        self.bump = bump;
+        self.pool = *pool;
+        self.token_mint = *token_mint;
+        self.vault = *vault;
+        self.premium_vault = *premium_vault;
+        self.liquidity = 0;
+        self.used_liquidity = 0;
+        self.protocol_fees_owed = 0;
+        self.founders_fees_owed = 0;
+    }
+
+    /// Get the liquidity that is not used for coverage
+    pub fn get_available_liquidity(&self) -> u128 {
+        self.liquidity - self.used_liquidity
+    }
+
+    /// Update the liquidity of the token pool
+    ///
+    /// # Arguments
+    /// * liquidity_delta: liquidity added (removed if neg)
+    pub fn update_liquidity(&mut self, liquidity_delta: i128) -> Result<()> {
+        let liquidity = add_liquidity_delta(self.liquidity, liquidity_delta)?;
+        if liquidity < self.used_liquidity {
+            return Err(SureError::LiquidityFilled.into());
+        }
+        self.liquidity = liquidity;
+        Ok(())
+    }
+
+    /// Update the liquidity used for coverage
+    ///
+    /// # Arguments
+    /// * coverage_delta: coverage bought (sold if neg)
+    pub fn update_used_liquidity(&mut self, coverage_delta: i128) -> Result<()> {
+        let used_liquidity = add_liquidity_delta(self.used_liquidity, coverage_delta)?;
+        if used_liquidity > self.liquidity {
+            return Err(SureError::LiquidityFilled.into());
+        }
+        self.used_liquidity = used_liquidity;
+        Ok(())
+    }
+
+    /// Accrue protocol and founders fees
+    pub fn accrue_fees(&mut self, protocol_fee: u64, founders_fee: u64) -> Result<()> {
+        self.protocol_fees_owed = self
+            .protocol_fees_owed
+            .checked_add(protocol_fee)
+            .ok_or(SureError::OverflowU64)?;
+        self.founders_fees_owed = self
+            .founders_fees_owed
+            .checked_add(founders_fee)
+            .ok_or(SureError::OverflowU64)?;
+        Ok(())
+    }
+
+    /// Reset the owed protocol fees
+    ///
+    /// # Returns
+    /// * amount: protocol fees to withdraw
+    pub fn collect_protocol_fees(&mut self) -> u64 {
+        let fees = self.protocol_fees_owed;
+        self.protocol_fees_owed = 0;
+        fees
+    }
+
+    /// Take the keeper tip out of the owed protocol fees
+    ///
+    /// The premium in the premium vault is owed to the
+    /// liquidity providers, so keepers are paid by the protocol
+    ///
+    /// # Arguments
+    /// * keeper_tip: the tip earned by the keeper
+    ///
+    /// # Returns
+    /// * amount: tip to withdraw, capped by the owed protocol fees
+    pub fn take_keeper_tip(&mut self, keeper_tip: u64) -> u64 {
+        let tip = keeper_tip.min(self.protocol_fees_owed);
+        self.protocol_fees_owed -= tip;
+        tip
+    }
+
+    /// Reset the owed founders fees
+    ///
+    /// # Returns
+    /// * amount: founders fees to withdraw
+    pub fn collect_founders_fees(&mut self) -> u64 {
+        let fees = self.founders_fees_owed;
+        self.founders_fees_owed = 0;
+        fees
+    }
+}
+
+/// Find the address of the token pool
+///
+/// # Arguments
+/// * pool: the pool the token pool belongs to
+/// * token_mint: mint of the collateral
+pub fn find_token_pool_address(pool: &Pubkey, token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SURE_TOKEN_POOL_SEED.as_bytes(),
+            pool.as_ref(),
+            token_mint.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
+#[cfg(test)]
+pub mod test_token_pool {
+    use super::*;
+
+    #[test]
+    pub fn test_update_liquidity() {
+        let mut token_pool = TokenPool::default();
+        token_pool.update_liquidity(1_000).unwrap();
+        token_pool.update_used_liquidity(600).unwrap();
+        assert_eq!(token_pool.get_available_liquidity(), 400);
+
+        assert_eq!(
+            token_pool.update_used_liquidity(401).unwrap_err(),
+            SureError::LiquidityFilled.into(),
+            "coverage exceeds liquidity"
+        );
+        assert_eq!(
+            token_pool.update_liquidity(-401).unwrap_err(),
+            SureError::LiquidityFilled.into(),
+            "used liquidity cannot be withdrawn"
+        );
+
+        token_pool.update_used_liquidity(-600).unwrap();
+        token_pool.update_liquidity(-1_000).unwrap();
+        assert_eq!(token_pool.liquidity, 0);
+        assert_eq!(
+            token_pool.update_liquidity(-1).unwrap_err(),
+            SureError::LiquidityUnderflow.into()
+        );
+    }
+
+    #[test]
+    pub fn test_fees() {
+        let mut token_pool = TokenPool::default();
+        token_pool.accrue_fees(10, 4).unwrap();
+        token_pool.accrue_fees(5, 1).unwrap();
+        assert_eq!(token_pool.collect_protocol_fees(), 15);
+        assert_eq!(token_pool.collect_protocol_fees(), 0);
+        assert_eq!(token_pool.collect_founders_fees(), 5);
+
+        token_pool.accrue_fees(0, u64::MAX).unwrap();
+        assert!(token_pool.accrue_fees(0, 1).is_err());
+    }
+
+    #[test]
+    pub fn test_take_keeper_tip() {
+        let mut token_pool = TokenPool::default();
+        token_pool.accrue_fees(10, 4).unwrap();
+        assert_eq!(token_pool.take_keeper_tip(3), 3);
+        assert_eq!({ token_pool.protocol_fees_owed }, 7);
+        assert_eq!(
+            token_pool.take_keeper_tip(8),
+            7,
+            "tip is capped by the protocol fees"
+        );
+        assert_eq!(token_pool.collect_protocol_fees(), 0);
+        assert_eq!(
+            token_pool.collect_founders_fees(),
+            4,
+            "founders fees are not used for tips"
+        );
+    }
+}
@@ -1,7 +1,30 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::utils::SureError;
 
 pub use sure_nft_update_authority::ID as SURE_NFT_UPDATE_AUTH;
 mod sure_nft_update_authority {
     use super::*;
     declare_id!("rYhoVCsVF8dahDpAYUZ9sDygLbhoVgRcczMxnQhWWjg");
 }
+
+/// Oracle program whose proposals can claim against pools
+pub use sure_oracle::ID as SURE_ORACLE;
+mod sure_oracle {
+    use super::*;
+    declare_id!("2prR7H6LfRqwiP2iTyZG1suG4B3zU6JEpUBXWeQB66qH");
+}
+
+/// Get the oracle config of a claim
+///
+/// The claim is an oracle proposal. The config is the
+/// first field after the account discriminator
+pub fn get_claim_config(claim: &AccountInfo) -> Result<Pubkey> {
+    let data = claim.try_borrow_data()?;
+    let discriminator = &hash(b"account:Proposal").to_bytes()[..8];
+    if data.len() < 8 + 32 || &data[..8] != discriminator {
+        return Err(SureError::InvalidClaimAuthority.into());
+    }
+    Ok(Pubkey::new(&data[8..8 + 32]))
+}
@@ -23,6 +23,9 @@ pub enum SureError {
     #[msg("Invalid Pool creator provided. Are you sure you are the protocol owner?")]
     InvalidPoolCreator,
 
+    #[msg("The pool has no creation bond")]
+    NoCreationBond,
+
     #[msg("Could not provide liquidity")]
     CouldNotProvideLiquidity,
 
@@ -39,6 +42,24 @@ pub enum SureError {
     #[msg("Insurance Contract is not active")]
     InsuranceContractIsNotActive,
 
+    #[msg("Coverage position expires at a different time")]
+    InvalidCoverageExpiry,
+
+    #[msg("Coverage position holds coverage from another pool")]
+    InvalidCoveragePool,
+
+    #[msg("Coverage position has not expired")]
+    CoverageNotExpired,
+
+    #[msg("The max number of ticks in a coverage position is exceeded")]
+    MaxCoverageTicksExceeded,
+
+    #[msg("The max number of ticks in a liquidity position is exceeded")]
+    MaxLiquidityTicksExceeded,
+
+    #[msg("Coverage position still holds liquidity in ticks")]
+    CoverageTicksNotReleased,
+
     #[msg("Invalid Tick: Between tick spaces")]
     TickOutsideSpacing,
 
@@ -54,6 +75,15 @@ pub enum SureError {
     #[msg("Tick array not found in tick array pool")]
     InvalidTickArrayIndexInTickArrayPool,
 
+    #[msg("Start tick index is not aligned to the tick array width")]
+    InvalidTickArrayStartIndex,
+
+    #[msg("Tick range does not fit within three tick arrays")]
+    TickRangeExceedsTickArrays,
+
+    #[msg("Tick arrays are not consecutive")]
+    InvalidTickArraySequence,
+
     #[msg("Provided Liquidity is too large")]
     LiquidityTooLarge,
 
@@ -140,6 +170,63 @@ pub enum SureError {
 
     #[msg("Invalid product type id")]
     InvalidProductTypeId,
+
+    // ======= Claims ======
+    #[msg("Pool is locked")]
+    PoolIsLocked,
+
+    #[msg("Pool already has an active claim")]
+    PoolHasActiveClaim,
+
+    #[msg("Claim is not the active claim of the pool")]
+    InvalidClaim,
+
+    #[msg("Claim is not an oracle proposal")]
+    InvalidClaimAuthority,
+
+    #[msg("Claim is not made through the oracle config of the pool manager")]
+    InvalidOracleConfig,
+
+    #[msg("Pool is paused")]
+    PoolIsPaused,
+
+    #[msg("Liquidity withdrawals are frozen until the claim is resolved")]
+    LiquidityIsFrozen,
+
+    #[msg("Claim is larger than the coverage of the position")]
+    ClaimExceedsCoverage,
+
+    #[msg("Claim loss is larger than the liquidity used from the tick")]
+    LossExceedsUsedLiquidity,
+
+    #[msg("Invalid loss growth subtraction")]
+    InvalidLossGrowthSubtraction,
+
+    #[msg("Liquidity position does not hold liquidity in the tick")]
+    InvalidLiquidityPositionTick,
+
+    // ======= Token Pools ======
+    #[msg("The pool already has a token pool for the mint")]
+    TokenPoolAlreadyExists,
+
+    #[msg("The max number of token pools is exceeded")]
+    MaxTokenPoolsExceeded,
+
+    #[msg("The token pool does not belong to the pool")]
+    InvalidTokenPool,
+
+    #[msg("All the token pools of the pool have to be provided")]
+    MissingTokenPools,
+
+    // ======= Registry ======
+    #[msg("The registry page has no free slots")]
+    RegistryPageIsFull,
+
+    #[msg("The max number of registry pages is exceeded")]
+    MaxRegistryPagesExceeded,
+
+    #[msg("The insured contract info does not belong to the pool")]
+    InvalidInsuredContractInfo,
 }
 
 impl From<TryFromIntError> for SureError {
@@ -14,3 +14,5 @@ pub const SURE_NFT_MINT_SEED: &str = "sure-nft";
 pub const SURE_TOKEN_ACCOUNT_SEED: &str = "sure-token-account";
 pub const SURE_MP_METADATA_SEED: &str = "metadata";
 pub const SURE_DOMAIN: &str = "sure-pools";
+pub const SURE_FEE_PACKAGE_SEED: &str = "sure-fee-package";
+pub const SURE_TOKEN_POOL_SEED: &str = "sure-token-pool";
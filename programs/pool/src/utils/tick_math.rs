@@ -1,7 +1,6 @@
-use std::ops::Shr;
-
 use super::{uint::U256, SureError};
 use anchor_lang::prelude::*;
+use sure_common::fixed::Q64x64;
 /// the minimum tick i is calculated as
 /// i_min = ln(p_min)/ln(1.0001)
 /// where
@@ -26,18 +25,71 @@ pub const UNIX_TIME_IN_YEARS: i128 = 31556926;
 pub const BASE_FACTOR: f64 = 1.0001;
 pub const Q32_RESOLUTION: u8 = 32;
 
+/// The tick price is the yearly premium rate in bp
+pub const PREMIUM_RATE_BASIS_POINTS: u64 = 10_000;
+
+/// √1.0001^(-2^m) as Q64.64 for m = 1..=18
+const NEGATIVE_TICK_RATIOS_X64: [u128; 18] = [
+    18444899583751176498,
+    18443055278223354162,
+    18439367220385604838,
+    18431993317065449817,
+    18417254355718160513,
+    18387811781193591352,
+    18329067761203520168,
+    18212142134806087854,
+    17980523815641551639,
+    17526086738831147013,
+    16651378430235024244,
+    15030750278693429944,
+    12247334978882834399,
+    8131365268884726200,
+    3584323654723342297,
+    696457651847595233,
+    26294789957452057,
+    37481735321082,
+];
+
+/// √1.0001^(2^m) as Q32.96 for m = 1..=18
+const POSITIVE_TICK_RATIOS_X96: [u128; 18] = [
+    79236085330515764027303304731,
+    79244008939048815603706035061,
+    79259858533276714757314932305,
+    79291567232598584799939703904,
+    79355022692464371645785046466,
+    79482085999252804386437311141,
+    79736823300114093921829183326,
+    80248749790819932309965073892,
+    81282483887344747381513967011,
+    83390072131320151908154831281,
+    87770609709833776024991924138,
+    97234110755111693312479820773,
+    119332217159966728226237229890,
+    179736315981702064433883588727,
+    407748233172238350107850275304,
+    2098478828474011932436660412517,
+    55581415166113811149459800483533,
+    38992368544603139932233054999993551,
+];
+
 /// Get the price at a given tick
 ///
 /// Assume the relation
 ///  - P = 1.0001^(t)
 /// where P is the price and t is the tick
-pub fn get_price_ratio_at_tick(tick: i32) -> u128 {
-    1 as u128
-}
-
-/// TODO: find the correct ratios
-fn get_price_ratio_at_negative_tick() -> u128 {
-    return 0;
+///
+/// The price is calculated as the square of the sqrt price,
+/// P = (√1.0001^t)^2, which keeps it consistent with the
+/// premium calculation.
+///
+/// Returns: price as Q64.64
+pub fn get_price_ratio_at_tick(tick: i32) -> Result<u128> {
+    let sqrt_price_x64 = Q64x64::from_raw(get_sqrt_ratio_at_tick(tick)?);
+    // Q64.64 x Q64.64 -> Q128.128 >> 64 -> Q64.64
+    let price_x64 = sqrt_price_x64
+        .checked_mul(sqrt_price_x64)
+        .ok_or(SureError::MultiplictationQ3232Overflow)?;
+    Ok(price_x64.raw())
 }
 
 /// Calculate the sqrt price
@@ -51,7 +103,10 @@ fn get_price_ratio_at_negative_tick() -> u128 {
 ///
 /// When sign(tick) == 1, √1.0001^(2^m) is represented as Q32.96 as
 /// Q_m = √1.0001^(2^m) x 2^96
-pub fn get_sqrt_ratio_at_tick(tick: i32) -> u128 {
+pub fn get_sqrt_ratio_at_tick(tick: i32) -> Result<u128> {
+    if !(MIN_TICK_INDEX..=MAX_TICK_INDEX).contains(&tick) {
+        return Err(SureError::InvalidTick.into());
+    }
     if tick >= 0 {
         get_sqrt_ratio_at_positive_tick(tick)
     } else {
@@ -59,217 +114,113 @@ pub fn get_sqrt_ratio_at_tick(tick: i32) -> u128 {
     }
 }
 
-pub fn get_sqrt_ratio_at_negative_tick(tick_i: i32) -> u128 {
-    let tick = tick_i.abs();
-    let mut ratio: u128 = if tick & 1 != 0 {
+/// Calculate the sqrt price ratio at a negative tick
+///
+/// all factors are at most 1 and are multiplied as Q64.64
+pub fn get_sqrt_ratio_at_negative_tick(tick_i: i32) -> Result<u128> {
+    let tick = tick_i.unsigned_abs();
+    let mut ratio = Q64x64::from_raw(if tick & 1 != 0 {
         18445821805675392311
     } else {
         18446744073709551616
-    };
+    });
 
-    if tick & 2 != 0 {
-        ratio = (ratio * 18444899583751176498) >> 64
-    }
-    if tick & 4 != 0 {
-        ratio = (ratio * 18443055278223354162) >> 64
-    }
-    if tick & 8 != 0 {
-        ratio = (ratio * 18439367220385604838) >> 64
-    }
-    if tick & 16 != 0 {
-        ratio = (ratio * 18431993317065449817) >> 64
-    }
-    if tick & 32 != 0 {
-        ratio = (ratio * 18417254355718160513) >> 64
-    }
-    if tick & 64 != 0 {
-        ratio = (ratio * 18387811781193591352) >> 64
-    }
-    if tick & 128 != 0 {
-        ratio = (ratio * 18329067761203520168) >> 64
-    }
-    if tick & 256 != 0 {
-        ratio = (ratio * 18212142134806087854) >> 64
-    }
-    if tick & 512 != 0 {
-        ratio = (ratio * 17980523815641551639) >> 64
-    }
-    if tick & 1024 != 0 {
-        ratio = (ratio * 17526086738831147013) >> 64
-    }
-    if tick & 2048 != 0 {
-        ratio = (ratio * 16651378430235024244) >> 64
-    }
-    if tick & 4096 != 0 {
-        ratio = (ratio * 15030750278693429944) >> 64
-    }
-    if tick & 8192 != 0 {
-        ratio = (ratio * 12247334978882834399) >> 64
-    }
-    if tick & 16384 != 0 {
-        ratio = (ratio * 8131365268884726200) >> 64
-    }
-    if tick & 32768 != 0 {
-        ratio = (ratio * 3584323654723342297) >> 64
-    }
-    if tick & 65536 != 0 {
-        ratio = (ratio * 696457651847595233) >> 64
-    }
-    if tick & 131072 != 0 {
-        ratio = (ratio * 26294789957452057) >> 64
-    }
-    if tick & 262144 != 0 {
-        ratio = (ratio * 37481735321082) >> 64
+    for (m, constant) in NEGATIVE_TICK_RATIOS_X64.iter().enumerate() {
+        if tick & (2 << m) != 0 {
+            ratio = mul_ratio(ratio, *constant)?;
+        }
     }
+    Ok(ratio.raw())
+}
 
+/// Multiply the Q64.64 ratio with the Q64.64 constant √1.0001^(-2^m)
+fn mul_ratio(ratio: Q64x64, constant: u128) -> Result<Q64x64> {
     ratio
+        .checked_mul(Q64x64::from_raw(constant))
+        .ok_or_else(|| SureError::MultiplictationQ3232Overflow.into())
 }
 
 /// Calculate the sqrt price ratio at
 /// the given tick_index
 /// If tick > 0, need less precision in decimals, consider Q32.96 x Q32.96, where 96 is the binary scaling factor
 /// then rsh with 32 to get Q64.64
-pub fn get_sqrt_ratio_at_positive_tick(tick: i32) -> u128 {
-    let mut ratio: U256 = if tick & 1 != 0 {
-        U256::from(79232123823359799118286999567 as u128)
+pub fn get_sqrt_ratio_at_positive_tick(tick: i32) -> Result<u128> {
+    let tick = tick.unsigned_abs();
+    let mut ratio_x96 = U256::from(if tick & 1 != 0 {
+        79232123823359799118286999567_u128
     } else {
-        U256::from(79228162514264337593543950336 as u128)
-    };
-
-    if tick & 2 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(79236085330515764027303304731 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 4 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(79244008939048815603706035061 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 8 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(79259858533276714757314932305 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 16 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(79291567232598584799939703904 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 32 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(79355022692464371645785046466 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 64 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(79482085999252804386437311141 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 128 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(79736823300114093921829183326 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 256 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(80248749790819932309965073892 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 512 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(81282483887344747381513967011 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 1024 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(83390072131320151908154831281 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 2048 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(87770609709833776024991924138 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 4096 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(97234110755111693312479820773 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 8192 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(119332217159966728226237229890 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 16384 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(179736315981702064433883588727 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 32768 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(407748233172238350107850275304 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 65536 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(2098478828474011932436660412517 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 131072 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(55581415166113811149459800483533 as u128))
-            .unwrap()
-            .shr(96 as u128)
-    }
-    if tick & 262144 != 0 {
-        ratio = ratio
-            .checked_mul(U256::from(38992368544603139932233054999993551 as u128))
-            .unwrap()
-            .shr(96 as u128)
+        79228162514264337593543950336_u128
+    });
+
+    for (m, constant) in POSITIVE_TICK_RATIOS_X96.iter().enumerate() {
+        if tick & (2 << m) != 0 {
+            // Q32.96 x Q32.96 -> Q64.192 >> 96 -> Q32.96
+            ratio_x96 = ratio_x96
+                .checked_mul(U256::from(*constant))
+                .ok_or(SureError::MultiplictationQ3232Overflow)?
+                >> 96;
+        }
     }
 
-    // Return result as Q64.64 by shifting(div) Q32.96 by 32 bytes
-    ratio.shr(32 as u128).as_u128()
+    // Q32.96 >> 32 -> Q64.64
+    let ratio_x64 = ratio_x96 >> 32;
+    if ratio_x64 > U256::from(u128::MAX) {
+        return Err(SureError::MultiplictationQ3232Overflow.into());
+    }
+    Ok(ratio_x64.as_u128())
 }
 
-/// Calculate premium difference
+/// Calculate the yearly premium
 ///
-/// # Arguments
-/// - sqrt_price_x64: <U64.64>, price in %
-/// - amount: Q64.64
-/// Premium are given in bp 0.01% = 0.0001
-/// yearly premium
-/// P_a = A*sqrt(P)^2/10_000 , A: amount u64, P: price Q32.32
+/// The price of a tick is the yearly premium rate in bp,
+/// 0.01% = 0.0001. Covering the amount A with the liquidity
+/// between two sqrt prices costs
+///
+/// P_a = A/(sb - sa) * ∫_sa^sb s^2 ds / 10_000
+///     = A * (sa^2 + sa*sb + sb^2) / 30_000
 ///
-/// O_1 = sqrt(P)/100
-/// P_a = A*O_1^2
+/// where sa < sb are the sqrt prices. For a single tick,
+/// sa = sb, this reduces to P_a = A*sqrt(P)^2/10_000
 ///
-/// Is a BtoA trade -> price increasing. Should choose sqrt_price_target to
-/// be the smallest sqrt_price in tick sequence
+/// # Arguments
+/// - sqrt_price_target: Q64.64, the sqrt price to cover to
+/// - sqrt_price_current: Q64.64, the sqrt price to cover from
+/// - amount: the amount to be covered
+///
+/// Returns: yearly premium rounded up
 pub fn calculate_yearly_premium(
     sqrt_price_target: u128,
     sqrt_price_current: u128,
     amount: u128,
 ) -> Result<u64> {
-    //get_conditional_delta_amount_1(sqrt_price_target, sqrt_price_current, amount as i128)
-    Ok(2)
+    let (sqrt_price_lower, sqrt_price_upper) = if sqrt_price_target < sqrt_price_current {
+        (sqrt_price_target, sqrt_price_current)
+    } else {
+        (sqrt_price_current, sqrt_price_target)
+    };
+    let sqrt_price_lower = U256::from(sqrt_price_lower);
+    let sqrt_price_upper = U256::from(sqrt_price_upper);
+
+    // Q64.64 x Q64.64 -> Q128.128
+    let price_sum_x128 = sqrt_price_lower
+        .checked_mul(sqrt_price_lower)
+        .and_then(|sum| sum.checked_add(sqrt_price_lower.checked_mul(sqrt_price_upper)?))
+        .and_then(|sum| sum.checked_add(sqrt_price_upper.checked_mul(sqrt_price_upper)?))
+        .ok_or(SureError::MultiplictationQ3232Overflow)?;
+    let premium_x128 = price_sum_x128
+        .checked_mul(U256::from(amount))
+        .ok_or(SureError::MultiplictationQ3232Overflow)?;
+
+    // Q128.128 -> Q128.0 rounded up
+    let denominator = U256::from(3 * PREMIUM_RATE_BASIS_POINTS) << 128;
+    let premium = premium_x128
+        .checked_add(denominator - 1)
+        .ok_or(SureError::AdditionQ3232OverflowError)?
+        / denominator;
+    if premium > U256::from(u64::MAX) {
+        return Err(SureError::OverflowU64.into());
+    }
+    Ok(premium.as_u64())
 }
 
 /// Calculate the premium change
@@ -278,6 +229,7 @@ pub fn calculate_yearly_premium(
 /// - prev_premium: the previous premium for the period t_0 t_1
 /// - sqrt_price_x32: constant. The sqrt price
 /// - amount: new amount to be covered
+/// - current_ts: the current time
 /// - expiry_ts: the new expiry time
 ///
 /// Returns: tuple (Increased premium, premium change)
@@ -291,9 +243,16 @@ pub fn calculate_premium_diff(
     sqrt_price_target: u128,
     sqrt_price_current: u128,
     amount: u128,
+    current_ts: i64,
     expiry_ts: i64,
 ) -> Result<(bool, u128)> {
-    let new_premium = calculate_premium(sqrt_price_target, sqrt_price_current, amount, expiry_ts)?;
+    let new_premium = calculate_premium(
+        sqrt_price_target,
+        sqrt_price_current,
+        amount,
+        current_ts,
+        expiry_ts,
+    )?;
     let (increase_premium, premium_delta) = if new_premium > remaining_premium {
         (true, new_premium - remaining_premium)
     } else {
@@ -302,16 +261,19 @@ pub fn calculate_premium_diff(
     return Ok((increase_premium, premium_delta));
 }
 
+/// Calculate the premium for covering the amount
+/// from the current time until expiry
+///
+/// Returns: premium as Q64.64
 pub fn calculate_premium(
     sqrt_price_target: u128,
     sqrt_price_current: u128,
     amount: u128,
+    current_ts: i64,
     expiry_ts: i64,
 ) -> Result<u128> {
     let yearly_premium = calculate_yearly_premium(sqrt_price_target, sqrt_price_current, amount)?;
-    let time = Clock::get()?;
-    let t0 = time.unix_timestamp;
-    let premium = time_fraction(yearly_premium, t0, expiry_ts)?;
+    let premium = time_fraction(yearly_premium, current_ts, expiry_ts)?;
     Ok(premium)
 }
 
@@ -323,16 +285,16 @@ pub fn time_fraction(num: u64, t0: i64, t1: i64) -> Result<u128> {
     if t0 >= t1 {
         return Err(SureError::InvalidTimestamp.into());
     }
-    let t0_xi64 = (t0 as i128) << 64;
-    let t1_xi64 = (t1 as i128) << 64;
-    let time_frac_xi64 = (t1_xi64 - t0_xi64)
-        .checked_div(UNIX_TIME_IN_YEARS)
+    let duration = t1.checked_sub(t0).ok_or(SureError::InvalidTimestamp)?;
+    // Q64.64
+    let time_frac = Q64x64::from_int(duration as u64)
+        .checked_div_int(UNIX_TIME_IN_YEARS as u64)
         .ok_or(SureError::DivisionQ3232Error)?;
-    let num_xu64 = (num as u128) << 64;
-    let num_frac_x64 = num_xu64
-        .checked_mul(time_frac_xi64.try_into().unwrap())
+    // Q64.0 x Q64.64 -> Q64.64
+    let num_frac = time_frac
+        .checked_mul_int(num)
         .ok_or(SureError::MultiplictationQ3232Overflow)?;
-    Ok(num_frac_x64 >> 64)
+    Ok(num_frac.raw())
 }
 
 /// Calculate
@@ -344,157 +306,50 @@ pub fn get_tick_at_sqrt_ratio(sqrt_price_x64: u128) -> Result<i32> {
         return Err(SureError::SqrtRatioNotWithinRange.into());
     }
 
-    let mut r = sqrt_price_x64;
-    let mut msb = 0;
-
-    // Binary search method from 2^64, 2^32, 2^16,2^8,2^5
-    let mut f: u8 = ((r >= 0x10000000000000000) as u8) << 6; // if r >= 2^64, f=64 else 0
-    msb |= f; // add f to msb
-    r >>= f;
-
-    f = ((r >= 0x100000000) as u8) << 5; // If r >= 2^32, f = 32 else 0
-    msb |= f; // Add f to MSB
-    r >>= f; // Right shift by f
-
-    f = ((r >= 0x10000) as u8) << 4; // 2^16
-    msb |= f;
-    r >>= f;
-
-    f = ((r >= 0x100) as u8) << 3; // 2^8
-    msb |= f;
-    r >>= f;
-
-    f = ((r >= 0x10) as u8) << 2; // 2^4
-    msb |= f;
-    r >>= f;
-
-    f = ((r >= 0x4) as u8) << 1; // 2^2
-    msb |= f;
-    r >>= f;
-
-    f = ((r >= 0x2) as u8) << 0; // 2^0
-    msb |= f;
+    // most significant bit of the sqrt price
+    let msb = 127 - sqrt_price_x64.leading_zeros();
 
     // log2 (m x 2^e) = log2 (m) + e
     // For U64.64, e = -64. Subtract by 64 to remove x64 notation.
-    // Then left shift by 16 bits to convert into U96.32 form
-    let mut log_2_x32 = (msb as i128 - 64) << 32;
+    // Then left shift by 32 bits to convert into I96.32 form
+    let mut log_2_x32 = (i128::from(msb) - 64) << 32;
 
     // ------------------------------------------------------
     // Fractional part of logarithm
 
-    // Set r = r / 2^n as a Q33.31 number, where n stands for msb
-    r = if msb >= 64 {
+    // Set r = r / 2^n as a Q1.63 number, where n stands for msb
+    let mut r = if msb >= 63 {
         sqrt_price_x64 >> (msb - 63)
     } else {
         sqrt_price_x64 << (63 - msb)
     };
 
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 31; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 30; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 29; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 28; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 27; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 26; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 25; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 24; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 23; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 22; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 21; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 20; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 19; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 18; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 17; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 16; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 15; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
-    r = (r * r) >> 63; // r^2 as U33.31
-    f = (r >> 64) as u8; // MSB of r^2 (0 or 1)
-    log_2_x32 |= (f as i128) << 14; // Add f at 1st fractional place
-    r >>= f; // Divide r by 2 if MSB of f is non-zero
-
     // 14 bit refinement gives an error margin of 2^-14 / log2 (√1.0001) = 0.8461 < 1
     // Since tick is a decimal, an error under 1 is acceptable
+    for bit in (14..32).rev() {
+        // r < 2^64 so r^2 < 2^128 as Q2.126 >> 63 -> Q2.63
+        r = (r * r) >> 63;
+        // if r^2 >= 2 add the bit and divide r by 2
+        if r >> 64 != 0 {
+            log_2_x32 |= 1 << bit;
+            r >>= 1;
+        }
+    }
 
     // Change of base rule: multiply with 2^32 / log2 (√1.0001)
     let log_sqrt_10001_x64 = log_2_x32 * 59543866431248i128;
 
     // tick - 0.01
-    let tick_low = ((log_sqrt_10001_x64 - 184467440737095516i128) >> 64) as i32;
+    let tick_low = i32::try_from((log_sqrt_10001_x64 - 184467440737095516i128) >> 64)
+        .map_err(|_| SureError::InvalidTick)?;
 
     // tick + (2^-14 / log2(√1.0001)) + 0.01
-    let tick_high = ((log_sqrt_10001_x64 + 15793534762490258745i128) >> 64) as i32;
+    let tick_high = i32::try_from((log_sqrt_10001_x64 + 15793534762490258745i128) >> 64)
+        .map_err(|_| SureError::InvalidTick)?;
 
     Ok(if tick_low == tick_high {
         tick_low
-    } else if get_sqrt_ratio_at_tick(tick_high) <= sqrt_price_x64 {
+    } else if get_sqrt_ratio_at_tick(tick_high)? <= sqrt_price_x64 {
         tick_high
     } else {
         tick_low
@@ -537,15 +392,24 @@ mod tests {
         let tick_index = get_tick_at_sqrt_ratio(expected_sqrt_price).unwrap();
         assert_eq!(tick_index, expected_tick_index);
         // fails!
-        let sqrt_price = get_sqrt_ratio_at_tick(expected_tick_index);
+        let sqrt_price = get_sqrt_ratio_at_tick(expected_tick_index).unwrap();
         println!("(6 as u128) << 64: {}", (6 as u128) << 64);
         assert_eq!(sqrt_price, expected_sqrt_price);
 
         // and back
-        let sqrt_price_back = get_sqrt_ratio_at_tick(tick_index);
+        let sqrt_price_back = get_sqrt_ratio_at_tick(tick_index).unwrap();
         assert_eq!(sqrt_price_back, expected_sqrt_price);
     }
 
+    #[test]
+    fn test_get_sqrt_price_outside_tick_range() {
+        assert!(get_sqrt_ratio_at_tick(MAX_TICK_INDEX + 1).is_err());
+        assert!(get_sqrt_ratio_at_tick(MIN_TICK_INDEX - 1).is_err());
+        assert!(get_sqrt_ratio_at_tick(i32::MIN).is_err());
+        assert!(get_tick_at_sqrt_ratio(MIN_SQRT_RATIO - 1).is_err());
+        assert!(get_tick_at_sqrt_ratio(MAX_SQRT_RATIO + 1).is_err());
+    }
+
     #[test]
     fn test_get_sqrt_price_at_tick() {
         let options = [
@@ -672,8 +536,8 @@ mod tests {
         ];
 
         for (tick, exp_pos_sqrt_price, exp_neg_sqrt_price, frac_bit) in options {
-            let pos_sqrt_price = get_sqrt_ratio_at_tick(tick);
-            let neg_sqrt_price = get_sqrt_ratio_at_tick(-tick);
+            let pos_sqrt_price = get_sqrt_ratio_at_tick(tick).unwrap();
+            let neg_sqrt_price = get_sqrt_ratio_at_tick(-tick).unwrap();
             println!("{}, {}", pos_sqrt_price, exp_pos_sqrt_price);
             assert_eq!(
                 pos_sqrt_price, exp_pos_sqrt_price,
@@ -689,46 +553,245 @@ mod tests {
     }
 
     #[test]
-    pub fn test_calculate_yearly_premium() {
-        const Q64_MASK: u128 = 0xFFFF_FFFF_FFFF_FFFF;
-        pub struct ExpectedOutput {
-            premium: u64,
+    fn test_time_fraction() {
+        pub struct Test<'a> {
+            name: &'a str,
+            num: u64,
+            t0: i64,
+            t1: i64,
+            expected_fraction: f64,
+        }
+
+        let test_data = [
+            Test {
+                name: "1. a full year",
+                num: 1_000,
+                t0: 0,
+                t1: UNIX_TIME_IN_YEARS as i64,
+                expected_fraction: 1_000.0,
+            },
+            Test {
+                name: "2. half a year",
+                num: 1_000,
+                t0: 1_000,
+                t1: 1_000 + (UNIX_TIME_IN_YEARS as i64) / 2,
+                expected_fraction: 500.0,
+            },
+            Test {
+                name: "3. large amount",
+                num: u64::MAX / 2,
+                t0: 0,
+                t1: UNIX_TIME_IN_YEARS as i64,
+                expected_fraction: (u64::MAX / 2) as f64,
+            },
+        ];
+
+        for test in test_data {
+            let fraction = Q64x64::from_raw(time_fraction(test.num, test.t0, test.t1).unwrap());
+            assert!(
+                (fraction.to_f64() - test.expected_fraction).abs() <= test.expected_fraction * 1e-6,
+                "time_fraction.{}: expected {} got {}",
+                test.name,
+                test.expected_fraction,
+                fraction.to_f64()
+            );
+        }
+        assert!(time_fraction(1_000, 10, 10).is_err());
+    }
+
+    /// the premium only depends on the given time
+    #[test]
+    fn test_calculate_premium_at_time() {
+        let sqrt_price_target = get_sqrt_ratio_at_tick(-40000).unwrap();
+        let sqrt_price_current = get_sqrt_ratio_at_tick(-20000).unwrap();
+        let current_ts = 1_660_681_219;
+        let year = UNIX_TIME_IN_YEARS as i64;
+        let yearly_premium =
+            calculate_yearly_premium(sqrt_price_target, sqrt_price_current, 10_000).unwrap();
+
+        let premium = calculate_premium(
+            sqrt_price_target,
+            sqrt_price_current,
+            10_000,
+            current_ts,
+            current_ts + year,
+        )
+        .unwrap();
+        assert_eq!(premium, (yearly_premium as u128) << 64);
+
+        // shorten the coverage to half a year
+        let (increase_premium, premium_delta) = calculate_premium_diff(
+            premium,
+            sqrt_price_target,
+            sqrt_price_current,
+            10_000,
+            current_ts,
+            current_ts + year / 2,
+        )
+        .unwrap();
+        assert!(!increase_premium);
+        assert_eq!(premium_delta, premium / 2);
+
+        // expired coverage
+        assert!(calculate_premium(
+            sqrt_price_target,
+            sqrt_price_current,
+            10_000,
+            current_ts + year,
+            current_ts,
+        )
+        .is_err());
+    }
+
+    /// Yearly premium of the amount covered between the two ticks
+    fn expected_yearly_premium(tick_target: i32, tick_current: i32, amount: u128) -> f64 {
+        let price_target = BASE_FACTOR.powf(tick_target as f64);
+        let price_current = BASE_FACTOR.powf(tick_current as f64);
+        amount as f64 * (price_target + (price_target * price_current).sqrt() + price_current)
+            / 30_000.0
+    }
+
+    #[test]
+    fn test_get_price_ratio_at_tick() {
+        let ticks = [MIN_TICK_INDEX, -40000, -1, 0, 1, 40000, 200000];
+        for tick in ticks {
+            let price = Q64x64::from_raw(get_price_ratio_at_tick(tick).unwrap()).to_f64();
+            let expected_price = BASE_FACTOR.powf(tick as f64);
+            assert!(
+                (price - expected_price).abs() <= expected_price * 1e-6 + 1e-18,
+                "get_price_ratio_at_tick.{}: expected {} got {}",
+                tick,
+                expected_price,
+                price
+            );
         }
-        #[derive()]
+        assert_eq!(get_price_ratio_at_tick(0).unwrap(), 1 << 64);
+    }
+
+    #[test]
+    pub fn test_calculate_yearly_premium() {
         pub struct Test<'a> {
             name: &'a str,
-            sqrt_price_target: u128,
-            sqrt_price_current: u128,
+            tick_target: i32,
+            tick_current: i32,
             amount: u128,
-            expected_output: ExpectedOutput,
-        };
+        }
 
-        let test_data = [Test {
-            name: "1. assume buying insurance. Sqrt price target < sqrt_price_current",
-            sqrt_price_target: get_sqrt_ratio_at_tick(MIN_TICK_INDEX),
-            sqrt_price_current: get_sqrt_ratio_at_tick(-40000),
-            amount: 10_000,
-            expected_output: ExpectedOutput { premium: 128 },
-        }];
+        let test_data = [
+            Test {
+                name: "1. assume buying insurance. Sqrt price target < sqrt_price_current",
+                tick_target: MIN_TICK_INDEX,
+                tick_current: -40000,
+                amount: 10_000,
+            },
+            Test {
+                name: "2. low ticks",
+                tick_target: -40000,
+                tick_current: -20000,
+                amount: 1_000_000_000,
+            },
+            Test {
+                name: "3. single tick at 1bp",
+                tick_target: 0,
+                tick_current: 0,
+                amount: 1_000_000,
+            },
+            Test {
+                name: "4. range is symmetric",
+                tick_target: 100,
+                tick_current: 0,
+                amount: 1_000_000,
+            },
+            Test {
+                name: "5. around 100%",
+                tick_target: 92100,
+                tick_current: 92110,
+                amount: 1_000_000,
+            },
+            Test {
+                name: "6. high ticks",
+                tick_target: 200000,
+                tick_current: 200064,
+                amount: 1_000_000,
+            },
+            Test {
+                name: "7. edge tick near MAX_TICK_INDEX",
+                tick_target: MAX_TICK_INDEX - 1,
+                tick_current: MAX_TICK_INDEX,
+                amount: 1,
+            },
+            Test {
+                name: "8. edge tick at MIN_TICK_INDEX",
+                tick_target: MIN_TICK_INDEX,
+                tick_current: MIN_TICK_INDEX,
+                amount: u64::MAX as u128,
+            },
+        ];
 
         for test in test_data {
             let premium = calculate_yearly_premium(
-                test.sqrt_price_target,
-                test.sqrt_price_current,
+                get_sqrt_ratio_at_tick(test.tick_target).unwrap(),
+                get_sqrt_ratio_at_tick(test.tick_current).unwrap(),
                 test.amount,
             )
             .unwrap();
-            assert_eq!(
-                premium,
-                test.expected_output.premium,
-                "calculate_yearly_premium.{}.fail with \n
-                sqrt_price_target: {} \n
-                sqrt_price_current: {}
-                ",
+            let expected_premium =
+                expected_yearly_premium(test.tick_target, test.tick_current, test.amount);
+            assert!(
+                (premium as f64 - expected_premium).abs() <= expected_premium * 1e-6 + 1.0,
+                "calculate_yearly_premium.{}: expected {} got {}",
                 test.name,
-                test.sqrt_price_target.bitand(Q64_MASK),
-                test.sqrt_price_current
+                expected_premium,
+                premium
+            );
+            assert!(
+                premium as f64 >= expected_premium * (1.0 - 1e-6),
+                "calculate_yearly_premium.{}: premium should be rounded up",
+                test.name
             );
         }
+
+        // a single tick costs amount*P/10_000
+        let amount = 1_000_000u128;
+        for tick in [-40000, 0, 40000] {
+            let sqrt_price = get_sqrt_ratio_at_tick(tick).unwrap();
+            let premium = calculate_yearly_premium(sqrt_price, sqrt_price, amount).unwrap();
+            let price_x64 = get_price_ratio_at_tick(tick).unwrap();
+            let expected_premium = Q64x64::from_raw(price_x64)
+                .checked_mul_int(amount as u64)
+                .unwrap()
+                .to_f64()
+                / PREMIUM_RATE_BASIS_POINTS as f64;
+            assert!((premium as f64 - expected_premium).abs() <= 1.0);
+        }
+
+        // exact premium at 1bp
+        assert_eq!(
+            calculate_yearly_premium(1 << 64, 1 << 64, 1_000_000).unwrap(),
+            100
+        );
+        // nothing covered, nothing paid
+        assert_eq!(
+            calculate_yearly_premium(
+                get_sqrt_ratio_at_tick(MIN_TICK_INDEX).unwrap(),
+                get_sqrt_ratio_at_tick(MAX_TICK_INDEX).unwrap(),
+                0
+            )
+            .unwrap(),
+            0
+        );
+        // the premium does not fit in a u64
+        assert!(calculate_yearly_premium(
+            get_sqrt_ratio_at_tick(MAX_TICK_INDEX - 1).unwrap(),
+            get_sqrt_ratio_at_tick(MAX_TICK_INDEX).unwrap(),
+            1_000_000,
+        )
+        .is_err());
+        assert!(calculate_yearly_premium(
+            get_sqrt_ratio_at_tick(MAX_TICK_INDEX).unwrap(),
+            get_sqrt_ratio_at_tick(MAX_TICK_INDEX).unwrap(),
+            u128::MAX,
+        )
+        .is_err());
     }
 }
@@ -1,5 +1,6 @@
 use crate::states::pool::*;
 use crate::states::*;
+use crate::utils::errors::SureError;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
 use anchor_lang::solana_program::instruction::Instruction;
@@ -58,6 +59,29 @@ pub fn withdraw_from_vault<'info>(
     )
 }
 
+/// Withdraw lamports from the pool
+///
+/// The pool is owned by the program so the
+/// lamports can be moved without a CPI
+pub fn withdraw_lamports_from_pool<'info>(
+    pool: &Account<'info, Pool>,
+    destination: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let pool_info = pool.to_account_info();
+    let pool_lamports = pool_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(SureError::InvalidAmount)?;
+    let destination_lamports = destination
+        .lamports()
+        .checked_add(amount)
+        .ok_or(SureError::OverflowU64)?;
+    **pool_info.try_borrow_mut_lamports()? = pool_lamports;
+    **destination.try_borrow_mut_lamports()? = destination_lamports;
+    Ok(())
+}
+
 /// Burn the NFT and close the nft token account
 pub fn burn_liquidity_position_nft<'info>(
     pool: &Account<'info, Pool>,
@@ -0,0 +1,602 @@
+//! fixed point arithmetic
+//!
+//! Typed Q-format numbers used by the Sure programs.
+//! A QM.N number stores the value x as x * 2^N in an
+//! unsigned integer of M+N bits. IM.N is the signed variant.
+//!
+//! All arithmetic is checked and returns None on overflow,
+//! underflow or division by zero. Results are truncated
+//! towards zero, except for the signed multiplication which
+//! rounds towards negative infinity.
+
+use std::ops::Div;
+
+/// 2^32 as a float
+const POW32: f64 = 4294967296.0;
+/// 2^16 as a float
+const POW16: f64 = 65536.0;
+
+/// Multiply two u128 and shift the 256 bit result
+/// right by 64 bits
+///
+/// ### Returns
+/// * (a x b) >> 64 if it fits in an u128
+fn mul_shr_64(a: u128, b: u128) -> Option<u128> {
+    let (a1, a0) = (a >> 64, a & u64::MAX as u128);
+    let (b1, b0) = (b >> 64, b & u64::MAX as u128);
+
+    // a x b = a1*b1 x 2^128 + (a1*b0 + a0*b1) x 2^64 + a0*b0
+    let high = a1 * b1;
+    if high > u64::MAX as u128 {
+        return None;
+    }
+    let mid = (a1 * b0).checked_add(a0 * b1)?;
+    let low = (a0 * b0) >> 64;
+    (high << 64).checked_add(mid)?.checked_add(low)
+}
+
+/// Divide (a << 64) by b without a 256 bit intermediate
+///
+/// ### Returns
+/// * (a << 64) / b if b > 0 and the result fits in an u128
+fn shl_64_div(a: u128, b: u128) -> Option<u128> {
+    if b == 0 {
+        return None;
+    }
+    let integer = a / b;
+    if integer > u64::MAX as u128 {
+        return None;
+    }
+
+    // long division of the remainder for the 64 fractional bits
+    let mut remainder = a % b;
+    let mut fraction: u128 = 0;
+    for _ in 0..64 {
+        let carry = remainder >> 127;
+        remainder <<= 1;
+        fraction <<= 1;
+        if carry == 1 || remainder >= b {
+            remainder = remainder.wrapping_sub(b);
+            fraction |= 1;
+        }
+    }
+    Some((integer << 64) | fraction)
+}
+
+/// Unsigned Q32.32
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Q32x32(u64);
+
+impl Q32x32 {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << 32);
+    pub const MAX: Self = Self(u64::MAX);
+
+    pub const fn from_raw(raw: u64) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(self) -> u64 {
+        self.0
+    }
+
+    /// Q32.0 -> Q32.32
+    pub fn from_int(num: u32) -> Self {
+        Self((num as u64) << 32)
+    }
+
+    /// Q64.0 -> Q32.32, None if num does not fit in 32 bits
+    pub fn checked_from_int(num: u64) -> Option<Self> {
+        u32::try_from(num).ok().map(Self::from_int)
+    }
+
+    /// Round a float to the nearest Q32.32
+    pub fn from_f64(num: f64) -> Option<Self> {
+        let raw = (num * POW32).round();
+        if !raw.is_finite() || raw < 0.0 || raw >= u64::MAX as f64 {
+            return None;
+        }
+        Some(Self(raw as u64))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        let integer = self.floor() as f64;
+        let fraction = (self.0 & u32::MAX as u64) as f64 / POW32;
+        integer + fraction
+    }
+
+    /// Integer part
+    pub fn floor(self) -> u32 {
+        (self.0 >> 32) as u32
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Q32.32 x Q32.32 -> Q64.64 >> 32 -> Q32.32
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let product = (self.0 as u128 * rhs.0 as u128) >> 32;
+        u64::try_from(product).ok().map(Self)
+    }
+
+    /// Q32.32 x Q64.0 -> Q32.32
+    pub fn checked_mul_int(self, rhs: u64) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self)
+    }
+
+    /// Q32.32 << 32 / Q32.32 -> Q32.32
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        let quotient = ((self.0 as u128) << 32).div(rhs.0 as u128);
+        u64::try_from(quotient).ok().map(Self)
+    }
+
+    /// Q32.32 / Q64.0 -> Q32.32
+    pub fn checked_div_int(self, rhs: u64) -> Option<Self> {
+        self.0.checked_div(rhs).map(Self)
+    }
+
+    /// Q32.32 -> Q64.64
+    pub fn to_q64x64(self) -> Q64x64 {
+        Q64x64((self.0 as u128) << 32)
+    }
+
+    /// Q32.32 -> Q16.16, None if the integer part
+    /// does not fit in 16 bits
+    pub fn to_q16x16(self) -> Option<Q16x16> {
+        u32::try_from(self.0 >> 16).ok().map(Q16x16)
+    }
+
+    /// Q32.32 -> I32.32, None if larger than i64::MAX
+    pub fn to_i32x32(self) -> Option<I32x32> {
+        i64::try_from(self.0).ok().map(I32x32)
+    }
+}
+
+/// Unsigned Q64.64
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Q64x64(u128);
+
+impl Q64x64 {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << 64);
+    pub const MAX: Self = Self(u128::MAX);
+
+    pub const fn from_raw(raw: u128) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(self) -> u128 {
+        self.0
+    }
+
+    /// Q64.0 -> Q64.64
+    pub fn from_int(num: u64) -> Self {
+        Self((num as u128) << 64)
+    }
+
+    /// Round a float to the nearest Q64.64
+    pub fn from_f64(num: f64) -> Option<Self> {
+        let raw = (num * POW32 * POW32).round();
+        if !raw.is_finite() || raw < 0.0 || raw >= u128::MAX as f64 {
+            return None;
+        }
+        Some(Self(raw as u128))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        let integer = self.floor() as f64;
+        let fraction = (self.0 & u64::MAX as u128) as f64 / POW32 / POW32;
+        integer + fraction
+    }
+
+    /// Integer part
+    pub fn floor(self) -> u64 {
+        (self.0 >> 64) as u64
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Q64.64 x Q64.64 -> Q128.128 >> 64 -> Q64.64
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        mul_shr_64(self.0, rhs.0).map(Self)
+    }
+
+    /// Q64.64 x Q64.0 -> Q64.64
+    pub fn checked_mul_int(self, rhs: u64) -> Option<Self> {
+        self.0.checked_mul(rhs as u128).map(Self)
+    }
+
+    /// Q64.64 << 64 / Q64.64 -> Q64.64
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        shl_64_div(self.0, rhs.0).map(Self)
+    }
+
+    /// Q64.64 / Q64.0 -> Q64.64
+    pub fn checked_div_int(self, rhs: u64) -> Option<Self> {
+        self.0.checked_div(rhs as u128).map(Self)
+    }
+
+    /// Q64.64 -> Q32.32, None if the integer part
+    /// does not fit in 32 bits
+    pub fn to_q32x32(self) -> Option<Q32x32> {
+        u64::try_from(self.0 >> 32).ok().map(Q32x32)
+    }
+}
+
+/// Signed I32.32
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct I32x32(i64);
+
+impl I32x32 {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << 32);
+    pub const MAX: Self = Self(i64::MAX);
+    pub const MIN: Self = Self(i64::MIN);
+
+    pub const fn from_raw(raw: i64) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+
+    /// I32.0 -> I32.32
+    pub fn from_int(num: i32) -> Self {
+        Self((num as i64) << 32)
+    }
+
+    /// Combine a sign and a magnitude
+    pub fn from_sign_and_abs(negative: bool, abs: Q32x32) -> Option<Self> {
+        if negative {
+            // -2^63 is representable while 2^63 is not
+            if abs.0 == 1 << 63 {
+                return Some(Self::MIN);
+            }
+            i64::try_from(abs.0).ok().map(|num| Self(-num))
+        } else {
+            i64::try_from(abs.0).ok().map(Self)
+        }
+    }
+
+    /// Round a float to the nearest I32.32
+    pub fn from_f64(num: f64) -> Option<Self> {
+        let raw = (num * POW32).round();
+        if !raw.is_finite() || raw < i64::MIN as f64 || raw >= i64::MAX as f64 {
+            return None;
+        }
+        Some(Self(raw as i64))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        let abs = self.abs().to_f64();
+        if self.is_negative() {
+            -abs
+        } else {
+            abs
+        }
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// |I32.32| -> Q32.32
+    pub fn abs(self) -> Q32x32 {
+        Q32x32(self.0.unsigned_abs())
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        self.0.checked_neg().map(Self)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// I32.32 x I32.32 -> I64.64 >> 32 -> I32.32
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let product = (self.0 as i128 * rhs.0 as i128) >> 32;
+        i64::try_from(product).ok().map(Self)
+    }
+
+    /// I32.32 x Q64.0 -> I32.32
+    pub fn checked_mul_int(self, rhs: u64) -> Option<Self> {
+        let product = self.0 as i128 * rhs as i128;
+        i64::try_from(product).ok().map(Self)
+    }
+
+    /// I32.32 << 32 / I32.32 -> I32.32
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        let quotient = ((self.0 as i128) << 32).div(rhs.0 as i128);
+        i64::try_from(quotient).ok().map(Self)
+    }
+
+    /// I32.32 / Q64.0 -> I32.32
+    pub fn checked_div_int(self, rhs: u64) -> Option<Self> {
+        if rhs == 0 {
+            return None;
+        }
+        let quotient = (self.0 as i128).div(rhs as i128);
+        i64::try_from(quotient).ok().map(Self)
+    }
+}
+
+/// Unsigned Q16.16
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Q16x16(u32);
+
+impl Q16x16 {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << 16);
+    pub const MAX: Self = Self(u32::MAX);
+
+    pub const fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(self) -> u32 {
+        self.0
+    }
+
+    /// Q16.0 -> Q16.16
+    pub fn from_int(num: u16) -> Self {
+        Self((num as u32) << 16)
+    }
+
+    /// Round a float to the nearest Q16.16
+    pub fn from_f64(num: f64) -> Option<Self> {
+        let raw = (num * POW16).round();
+        if !raw.is_finite() || raw < 0.0 || raw > u32::MAX as f64 {
+            return None;
+        }
+        Some(Self(raw as u32))
+    }
+
+    pub fn to_f64(self) -> f64 {
+        let integer = self.floor() as f64;
+        let fraction = (self.0 & u16::MAX as u32) as f64 / POW16;
+        integer + fraction
+    }
+
+    /// Integer part
+    pub fn floor(self) -> u16 {
+        (self.0 >> 16) as u16
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Q16.16 x Q16.16 -> Q32.32 >> 16 -> Q16.16
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let product = (self.0 as u64 * rhs.0 as u64) >> 16;
+        u32::try_from(product).ok().map(Self)
+    }
+
+    /// Q16.16 x Q16.16 -> Q32.32 without losing precision
+    pub fn widening_mul(self, rhs: Self) -> Q32x32 {
+        Q32x32(self.0 as u64 * rhs.0 as u64)
+    }
+
+    /// Q16.16 << 16 / Q16.16 -> Q16.16
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        let quotient = ((self.0 as u64) << 16).div(rhs.0 as u64);
+        u32::try_from(quotient).ok().map(Self)
+    }
+
+    /// Q16.16 -> Q32.32
+    pub fn to_q32x32(self) -> Q32x32 {
+        Q32x32((self.0 as u64) << 16)
+    }
+}
+
+#[cfg(test)]
+pub mod test_fixed {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    pub fn test_conversions() {
+        pub struct Test {
+            name: String,
+            num: f64,
+        }
+        let tests = [
+            Test {
+                name: "1. integer".to_string(),
+                num: 3.0,
+            },
+            Test {
+                name: "2. fraction".to_string(),
+                num: 0.5,
+            },
+            Test {
+                name: "3. mixed".to_string(),
+                num: 1234.5678,
+            },
+        ];
+
+        for test in tests {
+            let q32 = Q32x32::from_f64(test.num).unwrap();
+            assert!(
+                (q32.to_f64() - test.num).abs() < 1.0 / POW32,
+                "{}: Q32.32",
+                test.name
+            );
+            assert_eq!(
+                q32.to_q64x64().to_q32x32().unwrap(),
+                q32,
+                "{}: Q32.32 -> Q64.64 -> Q32.32",
+                test.name
+            );
+            let q64 = Q64x64::from_f64(test.num).unwrap();
+            assert!(
+                (q64.to_f64() - test.num).abs() < 1e-12,
+                "{}: Q64.64",
+                test.name
+            );
+            let signed = I32x32::from_f64(-test.num).unwrap();
+            assert_eq!(signed.abs(), q32, "{}: |I32.32|", test.name);
+            assert_eq!(signed.to_f64(), -q32.to_f64(), "{}: I32.32", test.name);
+            let q16 = Q16x16::from_f64(test.num).unwrap();
+            assert!(
+                (q16.to_f64() - test.num).abs() < 1.0 / POW16,
+                "{}: Q16.16",
+                test.name
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_overflow() {
+        assert_eq!(Q32x32::MAX.checked_add(Q32x32::from_raw(1)), None);
+        assert_eq!(Q32x32::ZERO.checked_sub(Q32x32::from_raw(1)), None);
+        assert_eq!(Q32x32::MAX.checked_mul(Q32x32::from_int(2)), None);
+        assert_eq!(Q32x32::ONE.checked_div(Q32x32::ZERO), None);
+        assert_eq!(Q64x64::MAX.checked_mul(Q64x64::from_int(2)), None);
+        assert_eq!(Q64x64::from_int(1).checked_div(Q64x64::ZERO), None);
+        assert_eq!(
+            Q64x64::from_int(u64::MAX).checked_div(Q64x64::from_raw(1 << 63)),
+            None
+        );
+        assert_eq!(I32x32::MIN.checked_neg(), None);
+        assert_eq!(I32x32::MAX.checked_mul_int(2), None);
+        assert_eq!(Q16x16::MAX.checked_mul(Q16x16::from_int(2)), None);
+        assert_eq!(Q64x64::from_int(1 << 32).to_q32x32(), None);
+        assert_eq!(Q32x32::from_int(1 << 16).to_q16x16(), None);
+        assert_eq!(Q32x32::from_f64(-1.0), None);
+        assert_eq!(Q32x32::from_f64(f64::NAN), None);
+    }
+
+    #[test]
+    pub fn test_sign_and_abs() {
+        let abs = Q32x32::from_f64(2.5).unwrap();
+        assert_eq!(
+            I32x32::from_sign_and_abs(true, abs).unwrap(),
+            I32x32::from_f64(-2.5).unwrap()
+        );
+        assert_eq!(
+            I32x32::from_sign_and_abs(true, I32x32::MIN.abs()).unwrap(),
+            I32x32::MIN
+        );
+        assert_eq!(I32x32::from_sign_and_abs(false, I32x32::MIN.abs()), None);
+    }
+
+    proptest! {
+        #[test]
+        fn q32x32_mul_matches_u128(a: u64, b: u64) {
+            let expected = (a as u128 * b as u128) >> 32;
+            let res = Q32x32::from_raw(a).checked_mul(Q32x32::from_raw(b));
+            if expected > u64::MAX as u128 {
+                prop_assert_eq!(res, None);
+            } else {
+                prop_assert_eq!(res.unwrap().raw() as u128, expected);
+            }
+        }
+
+        #[test]
+        fn q32x32_div_inverts_mul(a in 0u64..1 << 48, b in 1u64 << 24..1 << 40) {
+            let a = Q32x32::from_raw(a);
+            let b = Q32x32::from_raw(b);
+            if let Some(product) = a.checked_mul(b) {
+                let quotient = product.checked_div(b).unwrap();
+                // truncation in the multiplication loses at most 2^32 / b
+                let tolerance = (1u64 << 32) / b.raw() + 1;
+                prop_assert!(a.raw() - quotient.raw() <= tolerance);
+            }
+        }
+
+        #[test]
+        fn q64x64_mul_matches_small_u128(a: u64, b: u64) {
+            let expected = (a as u128 * b as u128) >> 64;
+            let res = Q64x64::from_raw(a as u128).checked_mul(Q64x64::from_raw(b as u128));
+            prop_assert_eq!(res.unwrap().raw(), expected);
+        }
+
+        #[test]
+        fn q64x64_mul_int_matches_mul(a: u128, n: u64) {
+            let res = Q64x64::from_raw(a).checked_mul(Q64x64::from_int(n));
+            prop_assert_eq!(res, Q64x64::from_raw(a).checked_mul_int(n));
+        }
+
+        #[test]
+        fn q64x64_mul_commutes(a: u128, b: u128) {
+            let a = Q64x64::from_raw(a);
+            let b = Q64x64::from_raw(b);
+            prop_assert_eq!(a.checked_mul(b), b.checked_mul(a));
+        }
+
+        #[test]
+        fn q64x64_div_matches_small_u128(a: u64, b in 1u128..u128::MAX) {
+            let expected = ((a as u128) << 64) / b;
+            let res = Q64x64::from_raw(a as u128).checked_div(Q64x64::from_raw(b));
+            prop_assert_eq!(res.unwrap().raw(), expected);
+        }
+
+        #[test]
+        fn q64x64_div_by_int_matches_div(a: u128, n in 1u64..u64::MAX) {
+            let res = Q64x64::from_raw(a).checked_div(Q64x64::from_int(n));
+            prop_assert_eq!(res, Q64x64::from_raw(a).checked_div_int(n));
+        }
+
+        #[test]
+        fn i32x32_mul_sign(a: i32, b: i32) {
+            let res = I32x32::from_int(a).checked_mul(I32x32::from_int(b));
+            prop_assert_eq!(res, I32x32::from_raw(a as i64 * b as i64).checked_mul_int(1 << 32));
+        }
+
+        #[test]
+        fn i32x32_abs_roundtrip(a: i64) {
+            let num = I32x32::from_raw(a);
+            prop_assert_eq!(I32x32::from_sign_and_abs(num.is_negative(), num.abs()), Some(num));
+        }
+
+        #[test]
+        fn q16x16_widening_mul_is_exact(a: u32, b: u32) {
+            let a = Q16x16::from_raw(a);
+            let b = Q16x16::from_raw(b);
+            let wide = a.widening_mul(b);
+            prop_assert_eq!(wide.raw(), a.raw() as u64 * b.raw() as u64);
+            if let Some(product) = a.checked_mul(b) {
+                prop_assert_eq!(product.to_q32x32().raw(), wide.raw() >> 16 << 16);
+            }
+        }
+
+        #[test]
+        fn float_roundtrip(num in 0.0f64..1e9) {
+            let q32 = Q32x32::from_f64(num).unwrap();
+            prop_assert!((q32.to_f64() - num).abs() <= 1.0 / POW32);
+            let q64 = Q64x64::from_f64(num).unwrap();
+            prop_assert!((q64.to_f64() - num).abs() <= num * f64::EPSILON + 1.0 / POW32 / POW32);
+        }
+    }
+}
@@ -1,2 +1,3 @@
+pub mod fixed;
 pub mod fp;
 pub mod token;
@@ -8,7 +8,7 @@ import * as spl from '@solana/spl-token';
 import { createMint, mintTo, getMint } from '@solana/spl-token';
 import { Oracle } from '../target/types/oracle';
 import { findProgramAddressSync } from '@project-serum/anchor/dist/cjs/utils/pubkey';
-import { SHAKE } from 'sha3';
+import { SHA3 } from 'sha3';
 import NodeWallet from '@project-serum/anchor/dist/cjs/nodewallet';
 import { assert } from 'chai';
 
@@ -22,28 +22,82 @@ const findConfigPDA = (
 	);
 };
 
+const findProtocolTreasuryPDA = (
+	config: web3.PublicKey,
+	programId: web3.PublicKey
+) => {
+	return findProgramAddressSync(
+		[Buffer.from('sure-oracle-treasury'), config.toBytes()],
+		programId
+	);
+};
+
 const findProposalPDA = (id: Buffer, programId: web3.PublicKey) => {
 	return findProgramAddressSync([Buffer.from('sure-oracle'), id], programId);
 };
 
-const findRevealVoteArrayPDA = (id: Buffer, programId: web3.PublicKey) => {
+const findRevealVoteArrayPDA = (
+	proposal: web3.PublicKey,
+	programId: web3.PublicKey
+) => {
 	return findProgramAddressSync(
-		[Buffer.from('sure-oracle-reveal-array'), id],
+		[Buffer.from('sure-oracle-reveal-array'), proposal.toBuffer()],
 		programId
 	);
 };
 
-const findProposalVaultPDA = (id: Buffer, programId: web3.PublicKey) => {
+const findProposalVaultPDA = (
+	proposal: web3.PublicKey,
+	programId: web3.PublicKey
+) => {
 	return findProgramAddressSync(
-		[Buffer.from('sure-oracle-propsal-vault'), id],
+		[Buffer.from('sure-oracle-propsal-vault'), proposal.toBuffer()],
 		programId
 	);
 };
 
-export const createProposalHash = ({ name }: { name: string }): Buffer => {
-	const hash = new SHAKE(128);
-	hash.update(name);
-	return hash.digest();
+type ProposalEvidence = {
+	uri: string;
+	contentHash: number[];
+	affectedProgram: web3.PublicKey;
+	startSlot: anchor.BN;
+	endSlot: anchor.BN;
+};
+
+const serializeString = (value: string): Buffer => {
+	const bytes = Buffer.from(value, 'utf8');
+	const length = Buffer.alloc(4);
+	length.writeUInt32LE(bytes.length);
+	return Buffer.concat([length, bytes]);
+};
+
+/// the proposal id is the truncated sha3-256 hash of
+/// the proposer and the content of the proposal
+export const createProposalHash = ({
+	proposer,
+	name,
+	description,
+	evidence,
+}: {
+	proposer: web3.PublicKey;
+	name: string;
+	description: string;
+	evidence: ProposalEvidence;
+}): Buffer => {
+	const hash = new SHA3(256);
+	hash.update(proposer.toBuffer());
+	hash.update(serializeString(name));
+	hash.update(serializeString(description));
+	hash.update(
+		Buffer.concat([
+			serializeString(evidence.uri),
+			Buffer.from(evidence.contentHash),
+			evidence.affectedProgram.toBuffer(),
+			evidence.startSlot.toArrayLike(Buffer, 'le', 8),
+			evidence.endSlot.toArrayLike(Buffer, 'le', 8),
+		])
+	);
+	return hash.digest().subarray(0, 16);
 };
 
 export const topUpAccount = async ({
@@ -92,6 +146,7 @@ describe('Test Sure Prediction Market ', () => {
 	const minterWallet = web3.Keypair.generate();
 	let sureMint: web3.PublicKey;
 	let minterWalletSureATA: web3.PublicKey;
+	let sureLockerPK: web3.PublicKey;
 	before(async () => {
 		const airdrop = await connection.requestAirdrop(
 			minterWallet.publicKey,
@@ -164,7 +219,7 @@ describe('Test Sure Prediction Market ', () => {
 				baseKP: base,
 			});
 			await createLocker.tx.confirm();
-			const sureLockerPK = createLocker.locker;
+			sureLockerPK = createLocker.locker;
 		} catch (err) {
 			throw new Error(`Failed to create Sure governance. Cause: ${err}`);
 		}
@@ -181,11 +236,19 @@ describe('Test Sure Prediction Market ', () => {
 				protocolAuthority.publicKey
 			);
 			const configAccount = findConfigPDA(sureMint, program.programId);
+			const protocolTreasury = findProtocolTreasuryPDA(
+				configAccount[0],
+				program.programId
+			);
 			await program.methods
 				.initializeConfig(protocolAuthority.publicKey)
 				.accounts({
 					config: configAccount[0],
 					tokenMint: sureMint,
+					locker: sureLockerPK,
+					protocolTreasury: protocolTreasury[0],
+					tokenProgram: spl.TOKEN_PROGRAM_ID,
+					rent: web3.SYSVAR_RENT_PUBKEY,
 				})
 				.rpc();
 		} catch (err) {
@@ -193,10 +256,15 @@ describe('Test Sure Prediction Market ', () => {
 		}
 	});
 	it('Propose vote with required params', async () => {
-		const id = createProposalHash({ name: '1' });
-		console.log('id lenght: ', id.byteLength);
 		const name = 'test123';
 		const description = 'This is a test proposal';
+		const evidence: ProposalEvidence = {
+			uri: 'https://arweave.net/report',
+			contentHash: Array(32).fill(1),
+			affectedProgram: web3.Keypair.generate().publicKey,
+			startSlot: new anchor.BN(100),
+			endSlot: new anchor.BN(120),
+		};
 		const stake = new anchor.BN(10).mul(new anchor.BN(1000000));
 
 		// get necessary accounts
@@ -233,12 +301,24 @@ describe('Test Sure Prediction Market ', () => {
 			const tokenAccount = await spl.getAccount(connection, proposer1Ata);
 			console.log('token balence: ', tokenAccount.amount.toString());
 			const [configPda] = findConfigPDA(sureMint, program.programId);
+			const id = createProposalHash({
+				proposer: proposer1.publicKey,
+				name,
+				description,
+				evidence,
+			});
 			const [proposalPda] = findProposalPDA(id, program.programId);
-			const [revealVoteArray] = findRevealVoteArrayPDA(id, program.programId);
-			const [proposalVault] = findProposalVaultPDA(id, program.programId);
+			const [revealVoteArray] = findRevealVoteArrayPDA(
+				proposalPda,
+				program.programId
+			);
+			const [proposalVault] = findProposalVaultPDA(
+				proposalPda,
+				program.programId
+			);
 			let tx = new web3.Transaction();
 			const instruction = await program.methods
-				.proposeVote(id, name, description, stake)
+				.proposeVote(name, description, evidence, stake)
 				.accounts({
 					proposer: proposer1.publicKey,
 					config: configPda,
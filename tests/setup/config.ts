@@ -5,11 +5,13 @@ import { TransactionReceipt } from '@saberhq/solana-contrib';
 export const createTestConfig = async (
 	protocolAuthority: web3.PublicKey,
 	oracleSdk: SureOracleSDK,
-	mint: web3.PublicKey
+	mint: web3.PublicKey,
+	locker: web3.PublicKey
 ): Promise<TransactionReceipt> => {
 	const createProposal = await oracleSdk.config().initializeOracleConfig({
 		protocolAuthority,
 		tokenMint: mint,
+		locker,
 	});
 
 	return createProposal.confirm();
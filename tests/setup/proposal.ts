@@ -12,6 +12,13 @@ export const createTestProposal = async (
 	const createProposal = await oracleSdk.proposal().proposeVote({
 		name: proposalName,
 		description: 'how many eggs are in the basket',
+		evidence: {
+			uri: 'https://arweave.net/eggs',
+			contentHash: Array(32).fill(0),
+			affectedProgram: web3.PublicKey.default,
+			startSlot: new anchor.BN(0),
+			endSlot: new anchor.BN(0),
+		},
 		stake: proposedStake,
 		mint,
 	});